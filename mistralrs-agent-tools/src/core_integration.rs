@@ -4,8 +4,8 @@
 //! tool callback infrastructure, enabling all 90+ utilities to be used by language models.
 
 use crate::types::{
-    CatOptions, CommandOptions, GrepOptions, HeadOptions, LsOptions, ShellType, SortOptions,
-    TailOptions, UniqOptions, WcOptions,
+    CatOptions, CommandOptions, GrepOptions, HeadOptions, LsOptions, LsTimeFormat, ShellType,
+    SortOptions, TailOptions, UniqOptions, WcOptions,
 };
 use crate::{AgentToolkit, SandboxConfig};
 use anyhow::{anyhow, Result};
@@ -179,6 +179,15 @@ impl AgentToolProvider {
                     "type": "boolean",
                     "description": "List subdirectories recursively",
                     "default": false
+                },
+                "time_format": {
+                    "type": "string",
+                    "description": "Format for modified_formatted: 'iso', 'relative' or a strftime pattern"
+                },
+                "dereference": {
+                    "type": "boolean",
+                    "description": "Describe symlinks by their targets instead of the links themselves",
+                    "default": false
                 }
             }),
         );
@@ -546,6 +555,7 @@ fn execute_cat(toolkit: &AgentToolkit, args: &Value) -> Result<String> {
             .get("squeeze_blank")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        ..Default::default()
     };
 
     let path_refs: Vec<&Path> = paths.iter().map(|p| Path::new(p.as_str())).collect();
@@ -577,6 +587,15 @@ fn execute_ls(toolkit: &AgentToolkit, args: &Value) -> Result<String> {
             .get("reverse")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        time_format: args
+            .get("time_format")
+            .and_then(|v| v.as_str())
+            .map(LsTimeFormat::parse),
+        dereference: args
+            .get("dereference")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        ..Default::default()
     };
 
     let result = crate::tools::file::ls(&toolkit.sandbox, path, &options)?;
@@ -586,12 +605,20 @@ fn execute_ls(toolkit: &AgentToolkit, args: &Value) -> Result<String> {
         "total": result.total,
         "total_size": result.total_size,
         "entries": result.entries.iter().map(|e| {
-            serde_json::json!({
+            let mut value = serde_json::json!({
                 "name": e.name,
                 "is_dir": e.is_dir,
+                "kind": e.kind.as_str(),
                 "size": e.size,
                 "modified": e.modified,
-            })
+            });
+            if let (Some(format), Some(secs)) = (&options.time_format, e.modified) {
+                value["modified_formatted"] = serde_json::json!(crate::tools::file::format_time(secs, Some(format)));
+            }
+            if let Some(target) = &e.link_target {
+                value["link_target"] = serde_json::json!(target.display().to_string());
+            }
+            value
         }).collect::<Vec<_>>()
     }))?)
 }
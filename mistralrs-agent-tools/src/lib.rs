@@ -32,13 +32,16 @@ pub mod test_utils;
 pub use catalog::{ToolCatalog, ToolDefinition, ToolExample};
 use std::path::PathBuf;
 pub use tools::file::{cat, ls};
-pub use tools::sandbox::Sandbox;
+pub use tools::sandbox::{
+    PolicySummary, Sandbox, WatchEvent, WatchEventKind, WatchHandle, DRY_RUN_PREFIX,
+};
 pub use tools::shell::execute;
-pub use tools::text::{grep, head, sort, tail, uniq, wc};
+pub use tools::text::{grep, head, sort, tail, uniq, wc, DiffResult};
 pub use types::{
-    AgentError, AgentResult, Bom, CatOptions, CommandOptions, CommandResult, FileEntry, GrepMatch,
-    GrepOptions, HeadOptions, LineEnding, LsOptions, LsResult, SandboxConfig, SecurityLevel,
-    SecurityPolicy, ShellType, SortOptions, TailOptions, UniqOptions, WcOptions, WcResult,
+    AgentError, AgentResult, Bom, CatOptions, CommandOptions, CommandResult, CutOptions, FileEntry,
+    FileKind, GrepMatch, GrepOptions, HeadOptions, LineEnding, LsOptions, LsResult, LsTimeFormat,
+    SandboxConfig, SecurityLevel, SecurityPolicy, ShellType, SortOptions, TailOptions, TrOptions,
+    UniqOptions, WcOptions, WcResult,
 };
 
 // Core integration exports
@@ -98,6 +101,11 @@ impl AgentToolkit {
         tools::file::cat(&self.sandbox, paths, options)
     }
 
+    /// Concatenate every file matching a glob pattern, in sorted order
+    pub fn cat_glob(&self, pattern: &str, options: &CatOptions) -> AgentResult<String> {
+        tools::file::cat_glob(&self.sandbox, pattern, options)
+    }
+
     /// List directory contents
     ///
     /// # Example
@@ -120,6 +128,11 @@ impl AgentToolkit {
         tools::file::ls(&self.sandbox, path, options)
     }
 
+    /// List directory contents in long format (`ls -l`)
+    pub fn vdir(&self, path: &std::path::Path, options: &LsOptions) -> AgentResult<String> {
+        tools::file::vdir(&self.sandbox, path, options)
+    }
+
     // Text processing operations
 
     /// Display first part of files
@@ -252,6 +265,28 @@ impl AgentToolkit {
         tools::text::uniq(&self.sandbox, paths, options)
     }
 
+    /// Compare two files as a unified diff with `context` lines of context
+    ///
+    /// # Example
+    /// ```no_run
+    /// use mistralrs_agent_tools::AgentToolkit;
+    /// use std::path::Path;
+    ///
+    /// let toolkit = AgentToolkit::with_defaults();
+    /// let result = toolkit.diff(Path::new("old.txt"), Path::new("new.txt"), 3).unwrap();
+    /// if !result.identical {
+    ///     println!("{}", result.diff);
+    /// }
+    /// ```
+    pub fn diff(
+        &self,
+        old_path: &std::path::Path,
+        new_path: &std::path::Path,
+        context: usize,
+    ) -> AgentResult<DiffResult> {
+        tools::text::diff_files(&self.sandbox, old_path, new_path, context)
+    }
+
     // Shell execution operations
 
     /// Execute a shell command
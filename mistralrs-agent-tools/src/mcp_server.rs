@@ -11,8 +11,8 @@
 // - HTTP/WebSocket transports (optional)
 
 use crate::types::{
-    CatOptions, CommandOptions, GrepOptions, HeadOptions, LsOptions, ShellType, SortOptions,
-    TailOptions, UniqOptions, WcOptions,
+    CatOptions, CommandOptions, GrepOptions, HeadOptions, LsOptions, LsTimeFormat, ShellType,
+    SortOptions, TailOptions, UniqOptions, WcOptions,
 };
 use crate::AgentToolkit;
 use anyhow::{anyhow, Context, Result};
@@ -164,6 +164,14 @@ impl McpServer {
                         "reverse": {
                             "type": "boolean",
                             "description": "Reverse the sort order"
+                        },
+                        "time_format": {
+                            "type": "string",
+                            "description": "Format for modified_formatted: 'iso', 'relative' or a strftime pattern"
+                        },
+                        "dereference": {
+                            "type": "boolean",
+                            "description": "Describe symlinks by their targets instead of the links themselves"
                         }
                     },
                     "required": ["path"]
@@ -483,6 +491,7 @@ impl McpServer {
                     number_lines: Self::bool_arg(&args_map, "number_lines", false),
                     show_ends: Self::bool_arg(&args_map, "show_ends", false),
                     squeeze_blank: Self::bool_arg(&args_map, "squeeze_blank", false),
+                    ..Default::default()
                 };
                 let path_refs = Self::path_refs(&paths);
                 let output = self.toolkit.cat(&path_refs, &options)?;
@@ -497,18 +506,30 @@ impl McpServer {
                     recursive: Self::bool_arg(&args_map, "recursive", false),
                     sort_by_time: Self::bool_arg(&args_map, "sort_by_time", false),
                     reverse: Self::bool_arg(&args_map, "reverse", false),
+                    time_format: Self::string_arg(&args_map, "time_format")
+                        .map(|spec| LsTimeFormat::parse(&spec)),
+                    dereference: Self::bool_arg(&args_map, "dereference", false),
+                    ..Default::default()
                 };
                 let listing = self.toolkit.ls(Path::new(&path), &options)?;
                 let listing_json = json!({
                     "total": listing.total,
                     "total_size": listing.total_size,
                     "entries": listing.entries.iter().map(|entry| {
-                        json!({
+                        let mut value = json!({
                             "name": entry.name,
                             "is_dir": entry.is_dir,
+                            "kind": entry.kind.as_str(),
                             "size": entry.size,
                             "modified": entry.modified,
-                        })
+                        });
+                        if let (Some(format), Some(secs)) = (&options.time_format, entry.modified) {
+                            value["modified_formatted"] = json!(crate::tools::file::format_time(secs, Some(format)));
+                        }
+                        if let Some(target) = &entry.link_target {
+                            value["link_target"] = json!(target.display().to_string());
+                        }
+                        value
                     }).collect::<Vec<_>>()
                 });
                 let text = serde_json::to_string_pretty(&listing_json)?;
@@ -3,10 +3,10 @@
 //! Adapted from winutils cat with agent tools integration.
 
 use crate::tools::sandbox::Sandbox;
-use crate::types::{AgentResult, Bom, CatOptions};
+use crate::types::{AgentError, AgentResult, Bom, CatOptions};
 use std::fs::File;
 use std::io::{BufReader, Read};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Read and concatenate files
 pub fn cat(sandbox: &Sandbox, paths: &[&Path], options: &CatOptions) -> AgentResult<String> {
@@ -55,6 +55,50 @@ pub fn cat(sandbox: &Sandbox, paths: &[&Path], options: &CatOptions) -> AgentRes
     Ok(output)
 }
 
+/// Read and concatenate every file matching a glob pattern
+///
+/// Relative patterns are resolved against the sandbox root, whose path is
+/// matched literally even if it contains glob metacharacters. Matches are
+/// sorted by path and directories are skipped; each file is validated as in
+/// [`cat`], and the options apply across the whole stream (e.g. line numbers
+/// continue from one file to the next). A pattern that matches no files
+/// yields an empty string, or `NotFound` with `error_on_no_match`.
+pub fn cat_glob(sandbox: &Sandbox, pattern: &str, options: &CatOptions) -> AgentResult<String> {
+    let pattern = if Path::new(pattern).is_absolute() {
+        pattern.to_string()
+    } else {
+        let root = glob::Pattern::escape(&sandbox.root().to_string_lossy());
+        Path::new(&root)
+            .join(pattern)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    let entries = glob::glob(&pattern)
+        .map_err(|e| AgentError::InvalidInput(format!("Invalid glob pattern: {}", e)))?;
+    let mut matches = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| AgentError::io(e.to_string()))?;
+        if path.is_file() {
+            matches.push(path);
+        }
+    }
+    matches.sort();
+
+    if matches.is_empty() {
+        if options.error_on_no_match {
+            return Err(AgentError::NotFound(format!(
+                "No files match pattern: {}",
+                pattern
+            )));
+        }
+        return Ok(String::new());
+    }
+
+    let paths: Vec<&Path> = matches.iter().map(PathBuf::as_path).collect();
+    cat(sandbox, &paths, options)
+}
+
 /// Read file content with encoding detection
 fn read_file_content(path: &Path, _options: &CatOptions) -> AgentResult<String> {
     let mut file = File::open(path)?;
@@ -90,8 +134,8 @@ fn read_file_content(path: &Path, _options: &CatOptions) -> AgentResult<String>
 mod tests {
     use super::*;
     use crate::types::{AgentError, SandboxConfig};
+    use std::fs;
     use std::io::Write;
-    use std::path::PathBuf;
     use tempfile::TempDir;
 
     fn create_test_sandbox() -> (Sandbox, TempDir) {
@@ -190,6 +234,51 @@ mod tests {
         assert!(result.contains("File 2"));
     }
 
+    #[test]
+    fn test_cat_glob_concatenates_sorted_matches() {
+        let (sandbox, temp_dir) = create_test_sandbox();
+        fs::write(temp_dir.path().join("b.log"), "second\n").unwrap();
+        fs::write(temp_dir.path().join("a.log"), "first\n").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "skipped\n").unwrap();
+        fs::create_dir(temp_dir.path().join("dir.log")).unwrap();
+
+        let options = CatOptions {
+            number_lines: true,
+            ..Default::default()
+        };
+        let result = cat_glob(&sandbox, "*.log", &options).unwrap();
+
+        assert_eq!(result, "     1\tfirst\n     2\tsecond\n");
+    }
+
+    #[test]
+    fn test_cat_glob_root_with_metacharacters() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("logs[1]");
+        fs::create_dir(&root).unwrap();
+        fs::write(root.join("a.log"), "first\n").unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(root));
+
+        let result = cat_glob(&sandbox, "*.log", &CatOptions::default()).unwrap();
+
+        assert_eq!(result, "first\n");
+    }
+
+    #[test]
+    fn test_cat_glob_no_match() {
+        let (sandbox, _temp_dir) = create_test_sandbox();
+
+        let result = cat_glob(&sandbox, "*.missing", &CatOptions::default()).unwrap();
+        assert_eq!(result, "");
+
+        let options = CatOptions {
+            error_on_no_match: true,
+            ..Default::default()
+        };
+        let result = cat_glob(&sandbox, "*.missing", &options);
+        assert!(matches!(result, Err(AgentError::NotFound(_))));
+    }
+
     #[test]
     fn test_cat_with_bom() {
         let (sandbox, temp_dir) = create_test_sandbox();
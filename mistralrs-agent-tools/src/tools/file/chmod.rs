@@ -0,0 +1,285 @@
+//! Chmod utility - change file mode bits
+//!
+//! Accepts octal modes (`755`) and symbolic modes (`+x`, `u+rw,go-w`, `a=r`).
+//! Symbolic clauses are applied in order starting from the file's current mode.
+
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use std::fs;
+use std::path::Path;
+
+/// Permission bits a mode string may change (rwx for user, group and other)
+const PERMISSION_BITS: u32 = 0o777;
+
+/// Result of chmod operation
+#[derive(Debug, Clone)]
+pub struct ChmodResult {
+    /// Paths that were changed, with their new permission bits
+    pub changed: Vec<(String, u32)>,
+    /// Number of paths changed
+    pub count: usize,
+    /// Whether this was a dry run, so `changed` lists modes that would be set
+    pub dry_run: bool,
+}
+
+/// Change the permission bits of files
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `paths` - Paths to change
+/// * `mode` - Octal (`644`) or symbolic (`u+rw,go-w`) mode
+///
+/// # Errors
+/// Returns error if:
+/// - The mode is invalid
+/// - Path is outside sandbox
+/// - Path does not exist
+/// - Permission denied
+///
+/// On non-Unix platforms only the owner write bit is honoured, mapped to the
+/// read-only attribute. In dry-run mode no permissions are changed.
+pub fn chmod(sandbox: &Sandbox, paths: &[&Path], mode: &str) -> AgentResult<ChmodResult> {
+    if paths.is_empty() {
+        return Err(AgentError::validation("No paths specified for chmod"));
+    }
+    // Reject bad modes before touching any file
+    apply_mode(mode, 0)?;
+
+    let mut changed = Vec::new();
+    for path in paths {
+        let validated_path = sandbox.validate_write(path)?;
+        let metadata = fs::metadata(&validated_path).map_err(|_| {
+            AgentError::NotFound(format!(
+                "Cannot access {}: No such file or directory",
+                validated_path.display()
+            ))
+        })?;
+
+        let current = current_mode(&metadata);
+        let new_mode = apply_mode(mode, current)?;
+        if !sandbox.is_dry_run() {
+            set_mode(&validated_path, metadata.permissions(), new_mode).map_err(|e| {
+                AgentError::io(format!(
+                    "Failed to change mode of {}: {}",
+                    validated_path.display(),
+                    e
+                ))
+            })?;
+        }
+
+        let path_str = validated_path
+            .to_str()
+            .ok_or_else(|| AgentError::validation("Path contains invalid UTF-8"))?
+            .to_string();
+        changed.push((path_str, new_mode));
+    }
+
+    Ok(ChmodResult {
+        count: changed.len(),
+        changed,
+        dry_run: sandbox.is_dry_run(),
+    })
+}
+
+/// Compute the permission bits that result from applying `mode` to `current`
+///
+/// Octal modes replace the permission bits outright. Symbolic modes are
+/// comma-separated clauses of the form `[ugoa]*([+-=][rwx]*)+`; an omitted
+/// class means `a`. Only the low nine permission bits are returned.
+pub fn apply_mode(mode: &str, current: u32) -> AgentResult<u32> {
+    let mode = mode.trim();
+    if mode.is_empty() {
+        return Err(AgentError::validation("Empty mode"));
+    }
+
+    if mode.bytes().all(|b| b.is_ascii_digit()) {
+        return u32::from_str_radix(mode, 8)
+            .ok()
+            .filter(|bits| *bits <= PERMISSION_BITS)
+            .ok_or_else(|| AgentError::validation(format!("Invalid octal mode: {}", mode)));
+    }
+
+    let mut bits = current & PERMISSION_BITS;
+    for clause in mode.split(',') {
+        bits = apply_clause(clause, bits)?;
+    }
+    Ok(bits)
+}
+
+/// Apply a single symbolic clause such as `go-w` or `u+r-x`
+fn apply_clause(clause: &str, mut bits: u32) -> AgentResult<u32> {
+    let invalid = || AgentError::validation(format!("Invalid mode clause: '{}'", clause));
+
+    let ops_start = clause.find(['+', '-', '=']).ok_or_else(invalid)?;
+    let (who, ops) = clause.split_at(ops_start);
+
+    let mut who_mask = 0;
+    for c in who.chars() {
+        who_mask |= match c {
+            'u' => 0o700,
+            'g' => 0o070,
+            'o' => 0o007,
+            'a' => 0o777,
+            _ => return Err(invalid()),
+        };
+    }
+    if who_mask == 0 {
+        who_mask = 0o777;
+    }
+
+    let mut chars = ops.chars().peekable();
+    while let Some(op) = chars.next() {
+        let mut perms = 0;
+        while let Some(&c) = chars.peek() {
+            perms |= match c {
+                'r' => 0o444,
+                'w' => 0o222,
+                'x' => 0o111,
+                '+' | '-' | '=' => break,
+                _ => return Err(invalid()),
+            };
+            chars.next();
+        }
+
+        let selected = perms & who_mask;
+        bits = match op {
+            '+' => bits | selected,
+            '-' => bits & !selected,
+            '=' => (bits & !who_mask) | selected,
+            _ => return Err(invalid()),
+        };
+    }
+
+    Ok(bits)
+}
+
+/// Current permission bits of a file
+fn current_mode(metadata: &fs::Metadata) -> u32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & PERMISSION_BITS
+    }
+    #[cfg(not(unix))]
+    {
+        if metadata.permissions().readonly() {
+            0o444
+        } else {
+            0o666
+        }
+    }
+}
+
+/// Set the permission bits of a file, keeping any special bits
+fn set_mode(path: &Path, mut permissions: fs::Permissions, bits: u32) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode((permissions.mode() & !PERMISSION_BITS) | bits);
+    }
+    #[cfg(not(unix))]
+    {
+        permissions.set_readonly(bits & 0o200 == 0);
+    }
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_apply_mode_octal() {
+        assert_eq!(apply_mode("755", 0).unwrap(), 0o755);
+        assert_eq!(apply_mode("0644", 0o777).unwrap(), 0o644);
+        assert!(apply_mode("888", 0).is_err());
+        assert!(apply_mode("7777", 0).is_err());
+    }
+
+    #[test]
+    fn test_apply_mode_plus_x() {
+        assert_eq!(apply_mode("+x", 0o644).unwrap(), 0o755);
+        assert_eq!(apply_mode("a+x", 0o600).unwrap(), 0o711);
+        assert_eq!(apply_mode("u+x", 0o644).unwrap(), 0o744);
+    }
+
+    #[test]
+    fn test_apply_mode_combined_clauses() {
+        assert_eq!(apply_mode("u+rw,go-r", 0o044).unwrap(), 0o600);
+        assert_eq!(apply_mode("go-w,o-x", 0o777).unwrap(), 0o754);
+        assert_eq!(apply_mode("u+r-x", 0o300).unwrap(), 0o600);
+    }
+
+    #[test]
+    fn test_apply_mode_equals_replaces() {
+        assert_eq!(apply_mode("=r", 0o755).unwrap(), 0o444);
+        assert_eq!(apply_mode("g=", 0o775).unwrap(), 0o705);
+        assert_eq!(apply_mode("u=rwx,go=rx", 0).unwrap(), 0o755);
+    }
+
+    #[test]
+    fn test_apply_mode_invalid() {
+        assert!(apply_mode("", 0o644).is_err());
+        assert!(apply_mode("u", 0o644).is_err());
+        assert!(apply_mode("z+x", 0o644).is_err());
+        assert!(apply_mode("+q", 0o644).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_chmod_makes_file_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        let file = temp_dir.path().join("script.sh");
+        fs::write(&file, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let result = chmod(&sandbox, &[&file], "+x").unwrap();
+        assert_eq!(result.count, 1);
+        assert_eq!(result.changed[0].1, 0o755);
+        let mode = fs::metadata(&file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_chmod_dry_run_keeps_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config = SandboxConfig::builder()
+            .root(temp_dir.path())
+            .dry_run(true)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+        let file = temp_dir.path().join("script.sh");
+        fs::write(&file, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let result = chmod(&sandbox, &[&file], "+x").unwrap();
+        assert!(result.dry_run);
+        assert_eq!(result.changed[0].1, 0o755);
+        let mode = fs::metadata(&file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o644);
+    }
+
+    #[test]
+    fn test_chmod_rejects_bad_mode_and_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "data").unwrap();
+
+        assert!(chmod(&sandbox, &[&file], "u+q").is_err());
+        let missing = temp_dir.path().join("missing.txt");
+        assert!(matches!(
+            chmod(&sandbox, &[&missing], "644"),
+            Err(AgentError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,55 @@
+//! Confirmation hook for destructive file operations
+//!
+//! Library code cannot prompt a user directly, so `rm` and `mv` accept an
+//! optional callback that the host application uses to approve each
+//! deletion or overwrite.
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// Reason reported for paths the confirmation callback declined
+pub const SKIPPED_BY_CONFIRMATION: &str = "skipped by confirmation";
+
+/// Kind of destructive action awaiting confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// A file or directory is about to be removed
+    Remove,
+    /// An existing destination is about to be overwritten
+    Overwrite,
+}
+
+/// Details of a destructive action passed to the confirmation callback
+#[derive(Debug, Clone)]
+pub struct ConfirmRequest {
+    /// Action about to be performed
+    pub action: ConfirmAction,
+    /// Path that will be removed or overwritten
+    pub path: PathBuf,
+    /// Source path replacing `path` (for overwrites)
+    pub source: Option<PathBuf>,
+    /// Whether `path` is a directory
+    pub is_dir: bool,
+}
+
+/// Callback consulted before a destructive action; returns `true` to proceed
+pub type ConfirmCallback = Box<dyn Fn(&ConfirmRequest) -> bool + Send + Sync>;
+
+/// Ask the callback, if any, whether to proceed
+///
+/// Without a callback the action always proceeds.
+pub(crate) fn confirm(callback: Option<&ConfirmCallback>, request: ConfirmRequest) -> bool {
+    callback.is_none_or(|cb| cb(&request))
+}
+
+/// Debug helper for option structs holding a callback
+pub(crate) struct CallbackDebug<'a>(pub(crate) &'a Option<ConfirmCallback>);
+
+impl fmt::Debug for CallbackDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("Some(<callback>)"),
+            None => f.write_str("None"),
+        }
+    }
+}
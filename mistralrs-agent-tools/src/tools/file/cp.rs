@@ -1,12 +1,22 @@
 //! Cp utility - copy files and directories
 //!
-//! Copies files and directories with various options.
+//! Copies files and directories with various options. Large copies can
+//! report progress through a callback. In dry-run mode nothing is copied and
+//! the result lists what would be.
 
+use super::walk::{walk, WalkOptions};
 use crate::tools::sandbox::Sandbox;
 use crate::types::{AgentError, AgentResult};
-use std::fs;
+use std::fs::{self, File};
+use std::io::{Read, Write};
 use std::path::Path;
 
+/// Buffer size for streamed copies
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Progress callback receiving `(bytes_copied, total_bytes)` for the file being copied
+pub type ProgressCallback = Box<dyn FnMut(u64, u64) + Send>;
+
 /// Options for cp operation
 #[derive(Debug, Clone, Default)]
 pub struct CpOptions {
@@ -37,6 +47,9 @@ pub struct CpResult {
     pub count: usize,
     /// Total bytes copied
     pub bytes_copied: u64,
+    /// Whether this was a dry run, so `copied` and `bytes_copied` describe
+    /// what would be copied
+    pub dry_run: bool,
 }
 
 /// Copy files or directories
@@ -62,6 +75,21 @@ pub fn cp(
     sources: &[&Path],
     dest: &Path,
     options: &CpOptions,
+) -> AgentResult<CpResult> {
+    cp_with_progress(sandbox, sources, dest, options, None)
+}
+
+/// Copy files or directories, reporting progress for each file
+///
+/// With a callback, files are streamed in fixed-size chunks and the callback
+/// is invoked after every chunk with the bytes copied so far and the file's
+/// total size. Without one this is the same as [`cp`].
+pub fn cp_with_progress(
+    sandbox: &Sandbox,
+    sources: &[&Path],
+    dest: &Path,
+    options: &CpOptions,
+    mut progress: Option<ProgressCallback>,
 ) -> AgentResult<CpResult> {
     if sources.is_empty() {
         return Err(AgentError::validation("No source paths specified for cp"));
@@ -122,32 +150,62 @@ pub fn cp(
             continue;
         }
 
+        if validated_source.is_dir()
+            && !options.recursive
+            && !options.symbolic_link
+            && !options.link
+        {
+            return Err(AgentError::validation(format!(
+                "Cannot copy directory {} without --recursive flag",
+                validated_source.display()
+            )));
+        }
+
+        // Sizing a tree means walking it, so only do so when the size is used
+        let needs_plan = sandbox.config().max_write_bytes.is_some()
+            || sandbox.config().max_total_write_bytes.is_some()
+            || sandbox.is_dry_run();
+        let planned = if needs_plan {
+            planned_bytes(sandbox, &validated_source, options)?
+        } else {
+            0
+        };
+        sandbox.check_write_quota(planned)?;
+
         // Perform the copy
-        let bytes = if options.symbolic_link {
+        let bytes = if sandbox.is_dry_run() {
+            planned
+        } else if options.symbolic_link {
             create_symlink(&validated_source, &final_dest)?;
             0
         } else if options.link {
             create_hardlink(&validated_source, &final_dest)?;
             0
         } else if validated_source.is_dir() {
-            if !options.recursive {
-                return Err(AgentError::validation(format!(
-                    "Cannot copy directory {} without --recursive flag",
-                    validated_source.display()
-                )));
-            }
-            copy_dir_recursive(&validated_source, &final_dest, options)?
+            copy_dir_recursive(
+                sandbox,
+                &validated_source,
+                &final_dest,
+                options,
+                &mut progress,
+            )?
         } else {
-            copy_file(&validated_source, &final_dest, options)?
+            copy_file(&validated_source, &final_dest, options, &mut progress)?
         };
 
+        if !sandbox.is_dry_run() {
+            sandbox.record_write(bytes);
+        }
         bytes_copied += bytes;
 
         if options.verbose {
             eprintln!(
-                "cp: copied '{}' -> '{}'",
-                validated_source.display(),
-                final_dest.display()
+                "{}",
+                sandbox.dry_run_message(format!(
+                    "cp: copied '{}' -> '{}'",
+                    validated_source.display(),
+                    final_dest.display()
+                ))
             );
         }
 
@@ -163,11 +221,44 @@ pub fn cp(
         count: copied.len(),
         bytes_copied,
         copied,
+        dry_run: sandbox.is_dry_run(),
     })
 }
 
+/// Symlinks are copied as the files and directories they point to
+const COPY_WALK: WalkOptions = WalkOptions {
+    max_depth: None,
+    follow_symlinks: true,
+    skip_hidden: false,
+};
+
+/// Bytes a copy of `source` would write; links write no data
+///
+/// Each file is checked against `max_write_bytes`, so an oversized file
+/// fails the copy before anything is written.
+fn planned_bytes(sandbox: &Sandbox, source: &Path, options: &CpOptions) -> AgentResult<u64> {
+    if options.symbolic_link || options.link {
+        return Ok(0);
+    }
+    let mut total = 0u64;
+    walk(sandbox, source, &COPY_WALK, |entry| {
+        if entry.file_type().is_file() {
+            let size = entry.metadata().map_or(0, |metadata| metadata.len());
+            sandbox.validate_write_size(size)?;
+            total += size;
+        }
+        Ok(())
+    })?;
+    Ok(total)
+}
+
 /// Copy a single file
-fn copy_file(source: &Path, dest: &Path, options: &CpOptions) -> AgentResult<u64> {
+fn copy_file(
+    source: &Path,
+    dest: &Path,
+    options: &CpOptions,
+    progress: &mut Option<ProgressCallback>,
+) -> AgentResult<u64> {
     // Ensure parent directory exists
     if let Some(parent) = dest.parent() {
         if !parent.exists() {
@@ -181,8 +272,12 @@ fn copy_file(source: &Path, dest: &Path, options: &CpOptions) -> AgentResult<u64
         }
     }
 
-    // Copy the file
-    let bytes = fs::copy(source, dest).map_err(|e| {
+    // Copy the file, streaming it when progress is reported
+    let copied = match progress {
+        Some(callback) => copy_streamed(source, dest, callback),
+        None => fs::copy(source, dest),
+    };
+    let bytes = copied.map_err(|e| {
         AgentError::io(format!(
             "Failed to copy {} to {}: {}",
             source.display(),
@@ -199,44 +294,76 @@ fn copy_file(source: &Path, dest: &Path, options: &CpOptions) -> AgentResult<u64
     Ok(bytes)
 }
 
-/// Copy a directory recursively
-fn copy_dir_recursive(source: &Path, dest: &Path, options: &CpOptions) -> AgentResult<u64> {
-    // Create destination directory
-    fs::create_dir_all(dest).map_err(|e| {
-        AgentError::io(format!(
-            "Failed to create directory {}: {}",
-            dest.display(),
-            e
-        ))
-    })?;
+/// Copy a file in fixed-size chunks, reporting progress after each chunk
+fn copy_streamed(
+    source: &Path,
+    dest: &Path,
+    progress: &mut ProgressCallback,
+) -> std::io::Result<u64> {
+    let mut reader = File::open(source)?;
+    let total = reader.metadata()?.len();
+    let mut writer = File::create(dest)?;
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut copied = 0u64;
+
+    progress(0, total);
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..n])?;
+        copied += n as u64;
+        progress(copied, total);
+    }
+
+    // Match fs::copy, which also copies permission bits
+    writer.set_permissions(reader.metadata()?.permissions())?;
+    Ok(copied)
+}
 
+/// Copy a directory recursively
+fn copy_dir_recursive(
+    sandbox: &Sandbox,
+    source: &Path,
+    dest: &Path,
+    options: &CpOptions,
+    progress: &mut Option<ProgressCallback>,
+) -> AgentResult<u64> {
+    // Entry paths are relative to the root as walk resolves it
+    let root = sandbox.validate_read(source)?;
     let mut total_bytes = 0u64;
+    let mut dirs = Vec::new();
 
-    // Iterate through directory entries
-    for entry_result in fs::read_dir(source).map_err(|e| {
-        AgentError::io(format!(
-            "Failed to read directory {}: {}",
-            source.display(),
-            e
-        ))
-    })? {
-        let entry = entry_result
-            .map_err(|e| AgentError::io(format!("Failed to read directory entry: {}", e)))?;
+    walk(sandbox, &root, &COPY_WALK, |entry| {
+        // Links leaving the sandbox are reported by walk; never copy through them
+        if entry.path_is_symlink() {
+            sandbox.validate_read(entry.path())?;
+        }
 
-        let source_path = entry.path();
-        let file_name = entry.file_name();
-        let dest_path = dest.join(&file_name);
+        let relative = entry.path().strip_prefix(&root).unwrap_or(Path::new(""));
+        let dest_path = dest.join(relative);
 
-        if source_path.is_dir() {
-            total_bytes += copy_dir_recursive(&source_path, &dest_path, options)?;
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest_path).map_err(|e| {
+                AgentError::io(format!(
+                    "Failed to create directory {}: {}",
+                    dest_path.display(),
+                    e
+                ))
+            })?;
+            dirs.push((entry.path().to_path_buf(), dest_path));
         } else {
-            total_bytes += copy_file(&source_path, &dest_path, options)?;
+            total_bytes += copy_file(entry.path(), &dest_path, options, progress)?;
         }
-    }
+        Ok(())
+    })?;
 
-    // Preserve directory attributes if requested
+    // Preserve directory attributes once their contents are written
     if options.preserve {
-        preserve_attributes(source, dest)?;
+        for (source_dir, dest_dir) in dirs.iter().rev() {
+            preserve_attributes(source_dir, dest_dir)?;
+        }
     }
 
     Ok(total_bytes)
@@ -386,6 +513,42 @@ mod tests {
         assert_eq!(content, "test content");
     }
 
+    #[test]
+    fn test_cp_with_progress_reports_file_size() {
+        use std::sync::{Arc, Mutex};
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let source = temp_dir.path().join("large.bin");
+        let dest = temp_dir.path().join("large_copy.bin");
+        let size = 3 * 1024 * 1024 + 17;
+        let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        fs::write(&source, &data).expect("Failed to create source file");
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        let progress: ProgressCallback = Box::new(move |copied, total| {
+            recorded.lock().unwrap().push((copied, total));
+        });
+
+        let result = cp_with_progress(
+            &sandbox,
+            &[&source],
+            &dest,
+            &CpOptions::default(),
+            Some(progress),
+        )
+        .expect("cp failed");
+
+        let calls = calls.lock().unwrap();
+        assert!(calls.len() > 2);
+        assert_eq!(*calls.last().unwrap(), (size as u64, size as u64));
+        assert!(calls.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert_eq!(result.bytes_copied, size as u64);
+        assert_eq!(fs::read(&dest).unwrap(), data);
+    }
+
     #[test]
     fn test_cp_directory_recursive() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -411,6 +574,77 @@ mod tests {
         assert!(dest_dir.join("file2.txt").exists());
     }
 
+    #[test]
+    fn test_cp_rejects_file_over_max_write_bytes() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config = SandboxConfig::builder()
+            .root(temp_dir.path())
+            .max_write_bytes(8)
+            .build()
+            .expect("Failed to build config");
+        let sandbox = Sandbox::new(config);
+
+        let small = temp_dir.path().join("small.txt");
+        let large = temp_dir.path().join("large.txt");
+        fs::write(&small, "tiny").expect("Failed to create file");
+        fs::write(&large, "far too large").expect("Failed to create file");
+
+        let dest = temp_dir.path().join("copy.txt");
+        cp(&sandbox, &[&small], &dest, &CpOptions::default()).expect("cp failed");
+        assert!(dest.exists());
+
+        let dest = temp_dir.path().join("large_copy.txt");
+        let result = cp(&sandbox, &[&large], &dest, &CpOptions::default());
+        assert!(matches!(result, Err(AgentError::InvalidInput(_))));
+        assert!(!dest.exists());
+
+        // Checked per file inside a recursive copy, before anything is written
+        let dir = temp_dir.path().join("dir");
+        fs::create_dir(&dir).expect("Failed to create dir");
+        fs::copy(&small, dir.join("a.txt")).expect("Failed to copy");
+        fs::copy(&large, dir.join("b.txt")).expect("Failed to copy");
+        let options = CpOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let dest = temp_dir.path().join("dir_copy");
+        assert!(cp(&sandbox, &[&dir], &dest, &options).is_err());
+        assert!(!dest.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cp_directory_recursive_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path().canonicalize().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(root.clone()));
+
+        let source_dir = root.join("source_dir");
+        fs::create_dir_all(source_dir.join("sub")).expect("Failed to create source dir");
+        fs::write(source_dir.join("sub/file.txt"), "content").expect("Failed to create file");
+        symlink(&source_dir, source_dir.join("sub/loop")).unwrap();
+
+        let options = CpOptions {
+            recursive: true,
+            ..Default::default()
+        };
+
+        // A link back to an ancestor is not copied into itself forever
+        let result = cp(&sandbox, &[&source_dir], &root.join("dest_dir"), &options).unwrap();
+        assert_eq!(result.bytes_copied, 7);
+        assert!(root.join("dest_dir/sub/file.txt").exists());
+
+        // Links leaving the sandbox are refused
+        let outside = TempDir::new().expect("Failed to create temp dir");
+        fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        symlink(outside.path().join("secret.txt"), source_dir.join("secret")).unwrap();
+        let result = cp(&sandbox, &[&source_dir], &root.join("dest_two"), &options);
+        assert!(result.is_err());
+        assert!(!root.join("dest_two/secret").exists());
+    }
+
     #[test]
     fn test_cp_directory_without_recursive_fails() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
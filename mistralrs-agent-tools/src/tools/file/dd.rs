@@ -0,0 +1,460 @@
+//! Dd utility - convert and copy files with block-level control
+//!
+//! Copies data between files in fixed-size blocks, optionally skipping input
+//! blocks, seeking in the output and converting data as it streams through.
+
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Default block size in bytes (like `bs=512`)
+const DEFAULT_BLOCK_SIZE: usize = 512;
+
+/// Conversions applied while copying (`conv=`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DdConv {
+    /// Convert ASCII lowercase letters to uppercase (`ucase`)
+    pub ucase: bool,
+    /// Convert ASCII uppercase letters to lowercase (`lcase`)
+    pub lcase: bool,
+    /// Do not truncate the output file (`notrunc`)
+    pub notrunc: bool,
+}
+
+impl DdConv {
+    /// Parse a comma-separated conversion list such as `"ucase,notrunc"`
+    ///
+    /// # Errors
+    /// Returns error for unknown conversions or for `ucase` combined with
+    /// `lcase`.
+    pub fn parse(spec: &str) -> AgentResult<Self> {
+        let mut conv = Self::default();
+        for name in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match name {
+                "ucase" => conv.ucase = true,
+                "lcase" => conv.lcase = true,
+                "notrunc" => conv.notrunc = true,
+                other => {
+                    return Err(AgentError::InvalidInput(format!(
+                        "Unknown dd conversion: {}",
+                        other
+                    )))
+                }
+            }
+        }
+        conv.validate()?;
+        Ok(conv)
+    }
+
+    fn validate(&self) -> AgentResult<()> {
+        if self.ucase && self.lcase {
+            return Err(AgentError::InvalidInput(
+                "dd conversions 'ucase' and 'lcase' are mutually exclusive".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Apply the byte conversions in place; non-ASCII bytes are untouched
+    fn apply(&self, buf: &mut [u8]) {
+        if self.ucase {
+            buf.make_ascii_uppercase();
+        } else if self.lcase {
+            buf.make_ascii_lowercase();
+        }
+    }
+}
+
+/// Options for dd operation
+#[derive(Debug, Clone)]
+pub struct DdOptions {
+    /// Block size in bytes for reads and writes (`bs=`)
+    pub block_size: usize,
+    /// Copy at most this many input blocks (`count=`)
+    pub count: Option<u64>,
+    /// Skip this many blocks at the start of the input (`skip=`)
+    pub skip: u64,
+    /// Skip this many blocks at the start of the output (`seek=`)
+    pub seek: u64,
+    /// Conversions to apply (`conv=`)
+    pub conv: DdConv,
+}
+
+impl Default for DdOptions {
+    fn default() -> Self {
+        Self {
+            block_size: DEFAULT_BLOCK_SIZE,
+            count: None,
+            skip: 0,
+            seek: 0,
+            conv: DdConv::default(),
+        }
+    }
+}
+
+/// Result of dd operation
+#[derive(Debug, Clone, Default)]
+pub struct DdResult {
+    /// Number of full blocks read
+    pub full_blocks: u64,
+    /// Number of partial blocks read
+    pub partial_blocks: u64,
+    /// Total bytes copied
+    pub bytes: u64,
+    /// Whether this was a dry run, so nothing was written to the output
+    pub dry_run: bool,
+}
+
+/// Copy `input` to `output` block by block
+///
+/// In dry-run mode the input is still read and counted, but the output file
+/// is neither created nor written.
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `input` - File to read from
+/// * `output` - File to write to (created if missing)
+/// * `options` - Dd options
+///
+/// # Returns
+/// Result containing block and byte counts
+///
+/// # Errors
+/// Returns error if:
+/// - Either path is outside sandbox
+/// - Block size is zero or conversions conflict
+/// - The skip or seek offset overflows
+/// - The copy would exceed the sandbox write size limit or quota
+/// - Input cannot be read or output cannot be written
+pub fn dd(
+    sandbox: &Sandbox,
+    input: &Path,
+    output: &Path,
+    options: &DdOptions,
+) -> AgentResult<DdResult> {
+    if options.block_size == 0 {
+        return Err(AgentError::InvalidInput(
+            "dd block size must be greater than zero".into(),
+        ));
+    }
+    options.conv.validate()?;
+
+    let input_path = sandbox.validate_read(input)?;
+    let output_path = sandbox.validate_write(output)?;
+    let bs = options.block_size as u64;
+    let skip_offset = block_offset("skip", options.skip, bs)?;
+    let seek_offset = block_offset("seek", options.seek, bs)?;
+
+    let mut reader = File::open(&input_path).map_err(|e| {
+        AgentError::io(format!(
+            "Failed to open input {}: {}",
+            input_path.display(),
+            e
+        ))
+    })?;
+
+    // Reject oversized copies before the output is created or written, so a
+    // limit never leaves a partial copy behind
+    let input_len = reader
+        .metadata()
+        .map_err(|e| {
+            AgentError::io(format!(
+                "Failed to stat input {}: {}",
+                input_path.display(),
+                e
+            ))
+        })?
+        .len();
+    let mut planned = input_len.saturating_sub(skip_offset);
+    if let Some(count) = options.count {
+        planned = planned.min(count.saturating_mul(bs));
+    }
+    sandbox.validate_write_size(planned)?;
+    sandbox.check_write_quota(planned)?;
+
+    let mut writer = if sandbox.is_dry_run() {
+        None
+    } else {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&output_path)
+            .map_err(|e| {
+                AgentError::io(format!(
+                    "Failed to open output {}: {}",
+                    output_path.display(),
+                    e
+                ))
+            })?;
+        Some(file)
+    };
+
+    let out_err = |e: std::io::Error| {
+        AgentError::io(format!("Failed to write {}: {}", output_path.display(), e))
+    };
+
+    reader.seek(SeekFrom::Start(skip_offset)).map_err(|e| {
+        AgentError::io(format!(
+            "Failed to skip input {}: {}",
+            input_path.display(),
+            e
+        ))
+    })?;
+    if let Some(writer) = writer.as_mut() {
+        writer.seek(SeekFrom::Start(seek_offset)).map_err(out_err)?;
+    }
+
+    let mut result = DdResult {
+        dry_run: sandbox.is_dry_run(),
+        ..Default::default()
+    };
+    let mut buf = vec![0u8; options.block_size];
+
+    while options
+        .count
+        .is_none_or(|count| result.full_blocks + result.partial_blocks < count)
+    {
+        let n = read_block(&mut reader, &mut buf).map_err(|e| {
+            AgentError::io(format!("Failed to read {}: {}", input_path.display(), e))
+        })?;
+        if n == 0 {
+            break;
+        }
+        if n == buf.len() {
+            result.full_blocks += 1;
+        } else {
+            result.partial_blocks += 1;
+        }
+
+        // The input may have grown since it was measured
+        sandbox.validate_write_size(result.bytes + n as u64)?;
+        sandbox.check_write_quota(n as u64)?;
+
+        let block = &mut buf[..n];
+        options.conv.apply(block);
+        if let Some(writer) = writer.as_mut() {
+            writer.write_all(block).map_err(out_err)?;
+            sandbox.record_write(n as u64);
+        }
+        result.bytes += n as u64;
+    }
+
+    if let Some(mut writer) = writer {
+        // Without notrunc, nothing past the copied data survives
+        if !options.conv.notrunc {
+            let end = seek_offset
+                .checked_add(result.bytes)
+                .ok_or_else(|| AgentError::InvalidInput("dd output size overflows".into()))?;
+            writer.set_len(end).map_err(out_err)?;
+        }
+        writer.flush().map_err(out_err)?;
+    }
+
+    Ok(result)
+}
+
+/// Byte offset of `blocks` blocks of `bs` bytes, for the named operand
+fn block_offset(operand: &str, blocks: u64, bs: u64) -> AgentResult<u64> {
+    blocks.checked_mul(bs).ok_or_else(|| {
+        AgentError::InvalidInput(format!(
+            "dd {}={} overflows with a block size of {}",
+            operand, blocks, bs
+        ))
+    })
+}
+
+/// Fill `buf` from `reader`, stopping early only at end of input
+fn read_block(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dd_copy_blocks() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let input = temp_dir.path().join("in.bin");
+        let output = temp_dir.path().join("out.bin");
+        fs::write(&input, "0123456789").expect("Failed to create test file");
+
+        let options = DdOptions {
+            block_size: 4,
+            skip: 1,
+            count: Some(1),
+            ..Default::default()
+        };
+        let result = dd(&sandbox, &input, &output, &options).expect("dd failed");
+
+        assert_eq!(result.full_blocks, 1);
+        assert_eq!(result.bytes, 4);
+        assert_eq!(fs::read(&output).expect("read failed"), b"4567");
+    }
+
+    #[test]
+    fn test_dd_offset_overflow() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let input = temp_dir.path().join("in.bin");
+        let output = temp_dir.path().join("out.bin");
+        fs::write(&input, "0123456789").expect("Failed to create test file");
+
+        for options in [
+            DdOptions {
+                skip: u64::MAX,
+                ..Default::default()
+            },
+            DdOptions {
+                seek: u64::MAX / 2,
+                ..Default::default()
+            },
+        ] {
+            let err = dd(&sandbox, &input, &output, &options).unwrap_err();
+            assert!(matches!(err, AgentError::InvalidInput(_)));
+        }
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_dd_conv_parse() {
+        let conv = DdConv::parse("ucase,notrunc").expect("parse failed");
+        assert!(conv.ucase && conv.notrunc && !conv.lcase);
+        assert!(DdConv::parse("ucase,lcase").is_err());
+        assert!(DdConv::parse("sparse").is_err());
+    }
+
+    #[test]
+    fn test_dd_conv_ucase() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let input = temp_dir.path().join("in.txt");
+        let output = temp_dir.path().join("out.txt");
+        fs::write(&input, "Hello, Wörld 42!").expect("Failed to create test file");
+
+        let options = DdOptions {
+            block_size: 3,
+            conv: DdConv::parse("ucase").expect("parse failed"),
+            ..Default::default()
+        };
+        dd(&sandbox, &input, &output, &options).expect("dd failed");
+
+        assert_eq!(
+            fs::read_to_string(&output).expect("read failed"),
+            "HELLO, WöRLD 42!"
+        );
+    }
+
+    #[test]
+    fn test_dd_conv_notrunc() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let input = temp_dir.path().join("in.txt");
+        let output = temp_dir.path().join("out.txt");
+        fs::write(&input, "abc").expect("Failed to create test file");
+        fs::write(&output, "0123456789").expect("Failed to create test file");
+
+        let options = DdOptions {
+            conv: DdConv::parse("lcase,notrunc").expect("parse failed"),
+            ..Default::default()
+        };
+        dd(&sandbox, &input, &output, &options).expect("dd failed");
+        assert_eq!(
+            fs::read_to_string(&output).expect("read failed"),
+            "abc3456789"
+        );
+
+        dd(&sandbox, &input, &output, &DdOptions::default()).expect("dd failed");
+        assert_eq!(fs::read_to_string(&output).expect("read failed"), "abc");
+    }
+
+    #[test]
+    fn test_dd_over_write_limit_leaves_output_untouched() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config = SandboxConfig::builder()
+            .root(temp_dir.path())
+            .max_write_bytes(6)
+            .build()
+            .expect("Failed to build config");
+        let sandbox = Sandbox::new(config);
+
+        let input = temp_dir.path().join("in.txt");
+        let existing = temp_dir.path().join("existing.txt");
+        let missing = temp_dir.path().join("missing.txt");
+        fs::write(&input, "0123456789").expect("Failed to create test file");
+        fs::write(&existing, "abc").expect("Failed to create test file");
+
+        // Two 4-byte blocks fit, the third would not: nothing is written
+        let options = DdOptions {
+            block_size: 4,
+            ..Default::default()
+        };
+        assert!(dd(&sandbox, &input, &existing, &options).is_err());
+        assert_eq!(fs::read_to_string(&existing).expect("read failed"), "abc");
+        assert!(dd(&sandbox, &input, &missing, &options).is_err());
+        assert!(!missing.exists());
+        assert_eq!(sandbox.bytes_written(), 0);
+
+        // Skipping a block brings the copy under the limit
+        let options = DdOptions {
+            block_size: 4,
+            skip: 1,
+            ..Default::default()
+        };
+        let result = dd(&sandbox, &input, &existing, &options).expect("dd failed");
+        assert_eq!(result.bytes, 6);
+        assert_eq!(
+            fs::read_to_string(&existing).expect("read failed"),
+            "456789"
+        );
+    }
+
+    #[test]
+    fn test_dd_dry_run_writes_nothing() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config = SandboxConfig::builder()
+            .root(temp_dir.path())
+            .dry_run(true)
+            .build()
+            .expect("Failed to build config");
+        let sandbox = Sandbox::new(config);
+
+        let input = temp_dir.path().join("in.txt");
+        let existing = temp_dir.path().join("existing.txt");
+        let missing = temp_dir.path().join("missing.txt");
+        fs::write(&input, "hello").expect("Failed to create test file");
+        fs::write(&existing, "0123456789").expect("Failed to create test file");
+
+        let result = dd(&sandbox, &input, &existing, &DdOptions::default()).expect("dd failed");
+        assert!(result.dry_run);
+        assert_eq!(result.bytes, 5);
+        assert_eq!(
+            fs::read_to_string(&existing).expect("read failed"),
+            "0123456789"
+        );
+
+        dd(&sandbox, &input, &missing, &DdOptions::default()).expect("dd failed");
+        assert!(!missing.exists());
+        assert_eq!(sandbox.bytes_written(), 0);
+    }
+}
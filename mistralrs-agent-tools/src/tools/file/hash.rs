@@ -0,0 +1,268 @@
+//! Hash utility - compute file checksums
+//!
+//! Streams a file through MD5, SHA-1 or SHA-256 and reports the digest in
+//! the same format as `md5sum`, `sha1sum` and `sha256sum`, and verifies
+//! checksum files in that format (`sha256sum -c`).
+
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Read buffer size used while hashing
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Supported checksum algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgo {
+    /// MD5 (`md5sum`)
+    Md5,
+    /// SHA-1 (`sha1sum`)
+    Sha1,
+    /// SHA-256 (`sha256sum`)
+    Sha256,
+}
+
+impl FromStr for HashAlgo {
+    type Err = AgentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "").as_str() {
+            "md5" => Ok(Self::Md5),
+            "sha1" => Ok(Self::Sha1),
+            "sha256" => Ok(Self::Sha256),
+            other => Err(AgentError::InvalidInput(format!(
+                "Unknown hash algorithm: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Compute the checksum of a file
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `path` - File to hash
+/// * `algo` - Hash algorithm
+///
+/// # Returns
+/// * `"<hex digest>  <path>"`, as printed by `md5sum`/`sha256sum`
+pub fn hash_file(sandbox: &Sandbox, path: &Path, algo: HashAlgo) -> AgentResult<String> {
+    let validated_path = sandbox.validate_read(path)?;
+    if validated_path.is_dir() {
+        return Err(AgentError::InvalidInput(format!(
+            "Is a directory: {}",
+            path.display()
+        )));
+    }
+
+    let digest = digest_file(&validated_path, algo)
+        .map_err(|e| AgentError::io(format!("Failed to read {}: {}", path.display(), e)))?;
+
+    Ok(format!("{}  {}", digest, path.display()))
+}
+
+/// Verify the files listed in a checksum file (`sha256sum -c`)
+///
+/// Each line holds `<hex digest>  <path>` (or `<hex digest> *<path>`), and
+/// the algorithm is inferred from the digest length. Relative paths are
+/// resolved against the checksum file's directory. Files that are missing or
+/// unreadable are reported as failures instead of aborting the run.
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `checksum_file` - File listing the expected digests
+///
+/// # Returns
+/// * Each listed path with whether its digest matched
+///
+/// # Errors
+/// Returns error for malformed lines or listed paths outside the sandbox.
+pub fn verify_checksums(
+    sandbox: &Sandbox,
+    checksum_file: &Path,
+) -> AgentResult<Vec<(PathBuf, bool)>> {
+    let validated_path = sandbox.validate_read(checksum_file)?;
+    let base_dir = validated_path
+        .parent()
+        .unwrap_or(sandbox.root())
+        .to_path_buf();
+    let reader = BufReader::new(File::open(&validated_path)?);
+
+    let mut results = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (expected, name) = parse_checksum_line(&line).ok_or_else(|| {
+            AgentError::InvalidInput(format!(
+                "{}:{}: improperly formatted checksum line",
+                checksum_file.display(),
+                index + 1
+            ))
+        })?;
+
+        let listed = PathBuf::from(name);
+        let matched = match sandbox.validate_read(&base_dir.join(&listed)) {
+            Ok(target) => digest_file(&target, algo_for_digest(expected)?)
+                .is_ok_and(|actual| actual.eq_ignore_ascii_case(expected)),
+            Err(AgentError::SandboxViolation(msg)) => {
+                return Err(AgentError::SandboxViolation(msg))
+            }
+            Err(_) => false,
+        };
+        results.push((listed, matched));
+    }
+
+    Ok(results)
+}
+
+/// Split a checksum line into its digest and path
+fn parse_checksum_line(line: &str) -> Option<(&str, &str)> {
+    let (digest, rest) = line.split_once(' ')?;
+    // Text mode uses a second space, binary mode a `*`
+    let name = rest.strip_prefix(' ').or_else(|| rest.strip_prefix('*'))?;
+    let is_hex = !digest.is_empty() && digest.chars().all(|c| c.is_ascii_hexdigit());
+    (is_hex && !name.is_empty()).then_some((digest, name))
+}
+
+/// Infer the algorithm from the length of a hex digest
+fn algo_for_digest(digest: &str) -> AgentResult<HashAlgo> {
+    match digest.len() {
+        32 => Ok(HashAlgo::Md5),
+        40 => Ok(HashAlgo::Sha1),
+        64 => Ok(HashAlgo::Sha256),
+        len => Err(AgentError::InvalidInput(format!(
+            "Unrecognized digest length: {}",
+            len
+        ))),
+    }
+}
+
+/// Hash a file with the given algorithm
+fn digest_file(path: &Path, algo: HashAlgo) -> std::io::Result<String> {
+    let file = File::open(path)?;
+    match algo {
+        HashAlgo::Md5 => digest_reader::<Md5>(file),
+        HashAlgo::Sha1 => digest_reader::<Sha1>(file),
+        HashAlgo::Sha256 => digest_reader::<Sha256>(file),
+    }
+}
+
+/// Stream a reader through the hasher and return the lowercase hex digest
+fn digest_reader<D: Digest>(mut reader: impl Read) -> std::io::Result<String> {
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    let mut hex = String::new();
+    for byte in hasher.finalize() {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    Ok(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use tempfile::TempDir;
+
+    fn hash_content(content: &[u8], algo: HashAlgo) -> (String, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.txt");
+        std::fs::write(&path, content).unwrap();
+
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        let line = hash_file(&sandbox, &path, algo).unwrap();
+        (line, path.display().to_string())
+    }
+
+    #[test]
+    fn test_hash_file_known_digests() {
+        let cases = [
+            (HashAlgo::Md5, "5d41402abc4b2a76b9719d911017c592"),
+            (HashAlgo::Sha1, "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d"),
+            (
+                HashAlgo::Sha256,
+                "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+            ),
+        ];
+
+        for (algo, expected) in cases {
+            let (line, path) = hash_content(b"hello", algo);
+            assert_eq!(line, format!("{}  {}", expected, path));
+        }
+    }
+
+    #[test]
+    fn test_hash_file_spans_buffers() {
+        // Larger than one read buffer so the digest covers several chunks
+        let content = vec![b'a'; HASH_BUFFER_SIZE * 2 + 7];
+        let (line, _) = hash_content(&content, HashAlgo::Sha256);
+
+        let expected = format!("{:x}", Sha256::digest(&content));
+        assert!(line.starts_with(&format!("{}  ", expected)));
+    }
+
+    #[test]
+    fn test_verify_checksums_reports_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        let mut listing = String::new();
+        for name in ["good.txt", "corrupt.txt", "missing.txt"] {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, name).unwrap();
+            let line = hash_file(&sandbox, &path, HashAlgo::Sha256).unwrap();
+            let digest = line.split_whitespace().next().unwrap();
+            listing.push_str(&format!("{}  {}\n", digest, name));
+        }
+        let checksum_file = temp_dir.path().join("SHA256SUMS");
+        std::fs::write(&checksum_file, listing).unwrap();
+
+        std::fs::write(temp_dir.path().join("corrupt.txt"), "tampered").unwrap();
+        std::fs::remove_file(temp_dir.path().join("missing.txt")).unwrap();
+
+        let results = verify_checksums(&sandbox, &checksum_file).unwrap();
+        assert_eq!(
+            results,
+            vec![
+                (PathBuf::from("good.txt"), true),
+                (PathBuf::from("corrupt.txt"), false),
+                (PathBuf::from("missing.txt"), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_checksums_rejects_malformed_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        let checksum_file = temp_dir.path().join("SUMS");
+        std::fs::write(&checksum_file, "not a checksum line\n").unwrap();
+
+        let result = verify_checksums(&sandbox, &checksum_file);
+        assert!(matches!(result, Err(AgentError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_hash_algo_from_str() {
+        assert_eq!("MD5".parse::<HashAlgo>().unwrap(), HashAlgo::Md5);
+        assert_eq!("sha-256".parse::<HashAlgo>().unwrap(), HashAlgo::Sha256);
+        assert!("crc32".parse::<HashAlgo>().is_err());
+    }
+}
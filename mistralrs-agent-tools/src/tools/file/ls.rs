@@ -2,14 +2,24 @@
 //!
 //! Simplified implementation for agent tools.
 
+use super::walk::{walk, WalkOptions};
 use crate::tools::sandbox::Sandbox;
-use crate::types::{AgentResult, FileEntry, LsOptions, LsResult};
+use crate::types::{
+    AgentError, AgentResult, FileEntry, FileKind, LsOptions, LsResult, LsTimeFormat,
+};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write;
 use std::fs;
 use std::path::Path;
 use std::time::SystemTime;
 
 /// List directory contents
 pub fn ls(sandbox: &Sandbox, path: &Path, options: &LsOptions) -> AgentResult<LsResult> {
+    if let Some(format) = &options.time_format {
+        validate_time_format(format)?;
+    }
+
     // Validate path through sandbox
     let validated_path = sandbox.validate_read(path)?;
 
@@ -17,7 +27,7 @@ pub fn ls(sandbox: &Sandbox, path: &Path, options: &LsOptions) -> AgentResult<Ls
     let metadata = fs::metadata(&validated_path)?;
     if !metadata.is_dir() {
         // If it's a file, just return that file's info
-        let entry = create_file_entry(&validated_path)?;
+        let entry = create_file_entry(&validated_path, options, sandbox)?;
         return Ok(LsResult {
             entries: vec![entry.clone()],
             total: 1,
@@ -31,7 +41,7 @@ pub fn ls(sandbox: &Sandbox, path: &Path, options: &LsOptions) -> AgentResult<Ls
     if options.recursive {
         collect_recursive(&validated_path, &mut entries, options, sandbox)?;
     } else {
-        collect_dir(&validated_path, &mut entries, options)?;
+        collect_dir(&validated_path, &mut entries, options, sandbox)?;
     }
 
     // Sort entries
@@ -53,6 +63,7 @@ fn collect_dir(
     dir_path: &Path,
     entries: &mut Vec<FileEntry>,
     options: &LsOptions,
+    sandbox: &Sandbox,
 ) -> AgentResult<()> {
     let dir_entries = fs::read_dir(dir_path)?;
 
@@ -71,69 +82,60 @@ fn collect_dir(
             }
         }
 
-        entries.push(create_file_entry(&path)?);
+        entries.push(create_file_entry(&path, options, sandbox)?);
     }
 
     Ok(())
 }
 
 /// Collect entries recursively
+///
+/// Hidden entries (and their contents) are skipped unless `all` is set, and
+/// symlinked directories are only entered with `dereference`.
 fn collect_recursive(
     dir_path: &Path,
     entries: &mut Vec<FileEntry>,
     options: &LsOptions,
     sandbox: &Sandbox,
 ) -> AgentResult<()> {
-    use std::collections::HashSet;
-    let mut visited = HashSet::new();
-    collect_recursive_impl(dir_path, entries, options, sandbox, &mut visited)
-}
-
-/// Internal recursive implementation with visited tracking
-fn collect_recursive_impl(
-    dir_path: &Path,
-    entries: &mut Vec<FileEntry>,
-    options: &LsOptions,
-    sandbox: &Sandbox,
-    visited: &mut std::collections::HashSet<std::path::PathBuf>,
-) -> AgentResult<()> {
-    // Canonicalize path to handle symlinks
-    let canonical = dir_path
-        .canonicalize()
-        .unwrap_or_else(|_| dir_path.to_path_buf());
-
-    // Prevent infinite recursion
-    if visited.contains(&canonical) {
-        return Ok(());
-    }
-    visited.insert(canonical);
-
-    // Store current entry count
-    let start_idx = entries.len();
-
-    collect_dir(dir_path, entries, options)?;
-
-    // Get subdirectories from newly added entries only
-    let subdirs: Vec<_> = entries[start_idx..]
-        .iter()
-        .filter(|e| e.is_dir)
-        .map(|e| e.path.clone())
-        .collect();
-
-    // Recurse into subdirectories
-    for subdir in subdirs {
-        // Validate subdirectory is still in sandbox
-        if sandbox.validate_read(&subdir).is_ok() {
-            collect_recursive_impl(&subdir, entries, options, sandbox, visited)?;
+    let walk_options = WalkOptions {
+        follow_symlinks: options.dereference,
+        skip_hidden: !options.all,
+        ..Default::default()
+    };
+    walk(sandbox, dir_path, &walk_options, |entry| {
+        if entry.depth() > 0 {
+            entries.push(create_file_entry(entry.path(), options, sandbox)?);
         }
-    }
-
-    Ok(())
+        Ok(())
+    })
 }
 
 /// Create a FileEntry from a path
-fn create_file_entry(path: &Path) -> AgentResult<FileEntry> {
-    let metadata = fs::metadata(path)?;
+///
+/// Symlinks describe themselves unless `dereference` is set, in which case
+/// they describe their target if it resolves inside the sandbox.
+fn create_file_entry(
+    path: &Path,
+    options: &LsOptions,
+    sandbox: &Sandbox,
+) -> AgentResult<FileEntry> {
+    let link_metadata = fs::symlink_metadata(path)?;
+    let is_symlink = link_metadata.file_type().is_symlink();
+    let target_metadata = if is_symlink && options.dereference {
+        sandbox
+            .validate_read(path)
+            .ok()
+            .and_then(|target| fs::metadata(target).ok())
+    } else {
+        None
+    };
+    let link_target = if is_symlink && target_metadata.is_none() {
+        fs::read_link(path).ok()
+    } else {
+        None
+    };
+    let metadata = target_metadata.as_ref().unwrap_or(&link_metadata);
 
     let name = path
         .file_name()
@@ -147,16 +149,20 @@ fn create_file_entry(path: &Path) -> AgentResult<FileEntry> {
         .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
         .map(|d| d.as_secs());
 
-    // Get permissions (Unix-style, 0 on Windows)
-    let permissions = {
+    // Get permissions, link count and blocks (Unix only)
+    let (permissions, nlink, blocks) = {
         #[cfg(unix)]
         {
-            use std::os::unix::fs::PermissionsExt;
-            metadata.permissions().mode()
+            use std::os::unix::fs::MetadataExt;
+            (
+                metadata.mode(),
+                Some(metadata.nlink()),
+                Some(metadata.blocks()),
+            )
         }
         #[cfg(not(unix))]
         {
-            0
+            (0, None, None)
         }
     };
 
@@ -167,9 +173,53 @@ fn create_file_entry(path: &Path) -> AgentResult<FileEntry> {
         size: metadata.len(),
         modified,
         permissions,
+        nlink,
+        blocks,
+        kind: classify(path, metadata),
+        link_target,
     })
 }
 
+/// Classify an entry from its unfollowed metadata
+fn classify(path: &Path, metadata: &fs::Metadata) -> FileKind {
+    let file_type = metadata.file_type();
+    if file_type.is_symlink() {
+        FileKind::Symlink
+    } else if file_type.is_dir() {
+        FileKind::Directory
+    } else if is_executable(path, metadata) {
+        FileKind::Executable
+    } else {
+        FileKind::Regular
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(_path: &Path, metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path, _metadata: &fs::Metadata) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ["exe", "bat", "cmd", "com", "ps1"]
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Entry name with the `-F` indicator appended when `classify` is set
+fn display_name(entry: &FileEntry, options: &LsOptions) -> String {
+    if options.classify {
+        format!("{}{}", entry.name, entry.kind.indicator())
+    } else {
+        entry.name.clone()
+    }
+}
+
 /// Sort entries based on options
 fn sort_entries(entries: &mut [FileEntry], options: &LsOptions) {
     if options.sort_by_time {
@@ -194,6 +244,169 @@ fn sort_entries(entries: &mut [FileEntry], options: &LsOptions) {
     }
 }
 
+/// Render entries one name per line, as plain `ls` (or `ls -F` with `classify`)
+pub fn format_short(result: &LsResult, options: &LsOptions) -> String {
+    let mut output = String::new();
+    for entry in &result.entries {
+        output.push_str(&display_name(entry, options));
+        output.push('\n');
+    }
+    output
+}
+
+/// Render entries in long format (`ls -l`)
+///
+/// The listing starts with the `total` line coreutils prints: allocated space
+/// in 1K blocks, estimated from the size where block counts are unavailable.
+/// Each entry line has the permission string, link count, size, modification
+/// time (`YYYY-MM-DD HH:MM`, UTC, unless `time_format` is set) and name,
+/// followed by `-> target` for symlinks that were not dereferenced.
+pub fn format_long(result: &LsResult, options: &LsOptions) -> String {
+    let total_blocks: u64 = result
+        .entries
+        .iter()
+        .map(|e| e.blocks.map_or(e.size.div_ceil(1024), |b| b.div_ceil(2)))
+        .sum();
+
+    let sizes: Vec<String> = result
+        .entries
+        .iter()
+        .map(|e| format_size(e.size, options.human_readable))
+        .collect();
+    let size_width = sizes.iter().map(String::len).max().unwrap_or(0);
+    let nlink_width = result
+        .entries
+        .iter()
+        .map(|e| e.nlink.unwrap_or(1).to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let times: Vec<String> = result
+        .entries
+        .iter()
+        .map(|e| {
+            e.modified.map_or_else(
+                || "-".to_string(),
+                |secs| format_time(secs, options.time_format.as_ref()),
+            )
+        })
+        .collect();
+    let time_width = times.iter().map(|t| t.chars().count()).max().unwrap_or(0);
+
+    let mut output = format!("total {}\n", total_blocks);
+    for ((entry, size), time) in result.entries.iter().zip(&sizes).zip(&times) {
+        let mut permissions = format_permissions(entry.permissions, entry.is_dir);
+        if entry.kind == FileKind::Symlink {
+            permissions.replace_range(..1, "l");
+        }
+        let mut name = display_name(entry, options);
+        if let Some(target) = &entry.link_target {
+            let _ = write!(name, " -> {}", target.display());
+        }
+        let _ = writeln!(
+            output,
+            "{} {:>nlink_width$} {:>size_width$} {:<time_width$} {}",
+            permissions,
+            entry.nlink.unwrap_or(1),
+            size,
+            time,
+            name,
+        );
+    }
+    output
+}
+
+/// Format a Unix mode as an `ls -l` permission string (e.g. `drwxr-xr-x`)
+pub fn format_permissions(mode: u32, is_dir: bool) -> String {
+    let mut perms = String::with_capacity(10);
+    perms.push(if is_dir { 'd' } else { '-' });
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        perms.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        perms.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        perms.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    perms
+}
+
+/// Classic `ls` timestamp layout, `YYYY-MM-DD HH:MM` in UTC
+const CLASSIC_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Format a modification time (Unix seconds) for display
+///
+/// `None` gives the classic `YYYY-MM-DD HH:MM`. A custom pattern is expected
+/// to have been checked by [`ls`]; an invalid one falls back to the classic
+/// format.
+pub fn format_time(secs: u64, format: Option<&LsTimeFormat>) -> String {
+    // Timestamps chrono can't represent are shown as raw seconds
+    let Some(time) = i64::try_from(secs)
+        .ok()
+        .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+    else {
+        return secs.to_string();
+    };
+
+    match format {
+        None => time.format(CLASSIC_TIME_FORMAT).to_string(),
+        Some(LsTimeFormat::Iso) => time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        Some(LsTimeFormat::Relative) => {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            format_relative(secs, now)
+        }
+        Some(LsTimeFormat::Custom(pattern)) => {
+            let mut output = String::new();
+            if write!(output, "{}", time.format(pattern)).is_ok() {
+                output
+            } else {
+                time.format(CLASSIC_TIME_FORMAT).to_string()
+            }
+        }
+    }
+}
+
+/// Reject custom strftime patterns chrono can't render
+fn validate_time_format(format: &LsTimeFormat) -> AgentResult<()> {
+    if let LsTimeFormat::Custom(pattern) = format {
+        if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+            return Err(AgentError::InvalidInput(format!(
+                "Invalid time format pattern: {}",
+                pattern
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Describe `secs` relative to `now`, e.g. `5 minutes ago` or `in 2 days`
+fn format_relative(secs: u64, now: u64) -> String {
+    const UNITS: &[(u64, &str)] = &[
+        (365 * 86_400, "year"),
+        (30 * 86_400, "month"),
+        (86_400, "day"),
+        (3600, "hour"),
+        (60, "minute"),
+    ];
+
+    let (delta, future) = if secs > now {
+        (secs - now, true)
+    } else {
+        (now - secs, false)
+    };
+    let Some(&(size, unit)) = UNITS.iter().find(|(size, _)| delta >= *size) else {
+        return "just now".to_string();
+    };
+
+    let count = delta / size;
+    let plural = if count == 1 { "" } else { "s" };
+    if future {
+        format!("in {count} {unit}{plural}")
+    } else {
+        format!("{count} {unit}{plural} ago")
+    }
+}
+
 /// Format file size as human-readable
 pub fn format_size(size: u64, human_readable: bool) -> String {
     if !human_readable {
@@ -346,6 +559,116 @@ mod tests {
         assert!(!result.entries[0].is_dir);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_ls_classify_suffixes() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let (sandbox, temp_dir) = create_test_sandbox();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("docs")).unwrap();
+        File::create(root.join("notes.txt")).unwrap();
+        fs::write(root.join("run.sh"), "#!/bin/sh\n").unwrap();
+        fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        symlink(root.join("notes.txt"), root.join("link")).unwrap();
+
+        let options = LsOptions {
+            classify: true,
+            ..Default::default()
+        };
+        let result = ls(&sandbox, root, &options).unwrap();
+        let kinds: Vec<_> = result.entries.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FileKind::Directory,
+                FileKind::Symlink,
+                FileKind::Regular,
+                FileKind::Executable
+            ]
+        );
+        assert_eq!(
+            format_short(&result, &options),
+            "docs/\nlink@\nnotes.txt\nrun.sh*\n"
+        );
+
+        let long = format_long(&result, &options);
+        assert!(long.lines().any(|line| line.ends_with(" run.sh*")));
+
+        let plain = LsOptions::default();
+        assert_eq!(
+            format_short(&result, &plain),
+            "docs\nlink\nnotes.txt\nrun.sh\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ls_symlink_dereference() {
+        use std::os::unix::fs::symlink;
+
+        let (sandbox, temp_dir) = create_test_sandbox();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("list")).unwrap();
+        fs::create_dir(root.join("shared")).unwrap();
+        File::create(root.join("shared/inner.txt")).unwrap();
+        fs::write(root.join("list/data.txt"), "hello world").unwrap();
+        symlink("data.txt", root.join("list/link")).unwrap();
+        symlink("../shared", root.join("list/dirlink")).unwrap();
+        symlink("/etc", root.join("list/outside")).unwrap();
+
+        let entry = |result: &LsResult, name: &str| {
+            result
+                .entries
+                .iter()
+                .find(|e| e.name == name)
+                .cloned()
+                .unwrap()
+        };
+
+        // Links describe themselves by default
+        let mut options = LsOptions {
+            long: true,
+            recursive: true,
+            ..Default::default()
+        };
+        let result = ls(&sandbox, &root.join("list"), &options).unwrap();
+        let link = entry(&result, "link");
+        assert_eq!(link.kind, FileKind::Symlink);
+        assert_eq!(link.size, "data.txt".len() as u64);
+        assert_eq!(link.link_target, Some("data.txt".into()));
+        assert!(!entry(&result, "dirlink").is_dir);
+        assert!(!result.entries.iter().any(|e| e.name == "inner.txt"));
+
+        let long = format_long(&result, &options);
+        let line = long
+            .lines()
+            .find(|l| l.ends_with(" link -> data.txt"))
+            .unwrap();
+        assert!(line.starts_with('l'));
+        assert!(long.contains(" dirlink -> ../shared"));
+
+        // Dereferenced links take their target's metadata
+        options.dereference = true;
+        let result = ls(&sandbox, &root.join("list"), &options).unwrap();
+        let link = entry(&result, "link");
+        assert_eq!(link.kind, FileKind::Regular);
+        assert_eq!(link.size, 11);
+        assert_eq!(link.link_target, None);
+        let dirlink = entry(&result, "dirlink");
+        assert!(dirlink.is_dir);
+        assert_eq!(dirlink.kind, FileKind::Directory);
+        assert!(result.entries.iter().any(|e| e.name == "inner.txt"));
+
+        // Targets outside the sandbox are never followed
+        let outside = entry(&result, "outside");
+        assert_eq!(outside.kind, FileKind::Symlink);
+        assert_eq!(outside.link_target, Some("/etc".into()));
+
+        let long = format_long(&result, &options);
+        assert!(!long.contains("link -> data.txt"));
+    }
+
     #[test]
     fn test_format_size_human_readable() {
         assert_eq!(format_size(100, true), "100B");
@@ -355,6 +678,68 @@ mod tests {
         assert_eq!(format_size(1024 * 1024 * 1024, true), "1.0G");
     }
 
+    #[test]
+    fn test_format_permissions() {
+        assert_eq!(format_permissions(0o755, true), "drwxr-xr-x");
+        assert_eq!(format_permissions(0o100644, false), "-rw-r--r--");
+        assert_eq!(format_permissions(0, false), "----------");
+    }
+
+    #[test]
+    fn test_format_mtime() {
+        assert_eq!(format_time(0, None), "1970-01-01 00:00");
+        assert_eq!(format_time(1_710_504_000, None), "2024-03-15 12:00");
+        assert_eq!(format_time(951_782_400, None), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn test_ls_time_formats() {
+        let (sandbox, temp_dir) = create_test_sandbox();
+        let path = temp_dir.path().join("dated.txt");
+        let file = File::create(&path).unwrap();
+        let mtime = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_710_504_000);
+        file.set_modified(mtime).unwrap();
+
+        let mut options = LsOptions {
+            long: true,
+            time_format: Some(LsTimeFormat::Iso),
+            ..Default::default()
+        };
+        let result = ls(&sandbox, &path, &options).unwrap();
+        assert!(format_long(&result, &options).contains(" 2024-03-15T12:00:00Z dated.txt"));
+
+        options.time_format = Some(LsTimeFormat::Custom("%d/%m/%Y %Hh".to_string()));
+        let result = ls(&sandbox, &path, &options).unwrap();
+        assert!(format_long(&result, &options).contains(" 15/03/2024 12h dated.txt"));
+
+        // A file touched two hours ago
+        let recent = SystemTime::now() - std::time::Duration::from_secs(2 * 3600 + 30);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(recent)
+            .unwrap();
+        options.time_format = Some(LsTimeFormat::Relative);
+        let result = ls(&sandbox, &path, &options).unwrap();
+        assert!(format_long(&result, &options).contains(" 2 hours ago dated.txt"));
+
+        options.time_format = Some(LsTimeFormat::Custom("%Y-%Q".to_string()));
+        assert!(matches!(
+            ls(&sandbox, &path, &options),
+            Err(AgentError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_relative() {
+        let now = 1_000_000;
+        assert_eq!(format_relative(now - 30, now), "just now");
+        assert_eq!(format_relative(now - 60, now), "1 minute ago");
+        assert_eq!(format_relative(now - 3 * 86_400, now), "3 days ago");
+        assert_eq!(format_relative(now + 7200, now), "in 2 hours");
+    }
+
     #[test]
     fn test_format_size_bytes() {
         assert_eq!(format_size(100, false), "100");
@@ -2,34 +2,52 @@
 //!
 //! Implements core file system operations:
 //! - cat: Concatenate and display files
+//! - chmod: Change file mode bits (octal or symbolic)
 //! - cp: Copy files and directories
 //! - dd: Convert and copy files with block-level control
 //! - dir: List directory contents (DOS-style)
+//! - hash: Compute and verify file checksums (md5sum, sha1sum, sha256sum)
 //! - ln: Create links
 //! - ls: List directory contents (Unix-style)
 //! - mkdir: Create directories
 //! - mv: Move/rename files
 //! - rm: Remove files
 //! - rmdir: Remove directories
+//! - stat: Display file metadata
 //! - touch: Update file timestamps
 //! - vdir: Verbose directory listing
+//! - walk: Shared recursive traversal with depth and symlink-loop guards
 
 mod cat;
+mod chmod;
+mod confirm;
 mod cp;
+mod dd;
+mod hash;
 mod ls;
 mod mkdir;
+mod mv;
+mod rm;
+mod stat;
 mod touch;
+mod vdir;
+mod walk;
 // TODO @gemini: Implement remaining file operations
-// mod mv;
-// mod rm;
-// mod dd;
 // mod ln;
 // mod dir;
 // mod rmdir;
-// mod vdir;
 
-pub use cat::cat;
-pub use cp::cp;
-pub use ls::{format_size, ls};
+pub use cat::{cat, cat_glob};
+pub use chmod::{apply_mode, chmod, ChmodResult};
+pub use confirm::{ConfirmAction, ConfirmCallback, ConfirmRequest, SKIPPED_BY_CONFIRMATION};
+pub use cp::{cp, cp_with_progress, CpOptions, CpResult, ProgressCallback};
+pub use dd::{dd, DdConv, DdOptions, DdResult};
+pub use hash::{hash_file, verify_checksums, HashAlgo};
+pub use ls::{format_long, format_permissions, format_short, format_size, format_time, ls};
 pub use mkdir::mkdir;
-pub use touch::touch;
+pub use mv::{mv, MvOptions, MvResult};
+pub use rm::{rm, RmOptions, RmResult};
+pub use stat::{stat, stat_json, FileStat};
+pub use touch::{touch, touch_many, TouchManyResult, TouchOptions, TouchResult};
+pub use vdir::vdir;
+pub use walk::{walk, WalkOptions};
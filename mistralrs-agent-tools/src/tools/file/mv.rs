@@ -0,0 +1,331 @@
+//! Mv utility - move or rename files and directories
+//!
+//! Moves one or more sources to a destination path or into a destination
+//! directory. An optional confirmation callback lets the host approve each
+//! overwrite of an existing destination. In dry-run mode nothing is moved
+//! and the result lists what would be.
+
+use super::confirm::{
+    confirm, CallbackDebug, ConfirmAction, ConfirmCallback, ConfirmRequest, SKIPPED_BY_CONFIRMATION,
+};
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Reason reported for destinations kept because of `no_clobber`
+const SKIPPED_NO_CLOBBER: &str = "destination exists (no-clobber)";
+
+/// Options for mv operation
+#[derive(Default)]
+pub struct MvOptions {
+    /// Do not overwrite existing destinations (-n, --no-clobber)
+    pub no_clobber: bool,
+    /// Verbose output
+    pub verbose: bool,
+    /// Callback consulted before overwriting an existing destination
+    pub confirm: Option<ConfirmCallback>,
+}
+
+impl fmt::Debug for MvOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MvOptions")
+            .field("no_clobber", &self.no_clobber)
+            .field("verbose", &self.verbose)
+            .field("confirm", &CallbackDebug(&self.confirm))
+            .finish()
+    }
+}
+
+/// Result of mv operation
+#[derive(Debug, Clone)]
+pub struct MvResult {
+    /// Destination paths that sources were moved to
+    pub moved: Vec<String>,
+    /// Sources that were not moved, with the reason
+    pub skipped: Vec<(String, String)>,
+    /// Number of sources moved
+    pub count: usize,
+    /// Whether this was a dry run, so `moved` lists destinations that would be used
+    pub dry_run: bool,
+}
+
+/// Move or rename files and directories
+///
+/// With a single source, `dest` is the new path unless it is an existing
+/// directory, in which case the source is moved into it. With several
+/// sources, `dest` must be an existing directory.
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `sources` - Paths to move
+/// * `dest` - Destination path or directory
+/// * `options` - Mv options
+///
+/// # Returns
+/// Result containing moved and skipped paths
+///
+/// # Errors
+/// Returns error if:
+/// - Source or destination is outside sandbox
+/// - Source does not exist
+/// - Several sources are given and `dest` is not a directory
+/// - Permission denied
+pub fn mv(
+    sandbox: &Sandbox,
+    sources: &[&Path],
+    dest: &Path,
+    options: &MvOptions,
+) -> AgentResult<MvResult> {
+    if sources.is_empty() {
+        return Err(AgentError::validation("No source paths specified for mv"));
+    }
+
+    let validated_dest = sandbox.validate_write(dest)?;
+    let dest_is_dir = validated_dest.is_dir();
+
+    if sources.len() > 1 && !dest_is_dir {
+        return Err(AgentError::validation(format!(
+            "Target {} is not a directory",
+            validated_dest.display()
+        )));
+    }
+
+    let mut moved = Vec::new();
+    let mut skipped = Vec::new();
+
+    for source in sources {
+        // The source is removed from its location, so it must be writable
+        // A symlink is moved itself, never its target
+        let validated_source = sandbox.validate_write_entry(source)?;
+        if fs::symlink_metadata(&validated_source).is_err() {
+            return Err(AgentError::NotFound(format!(
+                "Cannot move {}: No such file or directory",
+                validated_source.display()
+            )));
+        }
+
+        let target = if dest_is_dir {
+            let name = validated_source.file_name().ok_or_else(|| {
+                AgentError::validation(format!(
+                    "Cannot move {}: invalid source name",
+                    validated_source.display()
+                ))
+            })?;
+            sandbox.validate_write_entry(&validated_dest.join(name))?
+        } else {
+            sandbox.validate_write_entry(dest)?
+        };
+
+        let source_str = validated_source.display().to_string();
+
+        if let Ok(existing) = fs::symlink_metadata(&target) {
+            if options.no_clobber {
+                skipped.push((source_str, SKIPPED_NO_CLOBBER.to_string()));
+                continue;
+            }
+
+            let request = ConfirmRequest {
+                action: ConfirmAction::Overwrite,
+                path: target.clone(),
+                source: Some(validated_source.clone()),
+                is_dir: existing.is_dir(),
+            };
+            if !confirm(options.confirm.as_ref(), request) {
+                skipped.push((source_str, SKIPPED_BY_CONFIRMATION.to_string()));
+                continue;
+            }
+        }
+
+        if !sandbox.is_dry_run() {
+            move_path(&validated_source, &target)?;
+        }
+
+        let target_str = target
+            .to_str()
+            .ok_or_else(|| AgentError::validation("Path contains invalid UTF-8"))?
+            .to_string();
+
+        if options.verbose {
+            eprintln!(
+                "{}",
+                sandbox
+                    .dry_run_message(format!("mv: renamed '{}' -> '{}'", source_str, target_str))
+            );
+        }
+
+        moved.push(target_str);
+    }
+
+    Ok(MvResult {
+        count: moved.len(),
+        moved,
+        skipped,
+        dry_run: sandbox.is_dry_run(),
+    })
+}
+
+/// Rename `source` to `target`, falling back to copy and delete for files
+/// when a plain rename fails (e.g. across filesystems)
+fn move_path(source: &Path, target: &Path) -> AgentResult<()> {
+    let rename_err = match fs::rename(source, target) {
+        Ok(()) => return Ok(()),
+        Err(e) => e,
+    };
+
+    let is_file = fs::symlink_metadata(source).is_ok_and(|metadata| metadata.is_file());
+    if !is_file {
+        return Err(AgentError::io(format!(
+            "Failed to move {} to {}: {}",
+            source.display(),
+            target.display(),
+            rename_err
+        )));
+    }
+
+    fs::copy(source, target)
+        .and_then(|_| fs::remove_file(source))
+        .map_err(|e| {
+            AgentError::io(format!(
+                "Failed to move {} to {}: {}",
+                source.display(),
+                target.display(),
+                e
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_mv_rename() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let source = temp_dir.path().join("old.txt");
+        let dest = temp_dir.path().join("new.txt");
+        fs::write(&source, "content").expect("Failed to create test file");
+
+        let result = mv(&sandbox, &[&source], &dest, &MvOptions::default()).expect("mv failed");
+
+        assert_eq!(result.count, 1);
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&dest).expect("read failed"), "content");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mv_symlink_moves_link() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let target = temp_dir.path().join("target.txt");
+        let link = temp_dir.path().join("link");
+        let moved = temp_dir.path().join("moved_link");
+        fs::write(&target, "content").expect("Failed to create test file");
+        std::os::unix::fs::symlink(&target, &link).expect("Failed to create symlink");
+
+        let result = mv(&sandbox, &[&link], &moved, &MvOptions::default()).expect("mv failed");
+
+        assert_eq!(result.count, 1);
+        assert!(result.moved[0].ends_with("moved_link"));
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert!(fs::symlink_metadata(&moved)
+            .expect("moved link missing")
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_link(&moved).expect("read_link failed"), target);
+        assert_eq!(fs::read_to_string(&target).expect("read failed"), "content");
+    }
+
+    #[test]
+    fn test_mv_into_directory() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let a = temp_dir.path().join("a.txt");
+        let b = temp_dir.path().join("b.txt");
+        let dir = temp_dir.path().join("dir");
+        fs::write(&a, "a").expect("Failed to create test file");
+        fs::write(&b, "b").expect("Failed to create test file");
+        fs::create_dir(&dir).expect("Failed to create test dir");
+
+        let result = mv(&sandbox, &[&a, &b], &dir, &MvOptions::default()).expect("mv failed");
+
+        assert_eq!(result.count, 2);
+        assert!(dir.join("a.txt").exists());
+        assert!(dir.join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_mv_no_clobber() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let source = temp_dir.path().join("source.txt");
+        let dest = temp_dir.path().join("dest.txt");
+        fs::write(&source, "new").expect("Failed to create test file");
+        fs::write(&dest, "old").expect("Failed to create test file");
+
+        let options = MvOptions {
+            no_clobber: true,
+            ..Default::default()
+        };
+        let result = mv(&sandbox, &[&source], &dest, &options).expect("mv failed");
+
+        assert_eq!(result.count, 0);
+        assert!(source.exists());
+        assert_eq!(fs::read_to_string(&dest).expect("read failed"), "old");
+    }
+
+    #[test]
+    fn test_mv_confirmation_denied() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let source = temp_dir.path().join("source.txt");
+        let dest = temp_dir.path().join("dest.txt");
+        fs::write(&source, "new").expect("Failed to create test file");
+        fs::write(&dest, "old").expect("Failed to create test file");
+
+        let options = MvOptions {
+            confirm: Some(Box::new(|request| {
+                assert_eq!(request.action, ConfirmAction::Overwrite);
+                false
+            })),
+            ..Default::default()
+        };
+        let result = mv(&sandbox, &[&source], &dest, &options).expect("mv failed");
+
+        assert_eq!(result.count, 0);
+        assert_eq!(result.skipped[0].1, SKIPPED_BY_CONFIRMATION);
+        assert!(source.exists());
+        assert_eq!(fs::read_to_string(&dest).expect("read failed"), "old");
+    }
+
+    #[test]
+    fn test_mv_confirmation_approved() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let source = temp_dir.path().join("source.txt");
+        let dest = temp_dir.path().join("dest.txt");
+        fs::write(&source, "new").expect("Failed to create test file");
+        fs::write(&dest, "old").expect("Failed to create test file");
+
+        let options = MvOptions {
+            confirm: Some(Box::new(|_| true)),
+            ..Default::default()
+        };
+        let result = mv(&sandbox, &[&source], &dest, &options).expect("mv failed");
+
+        assert_eq!(result.count, 1);
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&dest).expect("read failed"), "new");
+    }
+}
@@ -0,0 +1,413 @@
+//! Rm utility - remove files and directories
+//!
+//! Removes files, and directory trees when recursive removal is requested.
+//! An optional confirmation callback lets the host approve each removal.
+//! In dry-run mode nothing is removed and the result lists what would be.
+
+use super::confirm::{
+    confirm, CallbackDebug, ConfirmAction, ConfirmCallback, ConfirmRequest, SKIPPED_BY_CONFIRMATION,
+};
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Options for rm operation
+#[derive(Default)]
+pub struct RmOptions {
+    /// Remove directories and their contents recursively (-r, --recursive)
+    pub recursive: bool,
+    /// Ignore nonexistent files (-f, --force)
+    pub force: bool,
+    /// Verbose output
+    pub verbose: bool,
+    /// Callback consulted before each removal
+    pub confirm: Option<ConfirmCallback>,
+}
+
+impl fmt::Debug for RmOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RmOptions")
+            .field("recursive", &self.recursive)
+            .field("force", &self.force)
+            .field("verbose", &self.verbose)
+            .field("confirm", &CallbackDebug(&self.confirm))
+            .finish()
+    }
+}
+
+/// Result of rm operation
+#[derive(Debug, Clone)]
+pub struct RmResult {
+    /// Paths that were removed
+    pub removed: Vec<String>,
+    /// Paths that were not removed, with the reason
+    pub skipped: Vec<(String, String)>,
+    /// Number of paths removed
+    pub count: usize,
+    /// Whether this was a dry run, so `removed` lists paths that would be removed
+    pub dry_run: bool,
+}
+
+/// Remove files and directories
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `paths` - Paths to remove
+/// * `options` - Rm options
+///
+/// # Returns
+/// Result containing removed and skipped paths
+///
+/// # Errors
+/// Returns error if:
+/// - Path is outside sandbox
+/// - Path does not exist (unless force=true)
+/// - Path is a directory (unless recursive=true)
+/// - Permission denied
+pub fn rm(sandbox: &Sandbox, paths: &[&Path], options: &RmOptions) -> AgentResult<RmResult> {
+    if paths.is_empty() {
+        return Err(AgentError::validation("No paths specified for rm"));
+    }
+
+    let mut removed = Vec::new();
+    let mut skipped = Vec::new();
+
+    for path in paths {
+        // Like coreutils, refuse operands naming `.` or `..`
+        if is_dot_operand(path) {
+            return Err(AgentError::validation(format!(
+                "Refusing to remove '.' or '..' directory: {}",
+                path.display()
+            )));
+        }
+
+        // Validate path through sandbox (write permission required); a
+        // symlink is removed itself, never its target
+        let validated_path = sandbox.validate_write_entry(path)?;
+
+        let metadata = match fs::symlink_metadata(&validated_path) {
+            Ok(metadata) => metadata,
+            // With --force, silently ignore nonexistent files
+            Err(_) if options.force => continue,
+            Err(_) => {
+                return Err(AgentError::NotFound(format!(
+                    "Cannot remove {}: No such file or directory",
+                    validated_path.display()
+                )));
+            }
+        };
+        let is_dir = metadata.is_dir();
+
+        if is_dir && is_sandbox_root(sandbox, &validated_path) {
+            return Err(AgentError::validation(format!(
+                "Refusing to remove sandbox root: {}",
+                validated_path.display()
+            )));
+        }
+
+        if is_dir && !options.recursive {
+            return Err(AgentError::validation(format!(
+                "Cannot remove {}: Is a directory",
+                validated_path.display()
+            )));
+        }
+
+        if is_dir {
+            validate_tree(sandbox, &validated_path)?;
+        }
+
+        let path_str = validated_path
+            .to_str()
+            .ok_or_else(|| AgentError::validation("Path contains invalid UTF-8"))?
+            .to_string();
+
+        let request = ConfirmRequest {
+            action: ConfirmAction::Remove,
+            path: validated_path.clone(),
+            source: None,
+            is_dir,
+        };
+        if !confirm(options.confirm.as_ref(), request) {
+            skipped.push((path_str, SKIPPED_BY_CONFIRMATION.to_string()));
+            continue;
+        }
+
+        if !sandbox.is_dry_run() {
+            let result = if is_dir {
+                fs::remove_dir_all(&validated_path)
+            } else {
+                fs::remove_file(&validated_path)
+            };
+            result.map_err(|e| {
+                AgentError::io(format!(
+                    "Failed to remove {}: {}",
+                    validated_path.display(),
+                    e
+                ))
+            })?;
+        }
+
+        if options.verbose {
+            eprintln!(
+                "{}",
+                sandbox.dry_run_message(format!("rm: removed '{}'", path_str))
+            );
+        }
+
+        removed.push(path_str);
+    }
+
+    Ok(RmResult {
+        count: removed.len(),
+        removed,
+        skipped,
+        dry_run: sandbox.is_dry_run(),
+    })
+}
+
+/// Validate every entry below a directory about to be removed recursively
+///
+/// `remove_dir_all` does not consult the sandbox, so a nested entry it
+/// denies (e.g. one matching a deny glob) refuses the whole operand. Unlike
+/// [`walk`](super::walk::walk), this must see denied entries rather than
+/// skip them.
+fn validate_tree(sandbox: &Sandbox, dir: &Path) -> AgentResult<()> {
+    for entry in WalkDir::new(dir).min_depth(1) {
+        let entry = entry
+            .map_err(|e| AgentError::io(format!("Failed to walk {}: {}", dir.display(), e)))?;
+        sandbox.validate_write_entry(entry.path())?;
+    }
+    Ok(())
+}
+
+/// Whether the last component of `path` is `.` or `..`
+fn is_dot_operand(path: &Path) -> bool {
+    let text = path.to_string_lossy();
+    let last = text
+        .trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or("");
+    matches!(last, "." | "..")
+}
+
+/// Whether `path` is (or resolves to) one of the sandbox roots
+fn is_sandbox_root(sandbox: &Sandbox, path: &Path) -> bool {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    sandbox.roots().iter().any(|root| *root == canonical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rm_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "content").expect("Failed to create test file");
+
+        let result = rm(&sandbox, &[&file], &RmOptions::default()).expect("rm failed");
+
+        assert_eq!(result.count, 1);
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_rm_missing_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let file = temp_dir.path().join("missing.txt");
+        assert!(rm(&sandbox, &[&file], &RmOptions::default()).is_err());
+
+        let options = RmOptions {
+            force: true,
+            ..Default::default()
+        };
+        let result = rm(&sandbox, &[&file], &options).expect("rm failed");
+        assert_eq!(result.count, 0);
+    }
+
+    #[test]
+    fn test_rm_directory_requires_recursive() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let dir = temp_dir.path().join("dir");
+        fs::create_dir(&dir).expect("Failed to create test dir");
+        fs::write(dir.join("inner.txt"), "content").expect("Failed to create test file");
+
+        assert!(rm(&sandbox, &[&dir], &RmOptions::default()).is_err());
+
+        let options = RmOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        rm(&sandbox, &[&dir], &options).expect("rm failed");
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_rm_refuses_dot_operands_and_root() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path().canonicalize().expect("canonicalize failed");
+        let sandbox = Sandbox::new(SandboxConfig::new(root.clone()));
+        fs::create_dir(root.join("sub")).expect("Failed to create test dir");
+        fs::write(root.join("file.txt"), "content").expect("Failed to create test file");
+
+        let options = RmOptions {
+            recursive: true,
+            force: true,
+            ..Default::default()
+        };
+        for operand in [
+            Path::new("."),
+            Path::new(".."),
+            Path::new("sub/.."),
+            Path::new("sub/."),
+            &root.join("sub/.."),
+            &root,
+        ] {
+            assert!(
+                rm(&sandbox, &[operand], &options).is_err(),
+                "removed {}",
+                operand.display()
+            );
+        }
+
+        assert!(root.join("sub").is_dir());
+        assert!(root.join("file.txt").exists());
+    }
+
+    #[test]
+    fn test_rm_recursive_refuses_denied_entries() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path().canonicalize().expect("canonicalize failed");
+        let config = SandboxConfig::builder()
+            .root(&root)
+            .deny_glob("**/*.key")
+            .build()
+            .expect("Failed to build config");
+        let sandbox = Sandbox::new(config);
+
+        let dir = root.join("dir");
+        fs::create_dir_all(dir.join("nested")).expect("Failed to create test dir");
+        fs::write(dir.join("plain.txt"), "content").expect("Failed to create test file");
+        fs::write(dir.join("nested/secret.key"), "key").expect("Failed to create test file");
+
+        let options = RmOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let err = rm(&sandbox, &[&dir], &options).unwrap_err();
+
+        assert!(matches!(err, AgentError::SandboxViolation(_)));
+        assert!(dir.join("plain.txt").exists());
+        assert!(dir.join("nested/secret.key").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rm_symlink_keeps_target() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let file = temp_dir.path().join("target.txt");
+        let dir = temp_dir.path().join("target_dir");
+        fs::write(&file, "content").expect("Failed to create test file");
+        fs::create_dir(&dir).expect("Failed to create test dir");
+        fs::write(dir.join("inner.txt"), "inner").expect("Failed to create test file");
+        let file_link = temp_dir.path().join("file_link");
+        let dir_link = temp_dir.path().join("dir_link");
+        std::os::unix::fs::symlink(&file, &file_link).expect("Failed to create symlink");
+        std::os::unix::fs::symlink(&dir, &dir_link).expect("Failed to create symlink");
+
+        let options = RmOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let result = rm(&sandbox, &[&file_link, &dir_link], &options).expect("rm failed");
+
+        assert_eq!(result.count, 2);
+        assert!(result.removed[0].ends_with("file_link"));
+        assert!(fs::symlink_metadata(&file_link).is_err());
+        assert!(fs::symlink_metadata(&dir_link).is_err());
+        assert_eq!(fs::read_to_string(&file).expect("read failed"), "content");
+        assert!(dir.join("inner.txt").exists());
+    }
+
+    #[test]
+    fn test_rm_dry_run_keeps_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config = SandboxConfig::builder()
+            .root(temp_dir.path())
+            .dry_run(true)
+            .build()
+            .expect("Failed to build config");
+        let sandbox = Sandbox::new(config);
+
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "content").expect("Failed to create test file");
+
+        let result = rm(&sandbox, &[&file], &RmOptions::default()).expect("rm failed");
+
+        assert!(result.dry_run);
+        assert_eq!(result.count, 1);
+        assert!(result.removed[0].ends_with("file.txt"));
+        assert!(file.exists());
+
+        // Validation still applies
+        let missing = temp_dir.path().join("missing.txt");
+        assert!(rm(&sandbox, &[&missing], &RmOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_rm_confirmation_denied() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let file = temp_dir.path().join("keep.txt");
+        fs::write(&file, "content").expect("Failed to create test file");
+
+        let options = RmOptions {
+            confirm: Some(Box::new(|request| {
+                assert_eq!(request.action, ConfirmAction::Remove);
+                false
+            })),
+            ..Default::default()
+        };
+        let result = rm(&sandbox, &[&file], &options).expect("rm failed");
+
+        assert_eq!(result.count, 0);
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].1, SKIPPED_BY_CONFIRMATION);
+        assert!(file.exists());
+    }
+
+    #[test]
+    fn test_rm_confirmation_approved() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let file = temp_dir.path().join("remove.txt");
+        fs::write(&file, "content").expect("Failed to create test file");
+
+        let options = RmOptions {
+            confirm: Some(Box::new(|_| true)),
+            ..Default::default()
+        };
+        let result = rm(&sandbox, &[&file], &options).expect("rm failed");
+
+        assert_eq!(result.count, 1);
+        assert!(result.skipped.is_empty());
+        assert!(!file.exists());
+    }
+}
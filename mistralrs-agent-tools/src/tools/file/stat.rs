@@ -0,0 +1,214 @@
+//! Stat utility - display file metadata
+//!
+//! Reports size, type, permissions, link count and timestamps, either as a
+//! [`FileStat`] or as a JSON object for deterministic parsing by agents.
+
+use super::ls::format_permissions;
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// File metadata returned by [`stat`]
+///
+/// Symlinks are followed: the type, size and permissions describe the
+/// target, while `is_symlink` and `symlink_target` describe the link.
+#[derive(Debug, Clone)]
+pub struct FileStat {
+    /// Resolved path of the file
+    pub path: PathBuf,
+    /// Whether the (followed) path is a regular file
+    pub is_file: bool,
+    /// Whether the (followed) path is a directory
+    pub is_dir: bool,
+    /// Whether the given path is a symbolic link
+    pub is_symlink: bool,
+    /// Link target as stored in the symlink
+    pub symlink_target: Option<PathBuf>,
+    /// Size in bytes
+    pub size: u64,
+    /// Permission bits (Unix-style, 0 if not available)
+    pub permissions: u32,
+    /// Number of hard links (None if not available)
+    pub nlink: Option<u64>,
+    /// Last modification time
+    pub modified: Option<SystemTime>,
+    /// Last access time
+    pub accessed: Option<SystemTime>,
+    /// Creation time, where the platform records it
+    pub created: Option<SystemTime>,
+}
+
+impl FileStat {
+    /// File type as `stat` names it
+    pub fn file_type(&self) -> &'static str {
+        if self.is_dir {
+            "directory"
+        } else if self.is_file {
+            "regular file"
+        } else {
+            "other"
+        }
+    }
+}
+
+/// Get metadata for a file
+///
+/// # Errors
+/// Returns error if:
+/// - Path is outside sandbox
+/// - Path does not exist
+pub fn stat(sandbox: &Sandbox, path: &Path) -> AgentResult<FileStat> {
+    let validated_path = sandbox.validate_read(path)?;
+    let metadata = fs::metadata(&validated_path).map_err(|_| {
+        AgentError::NotFound(format!(
+            "Cannot stat {}: No such file or directory",
+            path.display()
+        ))
+    })?;
+
+    // Validation resolves symlinks, so inspect the path as given
+    let given = if path.is_relative() {
+        sandbox.root().join(path)
+    } else {
+        path.to_path_buf()
+    };
+    let is_symlink = fs::symlink_metadata(&given).is_ok_and(|m| m.file_type().is_symlink());
+    let symlink_target = if is_symlink {
+        fs::read_link(&given).ok()
+    } else {
+        None
+    };
+
+    let (permissions, nlink) = {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            (metadata.mode(), Some(metadata.nlink()))
+        }
+        #[cfg(not(unix))]
+        {
+            (0, None)
+        }
+    };
+
+    Ok(FileStat {
+        path: validated_path,
+        is_file: metadata.is_file(),
+        is_dir: metadata.is_dir(),
+        is_symlink,
+        symlink_target,
+        size: metadata.len(),
+        permissions,
+        nlink,
+        modified: metadata.modified().ok(),
+        accessed: metadata.accessed().ok(),
+        created: metadata.created().ok(),
+    })
+}
+
+/// Get metadata for a file as a JSON object
+///
+/// Permissions are reported as an octal string (`"0644"`) and an `ls -l`
+/// style string (`"-rw-r--r--"`); timestamps are RFC 3339 in UTC, or `null`
+/// when unavailable.
+pub fn stat_json(sandbox: &Sandbox, path: &Path) -> AgentResult<Value> {
+    let stat = stat(sandbox, path)?;
+
+    Ok(json!({
+        "path": stat.path.display().to_string(),
+        "type": stat.file_type(),
+        "size": stat.size,
+        "is_file": stat.is_file,
+        "is_dir": stat.is_dir,
+        "is_symlink": stat.is_symlink,
+        "symlink_target": stat.symlink_target.map(|t| t.display().to_string()),
+        "permissions": {
+            "octal": format!("{:04o}", stat.permissions & 0o7777),
+            "symbolic": format_permissions(stat.permissions, stat.is_dir),
+        },
+        "nlink": stat.nlink,
+        "modified": stat.modified.map(format_rfc3339),
+        "accessed": stat.accessed.map(format_rfc3339),
+        "created": stat.created.map(format_rfc3339),
+    }))
+}
+
+/// Format a time as RFC 3339 in UTC with second precision
+fn format_rfc3339(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use std::time::{Duration, UNIX_EPOCH};
+    use tempfile::TempDir;
+
+    fn create_test_sandbox() -> (Sandbox, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(root));
+        (sandbox, temp_dir)
+    }
+
+    #[test]
+    fn test_stat_json_file() {
+        let (sandbox, temp_dir) = create_test_sandbox();
+        let file = temp_dir.path().join("data.txt");
+        fs::write(&file, "hello world").unwrap();
+
+        let value = stat_json(&sandbox, &file).unwrap();
+
+        assert_eq!(value["size"], 11);
+        assert_eq!(value["type"], "regular file");
+        assert_eq!(value["is_file"], true);
+        assert_eq!(value["is_dir"], false);
+        assert_eq!(value["is_symlink"], false);
+        assert!(value["symlink_target"].is_null());
+        let symbolic = value["permissions"]["symbolic"].as_str().unwrap();
+        assert!(symbolic.starts_with('-'));
+        assert_eq!(value["permissions"]["octal"].as_str().unwrap().len(), 4);
+        assert!(value["modified"].as_str().unwrap().ends_with('Z'));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stat_follows_symlink() {
+        let (sandbox, temp_dir) = create_test_sandbox();
+        let dir = temp_dir.path().join("target_dir");
+        fs::create_dir(&dir).unwrap();
+        let link = temp_dir.path().join("link");
+        std::os::unix::fs::symlink(&dir, &link).unwrap();
+
+        let value = stat_json(&sandbox, Path::new("link")).unwrap();
+
+        assert_eq!(value["is_symlink"], true);
+        assert_eq!(value["is_dir"], true);
+        assert_eq!(value["symlink_target"], dir.display().to_string());
+        assert!(value["permissions"]["symbolic"]
+            .as_str()
+            .unwrap()
+            .starts_with('d'));
+    }
+
+    #[test]
+    fn test_stat_missing_file() {
+        let (sandbox, temp_dir) = create_test_sandbox();
+        assert!(stat(&sandbox, &temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_format_rfc3339() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_710_504_000);
+        assert_eq!(format_rfc3339(time), "2024-03-15T12:00:00Z");
+        assert_eq!(
+            format_rfc3339(UNIX_EPOCH - Duration::from_secs(1)),
+            "1969-12-31T23:59:59Z"
+        );
+    }
+}
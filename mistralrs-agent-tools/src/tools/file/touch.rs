@@ -1,11 +1,14 @@
 //! Touch utility - create files or update timestamps
 //!
 //! Creates empty files or updates access/modification times of existing files.
+//! In dry-run mode nothing is created or updated and the result lists what
+//! would be.
 
 use crate::tools::sandbox::Sandbox;
 use crate::types::{AgentError, AgentResult};
+use filetime::FileTime;
 use std::fs::{self, OpenOptions};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 /// Options for touch operation
@@ -17,8 +20,12 @@ pub struct TouchOptions {
     pub access_only: bool,
     /// Update only modification time
     pub modification_only: bool,
-    /// Use this time instead of current time (Unix timestamp)
+    /// Use this time instead of current time (like `-d`/`-t`)
     pub reference_time: Option<SystemTime>,
+    /// Use this file's times instead of current time (like `-r`)
+    ///
+    /// Takes precedence over `reference_time`.
+    pub reference_file: Option<PathBuf>,
     /// Verbose output
     pub verbose: bool,
 }
@@ -32,6 +39,26 @@ pub struct TouchResult {
     pub count: usize,
     /// Number of files created
     pub created: usize,
+    /// Whether this was a dry run, so the counts describe what would change
+    pub dry_run: bool,
+}
+
+/// Result of a batch touch operation
+#[derive(Debug, Clone, Default)]
+pub struct TouchManyResult {
+    /// Paths that were touched (created or updated)
+    pub succeeded: Vec<String>,
+    /// Paths that could not be touched, with the error for each
+    pub failed: Vec<(String, AgentError)>,
+    /// Number of files created
+    pub created: usize,
+}
+
+impl TouchManyResult {
+    /// Whether every path was touched successfully
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
 }
 
 /// Create files or update timestamps
@@ -61,160 +88,189 @@ pub fn touch(
 
     let mut touched = Vec::new();
     let mut created_count = 0;
-    let time_to_use = options.reference_time.unwrap_or_else(SystemTime::now);
+    let times = resolve_times(sandbox, options)?;
 
     for path in paths {
-        // Validate path through sandbox (write permission required)
-        let validated_path = sandbox.validate_write(path)?;
-
-        let existed = validated_path.exists();
-
-        // Create file if it doesn't exist (unless no_create is set)
-        if !existed {
-            if options.no_create {
-                // Skip non-existent files when no_create is true
-                continue;
-            }
-
-            // Create empty file
-            OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(false)
-                .open(&validated_path)
-                .map_err(|e| {
-                    AgentError::io(format!(
-                        "Failed to create file {}: {}",
-                        validated_path.display(),
-                        e
-                    ))
-                })?;
-
-            created_count += 1;
-
-            if options.verbose {
-                eprintln!("touch: created file '{}'", validated_path.display());
-            }
-        }
-
-        // Update timestamps if file exists or was just created
-        if validated_path.exists() {
-            update_timestamps(&validated_path, time_to_use, options)?;
-
-            if existed && options.verbose {
-                eprintln!(
-                    "touch: updated timestamps for '{}'",
-                    validated_path.display()
-                );
+        if let Some((path_str, created)) = touch_one(sandbox, path, times, options)? {
+            if created {
+                created_count += 1;
             }
+            touched.push(path_str);
         }
-
-        let path_str = validated_path
-            .to_str()
-            .ok_or_else(|| AgentError::validation("Path contains invalid UTF-8"))?
-            .to_string();
-
-        touched.push(path_str);
     }
 
     Ok(TouchResult {
         count: touched.len(),
         created: created_count,
         touched,
+        dry_run: sandbox.is_dry_run(),
     })
 }
 
-/// Update file timestamps based on options
-fn update_timestamps(path: &Path, time: SystemTime, options: &TouchOptions) -> AgentResult<()> {
-    // Get current times first
-    let metadata = fs::metadata(path).map_err(|e| {
-        AgentError::io(format!(
-            "Failed to get metadata for {}: {}",
-            path.display(),
-            e
-        ))
-    })?;
+/// Apply the same timestamp policy to many files
+///
+/// Unlike [`touch`], a failure on one path does not abort the batch: each
+/// path is validated and touched independently and its error is recorded.
+///
+/// # Errors
+/// Returns error only if no paths are given or the reference file cannot be
+/// read, since then no path could be touched.
+pub fn touch_many(
+    sandbox: &Sandbox,
+    paths: &[&Path],
+    opts: TouchOptions,
+) -> AgentResult<TouchManyResult> {
+    if paths.is_empty() {
+        return Err(AgentError::validation("No paths specified for touch"));
+    }
 
-    let current_accessed = metadata.accessed().unwrap_or_else(|_| SystemTime::now());
-    let current_modified = metadata.modified().unwrap_or_else(|_| SystemTime::now());
+    let times = resolve_times(sandbox, &opts)?;
+    let mut result = TouchManyResult::default();
 
-    // Determine which times to set
-    let (_atime, _mtime) = match (options.access_only, options.modification_only) {
-        (true, false) => (time, current_modified),
-        (false, true) => (current_accessed, time),
-        _ => (time, time), // Both or neither specified: update both
-    };
+    for path in paths {
+        match touch_one(sandbox, path, times, &opts) {
+            Ok(Some((path_str, created))) => {
+                if created {
+                    result.created += 1;
+                }
+                result.succeeded.push(path_str);
+            }
+            Ok(None) => {}
+            Err(e) => result.failed.push((path.display().to_string(), e)),
+        }
+    }
 
-    // Platform-specific timestamp update
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::MetadataExt;
-        use std::os::unix::prelude::*;
-
-        let atime_sec = atime
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map_err(|e| AgentError::validation(format!("Invalid access time: {}", e)))?
-            .as_secs() as i64;
-
-        let mtime_sec = mtime
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map_err(|e| AgentError::validation(format!("Invalid modification time: {}", e)))?
-            .as_secs() as i64;
-
-        // Use utime to set both times
-        let times = libc::utimbuf {
-            actime: atime_sec,
-            modtime: mtime_sec,
-        };
+    Ok(result)
+}
 
-        let c_path = std::ffi::CString::new(
-            path.to_str()
-                .ok_or_else(|| AgentError::validation("Path contains invalid UTF-8"))?,
-        )
-        .map_err(|e| AgentError::validation(format!("Invalid path: {}", e)))?;
+/// Determine the (access, modification) times to apply
+fn resolve_times(
+    sandbox: &Sandbox,
+    options: &TouchOptions,
+) -> AgentResult<(SystemTime, SystemTime)> {
+    if let Some(reference) = &options.reference_file {
+        let validated = sandbox.validate_read(reference)?;
+        let metadata = fs::metadata(&validated).map_err(|e| {
+            AgentError::io(format!(
+                "Failed to get metadata for reference {}: {}",
+                validated.display(),
+                e
+            ))
+        })?;
+        let modified = metadata.modified().map_err(|e| {
+            AgentError::io(format!(
+                "Failed to read modification time of {}: {}",
+                validated.display(),
+                e
+            ))
+        })?;
+        let accessed = metadata.accessed().unwrap_or(modified);
+        return Ok((accessed, modified));
+    }
 
-        let result = unsafe { libc::utime(c_path.as_ptr(), &times) };
+    let time = options.reference_time.unwrap_or_else(SystemTime::now);
+    Ok((time, time))
+}
 
-        if result != 0 {
-            return Err(AgentError::io(format!(
-                "Failed to update timestamps for {}: {}",
-                path.display(),
-                std::io::Error::last_os_error()
-            )));
-        }
+/// Touch a single path
+///
+/// Returns `None` if the path was skipped because of `no_create`, otherwise
+/// the validated path and whether the file was created.
+fn touch_one(
+    sandbox: &Sandbox,
+    path: &Path,
+    times: (SystemTime, SystemTime),
+    options: &TouchOptions,
+) -> AgentResult<Option<(String, bool)>> {
+    // Validate path through sandbox (write permission required)
+    let validated_path = sandbox.validate_write(path)?;
+
+    let existed = validated_path.exists();
+    let path_str = validated_path
+        .to_str()
+        .ok_or_else(|| AgentError::validation("Path contains invalid UTF-8"))?
+        .to_string();
+
+    // Skip non-existent files when no_create is true
+    if !existed && options.no_create {
+        return Ok(None);
     }
 
-    #[cfg(not(unix))]
-    {
-        // On Windows, we use filetime crate or SetFileTime
-        // For simplicity, we'll use a basic approach with limitations
-        
-        
-
-        // Windows doesn't have a direct equivalent to utime in std::fs
-        // We'll need to use the filetime crate for proper implementation
-        // For now, we'll use a workaround: touch the file to update modification time
+    if sandbox.is_dry_run() {
+        if options.verbose {
+            let action = if existed {
+                "updated timestamps for"
+            } else {
+                "created file"
+            };
+            eprintln!(
+                "{}",
+                sandbox.dry_run_message(format!("touch: {} '{}'", action, path_str))
+            );
+        }
+        return Ok(Some((path_str, !existed)));
+    }
 
-        if !options.access_only {
-            // Update modification time by opening and immediately closing
-            let file = OpenOptions::new().write(true).open(path).map_err(|e| {
+    // Create file if it doesn't exist
+    if !existed {
+        // Create empty file
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&validated_path)
+            .map_err(|e| {
                 AgentError::io(format!(
-                    "Failed to open file for timestamp update {}: {}",
-                    path.display(),
+                    "Failed to create file {}: {}",
+                    validated_path.display(),
                     e
                 ))
             })?;
 
-            // On Windows, we need to actually write something or use platform APIs
-            // This is a simplified version - production code should use filetime crate
-            drop(file);
+        if options.verbose {
+            eprintln!("touch: created file '{}'", validated_path.display());
         }
+    }
+
+    // Update timestamps now that the file exists
+    update_timestamps(&validated_path, times, options)?;
 
-        // Note: Windows access time updates are not implemented in this simplified version
-        // Production code should use the `filetime` crate for cross-platform timestamp control
+    if existed && options.verbose {
+        eprintln!(
+            "touch: updated timestamps for '{}'",
+            validated_path.display()
+        );
     }
 
-    Ok(())
+    Ok(Some((path_str, !existed)))
+}
+
+/// Update file timestamps based on options
+///
+/// Times are set by path, so the file is never opened: directories, FIFOs
+/// and files without read or write permission can be touched too.
+fn update_timestamps(
+    path: &Path,
+    (atime, mtime): (SystemTime, SystemTime),
+    options: &TouchOptions,
+) -> AgentResult<()> {
+    let atime = FileTime::from_system_time(atime);
+    let mtime = FileTime::from_system_time(mtime);
+
+    // Determine which times to set; the other one is left untouched
+    let result = match (options.access_only, options.modification_only) {
+        (true, false) => filetime::set_file_atime(path, atime),
+        (false, true) => filetime::set_file_mtime(path, mtime),
+        _ => filetime::set_file_times(path, atime, mtime), // Both or neither specified: update both
+    };
+
+    result.map_err(|e| {
+        AgentError::io(format!(
+            "Failed to update timestamps for {}: {}",
+            path.display(),
+            e
+        ))
+    })
 }
 
 #[cfg(test)]
@@ -354,4 +410,150 @@ mod tests {
         let result = touch(&sandbox, &[&outside_file], &TouchOptions::default());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_touch_many_reference_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let reference = temp_dir.path().join("reference.txt");
+        fs::write(&reference, "ref").expect("Failed to create reference file");
+        let reference_mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        fs::File::options()
+            .write(true)
+            .open(&reference)
+            .and_then(|f| f.set_modified(reference_mtime))
+            .expect("Failed to set reference mtime");
+
+        let file1 = temp_dir.path().join("a.txt");
+        let file2 = temp_dir.path().join("b.txt");
+        let file3 = temp_dir.path().join("c.txt");
+        fs::write(&file2, "existing").expect("Failed to create test file");
+
+        let options = TouchOptions {
+            reference_file: Some(reference.clone()),
+            ..Default::default()
+        };
+        let result =
+            touch_many(&sandbox, &[&file1, &file2, &file3], options).expect("touch_many failed");
+
+        assert!(result.is_complete());
+        assert_eq!(result.succeeded.len(), 3);
+        assert_eq!(result.created, 2);
+        for file in [&file1, &file2, &file3] {
+            let mtime = fs::metadata(file)
+                .expect("Failed to get metadata")
+                .modified()
+                .expect("No mtime");
+            assert_eq!(mtime, reference_mtime);
+        }
+    }
+
+    #[test]
+    fn test_touch_many_collects_failures() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let inside = temp_dir.path().join("inside.txt");
+        let outside = std::path::PathBuf::from("/tmp/outside_sandbox_many.txt");
+
+        let result = touch_many(&sandbox, &[&outside, &inside], TouchOptions::default())
+            .expect("touch_many failed");
+
+        assert!(!result.is_complete());
+        assert_eq!(result.succeeded.len(), 1);
+        assert_eq!(result.failed.len(), 1);
+        assert!(inside.exists());
+    }
+
+    #[test]
+    fn test_touch_directory() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let dir = temp_dir.path().join("subdir");
+        fs::create_dir(&dir).expect("Failed to create dir");
+
+        let stamp = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let options = TouchOptions {
+            reference_time: Some(stamp),
+            ..Default::default()
+        };
+        let result = touch(&sandbox, &[&dir], &options).expect("touch failed");
+
+        assert_eq!(result.count, 1);
+        assert_eq!(result.created, 0);
+        let mtime = fs::metadata(&dir)
+            .expect("Failed to get metadata")
+            .modified()
+            .expect("No mtime");
+        assert_eq!(mtime, stamp);
+    }
+
+    #[test]
+    fn test_touch_read_only_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let file = temp_dir.path().join("readonly.txt");
+        fs::write(&file, "content").expect("Failed to create test file");
+        let mut perms = fs::metadata(&file)
+            .expect("Failed to get metadata")
+            .permissions();
+        perms.set_readonly(true);
+        fs::set_permissions(&file, perms).expect("Failed to set permissions");
+
+        let stamp = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let options = TouchOptions {
+            reference_time: Some(stamp),
+            ..Default::default()
+        };
+        let result = touch(&sandbox, &[&file], &options);
+
+        let mtime = fs::metadata(&file)
+            .expect("Failed to get metadata")
+            .modified()
+            .expect("No mtime");
+        let mut perms = fs::metadata(&file)
+            .expect("Failed to get metadata")
+            .permissions();
+        #[allow(clippy::permissions_set_readonly_false)]
+        perms.set_readonly(false);
+        fs::set_permissions(&file, perms).expect("Failed to set permissions");
+
+        assert!(result.is_ok(), "touch failed: {:?}", result.err());
+        assert_eq!(mtime, stamp);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_touch_write_only_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+
+        let file = temp_dir.path().join("writeonly.txt");
+        fs::write(&file, "content").expect("Failed to create test file");
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o200))
+            .expect("Failed to set permissions");
+
+        let stamp = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let options = TouchOptions {
+            reference_time: Some(stamp),
+            modification_only: true,
+            ..Default::default()
+        };
+        let result = touch(&sandbox, &[&file], &options);
+
+        let mtime = fs::metadata(&file)
+            .expect("Failed to get metadata")
+            .modified()
+            .expect("No mtime");
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o644))
+            .expect("Failed to set permissions");
+
+        assert!(result.is_ok(), "touch failed: {:?}", result.err());
+        assert_eq!(mtime, stamp);
+    }
 }
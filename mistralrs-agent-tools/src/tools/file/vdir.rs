@@ -0,0 +1,65 @@
+//! Vdir utility - verbose directory listing
+//!
+//! Equivalent to `ls -l`: a long listing with a `total` header line.
+
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentResult, LsOptions};
+use std::path::Path;
+
+use super::ls::{format_long, ls};
+
+/// List directory contents in long format
+///
+/// Behaves like [`ls`] with `long` forced on and returns the rendered listing.
+pub fn vdir(sandbox: &Sandbox, path: &Path, options: &LsOptions) -> AgentResult<String> {
+    let options = LsOptions {
+        long: true,
+        ..options.clone()
+    };
+    let result = ls(sandbox, path, &options)?;
+    Ok(format_long(&result, &options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_sandbox() -> (Sandbox, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        (sandbox, temp_dir)
+    }
+
+    #[test]
+    fn test_vdir_long_listing() {
+        let (sandbox, temp_dir) = create_test_sandbox();
+        fs::write(temp_dir.path().join("data.txt"), "x".repeat(1234)).unwrap();
+        fs::create_dir(temp_dir.path().join("sub")).unwrap();
+
+        let output = vdir(&sandbox, temp_dir.path(), &LsOptions::default()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].starts_with("total "));
+        assert_eq!(lines.len(), 3);
+
+        let file_line = lines[1];
+        assert!(file_line.ends_with(" data.txt"));
+        assert!(file_line.contains(" 1234 "));
+        assert!(file_line.starts_with('-'));
+        #[cfg(unix)]
+        assert!(file_line.starts_with("-rw"));
+
+        assert!(lines[2].starts_with('d'));
+        assert!(lines[2].ends_with(" sub"));
+    }
+
+    #[test]
+    fn test_vdir_sandbox_violation() {
+        let (sandbox, _temp_dir) = create_test_sandbox();
+        let result = vdir(&sandbox, Path::new("/tmp/outside"), &LsOptions::default());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! Walk utility - shared recursive traversal for file operations
+//!
+//! Walks a directory tree inside the sandbox with an optional depth limit.
+//! Symlinks are not followed unless requested; when they are, links leaving
+//! the sandbox are reported but not entered, and each directory is entered
+//! at most once, so symlink cycles terminate.
+
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use std::collections::HashSet;
+use std::path::Path;
+use walkdir::{DirEntry, WalkDir};
+
+/// Options for walk operation
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    /// Maximum depth to descend; the root is depth 0 (None = unlimited)
+    pub max_depth: Option<usize>,
+    /// Descend into symlinked directories instead of reporting the links
+    pub follow_symlinks: bool,
+    /// Skip entries whose names start with `.`, along with their contents
+    /// (the root is always visited)
+    pub skip_hidden: bool,
+}
+
+/// Identity of a directory, used to enter each one only once
+#[cfg(unix)]
+type DirId = (u64, u64);
+#[cfg(not(unix))]
+type DirId = std::path::PathBuf;
+
+/// Walk a directory tree, calling `visit` for each entry
+///
+/// The root is visited first, then its contents depth-first. Entries whose
+/// (resolved) path fails sandbox validation are skipped along with their
+/// contents, except followed symlinks, which are visited but not entered.
+/// Unreadable entries below the root are skipped with a warning.
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `root` - Directory (or file) to walk
+/// * `options` - Walk options
+/// * `visit` - Called with each entry; an error stops the walk
+///
+/// # Errors
+/// Returns error if the root fails validation or cannot be read, or the
+/// first error returned by `visit`.
+pub fn walk<F>(
+    sandbox: &Sandbox,
+    root: &Path,
+    options: &WalkOptions,
+    mut visit: F,
+) -> AgentResult<()>
+where
+    F: FnMut(&DirEntry) -> AgentResult<()>,
+{
+    let root = sandbox.validate_read(root)?;
+
+    let mut walker = WalkDir::new(&root).follow_links(options.follow_symlinks);
+    if let Some(depth) = options.max_depth {
+        walker = walker.max_depth(depth);
+    }
+
+    let mut entered: HashSet<DirId> = HashSet::new();
+    let mut entries = walker.into_iter();
+    while let Some(entry) = entries.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.depth() == 0 => {
+                return Err(AgentError::io(format!(
+                    "Failed to walk {}: {}",
+                    root.display(),
+                    e
+                )))
+            }
+            Err(e) => {
+                // Includes symlinks leading back to an ancestor
+                tracing::warn!("Skipping entry while walking {}: {}", root.display(), e);
+                continue;
+            }
+        };
+
+        let is_hidden = entry.depth() > 0 && entry.file_name().to_string_lossy().starts_with('.');
+        if options.skip_hidden && is_hidden {
+            if entry.file_type().is_dir() {
+                entries.skip_current_dir();
+            }
+            continue;
+        }
+
+        // Unfollowed links are reported as links, wherever they point
+        let is_link = entry.path_is_symlink() && !options.follow_symlinks;
+        if !is_link && !sandbox.is_allowed(entry.path()) {
+            if entry.file_type().is_dir() {
+                entries.skip_current_dir();
+            }
+            if entry.path_is_symlink() {
+                visit(&entry)?;
+            }
+            continue;
+        }
+
+        if entry.file_type().is_dir() {
+            if let Some(id) = dir_id(&entry) {
+                if !entered.insert(id) {
+                    entries.skip_current_dir();
+                    continue;
+                }
+            }
+        }
+
+        visit(&entry)?;
+    }
+
+    Ok(())
+}
+
+/// Device and inode of a directory
+#[cfg(unix)]
+fn dir_id(entry: &DirEntry) -> Option<DirId> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = entry.metadata().ok()?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Canonical path of a directory
+#[cfg(not(unix))]
+fn dir_id(entry: &DirEntry) -> Option<DirId> {
+    entry.path().canonicalize().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Sandbox) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("a/b/c")).unwrap();
+        fs::write(temp_dir.path().join("top.txt"), b"top").unwrap();
+        fs::write(temp_dir.path().join("a/b/c/deep.txt"), b"deep").unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        (temp_dir, sandbox)
+    }
+
+    fn walk_depths(sandbox: &Sandbox, root: &Path, options: &WalkOptions) -> Vec<usize> {
+        let mut depths = Vec::new();
+        walk(sandbox, root, options, |entry| {
+            depths.push(entry.depth());
+            Ok(())
+        })
+        .unwrap();
+        depths
+    }
+
+    #[test]
+    fn test_walk_max_depth() {
+        let (temp_dir, sandbox) = setup();
+
+        let options = WalkOptions {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let mut depths = walk_depths(&sandbox, temp_dir.path(), &options);
+        depths.sort_unstable();
+        // root, a, top.txt, a/b
+        assert_eq!(depths, vec![0, 1, 1, 2]);
+
+        let depths = walk_depths(&sandbox, temp_dir.path(), &WalkOptions::default());
+        assert_eq!(depths.len(), 6);
+        assert_eq!(depths.iter().max(), Some(&4));
+    }
+
+    #[test]
+    fn test_walk_skip_hidden_and_visit_error() {
+        let (temp_dir, sandbox) = setup();
+        fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        fs::write(temp_dir.path().join(".git/HEAD"), b"ref").unwrap();
+
+        let options = WalkOptions {
+            skip_hidden: true,
+            ..Default::default()
+        };
+        assert_eq!(walk_depths(&sandbox, temp_dir.path(), &options).len(), 6);
+        assert_eq!(
+            walk_depths(&sandbox, temp_dir.path(), &WalkOptions::default()).len(),
+            8
+        );
+
+        let mut visited = 0;
+        let result = walk(&sandbox, temp_dir.path(), &options, |_| {
+            visited += 1;
+            Err(AgentError::io("stop"))
+        });
+        assert!(result.is_err());
+        assert_eq!(visited, 1);
+    }
+
+    #[test]
+    fn test_walk_rejects_outside_root() {
+        let (_temp_dir, sandbox) = setup();
+        let outside = TempDir::new().unwrap();
+
+        let result = walk(
+            &sandbox,
+            outside.path(),
+            &WalkOptions::default(),
+            |_| Ok(()),
+        );
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_symlink_cycle_terminates() {
+        let (temp_dir, sandbox) = setup();
+        std::os::unix::fs::symlink(temp_dir.path(), temp_dir.path().join("a/b/loop")).unwrap();
+        std::os::unix::fs::symlink(temp_dir.path().join("a"), temp_dir.path().join("alias"))
+            .unwrap();
+
+        // Unfollowed, links are visited but not entered
+        let mut links = Vec::new();
+        let mut count = 0;
+        walk(
+            &sandbox,
+            temp_dir.path(),
+            &WalkOptions::default(),
+            |entry| {
+                count += 1;
+                if entry.path_is_symlink() {
+                    links.push(entry.file_name().to_string_lossy().into_owned());
+                }
+                Ok(())
+            },
+        )
+        .unwrap();
+        links.sort();
+        assert_eq!(links, vec!["alias", "loop"]);
+        assert_eq!(count, 8);
+
+        // Followed, every directory is still entered only once
+        let options = WalkOptions {
+            follow_symlinks: true,
+            ..Default::default()
+        };
+        let mut deep = 0;
+        walk(&sandbox, temp_dir.path(), &options, |entry| {
+            if entry.file_name() == "deep.txt" {
+                deep += 1;
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(deep, 1);
+    }
+}
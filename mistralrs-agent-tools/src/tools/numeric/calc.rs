@@ -0,0 +1,357 @@
+//! calc - bc-style floating point calculator
+//!
+//! Recursive-descent evaluator over a small grammar:
+//!
+//! ```text
+//! expr    := term (('+' | '-') term)*
+//! term    := unary (('*' | '/' | '%') unary)*
+//! unary   := ('-' | '+') unary | power
+//! power   := primary ('^' unary)?
+//! primary := NUMBER | IDENT | IDENT '(' expr (',' expr)* ')' | '(' expr ')'
+//! ```
+
+use crate::types::{AgentError, AgentResult};
+use std::collections::HashMap;
+
+/// Maximum nesting of parentheses, calls, signs and exponents
+const MAX_DEPTH: usize = 128;
+
+/// Calc - evaluate a floating point expression with variables.
+///
+/// Supports `+`, `-`, `*`, `/`, `%`, `^` (right associative), unary minus,
+/// parentheses and the functions `sqrt`, `abs`, `min` and `max` (the latter
+/// two take one or more arguments). Identifiers are looked up in `vars`.
+///
+/// # Errors
+/// Returns `InvalidInput` for syntax errors, division by zero, unknown
+/// variables or functions, non-finite results, and expressions nested more
+/// than 128 levels deep.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::calc;
+/// use std::collections::HashMap;
+///
+/// let vars = HashMap::from([("x".to_string(), 2.0)]);
+/// assert_eq!(calc("sqrt(16) + x", &vars).unwrap(), 6.0);
+/// assert_eq!(calc("2 + 3 * 4", &HashMap::new()).unwrap(), 14.0);
+/// ```
+pub fn calc(expression: &str, vars: &HashMap<String, f64>) -> AgentResult<f64> {
+    let tokens = tokenize(expression)?;
+    if tokens.is_empty() {
+        return Err(AgentError::InvalidInput("Empty expression".into()));
+    }
+
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        depth: 0,
+        vars,
+    };
+    let value = parser.expr()?;
+    if let Some(token) = parser.peek() {
+        return Err(AgentError::InvalidInput(format!(
+            "Unexpected token: {}",
+            token
+        )));
+    }
+    if !value.is_finite() {
+        return Err(AgentError::InvalidInput(
+            "Result is not a finite number".into(),
+        ));
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Ident(name) => write!(f, "{}", name),
+            Token::Op(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> AgentResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, d)) = chars.peek() {
+                if d.is_ascii_digit() || d == '.' {
+                    end = i + d.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let literal = &expression[start..end];
+            let value = literal
+                .parse()
+                .map_err(|_| AgentError::InvalidInput(format!("Invalid number: {}", literal)))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, d)) = chars.peek() {
+                if d.is_alphanumeric() || d == '_' {
+                    end = i + d.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(expression[start..end].to_string()));
+        } else if "+-*/%^(),".contains(c) {
+            tokens.push(Token::Op(c));
+            chars.next();
+        } else {
+            return Err(AgentError::InvalidInput(format!(
+                "Unexpected character: {}",
+                c
+            )));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    /// Current recursion depth, bounded by `MAX_DEPTH`
+    depth: usize,
+    vars: &'a HashMap<String, f64>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// Consume `op` if it is the next token
+    fn eat(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, op: char) -> AgentResult<()> {
+        if self.eat(op) {
+            Ok(())
+        } else {
+            Err(AgentError::InvalidInput(format!("Expected '{}'", op)))
+        }
+    }
+
+    fn expr(&mut self) -> AgentResult<f64> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> AgentResult<f64> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value *= self.unary()?;
+            } else if self.eat('/') {
+                value /= nonzero(self.unary()?)?;
+            } else if self.eat('%') {
+                value %= nonzero(self.unary()?)?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Every recursive rule passes through `unary`, so the depth is bounded here
+    fn unary(&mut self) -> AgentResult<f64> {
+        if self.depth >= MAX_DEPTH {
+            return Err(AgentError::InvalidInput(format!(
+                "Expression nested too deeply (limit {})",
+                MAX_DEPTH
+            )));
+        }
+        self.depth += 1;
+        let value = if self.eat('-') {
+            self.unary().map(|v| -v)
+        } else if self.eat('+') {
+            self.unary()
+        } else {
+            self.power()
+        };
+        self.depth -= 1;
+        value
+    }
+
+    fn power(&mut self) -> AgentResult<f64> {
+        let base = self.primary()?;
+        if self.eat('^') {
+            // Recursing through unary makes `^` right associative
+            Ok(base.powf(self.unary()?))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn primary(&mut self) -> AgentResult<f64> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| AgentError::InvalidInput("Unexpected end of expression".into()))?;
+        self.pos += 1;
+
+        match token {
+            Token::Number(value) => Ok(value),
+            Token::Op('(') => {
+                let value = self.expr()?;
+                self.expect(')')?;
+                Ok(value)
+            }
+            Token::Ident(name) if self.eat('(') => {
+                let mut args = vec![self.expr()?];
+                while self.eat(',') {
+                    args.push(self.expr()?);
+                }
+                self.expect(')')?;
+                call(&name, &args)
+            }
+            Token::Ident(name) => self
+                .vars
+                .get(&name)
+                .copied()
+                .ok_or_else(|| AgentError::InvalidInput(format!("Unknown variable: {}", name))),
+            Token::Op(op) => Err(AgentError::InvalidInput(format!(
+                "Unexpected token: {}",
+                op
+            ))),
+        }
+    }
+}
+
+fn nonzero(divisor: f64) -> AgentResult<f64> {
+    if divisor == 0.0 {
+        Err(AgentError::InvalidInput("Division by zero".into()))
+    } else {
+        Ok(divisor)
+    }
+}
+
+/// Apply a built-in function
+fn call(name: &str, args: &[f64]) -> AgentResult<f64> {
+    let single = || match args {
+        [x] => Ok(*x),
+        _ => Err(AgentError::InvalidInput(format!(
+            "{} takes exactly one argument",
+            name
+        ))),
+    };
+
+    match name {
+        "sqrt" => {
+            let x = single()?;
+            if x < 0.0 {
+                return Err(AgentError::InvalidInput(format!(
+                    "Square root of negative number: {}",
+                    x
+                )));
+            }
+            Ok(x.sqrt())
+        }
+        "abs" => Ok(single()?.abs()),
+        "min" => Ok(args.iter().copied().fold(f64::INFINITY, f64::min)),
+        "max" => Ok(args.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+        _ => Err(AgentError::InvalidInput(format!(
+            "Unknown function: {}",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expression: &str) -> AgentResult<f64> {
+        calc(expression, &HashMap::new())
+    }
+
+    #[test]
+    fn test_calc_variables_and_functions() {
+        let vars = HashMap::from([("x".to_string(), 2.0), ("rate_2".to_string(), 0.5)]);
+        assert_eq!(calc("sqrt(16) + x", &vars).unwrap(), 6.0);
+        assert_eq!(calc("max(x, 3, -1) * rate_2", &vars).unwrap(), 1.5);
+        assert_eq!(calc("min(abs(-4), x)", &vars).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn test_calc_precedence() {
+        assert_eq!(eval("2 + 3 * 4").unwrap(), 14.0);
+        assert_eq!(eval("(2 + 3) * 4").unwrap(), 20.0);
+        assert_eq!(eval("10 - 4 - 3").unwrap(), 3.0);
+        assert_eq!(eval("2 ^ 3 ^ 2").unwrap(), 512.0);
+        assert_eq!(eval("-2 ^ 2").unwrap(), -4.0);
+        assert_eq!(eval("7 % 4 / 2").unwrap(), 1.5);
+        // No exponent notation: `e0` is read as an identifier
+        assert!(eval("1.5e0").is_err());
+    }
+
+    #[test]
+    fn test_calc_errors() {
+        let unknown = eval("y + 1").unwrap_err();
+        assert!(
+            matches!(&unknown, AgentError::InvalidInput(msg) if msg.contains("Unknown variable: y"))
+        );
+
+        assert!(matches!(
+            eval("foo(1)"),
+            Err(AgentError::InvalidInput(msg)) if msg.contains("Unknown function: foo")
+        ));
+        assert!(matches!(
+            eval("1 / (2 - 2)"),
+            Err(AgentError::InvalidInput(msg)) if msg == "Division by zero"
+        ));
+        assert!(eval("sqrt(-1)").is_err());
+        assert!(eval("sqrt(1, 2)").is_err());
+        assert!(eval("(1 + 2").is_err());
+        assert!(eval("1 2").is_err());
+        assert!(eval("").is_err());
+        assert!(eval("1 $ 2").is_err());
+    }
+
+    #[test]
+    fn test_calc_depth_limit() {
+        let nested = |n: usize| format!("{}1{}", "(".repeat(n), ")".repeat(n));
+        assert_eq!(eval(&nested(MAX_DEPTH - 1)).unwrap(), 1.0);
+
+        for expression in [nested(100_000), "-".repeat(100_000) + "1"] {
+            assert!(matches!(
+                eval(&expression),
+                Err(AgentError::InvalidInput(msg)) if msg.contains("nested too deeply")
+            ));
+        }
+    }
+}
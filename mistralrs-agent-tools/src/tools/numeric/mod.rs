@@ -1,16 +1,38 @@
 //! Numeric operations module.
 //!
 //! Implements numeric and mathematical utilities:
-//! - expr: Evaluate simple integer expressions
-//! - factor: Print prime factors of a number
-//! - seq: Generate sequences of numbers
-//! - numfmt: Format numbers with SI/IEC unit prefixes
+//! - expr: Evaluate simple integer, comparison and logical expressions,
+//!   optionally with `0x`/`0o`/`0b` operands
+//! - factor, factor_report: Print prime factors of one or more numbers
+//! - divisors, divisor_count, divisor_sum: Divisor functions
+//! - seq: Generate sequences of numbers, optionally printf-formatted
+//! - numfmt, numfmt_from: Format numbers with SI/IEC unit prefixes and parse
+//!   them back
+//! - calc: Evaluate floating point expressions with variables and functions
+//! - round_to, clamp_i64, percent: Rounding, clamping and percentages for reports
+
+mod calc;
+mod round;
+
+pub use calc::calc;
+pub use round::{clamp_i64, percent, round_to};
 
 use crate::types::{AgentError, AgentResult};
+use serde::Serialize;
+use std::io::{BufWriter, Write};
+
+/// Buffer size used when streaming a sequence to a writer
+const SEQ_WRITE_BUFFER: usize = 64 * 1024;
 
-/// Expr - evaluate a simple integer expression of the form `VALUE OP VALUE`.
+/// Expr - evaluate a simple expression of the form `VALUE OP VALUE`.
+///
+/// Supported operators:
+/// - Arithmetic: `+`, `-`, `*`, `/`, `%`
+/// - Comparison: `<`, `<=`, `=`, `!=`, `>=`, `>`, returning `1` or `0`.
+///   Operands are compared as integers when both parse, otherwise as strings.
+/// - Logical: `a | b` is `a` if it is neither null nor `0`, otherwise `b`;
+///   `a & b` is `a` if neither operand is null or `0`, otherwise `0`.
 ///
-/// Supported operators: `+`, `-`, `*`, `/`, `%`.
 /// A single token is returned as-is if it is a valid integer.
 ///
 /// # Examples
@@ -19,25 +41,83 @@ use crate::types::{AgentError, AgentResult};
 /// use mistralrs_agent_tools::tools::numeric::expr;
 /// assert_eq!(expr("3 + 4").unwrap(), "7");
 /// assert_eq!(expr("10 / 3").unwrap(), "3");
+/// assert_eq!(expr("2 < 10").unwrap(), "1");
+/// assert_eq!(expr("0 | 5").unwrap(), "5");
 /// ```
 pub fn expr(expression: &str) -> AgentResult<String> {
+    expr_with_options(expression, &ExprOptions::default())
+}
+
+/// Base of an integer written by [`expr_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExprBase {
+    /// Plain decimal, e.g. `255`
+    #[default]
+    Decimal,
+    /// `0x`-prefixed lowercase hexadecimal, e.g. `0xff`
+    Hex,
+    /// `0o`-prefixed octal, e.g. `0o377`
+    Octal,
+    /// `0b`-prefixed binary, e.g. `0b11111111`
+    Binary,
+}
+
+impl ExprBase {
+    /// Format `value` in this base, keeping the sign in front of the prefix
+    fn format(self, value: i64) -> String {
+        let sign = if value < 0 { "-" } else { "" };
+        let magnitude = value.unsigned_abs();
+        match self {
+            Self::Decimal => value.to_string(),
+            Self::Hex => format!("{}0x{:x}", sign, magnitude),
+            Self::Octal => format!("{}0o{:o}", sign, magnitude),
+            Self::Binary => format!("{}0b{:b}", sign, magnitude),
+        }
+    }
+}
+
+/// Options for [`expr_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ExprOptions {
+    /// Parse operands prefixed with `0x`, `0o` or `0b` (either case) as
+    /// hexadecimal, octal or binary. Bare numbers stay decimal, so a
+    /// leading zero does not mean octal.
+    pub detect_base: bool,
+    /// Base of integer results. Comparison results and operands returned
+    /// by `|` and `&` are written unchanged.
+    pub output_base: ExprBase,
+}
+
+/// Expr with explicit parsing and formatting options.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::{expr_with_options, ExprBase, ExprOptions};
+/// let options = ExprOptions { detect_base: true, ..Default::default() };
+/// assert_eq!(expr_with_options("0xFF + 1", &options).unwrap(), "256");
+/// let options = ExprOptions { detect_base: true, output_base: ExprBase::Hex };
+/// assert_eq!(expr_with_options("0o17 + 0b1", &options).unwrap(), "0x10");
+/// ```
+pub fn expr_with_options(expression: &str, options: &ExprOptions) -> AgentResult<String> {
     let tokens: Vec<&str> = expression.split_whitespace().collect();
+    let parse = |token: &str| {
+        parse_operand(token, options.detect_base)
+            .ok_or_else(|| AgentError::InvalidInput(format!("Not a number: {}", token)))
+    };
 
     match tokens.len() {
         0 => Err(AgentError::InvalidInput("Empty expression".into())),
-        1 => {
-            let val: i64 = tokens[0]
-                .parse()
-                .map_err(|_| AgentError::InvalidInput(format!("Not a number: {}", tokens[0])))?;
-            Ok(val.to_string())
-        }
+        1 => Ok(options.output_base.format(parse(tokens[0])?)),
         3 => {
-            let a: i64 = tokens[0]
-                .parse()
-                .map_err(|_| AgentError::InvalidInput(format!("Not a number: {}", tokens[0])))?;
-            let b: i64 = tokens[2]
-                .parse()
-                .map_err(|_| AgentError::InvalidInput(format!("Not a number: {}", tokens[2])))?;
+            if let Some(result) =
+                expr_logical_or_compare(tokens[0], tokens[1], tokens[2], options.detect_base)
+            {
+                return Ok(result);
+            }
+
+            let a = parse(tokens[0])?;
+            let b = parse(tokens[2])?;
             let result = match tokens[1] {
                 "+" => a + b,
                 "-" => a - b,
@@ -61,12 +141,81 @@ pub fn expr(expression: &str) -> AgentResult<String> {
                     )))
                 }
             };
-            Ok(result.to_string())
+            Ok(options.output_base.format(result))
         }
         _ => Err(AgentError::InvalidInput("Expected: VALUE OP VALUE".into())),
     }
 }
 
+/// Parse an integer operand, honouring `0x`/`0o`/`0b` prefixes if `detect_base`.
+fn parse_operand(token: &str, detect_base: bool) -> Option<i64> {
+    if detect_base {
+        let (negative, unsigned) = match token.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, token),
+        };
+        let radix = match unsigned.get(..2) {
+            Some("0x" | "0X") => Some(16),
+            Some("0o" | "0O") => Some(8),
+            Some("0b" | "0B") => Some(2),
+            _ => None,
+        };
+        if let Some(radix) = radix {
+            let digits = &unsigned[2..];
+            // from_str_radix would accept a second sign after the prefix
+            if digits.starts_with(['+', '-']) {
+                return None;
+            }
+            let magnitude = i64::from_str_radix(digits, radix).ok()?;
+            return Some(if negative { -magnitude } else { magnitude });
+        }
+    }
+    token.parse().ok()
+}
+
+/// Evaluate a logical or comparison operator, or `None` for other operators.
+fn expr_logical_or_compare(a: &str, op: &str, b: &str, detect_base: bool) -> Option<String> {
+    let parse = |s: &str| parse_operand(s, detect_base);
+    let is_null = |s: &str| s.is_empty() || parse(s) == Some(0);
+    let result = match op {
+        "|" => {
+            if is_null(a) {
+                b
+            } else {
+                a
+            }
+        }
+        "&" => {
+            if is_null(a) || is_null(b) {
+                "0"
+            } else {
+                a
+            }
+        }
+        "<" | "<=" | "=" | "!=" | ">=" | ">" => {
+            let ordering = match (parse(a), parse(b)) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                _ => a.cmp(b),
+            };
+            let holds = match op {
+                "<" => ordering.is_lt(),
+                "<=" => ordering.is_le(),
+                "=" => ordering.is_eq(),
+                "!=" => ordering.is_ne(),
+                ">=" => ordering.is_ge(),
+                _ => ordering.is_gt(),
+            };
+            if holds {
+                "1"
+            } else {
+                "0"
+            }
+        }
+        _ => return None,
+    };
+    Some(result.to_string())
+}
+
 /// Factor - print the prime factorisation of `n` in GNU coreutils format.
 ///
 /// # Examples
@@ -85,23 +234,209 @@ pub fn factor(n: u64) -> AgentResult<String> {
         return Ok("1:".to_string());
     }
 
-    let mut factors: Vec<u64> = Vec::new();
+    let factor_strs: Vec<String> = prime_factors(n)
+        .iter()
+        .flat_map(|&(p, k)| std::iter::repeat_n(p.to_string(), k as usize))
+        .collect();
+    Ok(format!("{}: {}", n, factor_strs.join(" ")))
+}
+
+/// Output shape of [`factor_report`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FactorMode {
+    /// One line per number with repeated primes, as [`factor`]: `12: 2 2 3`
+    #[default]
+    Flat,
+    /// One line per number with exponents: `12: 2^2 3`
+    Exponent,
+    /// A JSON array of `{"n", "factors": [{"prime", "exp"}]}` objects
+    Json,
+}
+
+/// One number's factorisation in [`FactorMode::Json`] output.
+#[derive(Serialize)]
+struct FactorEntry {
+    n: u64,
+    factors: Vec<PrimePower>,
+}
+
+/// A prime and its exponent in [`FactorMode::Json`] output.
+#[derive(Serialize)]
+struct PrimePower {
+    prime: u64,
+    exp: u32,
+}
+
+/// Factor report - factorise several numbers in the requested output shape.
+///
+/// 0 and 1 have no prime factors (`1:` or an empty `factors` array).
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::{factor_report, FactorMode};
+/// assert_eq!(factor_report(&[12, 7], FactorMode::Flat).unwrap(), "12: 2 2 3\n7: 7");
+/// assert_eq!(factor_report(&[12], FactorMode::Exponent).unwrap(), "12: 2^2 3");
+/// ```
+pub fn factor_report(numbers: &[u64], mode: FactorMode) -> AgentResult<String> {
+    match mode {
+        FactorMode::Flat => {
+            let lines = numbers
+                .iter()
+                .map(|&n| factor(n))
+                .collect::<AgentResult<Vec<_>>>()?;
+            Ok(lines.join("\n"))
+        }
+        FactorMode::Exponent => {
+            let lines: Vec<String> = numbers
+                .iter()
+                .map(|&n| {
+                    let powers: Vec<String> = prime_factors(n)
+                        .iter()
+                        .map(|&(p, k)| {
+                            if k == 1 {
+                                p.to_string()
+                            } else {
+                                format!("{}^{}", p, k)
+                            }
+                        })
+                        .collect();
+                    if powers.is_empty() {
+                        format!("{}:", n)
+                    } else {
+                        format!("{}: {}", n, powers.join(" "))
+                    }
+                })
+                .collect();
+            Ok(lines.join("\n"))
+        }
+        FactorMode::Json => {
+            let entries: Vec<FactorEntry> = numbers
+                .iter()
+                .map(|&n| FactorEntry {
+                    n,
+                    factors: prime_factors(n)
+                        .into_iter()
+                        .map(|(prime, exp)| PrimePower { prime, exp })
+                        .collect(),
+                })
+                .collect();
+            serde_json::to_string(&entries).map_err(|e| AgentError::EncodingError(e.to_string()))
+        }
+    }
+}
+
+/// Prime factorisation of `n` as ascending `(prime, exponent)` pairs.
+///
+/// Empty for 0 and 1.
+fn prime_factors(n: u64) -> Vec<(u64, u32)> {
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    if n < 2 {
+        return factors;
+    }
+
     let mut remaining = n;
     let mut divisor = 2u64;
-
     while divisor * divisor <= remaining {
+        let mut exponent = 0;
         while remaining.is_multiple_of(divisor) {
-            factors.push(divisor);
+            exponent += 1;
             remaining /= divisor;
         }
+        if exponent > 0 {
+            factors.push((divisor, exponent));
+        }
         divisor += 1;
     }
     if remaining > 1 {
-        factors.push(remaining);
+        factors.push((remaining, 1));
     }
+    factors
+}
 
-    let factor_strs: Vec<String> = factors.iter().map(|f| f.to_string()).collect();
-    Ok(format!("{}: {}", n, factor_strs.join(" ")))
+/// Divisors - all positive divisors of `n` in ascending order.
+///
+/// Built from the prime factorisation. `divisors(1)` is `[1]`; 0 has no
+/// finite divisor list, so `divisors(0)` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::divisors;
+/// assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+/// assert_eq!(divisors(13), vec![1, 13]);
+/// ```
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut result = vec![1u64];
+    for (p, k) in prime_factors(n) {
+        let current = result.len();
+        let mut power = 1u64;
+        for _ in 0..k {
+            power *= p;
+            for i in 0..current {
+                result.push(result[i] * power);
+            }
+        }
+    }
+    result.sort_unstable();
+    result
+}
+
+/// Divisor count - number of positive divisors of `n` (τ(n)).
+///
+/// Computed as the product of `exponent + 1` over the prime factorisation.
+/// Returns 1 for 1 and 0 for 0.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::divisor_count;
+/// assert_eq!(divisor_count(12), 6);
+/// ```
+pub fn divisor_count(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    prime_factors(n)
+        .iter()
+        .map(|&(_, k)| u64::from(k) + 1)
+        .product()
+}
+
+/// Divisor sum - sum of the positive divisors of `n` (σ(n)).
+///
+/// Computed as the product of `(p^(k+1) - 1) / (p - 1)` over the prime
+/// factorisation. The result is `u128` because σ(n) can exceed `u64::MAX`.
+/// Returns 1 for 1 and 0 for 0.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::divisor_sum;
+/// assert_eq!(divisor_sum(12), 28);
+/// ```
+pub fn divisor_sum(n: u64) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    prime_factors(n)
+        .iter()
+        .map(|&(p, k)| {
+            let p = u128::from(p);
+            // 1 + p + ... + p^k, summed directly to avoid overflowing p^(k+1)
+            let mut term = 1u128;
+            let mut power = 1u128;
+            for _ in 0..k {
+                power *= p;
+                term += power;
+            }
+            term
+        })
+        .product()
 }
 
 /// Seq - generate a sequence of integers from `first` to `last` (inclusive)
@@ -117,75 +452,547 @@ pub fn factor(n: u64) -> AgentResult<String> {
 /// assert_eq!(seq(5, -1, 3).unwrap(), "5\n4\n3");
 /// ```
 pub fn seq(first: i64, increment: i64, last: i64) -> AgentResult<String> {
+    seq_with_options(first, increment, last, &SeqOptions::default())
+}
+
+/// Options for [`seq_with_options`] and [`seq_f64`].
+#[derive(Debug, Clone, Default)]
+pub struct SeqOptions {
+    /// printf-style floating point format applied to each number (`seq -f`),
+    /// e.g. `%.3f` or `%03g`. It must contain exactly one `%f`, `%e` or `%g`
+    /// conversion; `%%` is a literal percent sign.
+    pub format: Option<String>,
+    /// Zero-pad integers to the width of the widest value (`seq -w`). The
+    /// padding goes after the sign, so `-10..=5` gives `-10`, `-07`, ...,
+    /// `002`, `005`. Integer sequences only; cannot be combined with `format`.
+    pub equal_width: bool,
+}
+
+/// Seq with explicit formatting options.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::{seq_with_options, SeqOptions};
+/// let options = SeqOptions { format: Some("%03g".into()), ..Default::default() };
+/// assert_eq!(seq_with_options(8, 1, 10, &options).unwrap(), "008\n009\n010");
+/// let options = SeqOptions { equal_width: true, ..Default::default() };
+/// assert_eq!(seq_with_options(-1, 1, 1, &options).unwrap(), "-1\n00\n01");
+/// ```
+pub fn seq_with_options(
+    first: i64,
+    increment: i64,
+    last: i64,
+    options: &SeqOptions,
+) -> AgentResult<String> {
+    let format = options
+        .format
+        .as_deref()
+        .map(FloatFormat::parse)
+        .transpose()?;
+    let width = if options.equal_width {
+        if format.is_some() {
+            return Err(AgentError::InvalidInput(
+                "A format cannot be combined with equal width".into(),
+            ));
+        }
+        Some(seq_width(first, increment, last))
+    } else {
+        None
+    };
+
+    let mut output = Vec::new();
+    write_seq(
+        first,
+        increment,
+        last,
+        format.as_ref(),
+        width,
+        &mut output,
+        "\n",
+    )?;
+    // Numbers and the format's literal text are both valid UTF-8
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+/// Seq - stream the sequence to `out` instead of building a string.
+///
+/// Numbers are separated by `separator` (no trailing separator) and pass
+/// through a bounded internal buffer, so sequences of any length use
+/// constant memory.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::seq_write;
+/// let mut out = Vec::new();
+/// seq_write(1, 2, 7, &mut out, ",").unwrap();
+/// assert_eq!(out, b"1,3,5,7");
+/// ```
+pub fn seq_write(
+    first: i64,
+    increment: i64,
+    last: i64,
+    out: &mut impl Write,
+    separator: &str,
+) -> AgentResult<()> {
+    write_seq(first, increment, last, None, None, out, separator)
+}
+
+/// Width of the widest value of an integer sequence, sign included.
+///
+/// Magnitudes grow towards the ends of a range, so the widest value is
+/// either `first` or the last value actually reached.
+fn seq_width(first: i64, increment: i64, last: i64) -> usize {
+    let (first, increment, last) = (i128::from(first), i128::from(increment), i128::from(last));
+    let reached = if (last - first).signum() == increment.signum() {
+        first + (last - first) / increment * increment
+    } else {
+        first
+    };
+    first.to_string().len().max(reached.to_string().len())
+}
+
+/// Zero-pad an integer to `width` characters, keeping the sign in front.
+fn pad_integer(value: i64, width: usize) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let digits = width.saturating_sub(sign.len());
+    format!("{}{:0>digits$}", sign, value.unsigned_abs())
+}
+
+/// Write an integer sequence, optionally formatted or zero-padded to
+/// `width`, through a bounded buffer.
+fn write_seq(
+    first: i64,
+    increment: i64,
+    last: i64,
+    format: Option<&FloatFormat>,
+    width: Option<usize>,
+    out: &mut impl Write,
+    separator: &str,
+) -> AgentResult<()> {
     if increment == 0 {
         return Err(AgentError::InvalidInput("Increment cannot be zero".into()));
     }
 
-    let mut result: Vec<String> = Vec::new();
-    let mut current = first;
+    let mut out = BufWriter::with_capacity(SEQ_WRITE_BUFFER, out);
+    let mut current = Some(first);
+    let mut needs_separator = false;
 
-    if increment > 0 {
-        while current <= last {
-            result.push(current.to_string());
-            current += increment;
+    while let Some(value) = current {
+        let past_end = if increment > 0 {
+            value > last
+        } else {
+            value < last
+        };
+        if past_end {
+            break;
         }
-    } else {
-        while current >= last {
-            result.push(current.to_string());
-            current += increment;
+
+        if needs_separator {
+            out.write_all(separator.as_bytes())?;
         }
+        needs_separator = true;
+        match (format, width) {
+            (Some(format), _) => out.write_all(format.apply(value as f64).as_bytes())?,
+            (None, Some(width)) => out.write_all(pad_integer(value, width).as_bytes())?,
+            (None, None) => write!(out, "{}", value)?,
+        }
+        // Stop instead of overflowing at the ends of the i64 range
+        current = value.checked_add(increment);
     }
 
-    Ok(result.join("\n"))
+    out.flush()?;
+    Ok(())
 }
 
-/// Numfmt - format a byte count with a unit prefix.
+/// Seq over floating point values.
 ///
-/// `to_unit` may be `"iec"` (powers of 1024, e.g. `1.0K`) or `"si"` (powers
-/// of 1000, e.g. `1.0K`).
+/// Each value is computed as `first + i * increment` so rounding errors do
+/// not accumulate. Without a format, values are printed as with `%g`.
 ///
 /// # Examples
 ///
 /// ```
-/// use mistralrs_agent_tools::tools::numeric::numfmt;
-/// assert_eq!(numfmt(1024, "iec").unwrap(), "1.0K");
-/// assert_eq!(numfmt(1_000_000, "si").unwrap(), "1.0M");
+/// use mistralrs_agent_tools::tools::numeric::{seq_f64, SeqOptions};
+/// let options = SeqOptions { format: Some("%.2f".into()), ..Default::default() };
+/// assert_eq!(seq_f64(0.5, 0.25, 1.0, &options).unwrap(), "0.50\n0.75\n1.00");
+/// assert_eq!(seq_f64(0.1, 0.1, 0.3, &SeqOptions::default()).unwrap(), "0.1\n0.2\n0.3");
 /// ```
-pub fn numfmt(value: u64, to_unit: &str) -> AgentResult<String> {
-    match to_unit {
-        "iec" => {
-            let units = ["", "K", "M", "G", "T", "P"];
-            let mut val = value as f64;
-            let mut unit_idx = 0usize;
-            while val >= 1024.0 && unit_idx < units.len() - 1 {
-                val /= 1024.0;
-                unit_idx += 1;
+pub fn seq_f64(first: f64, increment: f64, last: f64, options: &SeqOptions) -> AgentResult<String> {
+    if increment == 0.0 || !increment.is_finite() || !first.is_finite() || !last.is_finite() {
+        return Err(AgentError::InvalidInput(
+            "Increment must be non-zero and all values finite".into(),
+        ));
+    }
+    if options.equal_width {
+        return Err(AgentError::InvalidInput(
+            "Equal width applies to integer sequences only".into(),
+        ));
+    }
+    let format = FloatFormat::parse(options.format.as_deref().unwrap_or("%g"))?;
+
+    // Tolerate rounding error so `0.1 0.1 0.3` still reaches 0.3
+    let epsilon = increment.abs() * 1e-9;
+    let mut result: Vec<String> = Vec::new();
+    for i in 0u64.. {
+        let mut value = first + i as f64 * increment;
+        let past_end = if increment > 0.0 {
+            value > last + epsilon
+        } else {
+            value < last - epsilon
+        };
+        if past_end {
+            break;
+        }
+        // Avoid printing rounding noise around zero as `-0.00`
+        if value.abs() < epsilon {
+            value = 0.0;
+        }
+        result.push(format.apply(value));
+    }
+
+    Ok(result.join("\n"))
+}
+
+/// A parsed printf-style floating point format with a single conversion.
+#[derive(Debug)]
+struct FloatFormat {
+    prefix: String,
+    suffix: String,
+    left_align: bool,
+    zero_pad: bool,
+    plus_sign: bool,
+    space_sign: bool,
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+    conversion: char,
+}
+
+impl FloatFormat {
+    fn parse(format: &str) -> AgentResult<Self> {
+        let invalid = |reason: &str| {
+            AgentError::InvalidInput(format!("Invalid format '{}': {}", format, reason))
+        };
+
+        let mut prefix = String::new();
+        let mut chars = format.chars().peekable();
+        // Copy literal text up to the conversion
+        loop {
+            match chars.next() {
+                Some('%') if chars.peek() == Some(&'%') => {
+                    chars.next();
+                    prefix.push('%');
+                }
+                Some('%') => break,
+                Some(c) => prefix.push(c),
+                None => return Err(invalid("missing % directive")),
             }
-            if unit_idx == 0 {
-                Ok(format!("{}", value))
-            } else {
-                Ok(format!("{:.1}{}", val, units[unit_idx]))
+        }
+
+        let mut spec = Self {
+            prefix,
+            suffix: String::new(),
+            left_align: false,
+            zero_pad: false,
+            plus_sign: false,
+            space_sign: false,
+            alternate: false,
+            width: 0,
+            precision: None,
+            conversion: 'g',
+        };
+        while let Some(&c) = chars.peek() {
+            match c {
+                '-' => spec.left_align = true,
+                '0' => spec.zero_pad = true,
+                '+' => spec.plus_sign = true,
+                ' ' => spec.space_sign = true,
+                '#' => spec.alternate = true,
+                _ => break,
             }
+            chars.next();
+        }
+        while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+            spec.width = spec.width * 10 + digit as usize;
+            chars.next();
         }
-        "si" => {
-            let units = ["", "K", "M", "G", "T", "P"];
-            let mut val = value as f64;
-            let mut unit_idx = 0usize;
-            while val >= 1000.0 && unit_idx < units.len() - 1 {
-                val /= 1000.0;
-                unit_idx += 1;
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            let mut precision = 0usize;
+            while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                precision = precision * 10 + digit as usize;
+                chars.next();
             }
-            if unit_idx == 0 {
-                Ok(format!("{}", value))
-            } else {
-                Ok(format!("{:.1}{}", val, units[unit_idx]))
+            spec.precision = Some(precision);
+        }
+        spec.conversion = match chars.next() {
+            Some(c @ ('f' | 'F' | 'e' | 'E' | 'g' | 'G')) => c,
+            Some(c) => return Err(invalid(&format!("unsupported conversion '%{}'", c))),
+            None => return Err(invalid("incomplete % directive")),
+        };
+
+        while let Some(c) = chars.next() {
+            if c == '%' && chars.next() != Some('%') {
+                return Err(invalid("too many % directives"));
             }
+            spec.suffix.push(c);
         }
-        other => Err(AgentError::InvalidInput(format!(
+
+        Ok(spec)
+    }
+
+    fn apply(&self, value: f64) -> String {
+        let precision = self.precision.unwrap_or(6);
+        let mut body = match self.conversion.to_ascii_lowercase() {
+            'f' => format!("{:.*}", precision, value.abs()),
+            'e' => format_exponent(value.abs(), precision),
+            _ => self.format_general(value.abs(), precision),
+        };
+        if self.conversion.is_ascii_uppercase() {
+            body = body.to_uppercase();
+        }
+
+        let sign = if value.is_sign_negative() && value != 0.0 {
+            "-"
+        } else if self.plus_sign {
+            "+"
+        } else if self.space_sign {
+            " "
+        } else {
+            ""
+        };
+
+        let len = sign.len() + body.len();
+        let padding = self.width.saturating_sub(len);
+        let number = if self.left_align {
+            format!("{}{}{}", sign, body, " ".repeat(padding))
+        } else if self.zero_pad {
+            format!("{}{}{}", sign, "0".repeat(padding), body)
+        } else {
+            format!("{}{}{}", " ".repeat(padding), sign, body)
+        };
+
+        format!("{}{}{}", self.prefix, number, self.suffix)
+    }
+
+    /// `%g`: the shorter of `%e` and `%f` with `precision` significant digits
+    fn format_general(&self, value: f64, precision: usize) -> String {
+        let precision = precision.max(1);
+        if value == 0.0 {
+            return if self.alternate {
+                format!("{:.*}", precision - 1, 0.0)
+            } else {
+                "0".to_string()
+            };
+        }
+
+        // Exponent after rounding to the requested significant digits
+        let rounded = format!("{:.*e}", precision - 1, value);
+        let exponent: i32 = rounded
+            .rsplit('e')
+            .next()
+            .and_then(|e| e.parse().ok())
+            .unwrap_or(0);
+
+        let body = if exponent < -4 || exponent >= precision as i32 {
+            format_exponent(value, precision - 1)
+        } else {
+            let decimals = (precision as i32 - 1 - exponent) as usize;
+            format!("{:.*}", decimals, value)
+        };
+        if self.alternate {
+            body
+        } else {
+            strip_trailing_zeros(&body)
+        }
+    }
+}
+
+/// Format as `d.ddde+XX`, matching C's `%e`.
+fn format_exponent(value: f64, precision: usize) -> String {
+    let formatted = format!("{:.*e}", precision, value);
+    let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+}
+
+/// Drop trailing fractional zeros (and a bare `.`) from a `%g` mantissa.
+fn strip_trailing_zeros(body: &str) -> String {
+    let (mantissa, exponent) = match body.find('e') {
+        Some(idx) => body.split_at(idx),
+        None => (body, ""),
+    };
+    let mantissa = if mantissa.contains('.') {
+        mantissa.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        mantissa
+    };
+    format!("{}{}", mantissa, exponent)
+}
+
+/// Options for [`numfmt_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct NumfmtOptions {
+    /// Insert `,` thousands separators into raw values below the smallest
+    /// unit (e.g. `1,023`). Unit-prefixed values are unaffected.
+    pub grouping: bool,
+    /// Use powers of 1000 rather than 1024 when `to_unit` is `"auto"`.
+    pub auto_si: bool,
+    /// Text appended after the unit letter, or after raw values below the
+    /// smallest unit (e.g. `"B"` gives `1.5KB` and `512B`).
+    pub suffix: Option<String>,
+}
+
+/// Numfmt - format a byte count with a unit prefix.
+///
+/// `to_unit` may be `"iec"` (powers of 1024, e.g. `1.0K`), `"si"` (powers
+/// of 1000, e.g. `1.0K`) or `"auto"`, which picks between them according to
+/// [`NumfmtOptions::auto_si`] and defaults to `"iec"`. Negative values keep their sign through the
+/// scaling, so byte deltas format as e.g. `-1.5K`.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::numfmt;
+/// assert_eq!(numfmt(1024, "iec").unwrap(), "1.0K");
+/// assert_eq!(numfmt(1_000_000, "si").unwrap(), "1.0M");
+/// assert_eq!(numfmt(-1536, "iec").unwrap(), "-1.5K");
+/// ```
+pub fn numfmt(value: i64, to_unit: &str) -> AgentResult<String> {
+    numfmt_with_options(value, to_unit, &NumfmtOptions::default())
+}
+
+/// Numfmt with explicit formatting options.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::{numfmt_with_options, NumfmtOptions};
+/// let options = NumfmtOptions { grouping: true, ..Default::default() };
+/// assert_eq!(numfmt_with_options(1023, "iec", &options).unwrap(), "1,023");
+///
+/// let options = NumfmtOptions { suffix: Some("B".into()), ..Default::default() };
+/// assert_eq!(numfmt_with_options(1536, "auto", &options).unwrap(), "1.5KB");
+/// ```
+pub fn numfmt_with_options(
+    value: i64,
+    to_unit: &str,
+    options: &NumfmtOptions,
+) -> AgentResult<String> {
+    let base = match to_unit {
+        "iec" => 1024.0,
+        "si" => 1000.0,
+        "auto" if options.auto_si => 1000.0,
+        "auto" => 1024.0,
+        other => {
+            return Err(AgentError::InvalidInput(format!(
+                "Unknown unit system: {}",
+                other
+            )))
+        }
+    };
+
+    let units = ["", "K", "M", "G", "T", "P", "E"];
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = value.unsigned_abs();
+    let mut val = magnitude as f64;
+    let mut unit_idx = 0usize;
+    while val >= base && unit_idx < units.len() - 1 {
+        val /= base;
+        unit_idx += 1;
+    }
+    let suffix = options.suffix.as_deref().unwrap_or("");
+    if unit_idx > 0 {
+        Ok(format!("{}{:.1}{}{}", sign, val, units[unit_idx], suffix))
+    } else if options.grouping {
+        Ok(format!("{}{}{}", sign, group_thousands(magnitude), suffix))
+    } else {
+        Ok(format!("{}{}{}", sign, magnitude, suffix))
+    }
+}
+
+/// Numfmt `--from` - parse a unit-prefixed value back into an integer.
+///
+/// The inverse of [`numfmt`]: `from_unit` is `"iec"` (powers of 1024),
+/// `"si"` (powers of 1000) or `"auto"`, where `K` means 1000 and `Ki` means
+/// 1024. Unit letters are case-insensitive, a leading `-` is kept and
+/// fractional results are rounded to the nearest integer.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::numfmt_from;
+/// assert_eq!(numfmt_from("1.5K", "iec").unwrap(), 1536);
+/// assert_eq!(numfmt_from("1.5K", "si").unwrap(), 1500);
+/// assert_eq!(numfmt_from("-2Ki", "auto").unwrap(), -2048);
+/// ```
+pub fn numfmt_from(value: &str, from_unit: &str) -> AgentResult<i64> {
+    if !matches!(from_unit, "iec" | "si" | "auto") {
+        return Err(AgentError::InvalidInput(format!(
             "Unknown unit system: {}",
-            other
-        ))),
+            from_unit
+        )));
+    }
+    let invalid = || AgentError::InvalidInput(format!("Invalid number: {}", value));
+
+    let text = value.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let split = text
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return Err(invalid());
+    }
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+
+    let mut unit_chars = unit.chars();
+    let scale = match unit_chars.next() {
+        None => 1.0,
+        Some(letter) => {
+            let power = ['K', 'M', 'G', 'T', 'P', 'E']
+                .iter()
+                .position(|u| u.eq_ignore_ascii_case(&letter))
+                .ok_or_else(invalid)?;
+            let base: f64 = match (from_unit, unit_chars.as_str()) {
+                ("iec", "") => 1024.0,
+                ("si", "") | ("auto", "") => 1000.0,
+                ("auto", "i") => 1024.0,
+                _ => return Err(invalid()),
+            };
+            base.powi(power as i32 + 1)
+        }
+    };
+
+    let scaled = (number * scale).round();
+    let signed = if negative { -scaled } else { scaled };
+    // i64::MIN is exactly -2^63; i64::MAX rounds up to 2^63 as an f64
+    if signed < i64::MIN as f64 || signed >= i64::MAX as f64 {
+        return Err(AgentError::InvalidInput(format!(
+            "Value out of range: {}",
+            value
+        )));
     }
+    Ok(signed as i64)
+}
+
+/// Format an integer with `,` between each group of three digits.
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
 }
 
 #[cfg(test)]
@@ -243,6 +1050,72 @@ mod tests {
         assert!(expr("1 ^ 2").is_err());
     }
 
+    #[test]
+    fn test_expr_comparison() {
+        assert_eq!(expr("2 < 10").unwrap(), "1");
+        assert_eq!(expr("10 <= 10").unwrap(), "1");
+        assert_eq!(expr("3 > 4").unwrap(), "0");
+        assert_eq!(expr("4 >= 5").unwrap(), "0");
+        assert_eq!(expr("7 = 7").unwrap(), "1");
+        assert_eq!(expr("7 != 7").unwrap(), "0");
+        // Integers compare numerically, anything else lexically
+        assert_eq!(expr("-3 < 2").unwrap(), "1");
+        assert_eq!(expr("abc < abd").unwrap(), "1");
+        assert_eq!(expr("10 < 9x").unwrap(), "1");
+    }
+
+    #[test]
+    fn test_expr_detect_base() {
+        let options = ExprOptions {
+            detect_base: true,
+            ..Default::default()
+        };
+        let eval = |e: &str| expr_with_options(e, &options);
+        assert_eq!(eval("0xFF + 1").unwrap(), "256");
+        assert_eq!(eval("0b1010 * 2").unwrap(), "20");
+        assert_eq!(eval("0o17 - 0x0f").unwrap(), "0");
+        assert_eq!(eval("-0x10 + 010").unwrap(), "-6");
+        assert_eq!(eval("0x1F").unwrap(), "31");
+        assert_eq!(eval("0xa = 10").unwrap(), "1");
+        assert_eq!(eval("0x0 | 5").unwrap(), "5");
+        assert!(eval("0x-1 + 1").is_err());
+        assert!(eval("0b102 + 1").is_err());
+
+        // Prefixed operands are not numbers without detection
+        assert!(expr("0xFF + 1").is_err());
+    }
+
+    #[test]
+    fn test_expr_output_base() {
+        let options = ExprOptions {
+            detect_base: true,
+            output_base: ExprBase::Hex,
+        };
+        assert_eq!(expr_with_options("0xFF + 1", &options).unwrap(), "0x100");
+        assert_eq!(expr_with_options("1 - 17", &options).unwrap(), "-0x10");
+        assert_eq!(expr_with_options("2 < 10", &options).unwrap(), "1");
+
+        let options = ExprOptions {
+            output_base: ExprBase::Binary,
+            ..Default::default()
+        };
+        assert_eq!(expr_with_options("5", &options).unwrap(), "0b101");
+        assert_eq!(ExprBase::Octal.format(8), "0o10");
+    }
+
+    #[test]
+    fn test_expr_logical() {
+        assert_eq!(expr("3 | 5").unwrap(), "3");
+        assert_eq!(expr("0 | 5").unwrap(), "5");
+        assert_eq!(expr("0 | 0").unwrap(), "0");
+        assert_eq!(expr("foo | bar").unwrap(), "foo");
+
+        assert_eq!(expr("3 & 5").unwrap(), "3");
+        assert_eq!(expr("3 & 0").unwrap(), "0");
+        assert_eq!(expr("0 & 5").unwrap(), "0");
+        assert_eq!(expr("foo & bar").unwrap(), "foo");
+    }
+
     #[test]
     fn test_factor_composite() {
         assert_eq!(factor(12).unwrap(), "12: 2 2 3");
@@ -269,6 +1142,65 @@ mod tests {
         assert_eq!(factor(97).unwrap(), "97: 97");
     }
 
+    #[test]
+    fn test_factor_report_modes() {
+        assert_eq!(
+            factor_report(&[12, 7], FactorMode::Flat).unwrap(),
+            "12: 2 2 3\n7: 7"
+        );
+        assert_eq!(
+            factor_report(&[12, 7], FactorMode::Exponent).unwrap(),
+            "12: 2^2 3\n7: 7"
+        );
+        assert_eq!(
+            factor_report(&[12, 7], FactorMode::Json).unwrap(),
+            r#"[{"n":12,"factors":[{"prime":2,"exp":2},{"prime":3,"exp":1}]},{"n":7,"factors":[{"prime":7,"exp":1}]}]"#
+        );
+    }
+
+    #[test]
+    fn test_factor_report_edge_cases() {
+        assert_eq!(factor_report(&[], FactorMode::Flat).unwrap(), "");
+        assert_eq!(
+            factor_report(&[1, 0], FactorMode::Exponent).unwrap(),
+            "1:\n0:"
+        );
+        assert_eq!(
+            factor_report(&[1], FactorMode::Json).unwrap(),
+            r#"[{"n":1,"factors":[]}]"#
+        );
+    }
+
+    #[test]
+    fn test_divisors() {
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(97), vec![1, 97]);
+        assert_eq!(divisors(36), vec![1, 2, 3, 4, 6, 9, 12, 18, 36]);
+        assert_eq!(divisors(1), vec![1]);
+        assert!(divisors(0).is_empty());
+    }
+
+    #[test]
+    fn test_divisor_count_and_sum() {
+        assert_eq!(divisor_count(12), 6);
+        assert_eq!(divisor_sum(12), 28);
+        // 28 is perfect: σ(n) = 2n
+        assert_eq!(divisor_sum(28), 56);
+        assert_eq!(divisor_count(97), 2);
+        assert_eq!(divisor_sum(97), 98);
+        assert_eq!((divisor_count(1), divisor_sum(1)), (1, 1));
+        assert_eq!((divisor_count(0), divisor_sum(0)), (0, 0));
+
+        for n in 1..200u64 {
+            let list = divisors(n);
+            assert_eq!(list.len() as u64, divisor_count(n));
+            assert_eq!(
+                list.iter().map(|&d| d as u128).sum::<u128>(),
+                divisor_sum(n)
+            );
+        }
+    }
+
     #[test]
     fn test_seq_ascending() {
         assert_eq!(seq(1, 1, 5).unwrap(), "1\n2\n3\n4\n5");
@@ -300,6 +1232,141 @@ mod tests {
         assert!(seq(1, 0, 5).is_err());
     }
 
+    #[test]
+    fn test_seq_write_streams_large_sequence() {
+        let mut out = Vec::new();
+        seq_write(1, 1, 1_000_000, &mut out, "\n").unwrap();
+
+        let text = std::str::from_utf8(&out).unwrap();
+        assert_eq!(text.split('\n').next(), Some("1"));
+        assert_eq!(text.rsplit('\n').next(), Some("1000000"));
+        assert_eq!(text.matches('\n').count(), 999_999);
+    }
+
+    #[test]
+    fn test_seq_write_separator_and_bounds() {
+        let mut out = Vec::new();
+        seq_write(10, -3, 1, &mut out, ", ").unwrap();
+        assert_eq!(out, b"10, 7, 4, 1");
+
+        // The final step would overflow i64
+        let mut out = Vec::new();
+        seq_write(i64::MAX - 1, 1, i64::MAX, &mut out, " ").unwrap();
+        assert_eq!(out, format!("{} {}", i64::MAX - 1, i64::MAX).as_bytes());
+
+        assert!(seq_write(1, 0, 5, &mut Vec::new(), "\n").is_err());
+    }
+
+    #[test]
+    fn test_seq_f64_fixed_format() {
+        let options = SeqOptions {
+            format: Some("%.2f".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            seq_f64(1.0, 0.5, 2.5, &options).unwrap(),
+            "1.00\n1.50\n2.00\n2.50"
+        );
+        assert_eq!(
+            seq_f64(0.3, -0.1, 0.0, &options).unwrap(),
+            "0.30\n0.20\n0.10\n0.00"
+        );
+    }
+
+    #[test]
+    fn test_seq_f64_default_format() {
+        let options = SeqOptions::default();
+        assert_eq!(
+            seq_f64(0.1, 0.1, 0.5, &options).unwrap(),
+            "0.1\n0.2\n0.3\n0.4\n0.5"
+        );
+        assert!(seq_f64(1.0, 0.0, 2.0, &options).is_err());
+    }
+
+    #[test]
+    fn test_seq_width_format() {
+        let options = SeqOptions {
+            format: Some("%03g".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            seq_with_options(9, 1, 11, &options).unwrap(),
+            "009\n010\n011"
+        );
+
+        let labelled = SeqOptions {
+            format: Some("item-%-3g|".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            seq_with_options(1, 1, 2, &labelled).unwrap(),
+            "item-1  |\nitem-2  |"
+        );
+    }
+
+    #[test]
+    fn test_seq_equal_width() {
+        let options = SeqOptions {
+            equal_width: true,
+            ..Default::default()
+        };
+        let seq_w = |first, increment, last| {
+            seq_with_options(first, increment, last, &options)
+                .unwrap()
+                .replace('\n', " ")
+        };
+
+        // The sign goes before the padding, as in coreutils `seq -w`
+        assert_eq!(seq_w(-5, 1, 5), "-5 -4 -3 -2 -1 00 01 02 03 04 05");
+        assert_eq!(seq_w(-10, 3, 5), "-10 -07 -04 -01 002 005");
+        // The most negative value is the widest in a descending range
+        assert_eq!(
+            seq_w(-1, -1, -10),
+            "-01 -02 -03 -04 -05 -06 -07 -08 -09 -10"
+        );
+        // The last value reached, not `last`, sets the width
+        assert_eq!(seq_w(1, 4, 10), "1 5 9");
+        assert_eq!(seq_w(8, 1, 10), "08 09 10");
+        assert_eq!(seq_w(5, 1, 1), "");
+
+        let with_format = SeqOptions {
+            format: Some("%g".into()),
+            equal_width: true,
+        };
+        assert!(seq_with_options(1, 1, 3, &with_format).is_err());
+        assert!(seq_f64(1.0, 1.0, 3.0, &options).is_err());
+    }
+
+    #[test]
+    fn test_seq_invalid_format() {
+        for format in ["%d", "no directive", "%f %f", "%.2"] {
+            let options = SeqOptions {
+                format: Some(format.into()),
+                ..Default::default()
+            };
+            assert!(
+                matches!(
+                    seq_with_options(1, 1, 3, &options),
+                    Err(AgentError::InvalidInput(_))
+                ),
+                "{}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn test_float_format_conversions() {
+        let apply = |format: &str, value: f64| FloatFormat::parse(format).unwrap().apply(value);
+        assert_eq!(apply("%e", 1234.5), "1.234500e+03");
+        assert_eq!(apply("%g", 0.0001), "0.0001");
+        assert_eq!(apply("%g", 1e-5), "1e-05");
+        assert_eq!(apply("%g", 1234567.0), "1.23457e+06");
+        assert_eq!(apply("%+.1f", 2.0), "+2.0");
+        assert_eq!(apply("%06.1f", -2.5), "-002.5");
+        assert_eq!(apply("100%% at %g", 3.0), "100% at 3");
+    }
+
     #[test]
     fn test_numfmt_iec_kibibyte() {
         assert_eq!(numfmt(1024, "iec").unwrap(), "1.0K");
@@ -325,8 +1392,138 @@ mod tests {
         assert_eq!(numfmt(1_000_000, "si").unwrap(), "1.0M");
     }
 
+    #[test]
+    fn test_numfmt_negative_iec() {
+        assert_eq!(numfmt(-1536, "iec").unwrap(), "-1.5K");
+        assert_eq!(numfmt(-500, "iec").unwrap(), "-500");
+        assert_eq!(numfmt(i64::MIN, "iec").unwrap(), "-8.0E");
+    }
+
+    #[test]
+    fn test_numfmt_negative_si() {
+        assert_eq!(numfmt(-2_500_000, "si").unwrap(), "-2.5M");
+        let options = NumfmtOptions {
+            grouping: true,
+            ..Default::default()
+        };
+        assert_eq!(numfmt_with_options(-999, "si", &options).unwrap(), "-999");
+    }
+
+    #[test]
+    fn test_numfmt_zero() {
+        assert_eq!(numfmt(0, "iec").unwrap(), "0");
+        assert_eq!(numfmt(0, "si").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_numfmt_from_units() {
+        assert_eq!(numfmt_from("1.5K", "iec").unwrap(), 1536);
+        assert_eq!(numfmt_from("1.5K", "si").unwrap(), 1500);
+        assert_eq!(numfmt_from("2k", "si").unwrap(), 2000);
+        assert_eq!(numfmt_from("1K", "auto").unwrap(), 1000);
+        assert_eq!(numfmt_from("1Ki", "auto").unwrap(), 1024);
+        assert_eq!(numfmt_from("512", "iec").unwrap(), 512);
+        assert_eq!(numfmt_from("0", "si").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_numfmt_from_negative() {
+        assert_eq!(numfmt_from("-1.5K", "iec").unwrap(), -1536);
+        assert_eq!(numfmt_from("-2.5M", "si").unwrap(), -2_500_000);
+        assert_eq!(numfmt_from("-8.0E", "iec").unwrap(), i64::MIN);
+    }
+
+    #[test]
+    fn test_numfmt_from_round_trip() {
+        for value in [0, 512, -1536, 1_048_576, -3_221_225_472] {
+            let formatted = numfmt(value, "iec").unwrap();
+            assert_eq!(numfmt_from(&formatted, "iec").unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_numfmt_from_invalid() {
+        assert!(numfmt_from("", "iec").is_err());
+        assert!(numfmt_from("K", "iec").is_err());
+        assert!(numfmt_from("1.5X", "iec").is_err());
+        assert!(numfmt_from("1Ki", "iec").is_err());
+        assert!(numfmt_from("1.2.3", "si").is_err());
+        assert!(numfmt_from("8E", "iec").is_err());
+        assert!(numfmt_from("1K", "binary").is_err());
+    }
+
     #[test]
     fn test_numfmt_unknown_unit() {
         assert!(numfmt(1024, "binary").is_err());
     }
+
+    #[test]
+    fn test_numfmt_grouping_sub_unit() {
+        let options = NumfmtOptions {
+            grouping: true,
+            ..Default::default()
+        };
+        assert_eq!(numfmt_with_options(1023, "iec", &options).unwrap(), "1,023");
+        assert_eq!(numfmt_with_options(999, "si", &options).unwrap(), "999");
+        assert_eq!(group_thousands(12_345_678), "12,345,678");
+    }
+
+    #[test]
+    fn test_numfmt_grouping_unit_prefixed_unaffected() {
+        let options = NumfmtOptions {
+            grouping: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            numfmt_with_options(1_048_576, "iec", &options).unwrap(),
+            "1.0M"
+        );
+        assert_eq!(
+            numfmt_with_options(12_345, "si", &options).unwrap(),
+            "12.3K"
+        );
+        assert_eq!(numfmt(1023, "iec").unwrap(), "1023");
+    }
+
+    #[test]
+    fn test_numfmt_auto_unit_selection() {
+        assert_eq!(numfmt(1536, "auto").unwrap(), "1.5K");
+        assert_eq!(numfmt(1_048_576, "auto").unwrap(), "1.0M");
+        assert_eq!(numfmt(1000, "auto").unwrap(), "1000");
+
+        let si = NumfmtOptions {
+            auto_si: true,
+            ..Default::default()
+        };
+        assert_eq!(numfmt_with_options(1000, "auto", &si).unwrap(), "1.0K");
+        assert_eq!(numfmt_with_options(2_500_000, "auto", &si).unwrap(), "2.5M");
+        // auto_si only affects "auto"
+        assert_eq!(numfmt_with_options(1000, "iec", &si).unwrap(), "1000");
+    }
+
+    #[test]
+    fn test_numfmt_suffix() {
+        let options = NumfmtOptions {
+            suffix: Some("B".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            numfmt_with_options(1536, "auto", &options).unwrap(),
+            "1.5KB"
+        );
+        assert_eq!(
+            numfmt_with_options(-1536, "iec", &options).unwrap(),
+            "-1.5KB"
+        );
+        assert_eq!(numfmt_with_options(512, "si", &options).unwrap(), "512B");
+
+        let grouped = NumfmtOptions {
+            grouping: true,
+            ..options
+        };
+        assert_eq!(
+            numfmt_with_options(1023, "iec", &grouped).unwrap(),
+            "1,023B"
+        );
+    }
 }
@@ -0,0 +1,105 @@
+//! Rounding, clamping and percentage helpers for reporting numbers
+
+use crate::types::{AgentError, AgentResult};
+
+/// Round - round `value` to `places` decimal places, halves away from zero.
+///
+/// Non-finite values are returned unchanged, as are values that overflow
+/// when scaled by `10^places`.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::round_to;
+/// assert_eq!(round_to(1.23456, 2), 1.23);
+/// assert_eq!(round_to(-2.5, 0), -3.0);
+/// ```
+pub fn round_to(value: f64, places: u32) -> f64 {
+    let factor = 10f64.powi(places.min(i32::MAX as u32) as i32);
+    let scaled = value * factor;
+    if !scaled.is_finite() {
+        return value;
+    }
+    scaled.round() / factor
+}
+
+/// Clamp - limit `value` to the inclusive range `min..=max`.
+///
+/// # Errors
+/// Returns `InvalidInput` if `min` is greater than `max`.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::clamp_i64;
+/// assert_eq!(clamp_i64(15, 0, 10).unwrap(), 10);
+/// assert!(clamp_i64(5, 10, 0).is_err());
+/// ```
+pub fn clamp_i64(value: i64, min: i64, max: i64) -> AgentResult<i64> {
+    if min > max {
+        return Err(AgentError::InvalidInput(format!(
+            "Invalid range: min {} is greater than max {}",
+            min, max
+        )));
+    }
+    Ok(value.clamp(min, max))
+}
+
+/// Percent - `part` as a percentage of `whole`.
+///
+/// # Errors
+/// Returns `InvalidInput` if `whole` is zero or either value is not finite.
+///
+/// # Examples
+///
+/// ```
+/// use mistralrs_agent_tools::tools::numeric::percent;
+/// assert_eq!(percent(1.0, 4.0).unwrap(), 25.0);
+/// assert!(percent(1.0, 0.0).is_err());
+/// ```
+pub fn percent(part: f64, whole: f64) -> AgentResult<f64> {
+    if !part.is_finite() || !whole.is_finite() {
+        return Err(AgentError::InvalidInput(
+            "Percent of a non-finite value".into(),
+        ));
+    }
+    if whole == 0.0 {
+        return Err(AgentError::InvalidInput(
+            "Percent of a zero whole is undefined".into(),
+        ));
+    }
+    Ok(part / whole * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_to() {
+        assert_eq!(round_to(1.23456, 2), 1.23);
+        assert_eq!(round_to(2.675, 1), 2.7);
+        assert_eq!(round_to(-1.005, 0), -1.0);
+        assert_eq!(round_to(42.0, 3), 42.0);
+        assert_eq!(round_to(1e300, 10), 1e300);
+        assert!(round_to(f64::NAN, 2).is_nan());
+    }
+
+    #[test]
+    fn test_clamp_i64() {
+        assert_eq!(clamp_i64(-5, 0, 10).unwrap(), 0);
+        assert_eq!(clamp_i64(7, 0, 10).unwrap(), 7);
+        assert_eq!(clamp_i64(15, 0, 10).unwrap(), 10);
+        assert_eq!(clamp_i64(3, 3, 3).unwrap(), 3);
+        assert!(clamp_i64(5, 10, 0).is_err());
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(1.0, 4.0).unwrap(), 25.0);
+        assert_eq!(percent(3.0, -6.0).unwrap(), -50.0);
+        assert_eq!(round_to(percent(1.0, 3.0).unwrap(), 2), 33.33);
+        assert!(percent(1.0, 0.0).is_err());
+        assert!(percent(f64::INFINITY, 2.0).is_err());
+    }
+}
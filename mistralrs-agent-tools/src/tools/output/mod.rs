@@ -37,8 +37,11 @@ pub fn echo_n(args: &[&str]) -> AgentResult<String> {
 
 /// Printf - format and print data (simplified subset of POSIX printf).
 ///
-/// Supports `%s` and `%d` placeholders (replaced left-to-right) and the
-/// escape sequences `\n`, `\t`, and `\\`.
+/// Walks the format left to right, replacing each `%s`, `%d` or `%b` with
+/// the next argument and `%%` with `%`; placeholders without an argument are
+/// left as-is. The escape sequences `\n`, `\t` and `\\` are interpreted in the
+/// format. Arguments are inserted literally, except that `%b` interprets the
+/// same escapes within its argument.
 ///
 /// # Examples
 ///
@@ -49,27 +52,71 @@ pub fn echo_n(args: &[&str]) -> AgentResult<String> {
 ///     "Hello world, you are 42 years old"
 /// );
 /// assert_eq!(printf("line1\\nline2", &[]).unwrap(), "line1\nline2");
+/// assert_eq!(printf("%b|%s", &["a\\tb", "a\\tb"]).unwrap(), "a\tb|a\\tb");
 /// ```
 pub fn printf(format: &str, args: &[&str]) -> AgentResult<String> {
-    let mut result = format.to_string();
-
-    for arg in args {
-        if let Some(pos) = result.find("%s") {
-            result.replace_range(pos..pos + 2, arg);
-        } else if let Some(pos) = result.find("%d") {
-            result.replace_range(pos..pos + 2, arg);
+    let mut result = String::with_capacity(format.len());
+    let mut args = args.iter();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => push_escape(&mut result, &mut chars),
+            '%' => match chars.peek() {
+                Some('%') => {
+                    chars.next();
+                    result.push('%');
+                }
+                Some(&conversion @ ('s' | 'd' | 'b')) => match args.next() {
+                    Some(arg) => {
+                        chars.next();
+                        if conversion == 'b' {
+                            result.push_str(&interpret_escapes(arg));
+                        } else {
+                            result.push_str(arg);
+                        }
+                    }
+                    None => result.push('%'),
+                },
+                _ => result.push('%'),
+            },
+            c => result.push(c),
         }
     }
 
-    // Handle common escape sequences
-    result = result
-        .replace("\\n", "\n")
-        .replace("\\t", "\t")
-        .replace("\\\\", "\\");
-
     Ok(result)
 }
 
+/// Expand the `\n`, `\t` and `\\` escapes in `text`, as printf's `%b` does
+fn interpret_escapes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            push_escape(&mut result, &mut chars);
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Push the character for the escape following a backslash, consuming it,
+/// or the backslash itself if no supported escape follows
+fn push_escape(result: &mut String, chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    let escaped = match chars.peek() {
+        Some('n') => '\n',
+        Some('t') => '\t',
+        Some('\\') => '\\',
+        _ => {
+            result.push('\\');
+            return;
+        }
+    };
+    chars.next();
+    result.push(escaped);
+}
+
 /// Yes - output `text` (default `"y"`) repeated `count` times, one per line.
 ///
 /// # Examples
@@ -148,6 +195,23 @@ mod tests {
         assert_eq!(printf("back\\\\slash", &[]).unwrap(), "back\\slash");
     }
 
+    #[test]
+    fn test_printf_b_interprets_argument_escapes() {
+        assert_eq!(printf("%b", &["a\\tb"]).unwrap(), "a\tb");
+        assert_eq!(printf("[%b]", &["one\\ntwo\\\\"]).unwrap(), "[one\ntwo\\]");
+        // %s and %d insert arguments literally
+        assert_eq!(printf("%s", &["a\\tb"]).unwrap(), "a\\tb");
+        assert_eq!(printf("%d %s", &["1\\n", "x"]).unwrap(), "1\\n x");
+    }
+
+    #[test]
+    fn test_printf_placeholders_in_order() {
+        assert_eq!(printf("%d-%s", &["1", "a"]).unwrap(), "1-a");
+        assert_eq!(printf("100%% %s", &["done"]).unwrap(), "100% done");
+        assert_eq!(printf("%s and %s", &["one"]).unwrap(), "one and %s");
+        assert_eq!(printf("%x %s", &["a"]).unwrap(), "%x a");
+    }
+
     #[test]
     fn test_printf_no_args() {
         assert_eq!(printf("no placeholders", &[]).unwrap(), "no placeholders");
@@ -5,26 +5,65 @@
 
 use crate::pathlib::{is_absolute, normalize_path};
 use crate::types::{AgentError, AgentResult, SandboxConfig};
-use std::path::{Path, PathBuf};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Prefix for messages from write operations run in dry-run mode
+pub const DRY_RUN_PREFIX: &str = "[dry-run]";
 
 /// Sandbox enforcer that validates all file system operations
 #[derive(Debug, Clone)]
 pub struct Sandbox {
     config: SandboxConfig,
+    /// Canonicalized roots, primary first
+    roots: Vec<PathBuf>,
+    /// Compiled `deny_globs` from the config
+    deny_set: GlobSet,
     /// Whether to override all security policies (dangerous)
     override_enabled: bool,
+    /// Bytes written through the write tools, shared between clones
+    bytes_written: Arc<AtomicU64>,
 }
 
 impl Sandbox {
     /// Creates a new sandbox with the given configuration
+    ///
+    /// Invalid deny globs are skipped with a warning; use
+    /// [`SandboxConfigBuilder::build`](crate::types::SandboxConfigBuilder::build)
+    /// to reject them up front.
     pub fn new(mut config: SandboxConfig) -> Self {
-        // Canonicalize the root to ensure consistent comparisons
+        // Canonicalize the roots to ensure consistent comparisons
         if let Ok(canonical) = config.root.canonicalize() {
             config.root = canonical;
         }
+        for root in &mut config.extra_roots {
+            if let Ok(canonical) = root.canonicalize() {
+                *root = canonical;
+            }
+        }
+
+        let mut deny = GlobSetBuilder::new();
+        for pattern in &config.deny_globs {
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    deny.add(glob);
+                }
+                Err(e) => tracing::warn!("Ignoring invalid deny glob '{}': {}", pattern, e),
+            }
+        }
+        let deny_set = deny.build().unwrap_or_else(|_| GlobSet::empty());
+        let roots = config.roots().cloned().collect();
+
         Self {
             config,
+            roots,
+            deny_set,
             override_enabled: false,
+            bytes_written: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -52,6 +91,7 @@ impl Sandbox {
         }
 
         let normalized = self.normalize_and_canonicalize(path)?;
+        self.check_denied(&normalized)?;
 
         // Validate against security policy if present
         if let Some(policy) = &self.config.security_policy {
@@ -98,7 +138,15 @@ impl Sandbox {
             return Ok(path.to_path_buf());
         }
 
+        if self.config.read_only {
+            return Err(AgentError::PermissionDenied(format!(
+                "Sandbox is read-only: {}",
+                path.display()
+            )));
+        }
+
         let normalized = self.normalize_and_canonicalize(path)?;
+        self.check_denied(&normalized)?;
 
         // Validate against security policy if present
         if let Some(policy) = &self.config.security_policy {
@@ -127,6 +175,35 @@ impl Sandbox {
         Ok(normalized)
     }
 
+    /// Validates a path whose directory entry itself is modified, e.g. removed
+    /// or renamed
+    ///
+    /// Unlike [`validate_write`](Self::validate_write), a symlink is not
+    /// resolved: only its parent directory is validated and the returned path
+    /// names the link, so the caller acts on the link rather than its target.
+    pub fn validate_write_entry(&self, path: &Path) -> AgentResult<PathBuf> {
+        let Some(name) = path.file_name() else {
+            return self.validate_write(path);
+        };
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        let entry = self.validate_write(parent)?.join(name);
+        let is_link = entry
+            .symlink_metadata()
+            .is_ok_and(|metadata| metadata.file_type().is_symlink());
+        if !is_link {
+            return self.validate_write(path);
+        }
+
+        if !self.override_enabled {
+            self.check_denied(&entry)?;
+        }
+        Ok(entry)
+    }
+
     /// Validates multiple paths for read operations
     pub fn validate_reads(&self, paths: &[PathBuf]) -> AgentResult<Vec<PathBuf>> {
         // Override bypasses all checks
@@ -182,9 +259,104 @@ impl Sandbox {
         Ok(size)
     }
 
-    /// Checks if path is within sandbox
+    /// Validates the number of bytes a single write operation will produce
+    pub fn validate_write_size(&self, size: u64) -> AgentResult<()> {
+        if self.override_enabled {
+            return Ok(());
+        }
+
+        match self.config.max_write_bytes {
+            Some(max) if size > max => Err(AgentError::InvalidInput(format!(
+                "Write size {} exceeds maximum {}",
+                size, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that writing `bytes` more stays within `max_total_write_bytes`
+    ///
+    /// Write tools call this before writing and [`record_write`](Self::record_write)
+    /// afterwards with the bytes actually written.
+    pub fn check_write_quota(&self, bytes: u64) -> AgentResult<()> {
+        if self.override_enabled {
+            return Ok(());
+        }
+
+        let Some(max) = self.config.max_total_write_bytes else {
+            return Ok(());
+        };
+        let written = self.bytes_written();
+        let projected = written.saturating_add(bytes);
+        if projected > max {
+            return Err(AgentError::QuotaExceeded(format!(
+                "Writing {} bytes would bring the total to {} (already written {}, limit {})",
+                bytes, projected, written, max
+            )));
+        }
+        Ok(())
+    }
+
+    /// Adds `bytes` to the running write total
+    pub fn record_write(&self, bytes: u64) {
+        self.bytes_written.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Total bytes written through the write tools so far
+    ///
+    /// Clones of a sandbox share this total.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written.load(Ordering::Relaxed)
+    }
+
+    /// Checks if path is within any sandbox root
     fn is_within_sandbox(&self, path: &Path) -> bool {
-        path.starts_with(&self.config.root)
+        self.roots.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Rejects paths matching a deny glob
+    ///
+    /// Paths inside a root are matched relative to that root.
+    fn check_denied(&self, path: &Path) -> AgentResult<()> {
+        if self.deny_set.is_empty() {
+            return Ok(());
+        }
+
+        let relative = self
+            .config
+            .roots()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+
+        if self.deny_set.is_match(relative) {
+            return Err(AgentError::SandboxViolation(format!(
+                "Path matches a denied pattern: {}",
+                path.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rejects paths that traverse a symlink inside a sandbox root
+    fn check_symlinks(&self, path: &Path) -> AgentResult<()> {
+        for ancestor in path.ancestors() {
+            let inside_root = self
+                .config
+                .roots()
+                .any(|root| ancestor.starts_with(root) && ancestor != root.as_path());
+            if !inside_root {
+                continue;
+            }
+            if let Ok(metadata) = ancestor.symlink_metadata() {
+                if metadata.file_type().is_symlink() {
+                    return Err(AgentError::SandboxViolation(format!(
+                        "Symlinks are not allowed in sandbox: {}",
+                        ancestor.display()
+                    )));
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Normalizes and canonicalizes a path
@@ -204,6 +376,14 @@ impl Sandbox {
             normalized
         };
 
+        // Collapse `.` and `..` before touching the filesystem, so paths that
+        // do not exist yet cannot climb out of the root through `..`
+        let absolute = lexically_normalize(&absolute);
+
+        if !self.config.follow_symlinks {
+            self.check_symlinks(&absolute)?;
+        }
+
         // Canonicalize to resolve symlinks and .. components
         // Note: This will fail if the path doesn't exist, which is fine for write validation
         match absolute.canonicalize() {
@@ -233,6 +413,87 @@ impl Sandbox {
         }
     }
 
+    /// Watches a path for create, modify and delete events
+    ///
+    /// Directories are watched recursively. Events for paths outside the
+    /// sandbox (e.g. reached through a symlink) or matching a deny glob are
+    /// dropped. Watching stops when the returned handle is dropped.
+    ///
+    /// # Errors
+    /// Returns error if the path fails read validation or cannot be watched.
+    pub fn watch<F>(&self, path: &Path, mut callback: F) -> AgentResult<WatchHandle>
+    where
+        F: FnMut(WatchEvent) + Send + 'static,
+    {
+        let validated_path = self.validate_read(path)?;
+        let sandbox = self.clone();
+
+        let mut watcher =
+            notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+                let event = match result {
+                    Ok(event) => event,
+                    Err(e) => {
+                        tracing::warn!("File watch error: {}", e);
+                        return;
+                    }
+                };
+                let kind = match event.kind {
+                    EventKind::Create(_) => WatchEventKind::Create,
+                    EventKind::Modify(_) => WatchEventKind::Modify,
+                    EventKind::Remove(_) => WatchEventKind::Delete,
+                    _ => return,
+                };
+                for path in event.paths {
+                    if sandbox.allows_watched_path(&path) {
+                        callback(WatchEvent { kind, path });
+                    }
+                }
+            })
+            .map_err(|e| AgentError::IoError(format!("Failed to create watcher: {}", e)))?;
+
+        watcher
+            .watch(&validated_path, RecursiveMode::Recursive)
+            .map_err(|e| {
+                AgentError::IoError(format!(
+                    "Failed to watch {}: {}",
+                    validated_path.display(),
+                    e
+                ))
+            })?;
+
+        Ok(WatchHandle { _watcher: watcher })
+    }
+
+    /// Checks whether a path reported by a watcher may be exposed
+    ///
+    /// Deleted paths cannot be canonicalized, so their parent is used instead.
+    fn allows_watched_path(&self, path: &Path) -> bool {
+        let resolved = path.canonicalize().ok().or_else(|| {
+            let parent = path.parent()?.canonicalize().ok()?;
+            Some(parent.join(path.file_name()?))
+        });
+        match resolved {
+            Some(resolved) => {
+                self.is_within_sandbox(&resolved) && self.check_denied(&resolved).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    /// Whether write operations should only report what they would do
+    pub fn is_dry_run(&self) -> bool {
+        self.config.dry_run
+    }
+
+    /// Prefixes a write operation's message with [`DRY_RUN_PREFIX`] in dry-run mode
+    pub(crate) fn dry_run_message(&self, message: String) -> String {
+        if self.config.dry_run {
+            format!("{} {}", DRY_RUN_PREFIX, message)
+        } else {
+            message
+        }
+    }
+
     /// Gets the sandbox root
     pub fn root(&self) -> &Path {
         &self.config.root
@@ -242,6 +503,106 @@ impl Sandbox {
     pub fn config(&self) -> &SandboxConfig {
         &self.config
     }
+
+    /// Gets all canonicalized roots, primary first
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Checks whether a path may be read, without performing any operation
+    ///
+    /// Agrees with [`validate_read`](Self::validate_read); writes may still be
+    /// refused, e.g. when the sandbox is read-only.
+    pub fn is_allowed(&self, path: &Path) -> bool {
+        self.validate_read(path).is_ok()
+    }
+
+    /// Summarizes the effective policy, for display and logging
+    pub fn describe_policy(&self) -> PolicySummary {
+        PolicySummary {
+            roots: self.roots.clone(),
+            read_only: self.config.read_only,
+            deny_globs: self.config.deny_globs.clone(),
+            allow_read_outside: self.config.effective_allow_read_outside(),
+            max_read_size: self.config.effective_max_file_size(),
+            max_write_bytes: self.config.max_write_bytes,
+            max_total_write_bytes: self.config.max_total_write_bytes,
+            dry_run: self.config.dry_run,
+        }
+    }
+}
+
+/// Effective sandbox boundaries, as reported by [`Sandbox::describe_policy`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PolicySummary {
+    /// Canonicalized roots, primary first
+    pub roots: Vec<PathBuf>,
+    /// Whether all write operations are rejected
+    pub read_only: bool,
+    /// Glob patterns that are never accessible
+    pub deny_globs: Vec<String>,
+    /// Whether paths outside the roots may be read
+    pub allow_read_outside: bool,
+    /// Maximum file size to read, in bytes
+    pub max_read_size: usize,
+    /// Maximum bytes a single write operation may produce
+    pub max_write_bytes: Option<u64>,
+    /// Maximum bytes all write operations may produce together
+    pub max_total_write_bytes: Option<u64>,
+    /// Whether write operations only report their effects
+    pub dry_run: bool,
+}
+
+/// Resolves `.` and `..` components without accessing the filesystem
+///
+/// `..` never climbs above the root or prefix of an absolute path.
+fn lexically_normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Kind of change reported by [`Sandbox::watch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchEventKind {
+    /// A file or directory was created
+    Create,
+    /// A file's contents or metadata changed
+    Modify,
+    /// A file or directory was removed
+    Delete,
+}
+
+/// Filesystem change reported by [`Sandbox::watch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchEvent {
+    /// What happened
+    pub kind: WatchEventKind,
+    /// Path the change applies to
+    pub path: PathBuf,
+}
+
+/// Active watch started by [`Sandbox::watch`]; dropping it stops watching
+pub struct WatchHandle {
+    _watcher: RecommendedWatcher,
+}
+
+impl std::fmt::Debug for WatchHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatchHandle").finish_non_exhaustive()
+    }
 }
 
 /// Checks if a filename is safe (doesn't contain path traversal)
@@ -363,4 +724,272 @@ mod tests {
         // Clean up
         std::fs::remove_dir_all(&subdir).ok();
     }
+
+    #[test]
+    fn test_sandbox_builder_options() {
+        let primary = tempfile::TempDir::new().unwrap();
+        let secondary = tempfile::TempDir::new().unwrap();
+        std::fs::write(secondary.path().join("data.txt"), b"data").unwrap();
+        std::fs::write(primary.path().join("secret.key"), b"key").unwrap();
+
+        let config = SandboxConfig::builder()
+            .root(primary.path())
+            .add_root(secondary.path())
+            .deny_glob("*.key")
+            .max_write_bytes(16)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+
+        // Additional roots are inside the sandbox
+        assert!(sandbox
+            .validate_read(&secondary.path().join("data.txt"))
+            .is_ok());
+        assert!(sandbox
+            .validate_write(&secondary.path().join("new.txt"))
+            .is_ok());
+
+        // Denied globs are rejected for both reads and writes
+        assert!(sandbox.validate_read(Path::new("secret.key")).is_err());
+        assert!(sandbox.validate_write(Path::new("other.key")).is_err());
+
+        // Write size limit
+        assert!(sandbox.validate_write_size(16).is_ok());
+        assert!(sandbox.validate_write_size(17).is_err());
+    }
+
+    #[test]
+    fn test_sandbox_policy_introspection() {
+        let primary = tempfile::TempDir::new().unwrap();
+        let secondary = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        std::fs::write(primary.path().join("notes.txt"), b"notes").unwrap();
+        std::fs::write(primary.path().join("secret.key"), b"key").unwrap();
+        std::fs::write(secondary.path().join("data.txt"), b"data").unwrap();
+        std::fs::write(outside.path().join("other.txt"), b"other").unwrap();
+
+        let config = SandboxConfig::builder()
+            .root(primary.path())
+            .add_root(secondary.path())
+            .deny_glob("*.key")
+            .max_write_bytes(64)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+
+        assert_eq!(
+            sandbox.roots(),
+            &[
+                primary.path().canonicalize().unwrap(),
+                secondary.path().canonicalize().unwrap()
+            ]
+        );
+
+        let paths = [
+            primary.path().join("notes.txt"),
+            primary.path().join("secret.key"),
+            secondary.path().join("data.txt"),
+            outside.path().join("other.txt"),
+            PathBuf::from("notes.txt"),
+            PathBuf::from("../escape.txt"),
+        ];
+        for path in &paths {
+            let allowed = sandbox.is_allowed(path);
+            assert_eq!(allowed, sandbox.validate_read(path).is_ok(), "{:?}", path);
+            assert_eq!(allowed, sandbox.validate_write(path).is_ok(), "{:?}", path);
+        }
+        assert!(sandbox.is_allowed(Path::new("notes.txt")));
+        assert!(!sandbox.is_allowed(&outside.path().join("other.txt")));
+        assert!(!sandbox.is_allowed(Path::new("secret.key")));
+
+        let summary = sandbox.describe_policy();
+        assert_eq!(summary.roots, sandbox.roots());
+        assert!(!summary.read_only);
+        assert_eq!(summary.deny_globs, vec!["*.key".to_string()]);
+        assert_eq!(summary.max_write_bytes, Some(64));
+        assert_eq!(summary.max_total_write_bytes, None);
+        assert!(!summary.allow_read_outside);
+    }
+
+    #[test]
+    fn test_sandbox_write_quota() {
+        use crate::tools::file::{cp, CpOptions};
+        use crate::tools::security::truncate_file;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("six.txt"), b"123456").unwrap();
+
+        let config = SandboxConfig::builder()
+            .root(dir.path())
+            .max_total_write_bytes(10)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+        let clone = sandbox.clone();
+
+        cp(
+            &sandbox,
+            &[Path::new("six.txt")],
+            Path::new("copy.txt"),
+            &CpOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(clone.bytes_written(), 6);
+
+        // 6 + 8 would exceed the limit, so nothing is written
+        let result = truncate_file(&sandbox, Path::new("grown.bin"), 8, false);
+        assert!(matches!(result, Err(AgentError::QuotaExceeded(_))));
+        assert!(!dir.path().join("grown.bin").exists());
+        assert_eq!(sandbox.bytes_written(), 6);
+
+        truncate_file(&sandbox, Path::new("grown.bin"), 4, false).unwrap();
+        assert_eq!(sandbox.bytes_written(), 10);
+    }
+
+    #[test]
+    fn test_sandbox_read_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("file.txt"), b"test").unwrap();
+
+        let config = SandboxConfig::builder()
+            .root(dir.path())
+            .read_only(true)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+
+        assert!(sandbox.validate_read(Path::new("file.txt")).is_ok());
+        assert!(matches!(
+            sandbox.validate_write(Path::new("file.txt")),
+            Err(AgentError::PermissionDenied(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sandbox_follow_symlinks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("real")).unwrap();
+        std::fs::write(dir.path().join("real").join("file.txt"), b"test").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("real"), dir.path().join("link")).unwrap();
+
+        let following = Sandbox::new(SandboxConfig::new(dir.path().to_path_buf()));
+        assert!(following.validate_read(Path::new("link/file.txt")).is_ok());
+
+        let config = SandboxConfig::builder()
+            .root(dir.path())
+            .follow_symlinks(false)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+        assert!(sandbox.validate_read(Path::new("real/file.txt")).is_ok());
+        assert!(sandbox.validate_read(Path::new("link/file.txt")).is_err());
+    }
+
+    #[test]
+    fn test_sandbox_watch_reports_create() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(dir.path().to_path_buf()));
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let handle = sandbox
+            .watch(dir.path(), move |event| {
+                let _ = tx.send(event);
+            })
+            .unwrap();
+        let file = sandbox.root().join("created.txt");
+        std::fs::write(&file, b"hello").unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut created = false;
+        while let Some(remaining) = deadline.checked_duration_since(std::time::Instant::now()) {
+            match rx.recv_timeout(remaining) {
+                Ok(event) if event.kind == WatchEventKind::Create && event.path == file => {
+                    created = true;
+                    break;
+                }
+                Ok(_) => continue,
+                Err(_) => break,
+            }
+        }
+        assert!(created, "expected a create event for {}", file.display());
+        drop(handle);
+    }
+
+    #[test]
+    fn test_sandbox_watch_rejects_outside_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(dir.path().to_path_buf()));
+
+        let result = sandbox.watch(outside.path(), |_| {});
+        assert!(matches!(result, Err(AgentError::SandboxViolation(_))));
+    }
+
+    #[test]
+    fn test_lexically_normalize() {
+        assert_eq!(
+            lexically_normalize(Path::new("/root/sub/../../etc/passwd")),
+            PathBuf::from("/etc/passwd")
+        );
+        assert_eq!(
+            lexically_normalize(Path::new("/root/./a/../b")),
+            PathBuf::from("/root/b")
+        );
+        assert_eq!(lexically_normalize(Path::new("/../a")), PathBuf::from("/a"));
+        assert_eq!(
+            lexically_normalize(Path::new("../a/..")),
+            PathBuf::from("..")
+        );
+    }
+
+    #[test]
+    fn test_sandbox_rejects_parent_dir_escapes() {
+        let parent = tempfile::TempDir::new().unwrap();
+        let root = parent.path().join("root");
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(parent.path().join("secret.txt"), b"secret").unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(root.clone()));
+
+        for path in [
+            root.join("sub/../../secret.txt"),
+            root.join("missing/../../secret.txt"),
+            root.join("missing/deeper/../../../new.txt"),
+            PathBuf::from("sub/../../secret.txt"),
+        ] {
+            assert!(
+                matches!(
+                    sandbox.validate_read(&path),
+                    Err(AgentError::SandboxViolation(_))
+                ),
+                "read of {} should be rejected",
+                path.display()
+            );
+            assert!(
+                matches!(
+                    sandbox.validate_write(&path),
+                    Err(AgentError::SandboxViolation(_))
+                ),
+                "write of {} should be rejected",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_sandbox_allows_parent_dir_inside_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("file.txt"), b"data").unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(dir.path().to_path_buf()));
+
+        let resolved = sandbox.validate_read(Path::new("sub/../file.txt")).unwrap();
+        assert_eq!(resolved, sandbox.root().join("file.txt"));
+
+        // The intermediate directory does not need to exist
+        let resolved = sandbox
+            .validate_write(Path::new("missing/../new.txt"))
+            .unwrap();
+        assert_eq!(resolved, sandbox.root().join("new.txt"));
+    }
 }
@@ -4,6 +4,10 @@
 //! - shred: Overwrite file contents to impede recovery, then optionally remove
 //! - truncate_file: Set a file to a specific size
 //! - mktemp: Create a temporary file or directory inside the sandbox
+//!
+//! In dry-run mode ([`SandboxConfig::dry_run`](crate::types::SandboxConfig::dry_run))
+//! each operation validates its inputs and returns its usual message prefixed
+//! with `[dry-run]` without touching the filesystem.
 
 use crate::tools::sandbox::Sandbox;
 use crate::types::{AgentError, AgentResult};
@@ -13,7 +17,9 @@ use std::path::Path;
 /// recovery difficult, then optionally remove it.
 ///
 /// Each pass writes a different byte pattern (`0x00`, `0xFF`, `0xAA`, cycling).
-/// The file must reside within the sandbox.
+/// An empty file has nothing to overwrite, so the passes are skipped and the
+/// message says so; it is still removed if requested. The file must reside
+/// within the sandbox.
 ///
 /// # Examples
 ///
@@ -47,7 +53,24 @@ pub fn shred(sandbox: &Sandbox, path: &Path, passes: usize, remove: bool) -> Age
         .map_err(|e| AgentError::IoError(e.to_string()))?
         .len() as usize;
 
-    // Overwrite with alternating byte patterns
+    let message = match (file_size, remove) {
+        (0, true) => format!(
+            "shred: {}: empty file, nothing to overwrite; removed",
+            path.display()
+        ),
+        (0, false) => format!(
+            "shred: {}: empty file, nothing to overwrite",
+            path.display()
+        ),
+        (_, true) => format!("shred: {}: removed after {} passes", path.display(), passes),
+        (_, false) => format!("shred: {}: overwritten {} passes", path.display(), passes),
+    };
+    if sandbox.is_dry_run() {
+        return Ok(sandbox.dry_run_message(message));
+    }
+
+    // Overwrite with alternating byte patterns; an empty file needs no passes
+    let passes = if file_size == 0 { 0 } else { passes };
     for pass in 0..passes {
         let pattern: u8 = match pass % 3 {
             0 => 0x00,
@@ -60,24 +83,15 @@ pub fn shred(sandbox: &Sandbox, path: &Path, passes: usize, remove: bool) -> Age
 
     if remove {
         std::fs::remove_file(&resolved).map_err(|e| AgentError::IoError(e.to_string()))?;
-        Ok(format!(
-            "shred: {}: removed after {} passes",
-            path.display(),
-            passes
-        ))
-    } else {
-        Ok(format!(
-            "shred: {}: overwritten {} passes",
-            path.display(),
-            passes
-        ))
     }
+    Ok(message)
 }
 
 /// Truncate - set a file to exactly `size` bytes, creating it if necessary.
 ///
 /// Extending a file fills the new region with null bytes (platform-dependent
-/// behaviour; on most systems this creates a sparse file).
+/// behaviour; on most systems this creates a sparse file). With `no_create`
+/// (`truncate --no-create`) a missing file is skipped instead of created.
 ///
 /// # Examples
 ///
@@ -87,10 +101,34 @@ pub fn shred(sandbox: &Sandbox, path: &Path, passes: usize, remove: bool) -> Age
 /// use mistralrs_agent_tools::types::SandboxConfig;
 /// use std::path::Path;
 ///
-/// // truncate_file(&sandbox, Path::new("file.txt"), 1024).unwrap();
+/// // truncate_file(&sandbox, Path::new("file.txt"), 1024, false).unwrap();
 /// ```
-pub fn truncate_file(sandbox: &Sandbox, path: &Path, size: u64) -> AgentResult<String> {
+pub fn truncate_file(
+    sandbox: &Sandbox,
+    path: &Path,
+    size: u64,
+    no_create: bool,
+) -> AgentResult<String> {
     let resolved = sandbox.validate_write(path)?;
+    if no_create && !resolved.exists() {
+        let message = format!("truncate: {} skipped (does not exist)", path.display());
+        return Ok(if sandbox.is_dry_run() {
+            sandbox.dry_run_message(message)
+        } else {
+            message
+        });
+    }
+    sandbox.validate_write_size(size)?;
+
+    // Only growth counts towards the write quota
+    let current = std::fs::metadata(&resolved).map_or(0, |m| m.len());
+    let growth = size.saturating_sub(current);
+    sandbox.check_write_quota(growth)?;
+
+    let message = format!("truncate: {} set to {} bytes", path.display(), size);
+    if sandbox.is_dry_run() {
+        return Ok(sandbox.dry_run_message(message));
+    }
 
     let file = std::fs::OpenOptions::new()
         .write(true)
@@ -101,20 +139,17 @@ pub fn truncate_file(sandbox: &Sandbox, path: &Path, size: u64) -> AgentResult<S
 
     file.set_len(size)
         .map_err(|e| AgentError::IoError(e.to_string()))?;
+    sandbox.record_write(growth);
 
-    Ok(format!(
-        "truncate: {} set to {} bytes",
-        path.display(),
-        size
-    ))
+    Ok(message)
 }
 
 /// Mktemp - create a uniquely-named temporary file or directory inside the
-/// sandbox root and return its absolute path.
+/// sandbox and return its absolute path.
 ///
-/// The name is formed from `prefix` (default `"tmp"`) followed by a dot and a
-/// nanosecond-resolution timestamp.  The sandbox's `validate_write` check is
-/// applied before creation.
+/// Temporaries are created under the sandbox's configured
+/// [`SandboxConfig::temp_dir`], or directly under the sandbox root if none is
+/// set. See [`mktemp_in`] for the naming scheme.
 ///
 /// # Examples
 ///
@@ -127,7 +162,34 @@ pub fn truncate_file(sandbox: &Sandbox, path: &Path, size: u64) -> AgentResult<S
 /// // assert!(std::path::Path::new(&path).is_file());
 /// ```
 pub fn mktemp(sandbox: &Sandbox, directory: bool, prefix: Option<&str>) -> AgentResult<String> {
+    mktemp_in(sandbox, directory, prefix, None)
+}
+
+/// Mktemp under an explicit base directory.
+///
+/// The name is formed from `prefix` (default `"tmp"`) followed by a dot and a
+/// nanosecond-resolution timestamp. `base` overrides the sandbox's configured
+/// temp directory; relative bases resolve against the sandbox root. The base
+/// is created if needed, and both it and the final path must stay within the
+/// sandbox root.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mistralrs_agent_tools::tools::sandbox::Sandbox;
+/// use mistralrs_agent_tools::tools::security::mktemp_in;
+/// use mistralrs_agent_tools::types::SandboxConfig;
+///
+/// // let path = mktemp_in(&sandbox, false, None, Some(Path::new("tmp"))).unwrap();
+/// ```
+pub fn mktemp_in(
+    sandbox: &Sandbox,
+    directory: bool,
+    prefix: Option<&str>,
+    base: Option<&Path>,
+) -> AgentResult<String> {
     let pfx = prefix.unwrap_or("tmp");
+    let base_dir = temp_base(sandbox, base.or(sandbox.config().temp_dir.as_deref()))?;
 
     // Generate a name that is highly unlikely to collide
     let timestamp = std::time::SystemTime::now()
@@ -136,24 +198,72 @@ pub fn mktemp(sandbox: &Sandbox, directory: bool, prefix: Option<&str>) -> Agent
         .as_nanos();
 
     let name = format!("{}.{}", pfx, timestamp);
-    let target = sandbox.root().join(&name);
+
+    if sandbox.is_dry_run() && !base_dir.exists() {
+        let target = base_dir.join(&name);
+        return Ok(sandbox.dry_run_message(target.to_string_lossy().to_string()));
+    }
 
     // Confirm the target is within the sandbox before creating anything
-    sandbox.validate_write(&target)?;
+    let target = sandbox.validate_write(&base_dir.join(&name))?;
+    ensure_within_root(sandbox, &target)?;
+
+    if sandbox.is_dry_run() {
+        return Ok(sandbox.dry_run_message(target.to_string_lossy().to_string()));
+    }
 
     if directory {
         std::fs::create_dir_all(&target).map_err(|e| AgentError::IoError(e.to_string()))?;
     } else {
-        // Ensure the parent directory exists (it should be the sandbox root)
-        if let Some(parent) = target.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| AgentError::IoError(e.to_string()))?;
-        }
         std::fs::File::create(&target).map_err(|e| AgentError::IoError(e.to_string()))?;
     }
 
     Ok(target.to_string_lossy().to_string())
 }
 
+/// Resolve and create the directory temporaries are placed in.
+fn temp_base(sandbox: &Sandbox, base: Option<&Path>) -> AgentResult<std::path::PathBuf> {
+    let Some(base) = base else {
+        return Ok(sandbox.root().to_path_buf());
+    };
+
+    if base
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(AgentError::SandboxViolation(format!(
+            "Temp directory must not contain '..': {}",
+            base.display()
+        )));
+    }
+
+    let joined = sandbox.root().join(base);
+    ensure_within_root(sandbox, &joined)?;
+    if sandbox.is_dry_run() && !joined.exists() {
+        // The base would be created; validate it without creating it
+        sandbox.validate_write(&joined)?;
+        return Ok(joined);
+    }
+    std::fs::create_dir_all(&joined).map_err(|e| AgentError::IoError(e.to_string()))?;
+
+    // Re-check after creation so symlinked bases cannot escape the sandbox
+    let resolved = sandbox.validate_write(&joined)?;
+    ensure_within_root(sandbox, &resolved)?;
+    Ok(resolved)
+}
+
+/// Temporaries never leave the sandbox root, even if policy allows writes outside.
+fn ensure_within_root(sandbox: &Sandbox, path: &Path) -> AgentResult<()> {
+    if path.starts_with(sandbox.root()) {
+        Ok(())
+    } else {
+        Err(AgentError::SandboxViolation(format!(
+            "Temporary path outside sandbox: {}",
+            path.display()
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,6 +301,27 @@ mod tests {
         assert!(!file.exists());
     }
 
+    #[test]
+    fn test_shred_empty_file() {
+        let (dir, sandbox) = test_sandbox();
+        let file = dir.path().join("empty.txt");
+        std::fs::write(&file, "").unwrap();
+
+        let result = shred(&sandbox, &file, 3, false).unwrap();
+        assert_eq!(
+            result,
+            format!(
+                "shred: {}: empty file, nothing to overwrite",
+                file.display()
+            )
+        );
+        assert!(file.exists());
+
+        let result = shred(&sandbox, &file, 3, true).unwrap();
+        assert!(result.contains("empty file, nothing to overwrite; removed"));
+        assert!(!file.exists());
+    }
+
     #[test]
     fn test_shred_nonexistent_file() {
         let (_dir, sandbox) = test_sandbox();
@@ -218,7 +349,7 @@ mod tests {
         let file = dir.path().join("truncate_me.txt");
         std::fs::write(&file, "hello world").unwrap();
 
-        let result = truncate_file(&sandbox, &file, 5).unwrap();
+        let result = truncate_file(&sandbox, &file, 5, false).unwrap();
         assert!(result.contains("5 bytes"));
 
         let content = std::fs::read(&file).unwrap();
@@ -232,7 +363,7 @@ mod tests {
         let file = dir.path().join("extend_me.txt");
         std::fs::write(&file, "hi").unwrap();
 
-        truncate_file(&sandbox, &file, 10).unwrap();
+        truncate_file(&sandbox, &file, 10, false).unwrap();
 
         let content = std::fs::read(&file).unwrap();
         assert_eq!(content.len(), 10);
@@ -243,13 +374,30 @@ mod tests {
         let (dir, sandbox) = test_sandbox();
         let file = dir.path().join("new_file.bin");
 
-        truncate_file(&sandbox, &file, 64).unwrap();
+        truncate_file(&sandbox, &file, 64, false).unwrap();
 
         assert!(file.exists());
         let content = std::fs::read(&file).unwrap();
         assert_eq!(content.len(), 64);
     }
 
+    #[test]
+    fn test_truncate_no_create() {
+        let (dir, sandbox) = test_sandbox();
+        let missing = dir.path().join("missing.bin");
+
+        let result = truncate_file(&sandbox, &missing, 64, true).unwrap();
+        assert!(result.contains("skipped (does not exist)"));
+        assert!(!missing.exists());
+
+        // Existing files are truncated as usual
+        let file = dir.path().join("existing.txt");
+        std::fs::write(&file, "hello world").unwrap();
+        let result = truncate_file(&sandbox, &file, 5, true).unwrap();
+        assert!(result.contains("5 bytes"));
+        assert_eq!(std::fs::read(&file).unwrap(), b"hello");
+    }
+
     #[test]
     fn test_mktemp_file() {
         let (_dir, sandbox) = test_sandbox();
@@ -297,4 +445,62 @@ mod tests {
             "mktemp result must be within sandbox"
         );
     }
+
+    #[test]
+    fn test_mktemp_in_nested_base() {
+        let (_dir, sandbox) = test_sandbox();
+        let base = Path::new("session").join("tmp");
+
+        let result = mktemp_in(&sandbox, false, Some("nested"), Some(&base)).unwrap();
+        let path = Path::new(&result);
+        assert!(path.is_file());
+        assert!(path.starts_with(sandbox.root().join(&base)));
+    }
+
+    #[test]
+    fn test_mktemp_config_temp_dir() {
+        let dir = TempDir::new().unwrap();
+        let sandbox =
+            Sandbox::new(SandboxConfig::new(dir.path().to_path_buf()).temp_dir("tmp/agent"));
+
+        let result = mktemp(&sandbox, true, None).unwrap();
+        let path = Path::new(&result);
+        assert!(path.is_dir());
+        assert!(path.starts_with(sandbox.root().join("tmp").join("agent")));
+    }
+
+    #[test]
+    fn test_mktemp_in_rejects_escaping_base() {
+        let (_dir, sandbox) = test_sandbox();
+
+        assert!(mktemp_in(&sandbox, false, None, Some(Path::new("../escape"))).is_err());
+        #[cfg(not(windows))]
+        assert!(mktemp_in(&sandbox, false, None, Some(Path::new("/tmp"))).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_leaves_filesystem_untouched() {
+        let dir = TempDir::new().unwrap();
+        let config = SandboxConfig::builder()
+            .root(dir.path())
+            .dry_run(true)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+        let file = dir.path().join("secret.txt");
+        std::fs::write(&file, "secret data").unwrap();
+
+        let result = shred(&sandbox, &file, 3, true).unwrap();
+        assert!(result.starts_with("[dry-run] shred:"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "secret data");
+
+        let result = truncate_file(&sandbox, &file, 0, false).unwrap();
+        assert!(result.starts_with("[dry-run] truncate:"));
+        assert_eq!(std::fs::metadata(&file).unwrap().len(), 11);
+
+        let result = mktemp_in(&sandbox, true, None, Some(Path::new("scratch"))).unwrap();
+        let path = result.strip_prefix("[dry-run] ").unwrap();
+        assert!(!Path::new(path).exists());
+        assert!(!dir.path().join("scratch").exists());
+    }
 }
@@ -0,0 +1,196 @@
+//! cut - select fields or characters from each line
+//!
+//! Operates on in-memory text, so it needs no sandbox access.
+
+use crate::types::{AgentError, AgentResult, CutOptions};
+
+/// Select fields or characters from each line
+///
+/// Exactly one of `fields` or `characters` must be set. Selections are
+/// 1-based lists of indices and ranges such as `1,3-5`, `-2` or `4-`, and
+/// selected parts are output in input order. Lines without the delimiter are
+/// passed through unchanged when selecting fields, unless `only_delimited` is
+/// set. The output ends with a newline exactly when non-empty input does.
+///
+/// # Arguments
+/// * `content` - Text to process
+/// * `options` - Cut options
+///
+/// # Returns
+/// * The selected parts of each line
+///
+/// # Example
+/// ```
+/// use mistralrs_agent_tools::tools::text::cut;
+/// use mistralrs_agent_tools::CutOptions;
+///
+/// let options = CutOptions {
+///     fields: Some("1,3".to_string()),
+///     delimiter: Some(','),
+///     ..Default::default()
+/// };
+/// assert_eq!(cut("a,b,c", &options).unwrap(), "a,c");
+/// ```
+pub fn cut(content: &str, options: &CutOptions) -> AgentResult<String> {
+    let mut lines = Vec::new();
+
+    match (&options.fields, &options.characters) {
+        (Some(fields), None) => {
+            let ranges = parse_ranges(fields)?;
+            let delimiter = options.delimiter.unwrap_or('\t');
+            let mut buf = [0u8; 4];
+            let separator = delimiter.encode_utf8(&mut buf);
+
+            for line in content.lines() {
+                if !line.contains(delimiter) {
+                    if !options.only_delimited {
+                        lines.push(line.to_string());
+                    }
+                    continue;
+                }
+                let selected: Vec<&str> = line
+                    .split(delimiter)
+                    .enumerate()
+                    .filter(|(i, _)| is_selected(&ranges, i + 1))
+                    .map(|(_, field)| field)
+                    .collect();
+                lines.push(selected.join(separator));
+            }
+        }
+        (None, Some(characters)) => {
+            let ranges = parse_ranges(characters)?;
+            for line in content.lines() {
+                let selected: String = line
+                    .chars()
+                    .enumerate()
+                    .filter(|(i, _)| is_selected(&ranges, i + 1))
+                    .map(|(_, c)| c)
+                    .collect();
+                lines.push(selected);
+            }
+        }
+        _ => {
+            return Err(AgentError::InvalidInput(
+                "Specify exactly one of fields or characters".to_string(),
+            ))
+        }
+    }
+
+    let mut output = lines.join("\n");
+    if !lines.is_empty() && content.ends_with('\n') {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Parse a selection list such as `1,3-5,7-` into inclusive 1-based ranges
+fn parse_ranges(list: &str) -> AgentResult<Vec<(usize, usize)>> {
+    let invalid = || AgentError::InvalidInput(format!("Invalid field or character list: {}", list));
+    let parse_index = |s: &str| match s.parse::<usize>() {
+        Ok(0) => Err(AgentError::InvalidInput(
+            "Fields and positions are numbered from 1".to_string(),
+        )),
+        Ok(n) => Ok(n),
+        Err(_) => Err(invalid()),
+    };
+
+    let mut ranges = Vec::new();
+    for part in list.split(',') {
+        let range = match part.split_once('-') {
+            Some(("", "")) => return Err(invalid()),
+            Some((start, end)) => {
+                let start = if start.is_empty() {
+                    1
+                } else {
+                    parse_index(start)?
+                };
+                let end = if end.is_empty() {
+                    usize::MAX
+                } else {
+                    parse_index(end)?
+                };
+                if start > end {
+                    return Err(AgentError::InvalidInput(format!(
+                        "Invalid decreasing range: {}",
+                        part
+                    )));
+                }
+                (start, end)
+            }
+            None => {
+                let index = parse_index(part)?;
+                (index, index)
+            }
+        };
+        ranges.push(range);
+    }
+
+    Ok(ranges)
+}
+
+/// Whether the 1-based `index` falls in any of the ranges
+fn is_selected(ranges: &[(usize, usize)], index: usize) -> bool {
+    ranges
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cut_fields_with_comma() {
+        let options = CutOptions {
+            fields: Some("1,3-4".to_string()),
+            delimiter: Some(','),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            cut("a,b,c,d,e\n1,2,3,4,5\n", &options).unwrap(),
+            "a,c,d\n1,3,4\n"
+        );
+
+        let options = CutOptions {
+            fields: Some("2-".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(cut("x\ty\tz", &options).unwrap(), "y\tz");
+    }
+
+    #[test]
+    fn test_cut_character_range() {
+        let options = CutOptions {
+            characters: Some("-2,5-".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(cut("abcdefg\nxy\n", &options).unwrap(), "abefg\nxy\n");
+    }
+
+    #[test]
+    fn test_cut_only_delimited() {
+        let content = "a:b\nno delimiter\nc:d\n";
+        let mut options = CutOptions {
+            fields: Some("2".to_string()),
+            delimiter: Some(':'),
+            ..Default::default()
+        };
+        assert_eq!(cut(content, &options).unwrap(), "b\nno delimiter\nd\n");
+
+        options.only_delimited = true;
+        assert_eq!(cut(content, &options).unwrap(), "b\nd\n");
+    }
+
+    #[test]
+    fn test_cut_invalid_lists() {
+        let mut options = CutOptions::default();
+        assert!(cut("a", &options).is_err());
+
+        for list in ["0", "3-1", "a", "-", "1,,2"] {
+            options.fields = Some(list.to_string());
+            assert!(cut("a", &options).is_err(), "{list}");
+        }
+    }
+}
@@ -0,0 +1,438 @@
+//! diff - compare two files line by line
+//!
+//! Produces a unified diff for text files and a `cmp`-style byte-offset
+//! report for binary files.
+
+use crate::tools::sandbox::Sandbox;
+use crate::types::AgentResult;
+use std::path::Path;
+
+/// Result of comparing two files
+#[derive(Debug, Clone)]
+pub struct DiffResult {
+    /// Unified diff for text files, or the first-difference report for
+    /// binary files; empty when the files are identical
+    pub diff: String,
+    /// Whether the files have identical contents
+    pub identical: bool,
+}
+
+/// One step of the edit script turning the old lines into the new lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiffOp {
+    /// Line present in both files (old index, new index)
+    Equal(usize, usize),
+    /// Line only in the old file
+    Delete(usize),
+    /// Line only in the new file
+    Insert(usize),
+}
+
+/// Compare two files and produce a unified diff
+///
+/// Text files are compared line by line and reported as a unified diff with
+/// `context` lines of context around each change. Files containing NUL bytes
+/// or invalid UTF-8 are treated as binary and reported like `cmp`, with the
+/// byte and line of the first difference.
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `old_path` - Original file (`---` side)
+/// * `new_path` - Changed file (`+++` side)
+/// * `context` - Number of unchanged lines shown around each change
+///
+/// # Returns
+/// * The diff text and whether the files are identical
+pub fn diff_files(
+    sandbox: &Sandbox,
+    old_path: &Path,
+    new_path: &Path,
+    context: usize,
+) -> AgentResult<DiffResult> {
+    let old_bytes = read_validated(sandbox, old_path)?;
+    let new_bytes = read_validated(sandbox, new_path)?;
+
+    if old_bytes == new_bytes {
+        return Ok(DiffResult {
+            diff: String::new(),
+            identical: true,
+        });
+    }
+
+    let (old_text, new_text) = match (text_content(&old_bytes), text_content(&new_bytes)) {
+        (Some(old_text), Some(new_text)) => (old_text, new_text),
+        _ => {
+            return Ok(DiffResult {
+                diff: byte_report(old_path, new_path, &old_bytes, &new_bytes),
+                identical: false,
+            })
+        }
+    };
+
+    let old_lines: Vec<&str> = old_text.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new_text.split_inclusive('\n').collect();
+    let ops = diff_ops(&old_lines, &new_lines);
+
+    let mut diff = format!("--- {}\n+++ {}\n", old_path.display(), new_path.display());
+    for hunk in hunk_ranges(&ops, context) {
+        write_hunk(&mut diff, &ops, hunk, &old_lines, &new_lines);
+    }
+
+    Ok(DiffResult {
+        diff,
+        identical: false,
+    })
+}
+
+/// Validate `path` for reading and load its contents
+fn read_validated(sandbox: &Sandbox, path: &Path) -> AgentResult<Vec<u8>> {
+    let validated_path = sandbox.validate_read(path)?;
+    sandbox.validate_file_size(&validated_path)?;
+    Ok(std::fs::read(validated_path)?)
+}
+
+/// Contents as text, or `None` if they look binary
+fn text_content(bytes: &[u8]) -> Option<&str> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()
+}
+
+/// `cmp`-style report of the first byte where two different files diverge
+fn byte_report(old_path: &Path, new_path: &Path, old: &[u8], new: &[u8]) -> String {
+    match old.iter().zip(new).position(|(a, b)| a != b) {
+        Some(offset) => {
+            let line = old[..offset].iter().filter(|&&b| b == b'\n').count() + 1;
+            format!(
+                "{} {} differ: byte {}, line {}\n",
+                old_path.display(),
+                new_path.display(),
+                offset + 1,
+                line
+            )
+        }
+        None => {
+            let (shorter, len) = if old.len() < new.len() {
+                (old_path, old.len())
+            } else {
+                (new_path, new.len())
+            };
+            format!("cmp: EOF on {} after byte {}\n", shorter.display(), len)
+        }
+    }
+}
+
+/// Edit distance beyond which the search gives up and the changed middle of
+/// the files is reported as a whole replacement; bounds memory to O(D²)
+const MAX_EDIT_DISTANCE: usize = 1000;
+
+/// Shortest edit script between two line sequences
+///
+/// The common prefix and suffix are matched directly and the middle is
+/// searched with Myers' algorithm. If the middle needs more than
+/// [`MAX_EDIT_DISTANCE`] edits it is deleted and inserted wholesale.
+fn diff_ops(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(|i| DiffOp::Equal(i, i)).collect();
+    match myers(old_mid, new_mid, MAX_EDIT_DISTANCE) {
+        Some(middle) => ops.extend(middle.into_iter().map(|op| match op {
+            DiffOp::Equal(i, j) => DiffOp::Equal(prefix + i, prefix + j),
+            DiffOp::Delete(i) => DiffOp::Delete(prefix + i),
+            DiffOp::Insert(j) => DiffOp::Insert(prefix + j),
+        })),
+        None => {
+            ops.extend((0..old_mid.len()).map(|i| DiffOp::Delete(prefix + i)));
+            ops.extend((0..new_mid.len()).map(|j| DiffOp::Insert(prefix + j)));
+        }
+    }
+    let (old_tail, new_tail) = (old.len() - suffix, new.len() - suffix);
+    ops.extend((0..suffix).map(|i| DiffOp::Equal(old_tail + i, new_tail + i)));
+    ops
+}
+
+/// Furthest-reaching x on diagonals `first..first + x.len()` at the start
+/// of one round of the search
+struct Round {
+    first: isize,
+    x: Vec<isize>,
+}
+
+impl Round {
+    fn get(&self, k: isize) -> isize {
+        usize::try_from(k - self.first)
+            .ok()
+            .and_then(|i| self.x.get(i))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Myers' shortest edit script, or `None` if it needs more than `max_d` edits
+///
+/// Each round saves only the diagonals the backtrack can read, so memory is
+/// O(D²) rather than O(D·(N+M)).
+fn myers(old: &[&str], new: &[&str], max_d: usize) -> Option<Vec<DiffOp>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (n + m).min(max_d as isize);
+    let offset = max as usize + 1;
+    let index = |k: isize| (k + offset as isize) as usize;
+
+    // Furthest-reaching x on each diagonal k = x - y
+    let mut v = vec![0isize; 2 * offset + 1];
+    let mut trace = Vec::new();
+    let mut found = false;
+
+    'search: for d in 0..=max {
+        // Round d reads diagonals -(d - 1)..=(d - 1) of the previous round
+        let first = -(d - 1).max(0);
+        trace.push(Round {
+            first,
+            x: v[index(first)..=index(-first)].to_vec(),
+        });
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'search;
+            }
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, round) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && round.get(k - 1) < round.get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = round.get(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push(DiffOp::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                ops.push(DiffOp::Insert(prev_y as usize));
+            } else {
+                ops.push(DiffOp::Delete(prev_x as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    ops.reverse();
+    Some(ops)
+}
+
+/// Group changes into hunks, merging changes whose context would overlap
+fn hunk_ranges(ops: &[DiffOp], context: usize) -> Vec<std::ops::Range<usize>> {
+    let mut hunks: Vec<std::ops::Range<usize>> = Vec::new();
+
+    for (i, op) in ops.iter().enumerate() {
+        if matches!(op, DiffOp::Equal(..)) {
+            continue;
+        }
+        let start = i.saturating_sub(context);
+        let end = (i + context + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.end => last.end = end,
+            _ => hunks.push(start..end),
+        }
+    }
+
+    hunks
+}
+
+/// Append the hunk covering `ops[range]`, with its `@@` header, to `diff`
+fn write_hunk(
+    diff: &mut String,
+    ops: &[DiffOp],
+    range: std::ops::Range<usize>,
+    old_lines: &[&str],
+    new_lines: &[&str],
+) {
+    let old_count = |ops: &[DiffOp]| {
+        ops.iter()
+            .filter(|op| !matches!(op, DiffOp::Insert(_)))
+            .count()
+    };
+    let new_count = |ops: &[DiffOp]| {
+        ops.iter()
+            .filter(|op| !matches!(op, DiffOp::Delete(_)))
+            .count()
+    };
+
+    let (before, hunk) = (&ops[..range.start], &ops[range]);
+    diff.push_str(&format!(
+        "@@ -{} +{} @@\n",
+        hunk_span(old_count(before), old_count(hunk)),
+        hunk_span(new_count(before), new_count(hunk))
+    ));
+
+    for op in hunk {
+        let (prefix, line) = match *op {
+            DiffOp::Equal(i, _) => (' ', old_lines[i]),
+            DiffOp::Delete(i) => ('-', old_lines[i]),
+            DiffOp::Insert(j) => ('+', new_lines[j]),
+        };
+        diff.push(prefix);
+        diff.push_str(line);
+        if !line.ends_with('\n') {
+            diff.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+}
+
+/// Format a hunk range as `start,len` from the number of lines before it
+///
+/// The length is omitted when it is 1; an empty range is numbered after the
+/// line it follows.
+fn hunk_span(lines_before: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", lines_before),
+        1 => (lines_before + 1).to_string(),
+        _ => format!("{},{}", lines_before + 1, len),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup(old: &[u8], new: &[u8]) -> (TempDir, Sandbox) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("old.txt"), old).unwrap();
+        fs::write(temp_dir.path().join("new.txt"), new).unwrap();
+        let sandbox = Sandbox::new(SandboxConfig::new(temp_dir.path().to_path_buf()));
+        (temp_dir, sandbox)
+    }
+
+    fn run(temp_dir: &TempDir, sandbox: &Sandbox, context: usize) -> DiffResult {
+        diff_files(
+            sandbox,
+            &temp_dir.path().join("old.txt"),
+            &temp_dir.path().join("new.txt"),
+            context,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_changed_line() {
+        let (temp_dir, sandbox) = setup(
+            b"port = 80\nhost = a\ndebug = false\nlog = info\nmode = x\n",
+            b"port = 80\nhost = a\ndebug = true\nlog = info\nmode = x\n",
+        );
+
+        let result = run(&temp_dir, &sandbox, 1);
+
+        assert!(!result.identical);
+        let hunk = result.diff.lines().skip(2).collect::<Vec<_>>().join("\n");
+        assert_eq!(
+            hunk,
+            "@@ -2,3 +2,3 @@\n host = a\n-debug = false\n+debug = true\n log = info"
+        );
+        assert!(result.diff.starts_with("--- "));
+        assert!(result.diff.lines().nth(1).unwrap().starts_with("+++ "));
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let (temp_dir, sandbox) = setup(b"same\nlines\n", b"same\nlines\n");
+
+        let result = run(&temp_dir, &sandbox, 3);
+
+        assert!(result.identical);
+        assert!(result.diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_hunks_and_edges() {
+        let old: String = (1..=12).map(|i| format!("{}\n", i)).collect();
+        let new = old.replace("2\n", "two\n").replace("11\n", "") + "13";
+        let (temp_dir, sandbox) = setup(old.as_bytes(), new.as_bytes());
+
+        let result = run(&temp_dir, &sandbox, 1);
+
+        let hunks: Vec<_> = result
+            .diff
+            .lines()
+            .filter(|l| l.starts_with("@@"))
+            .collect();
+        assert_eq!(hunks, vec!["@@ -1,3 +1,3 @@", "@@ -10,3 +10,3 @@"]);
+        assert!(result.diff.ends_with("+13\n\\ No newline at end of file\n"));
+
+        // Pure insertion into an empty file
+        let (temp_dir, sandbox) = setup(b"", b"new\n");
+        let result = run(&temp_dir, &sandbox, 3);
+        assert!(result.diff.ends_with("@@ -0,0 +1 @@\n+new\n"));
+    }
+
+    #[test]
+    fn test_diff_unrelated_files_fall_back_to_replacement() {
+        let old: String = (0..600).map(|i| format!("old {}\n", i)).collect();
+        let new: String = (0..600).map(|i| format!("new {}\n", i)).collect();
+        let (temp_dir, sandbox) = setup(
+            format!("head\n{}tail\n", old).as_bytes(),
+            format!("head\n{}tail\n", new).as_bytes(),
+        );
+
+        let result = run(&temp_dir, &sandbox, 1);
+
+        let lines: Vec<&str> = result.diff.lines().skip(2).collect();
+        assert_eq!(lines[0], "@@ -1,602 +1,602 @@");
+        assert_eq!(lines[1], " head");
+        assert_eq!(lines[2], "-old 0");
+        assert_eq!(lines[602], "+new 0");
+        assert_eq!(lines.last(), Some(&" tail"));
+        assert_eq!(lines.len(), 1 + 600 + 600 + 2);
+    }
+
+    #[test]
+    fn test_diff_binary_falls_back_to_byte_report() {
+        let (temp_dir, sandbox) = setup(b"ab\ncd\0", b"ab\nce\0");
+
+        let result = run(&temp_dir, &sandbox, 3);
+
+        assert!(!result.identical);
+        assert!(result.diff.ends_with(" differ: byte 5, line 2\n"));
+
+        let (temp_dir, sandbox) = setup(b"\0abc", b"\0ab");
+        let result = run(&temp_dir, &sandbox, 3);
+        assert!(result.diff.starts_with("cmp: EOF on "));
+        assert!(result.diff.ends_with("new.txt after byte 3\n"));
+    }
+}
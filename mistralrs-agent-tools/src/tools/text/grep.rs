@@ -1,11 +1,13 @@
 //! grep - search for patterns in files
 //!
-//! Windows-optimized implementation with sandbox support.
+//! Windows-optimized implementation with sandbox support. Files are read
+//! line by line; binary files produce a single "binary file matches" entry.
 
 use crate::tools::sandbox::Sandbox;
 use crate::types::{AgentError, AgentResult, GrepMatch, GrepOptions};
 use regex::Regex;
 use std::collections::VecDeque;
+use std::fmt::Write;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -73,6 +75,45 @@ pub fn grep(
     Ok(all_matches)
 }
 
+/// Render matches as grep prints them
+///
+/// Match lines are prefixed with `file:` when `with_filename` is set and with
+/// `line:` when `options.line_number` is set; context lines use `-` instead of
+/// `:`. Binary files are reported as `Binary file <path> matches`.
+pub fn format_grep_output(
+    matches: &[GrepMatch],
+    options: &GrepOptions,
+    with_filename: bool,
+) -> String {
+    let mut output = String::new();
+    let write_line = |output: &mut String, m: &GrepMatch, number: usize, sep: char, line: &str| {
+        if with_filename {
+            let _ = write!(output, "{}{}", m.path, sep);
+        }
+        if options.line_number {
+            let _ = write!(output, "{}{}", number, sep);
+        }
+        let _ = writeln!(output, "{}", line);
+    };
+
+    for m in matches {
+        if m.binary {
+            let _ = writeln!(output, "Binary file {} matches", m.path);
+            continue;
+        }
+        let first = m.line_number - m.before.len();
+        for (i, line) in m.before.iter().enumerate() {
+            write_line(&mut output, m, first + i, '-', line);
+        }
+        write_line(&mut output, m, m.line_number, ':', &m.line);
+        for (i, line) in m.after.iter().enumerate() {
+            write_line(&mut output, m, m.line_number + 1 + i, '-', line);
+        }
+    }
+
+    output
+}
+
 /// Search a single file
 fn search_file(
     _sandbox: &Sandbox,
@@ -82,15 +123,20 @@ fn search_file(
     matches: &mut Vec<GrepMatch>,
 ) -> AgentResult<()> {
     let file = File::open(path)?;
-    let reader = BufReader::new(file);
+    let mut reader = BufReader::new(file);
     let path_str = path.display().to_string();
 
+    // Treat files with a NUL byte near the start as binary
+    if reader.fill_buf()?.contains(&0) {
+        return search_binary(matcher, reader, path_str, options, matches);
+    }
+
     let mut before_buffer: VecDeque<String> = VecDeque::new();
     let mut after_counter = 0;
     let mut pending_match: Option<GrepMatch> = None;
 
-    for (line_num, line_result) in reader.lines().enumerate() {
-        let line = line_result?;
+    for (line_num, line_result) in reader.split(b'\n').enumerate() {
+        let line = decode_line(line_result?);
         let line_num = line_num + 1; // Convert to 1-based
 
         let is_match = matcher.is_match(&line) != options.invert_match;
@@ -108,6 +154,7 @@ fn search_file(
                 line: line.clone(),
                 before: before_buffer.iter().cloned().collect(),
                 after: Vec::new(),
+                binary: false,
             };
 
             if options.after_context > 0 {
@@ -155,6 +202,37 @@ fn search_file(
     Ok(())
 }
 
+/// Search a binary file, recording at most one match for it
+fn search_binary(
+    matcher: &Regex,
+    reader: BufReader<File>,
+    path_str: String,
+    options: &GrepOptions,
+    matches: &mut Vec<GrepMatch>,
+) -> AgentResult<()> {
+    for (line_num, line_result) in reader.split(b'\n').enumerate() {
+        let line = decode_line(line_result?);
+        if matcher.is_match(&line) != options.invert_match {
+            matches.push(GrepMatch {
+                path: path_str,
+                line_number: line_num + 1,
+                line: String::new(),
+                before: Vec::new(),
+                after: Vec::new(),
+                binary: true,
+            });
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Decode a raw line, stripping a trailing `\r` and replacing invalid UTF-8
+fn decode_line(bytes: Vec<u8>) -> String {
+    let bytes = bytes.strip_suffix(b"\r").unwrap_or(&bytes);
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
 /// Search directory recursively
 fn search_directory(
     sandbox: &Sandbox,
@@ -323,6 +401,53 @@ mod tests {
         assert_eq!(matches[0].line, "test.*");
     }
 
+    #[test]
+    fn test_grep_binary_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("data.bin");
+        std::fs::write(&file, b"\x00\x01header\nneedle\xff\n").unwrap();
+
+        let config = SandboxConfig::new(temp_dir.path().to_path_buf());
+        let sandbox = Sandbox::new(config);
+        let options = GrepOptions::default();
+
+        let matches = grep(&sandbox, "needle", &[&file], &options).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].binary);
+        assert_eq!(
+            format_grep_output(&matches, &options, false),
+            format!("Binary file {} matches\n", file.display())
+        );
+
+        let matches = grep(&sandbox, "absent", &[&file], &options).unwrap();
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_format_grep_output_prefixes() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = create_test_file(temp_dir.path(), "test.txt", "one\ntwo\nthree\n");
+
+        let config = SandboxConfig::new(temp_dir.path().to_path_buf());
+        let sandbox = Sandbox::new(config);
+        let options = GrepOptions {
+            line_number: true,
+            before_context: 1,
+            ..Default::default()
+        };
+
+        let matches = grep(&sandbox, "three", &[&file], &options).unwrap();
+        let path = file.display();
+        assert_eq!(
+            format_grep_output(&matches, &options, true),
+            format!("{path}-2-two\n{path}:3:three\n")
+        );
+        assert_eq!(
+            format_grep_output(&matches, &GrepOptions::default(), false),
+            "two\nthree\n"
+        );
+    }
+
     #[test]
     fn test_grep_sandbox_violation() {
         let temp_dir = TempDir::new().unwrap();
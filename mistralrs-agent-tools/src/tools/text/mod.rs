@@ -5,6 +5,7 @@
 //! - comm, join: File comparison and joining
 //! - csplit, split: File splitting
 //! - cut, paste: Column extraction and merging
+//! - diff: Line-by-line file comparison
 //! - expand, unexpand: Tab/space conversion
 //! - fold, fmt: Text formatting
 //! - head, tail: Display file beginning/end
@@ -20,31 +21,36 @@
 //! - uniq: Report/filter repeated lines
 
 // Implemented utilities
+mod cut;
+mod diff;
 mod grep;
 mod head;
+mod paste;
 mod sort;
 mod tail;
+mod tr;
 mod uniq;
 mod wc;
 
 // TODO @codex: Implement remaining text processing utilities
-// mod cut;
-// mod tr;
 // mod base64, base32, basenc (encoding)
 // mod comm, join (comparison)
 // mod csplit, split (splitting)
-// mod cut, paste (columns)
 // mod expand, unexpand (tabs)
 // mod fold, fmt (formatting)
 // mod nl (numbering)
 // mod od (octal)
 // mod pr, ptx (printing)
 // mod shuf, tac (ordering)
-// mod tr, tsort (translation/topo)
+// mod tsort (topological sort)
 
-pub use grep::grep;
+pub use cut::cut;
+pub use diff::{diff_files, DiffResult};
+pub use grep::{format_grep_output, grep};
 pub use head::head;
-pub use sort::sort;
-pub use tail::tail;
-pub use uniq::uniq;
+pub use paste::{paste, paste_serial};
+pub use sort::{sort, sort_text};
+pub use tail::{tail, tail_follow};
+pub use tr::tr;
+pub use uniq::{uniq, uniq_text};
 pub use wc::{format_wc_output, wc};
@@ -0,0 +1,159 @@
+//! paste - merge lines of files
+//!
+//! Windows-optimized implementation with sandbox support.
+
+use crate::tools::sandbox::Sandbox;
+use crate::types::{AgentError, AgentResult};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::Path;
+
+/// Merge corresponding lines of files
+///
+/// Each output row joins the Nth line of every file with `delimiter`. Files
+/// are read line by line; when a file runs out of lines its field is left
+/// empty until all files are exhausted.
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `paths` - Paths to read from
+/// * `delimiter` - Separator placed between fields
+///
+/// # Returns
+/// * Merged rows, each terminated by a newline
+pub fn paste(sandbox: &Sandbox, paths: &[&Path], delimiter: &str) -> AgentResult<String> {
+    let mut readers = open_all(sandbox, paths)?;
+    let mut output = String::new();
+
+    loop {
+        let mut fields = Vec::with_capacity(readers.len());
+        let mut any_line = false;
+        for reader in &mut readers {
+            match reader.next().transpose()? {
+                Some(line) => {
+                    any_line = true;
+                    fields.push(line);
+                }
+                None => fields.push(String::new()),
+            }
+        }
+        if !any_line {
+            break;
+        }
+        output.push_str(&fields.join(delimiter));
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Merge the lines of each file onto a single row (`paste -s`)
+///
+/// # Arguments
+/// * `sandbox` - Sandbox for path validation
+/// * `paths` - Paths to read from
+/// * `delimiter` - Separator placed between lines
+///
+/// # Returns
+/// * One row per file, each terminated by a newline
+pub fn paste_serial(sandbox: &Sandbox, paths: &[&Path], delimiter: &str) -> AgentResult<String> {
+    let mut output = String::new();
+
+    for reader in open_all(sandbox, paths)? {
+        let lines = reader.collect::<Result<Vec<_>, _>>()?;
+        output.push_str(&lines.join(delimiter));
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Validate and open every path for line-by-line reading
+fn open_all(sandbox: &Sandbox, paths: &[&Path]) -> AgentResult<Vec<Lines<BufReader<File>>>> {
+    if paths.is_empty() {
+        return Err(AgentError::InvalidInput(
+            "No paths provided (stdin not supported)".to_string(),
+        ));
+    }
+
+    paths
+        .iter()
+        .map(|path| {
+            let validated_path = sandbox.validate_read(path)?;
+            Ok(BufReader::new(File::open(validated_path)?).lines())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn create_test_file(dir: &Path, name: &str, content: &str) -> std::path::PathBuf {
+        let path = dir.join(name);
+        let mut file = File::create(&path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_paste_equal_length() {
+        let temp_dir = TempDir::new().unwrap();
+        let names = create_test_file(temp_dir.path(), "names.txt", "alice\nbob\n");
+        let ages = create_test_file(temp_dir.path(), "ages.txt", "30\n25\n");
+
+        let config = SandboxConfig::new(temp_dir.path().to_path_buf());
+        let sandbox = Sandbox::new(config);
+
+        let result = paste(&sandbox, &[&names, &ages], "\t").unwrap();
+
+        assert_eq!(result, "alice\t30\nbob\t25\n");
+    }
+
+    #[test]
+    fn test_paste_unequal_length() {
+        let temp_dir = TempDir::new().unwrap();
+        let short = create_test_file(temp_dir.path(), "short.txt", "a\n");
+        let long = create_test_file(temp_dir.path(), "long.txt", "1\n2\n3\n");
+
+        let config = SandboxConfig::new(temp_dir.path().to_path_buf());
+        let sandbox = Sandbox::new(config);
+
+        let result = paste(&sandbox, &[&short, &long], ",").unwrap();
+        assert_eq!(result, "a,1\n,2\n,3\n");
+
+        let result = paste(&sandbox, &[&long, &short], ",").unwrap();
+        assert_eq!(result, "1,a\n2,\n3,\n");
+    }
+
+    #[test]
+    fn test_paste_serial() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = create_test_file(temp_dir.path(), "first.txt", "a\nb\nc\n");
+        let second = create_test_file(temp_dir.path(), "second.txt", "1\n2\n");
+
+        let config = SandboxConfig::new(temp_dir.path().to_path_buf());
+        let sandbox = Sandbox::new(config);
+
+        let result = paste_serial(&sandbox, &[&first, &second], ",").unwrap();
+
+        assert_eq!(result, "a,b,c\n1,2\n");
+    }
+
+    #[test]
+    fn test_paste_sandbox_violation() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let file = create_test_file(outside_dir.path(), "test.txt", "content");
+
+        let config = SandboxConfig::new(temp_dir.path().to_path_buf());
+        let sandbox = Sandbox::new(config);
+
+        let result = paste(&sandbox, &[&file], "\t");
+
+        assert!(matches!(result, Err(AgentError::SandboxViolation(_))));
+    }
+}
@@ -40,15 +40,45 @@ pub fn sort(sandbox: &Sandbox, paths: &[&Path], options: &SortOptions) -> AgentR
         }
     }
 
-    // Sort lines
-    sort_lines(&mut lines, options);
+    sort_and_dedup(&mut lines, options);
 
-    // Remove duplicates if unique flag is set
-    if options.unique {
-        lines.dedup();
+    Ok(lines.join("\n"))
+}
+
+/// Sort the lines of in-memory text
+///
+/// Applies the same ordering as [`sort`]. The output ends with a newline
+/// exactly when non-empty input does, and `\r\n` line endings are normalized
+/// to `\n`.
+///
+/// # Arguments
+/// * `content` - Text to sort
+/// * `options` - Sort options
+///
+/// # Returns
+/// * Sorted lines as a single string
+pub fn sort_text(content: &str, options: &SortOptions) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    sort_and_dedup(&mut lines, options);
+
+    let mut output = lines.join("\n");
+    if !output.is_empty() && content.ends_with('\n') {
+        output.push('\n');
     }
+    output
+}
 
-    Ok(lines.join("\n"))
+/// Sort lines, then drop adjacent duplicates if the unique flag is set
+fn sort_and_dedup(lines: &mut Vec<String>, options: &SortOptions) {
+    sort_lines(lines, options);
+
+    if options.unique {
+        if options.ignore_case {
+            lines.dedup_by(|a, b| a.to_lowercase() == b.to_lowercase());
+        } else {
+            lines.dedup();
+        }
+    }
 }
 
 /// Sort lines according to options
@@ -82,22 +112,22 @@ fn sort_lines(lines: &mut [String], options: &SortOptions) {
                 lines.reverse();
             }
         }
-        return;
-    }
-
-    // Apply reverse if needed (for non-lexical sorts)
-    if options.reverse && !options.numeric && !options.version_sort && !options.human_numeric {
-        lines.reverse();
     }
 }
 
 /// Compare numerically
+///
+/// Lines are ordered by their leading number; lines without one sort after
+/// numeric lines, and ties fall back to string order.
 fn compare_numeric(a: &str, b: &str, options: &SortOptions) -> Ordering {
-    let a_num = a.trim().parse::<f64>().ok();
-    let b_num = b.trim().parse::<f64>().ok();
+    let a_num = parse_leading_number(a);
+    let b_num = parse_leading_number(b);
 
     let cmp = match (a_num, b_num) {
-        (Some(an), Some(bn)) => an.partial_cmp(&bn).unwrap_or(Ordering::Equal),
+        (Some(an), Some(bn)) => an
+            .partial_cmp(&bn)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.cmp(b)),
         (Some(_), None) => Ordering::Less,
         (None, Some(_)) => Ordering::Greater,
         (None, None) => a.cmp(b),
@@ -110,6 +140,32 @@ fn compare_numeric(a: &str, b: &str, options: &SortOptions) -> Ordering {
     }
 }
 
+/// Parse the number at the start of a line (e.g. `42` in `42 apples`)
+fn parse_leading_number(s: &str) -> Option<f64> {
+    let s = s.trim_start();
+    let bytes = s.as_bytes();
+    let mut end = 0;
+
+    if matches!(bytes.first(), Some(b'-' | b'+')) {
+        end += 1;
+    }
+    let digits_start = end;
+    while end < bytes.len() && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    if end < bytes.len() && bytes[end] == b'.' {
+        end += 1;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+    }
+
+    if !bytes[digits_start..end].iter().any(u8::is_ascii_digit) {
+        return None;
+    }
+    s[..end].parse::<f64>().ok()
+}
+
 /// Compare version strings (natural sort)
 fn compare_version(a: &str, b: &str, options: &SortOptions) -> Ordering {
     let a_parts = split_version(a);
@@ -351,6 +407,64 @@ mod tests {
         assert_eq!(result, "apple\nbanana");
     }
 
+    #[test]
+    fn test_sort_text_lexical() {
+        let options = SortOptions::default();
+
+        assert_eq!(
+            sort_text("zebra\napple\nbanana\n", &options),
+            "apple\nbanana\nzebra\n"
+        );
+        assert_eq!(sort_text("b\na", &options), "a\nb");
+        assert_eq!(sort_text("", &options), "");
+    }
+
+    #[test]
+    fn test_sort_text_numeric() {
+        let options = SortOptions {
+            numeric: true,
+            ..Default::default()
+        };
+
+        assert_eq!(sort_text("10\n9\n1\n", &options), "1\n9\n10\n");
+        assert_eq!(
+            sort_text("10 apples\nbanana\n9 pears\n-1 debt\n", &options),
+            "-1 debt\n9 pears\n10 apples\nbanana\n"
+        );
+    }
+
+    #[test]
+    fn test_sort_text_reverse() {
+        let options = SortOptions {
+            reverse: true,
+            ..Default::default()
+        };
+        assert_eq!(sort_text("a\nc\nb\n", &options), "c\nb\na\n");
+
+        let options = SortOptions {
+            month_sort: true,
+            reverse: true,
+            ..Default::default()
+        };
+        assert_eq!(sort_text("Jan\nMar\nFeb", &options), "Mar\nFeb\nJan");
+    }
+
+    #[test]
+    fn test_sort_text_unique() {
+        let options = SortOptions {
+            unique: true,
+            ..Default::default()
+        };
+        assert_eq!(sort_text("b\na\nb\na\n", &options), "a\nb\n");
+
+        let options = SortOptions {
+            unique: true,
+            ignore_case: true,
+            ..Default::default()
+        };
+        assert_eq!(sort_text("B\na\nb\n", &options), "a\nB\n");
+    }
+
     #[test]
     fn test_sort_version() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,13 +1,22 @@
 //! Tail utility - display last part of files
 //!
-//! Shows the last N lines (or bytes) of one or more files.
+//! Shows the last N lines (or bytes) of one or more files, and can follow a
+//! file as it grows (`tail -f`).
 
 use crate::tools::sandbox::Sandbox;
 use crate::types::{AgentResult, TailOptions};
 use std::collections::VecDeque;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How often `tail_follow` checks the file for new data
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Chunk size used when scanning backwards for the last lines
+const SCAN_CHUNK_SIZE: u64 = 8 * 1024;
 
 /// Display the last part of files
 pub fn tail(sandbox: &Sandbox, paths: &[&Path], options: &TailOptions) -> AgentResult<String> {
@@ -45,6 +54,114 @@ pub fn tail(sandbox: &Sandbox, paths: &[&Path], options: &TailOptions) -> AgentR
     Ok(output)
 }
 
+/// Follow a file as it grows (`tail -f`)
+///
+/// Delivers the last `from_end` lines, then every line appended afterwards,
+/// to `on_line` (without the trailing newline). A trailing partial line is held
+/// back until it is completed. The file is polled until `cancel` is set; if it
+/// is truncated or replaced (log rotation), reading restarts from the
+/// beginning of the new contents.
+///
+/// # Errors
+/// Returns error if the path is outside the sandbox or cannot be read.
+pub fn tail_follow<F>(
+    sandbox: &Sandbox,
+    path: &Path,
+    from_end: usize,
+    cancel: &AtomicBool,
+    mut on_line: F,
+) -> AgentResult<()>
+where
+    F: FnMut(String),
+{
+    let validated_path = sandbox.validate_read(path)?;
+
+    let mut file = File::open(&validated_path)?;
+    let len = file.metadata()?.len();
+    let start = start_of_last_lines(&mut file, len, from_end)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut reader = BufReader::new(file);
+    let mut position = start;
+    let mut partial = String::new();
+
+    while !cancel.load(Ordering::Relaxed) {
+        // Deliver every complete line currently available
+        loop {
+            let read = reader.read_line(&mut partial)?;
+            if read == 0 {
+                break;
+            }
+            position += read as u64;
+            if partial.ends_with('\n') {
+                let line = partial.trim_end_matches(['\n', '\r']).to_string();
+                partial.clear();
+                on_line(line);
+            }
+        }
+
+        std::thread::sleep(FOLLOW_POLL_INTERVAL);
+
+        // Re-open from the start if the file was truncated or rotated
+        let Ok(current) = fs::metadata(&validated_path) else {
+            // Rotated away and not yet recreated
+            continue;
+        };
+        if current.len() < position || !is_same_file(reader.get_ref(), &current)? {
+            reader = BufReader::new(File::open(&validated_path)?);
+            position = 0;
+            partial.clear();
+        }
+    }
+
+    Ok(())
+}
+
+/// Byte offset where the last `n` lines of a file of `len` bytes begin
+fn start_of_last_lines(file: &mut File, len: u64, n: usize) -> AgentResult<u64> {
+    if n == 0 {
+        return Ok(len);
+    }
+
+    let mut end = len;
+    let mut newlines = 0;
+    let mut buffer = vec![0u8; SCAN_CHUNK_SIZE as usize];
+    while end > 0 {
+        let start = end.saturating_sub(SCAN_CHUNK_SIZE);
+        let chunk = &mut buffer[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+
+        for (i, &byte) in chunk.iter().enumerate().rev() {
+            let offset = start + i as u64;
+            // The newline ending the final line does not start a new one
+            if byte == b'\n' && offset + 1 < len {
+                newlines += 1;
+                if newlines == n {
+                    return Ok(offset + 1);
+                }
+            }
+        }
+        end = start;
+    }
+
+    Ok(0)
+}
+
+/// Whether an open file is still the file found at its path
+fn is_same_file(file: &File, current: &fs::Metadata) -> AgentResult<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let open = file.metadata()?;
+        Ok(open.ino() == current.ino() && open.dev() == current.dev())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (file, current);
+        Ok(true)
+    }
+}
+
 /// Read last N lines from a file using a sliding window
 fn read_last_lines(path: &Path, n: usize) -> AgentResult<String> {
     let file = File::open(path)?;
@@ -157,6 +274,71 @@ mod tests {
         assert!(!result.contains("Line 15"));
     }
 
+    #[test]
+    fn test_start_of_last_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        std::fs::write(&file_path, "a\nbb\nccc\n").unwrap();
+        let mut file = File::open(&file_path).unwrap();
+
+        assert_eq!(start_of_last_lines(&mut file, 9, 1).unwrap(), 5);
+        assert_eq!(start_of_last_lines(&mut file, 9, 2).unwrap(), 2);
+        assert_eq!(start_of_last_lines(&mut file, 9, 5).unwrap(), 0);
+        assert_eq!(start_of_last_lines(&mut file, 9, 0).unwrap(), 9);
+    }
+
+    #[test]
+    fn test_tail_follow_delivers_appended_lines() {
+        use std::sync::{Arc, Mutex};
+        use std::time::Instant;
+
+        let (sandbox, temp_dir) = create_test_sandbox();
+        let file_path = temp_dir.path().join("app.log");
+        std::fs::write(&file_path, "old 1\nold 2\nold 3\n").unwrap();
+
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let follower = {
+            let (lines, cancel, file_path) =
+                (Arc::clone(&lines), Arc::clone(&cancel), file_path.clone());
+            std::thread::spawn(move || {
+                tail_follow(&sandbox, &file_path, 2, &cancel, |line| {
+                    lines.lock().unwrap().push(line)
+                })
+            })
+        };
+
+        let wait_for = |count: usize| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while lines.lock().unwrap().len() < count && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        wait_for(2);
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&file_path)
+            .unwrap();
+        write!(file, "new 1\nnew").unwrap();
+        file.flush().unwrap();
+        wait_for(3);
+        writeln!(file, " 2").unwrap();
+        wait_for(4);
+
+        // Truncation restarts from the beginning
+        std::fs::write(&file_path, "fresh\n").unwrap();
+        wait_for(5);
+
+        cancel.store(true, Ordering::Relaxed);
+        follower.join().unwrap().unwrap();
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec!["old 2", "old 3", "new 1", "new 2", "fresh"]
+        );
+    }
+
     #[test]
     fn test_tail_bytes() {
         let (sandbox, temp_dir) = create_test_sandbox();
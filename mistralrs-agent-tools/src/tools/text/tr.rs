@@ -0,0 +1,190 @@
+//! tr - translate, squeeze, and delete characters
+//!
+//! Operates on in-memory text, so it needs no sandbox access.
+
+use crate::types::{AgentError, AgentResult, TrOptions};
+
+/// Translate, squeeze, or delete characters
+///
+/// Sets are lists of characters where `a-z` denotes an inclusive range and
+/// `\n`, `\t`, `\r`, `\\` and `\-` are escapes. When translating, each
+/// character of `set1` maps to the character at the same position in `set2`;
+/// if `set2` is shorter, its last character is repeated.
+///
+/// # Arguments
+/// * `content` - Text to process
+/// * `set1` - Characters to translate, delete, or squeeze
+/// * `set2` - Replacement characters (required unless deleting or squeezing)
+/// * `options` - Tr options
+///
+/// # Returns
+/// * The processed text
+///
+/// # Example
+/// ```
+/// use mistralrs_agent_tools::tools::text::tr;
+/// use mistralrs_agent_tools::TrOptions;
+///
+/// let upper = tr("hello", "a-z", Some("A-Z"), &TrOptions::default()).unwrap();
+/// assert_eq!(upper, "HELLO");
+/// ```
+pub fn tr(
+    content: &str,
+    set1: &str,
+    set2: Option<&str>,
+    options: &TrOptions,
+) -> AgentResult<String> {
+    let set1 = expand_set(set1)?;
+    let set2 = set2.map(expand_set).transpose()?;
+
+    if options.delete {
+        if set2.is_some() && !options.squeeze {
+            return Err(AgentError::InvalidInput(
+                "Extra operand: only one set may be given when deleting".to_string(),
+            ));
+        }
+        let kept = content.chars().filter(|c| !set1.contains(c));
+        return Ok(match set2 {
+            Some(squeeze_set) => squeeze(kept, &squeeze_set),
+            None => kept.collect(),
+        });
+    }
+
+    match set2 {
+        Some(set2) => {
+            let Some(&last) = set2.last() else {
+                return Err(AgentError::InvalidInput(
+                    "When translating, the second set must not be empty".to_string(),
+                ));
+            };
+            let translated = content.chars().map(|c| {
+                // Later occurrences of a character in set1 take precedence
+                match set1.iter().rposition(|&from| from == c) {
+                    Some(i) => set2.get(i).copied().unwrap_or(last),
+                    None => c,
+                }
+            });
+            if options.squeeze {
+                Ok(squeeze(translated, &set2))
+            } else {
+                Ok(translated.collect())
+            }
+        }
+        None if options.squeeze => Ok(squeeze(content.chars(), &set1)),
+        None => Err(AgentError::InvalidInput(
+            "Missing operand: a second set is required when translating".to_string(),
+        )),
+    }
+}
+
+/// Collapse runs of the same character into one if it is in `set`
+fn squeeze(chars: impl Iterator<Item = char>, set: &[char]) -> String {
+    let mut output = String::new();
+    let mut last = None;
+    for c in chars {
+        if last == Some(c) && set.contains(&c) {
+            continue;
+        }
+        output.push(c);
+        last = Some(c);
+    }
+    output
+}
+
+/// Expand escapes and ranges in a character set
+fn expand_set(set: &str) -> AgentResult<Vec<char>> {
+    // Resolve escapes first, remembering which characters were escaped so
+    // that `\-` stays a literal dash
+    let mut tokens: Vec<(char, bool)> = Vec::new();
+    let mut chars = set.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            tokens.push((c, false));
+            continue;
+        }
+        let escaped = match chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some(other) => other,
+            None => '\\',
+        };
+        tokens.push((escaped, true));
+    }
+
+    let mut expanded = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let start = tokens[i].0;
+        if tokens.get(i + 1) == Some(&('-', false)) && i + 2 < tokens.len() {
+            let end = tokens[i + 2].0;
+            if start > end {
+                return Err(AgentError::InvalidInput(format!(
+                    "Range-endpoints of '{}-{}' are in reverse collating sequence order",
+                    start, end
+                )));
+            }
+            expanded.extend(start..=end);
+            i += 3;
+        } else {
+            expanded.push(start);
+            i += 1;
+        }
+    }
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tr_translate_case() {
+        let options = TrOptions::default();
+
+        assert_eq!(
+            tr("Hello, World!\n", "a-z", Some("A-Z"), &options).unwrap(),
+            "HELLO, WORLD!\n"
+        );
+        // Shorter second set repeats its last character
+        assert_eq!(tr("abcd", "abcd", Some("xy"), &options).unwrap(), "xyyy");
+    }
+
+    #[test]
+    fn test_tr_delete_digits() {
+        let options = TrOptions {
+            delete: true,
+            ..Default::default()
+        };
+
+        assert_eq!(tr("a1b22c333", "0-9", None, &options).unwrap(), "abc");
+        assert!(tr("abc", "a", Some("b"), &options).is_err());
+    }
+
+    #[test]
+    fn test_tr_squeeze_spaces() {
+        let options = TrOptions {
+            squeeze: true,
+            ..Default::default()
+        };
+
+        assert_eq!(tr("a   b  c    d", " ", None, &options).unwrap(), "a b c d");
+        assert_eq!(
+            tr("a\t\tb  c", "\\t ", Some("  "), &options).unwrap(),
+            "a b c"
+        );
+    }
+
+    #[test]
+    fn test_tr_invalid_sets() {
+        let options = TrOptions::default();
+
+        assert!(tr("abc", "z-a", Some("A"), &options).is_err());
+        assert!(tr("abc", "a", None, &options).is_err());
+        assert!(tr("abc", "a", Some(""), &options).is_err());
+        // A trailing dash is literal
+        assert_eq!(tr("a-b", "a-", Some("xy"), &options).unwrap(), "xyb");
+        assert_eq!(tr("a-b", "a\\-b", Some("123"), &options).unwrap(), "123");
+    }
+}
@@ -4,6 +4,7 @@
 
 use crate::tools::sandbox::Sandbox;
 use crate::types::{AgentError, AgentResult, UniqOptions};
+use std::convert::Infallible;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -39,10 +40,33 @@ pub fn uniq(sandbox: &Sandbox, paths: &[&Path], options: &UniqOptions) -> AgentR
     Ok(result.join("\n"))
 }
 
+/// Filter adjacent duplicate lines of in-memory text
+///
+/// Applies the same filtering as [`uniq`], so input should already be sorted
+/// when all duplicates are to be collapsed. The output ends with a newline
+/// exactly when non-empty input does.
+///
+/// # Arguments
+/// * `content` - Text to filter
+/// * `options` - Uniq options
+///
+/// # Returns
+/// * Filtered lines as a single string
+pub fn uniq_text(content: &str, options: &UniqOptions) -> String {
+    let lines = content.lines().map(|line| Ok(line.to_string()));
+    let Ok(filtered) = filter_lines::<_, Infallible>(lines, options);
+
+    let mut output = filtered.join("\n");
+    if !output.is_empty() && content.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
 /// Filter lines according to options
-fn filter_lines<I>(lines: I, options: &UniqOptions) -> AgentResult<Vec<String>>
+fn filter_lines<I, E>(lines: I, options: &UniqOptions) -> Result<Vec<String>, E>
 where
-    I: Iterator<Item = std::io::Result<String>>,
+    I: Iterator<Item = Result<String, E>>,
 {
     let mut result = Vec::new();
     let mut prev_line: Option<String> = None;
@@ -309,6 +333,46 @@ mod tests {
         assert_eq!(result, "apple\nbanana\napple\nbanana");
     }
 
+    #[test]
+    fn test_uniq_text_dedup() {
+        let options = UniqOptions::default();
+
+        assert_eq!(uniq_text("a\na\nb\na\n", &options), "a\nb\na\n");
+        assert_eq!(uniq_text("a\na", &options), "a");
+        assert_eq!(uniq_text("", &options), "");
+    }
+
+    #[test]
+    fn test_uniq_text_count() {
+        let options = UniqOptions {
+            count: true,
+            ignore_case: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            uniq_text("Line\nline\nLINE\nother\n", &options),
+            "      3 Line\n      1 other\n"
+        );
+    }
+
+    #[test]
+    fn test_uniq_text_repeated_and_unique() {
+        let content = "a\na\nb\nc\nc\n";
+
+        let repeated = UniqOptions {
+            repeated: true,
+            ..Default::default()
+        };
+        assert_eq!(uniq_text(content, &repeated), "a\nc\n");
+
+        let unique = UniqOptions {
+            unique: true,
+            ..Default::default()
+        };
+        assert_eq!(uniq_text(content, &unique), "b\n");
+    }
+
     #[test]
     fn test_uniq_sandbox_violation() {
         let temp_dir = TempDir::new().unwrap();
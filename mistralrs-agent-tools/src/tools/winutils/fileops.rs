@@ -3,15 +3,21 @@
 //! Wrappers for: cp, mv, rm, mkdir, rmdir, touch
 
 use super::wrapper::WinutilCommand;
+use crate::tools::file::{walk, WalkOptions};
 use crate::tools::sandbox::Sandbox;
 use crate::types::{AgentError, AgentResult};
 use std::path::Path;
 
 /// Copy files and directories
+///
+/// The copy counts against the sandbox write quota like the native `cp`.
 pub fn cp(sandbox: &Sandbox, source: &Path, dest: &Path, recursive: bool) -> AgentResult<()> {
     sandbox.validate_read(source)?;
     sandbox.validate_write(dest)?;
 
+    let planned = copy_size(sandbox, source, recursive)?;
+    sandbox.check_write_quota(planned)?;
+
     let mut cmd = WinutilCommand::new("cp");
 
     if recursive {
@@ -31,9 +37,34 @@ pub fn cp(sandbox: &Sandbox, source: &Path, dest: &Path, recursive: bool) -> Age
         )));
     }
 
+    if !sandbox.is_dry_run() {
+        sandbox.record_write(planned);
+    }
+
     Ok(())
 }
 
+/// Bytes a copy of `source` would write
+///
+/// Each file is checked against `max_write_bytes` before anything runs.
+fn copy_size(sandbox: &Sandbox, source: &Path, recursive: bool) -> AgentResult<u64> {
+    let options = WalkOptions {
+        max_depth: if recursive { None } else { Some(0) },
+        follow_symlinks: true,
+        skip_hidden: false,
+    };
+    let mut total = 0u64;
+    walk(sandbox, source, &options, |entry| {
+        if entry.file_type().is_file() {
+            let size = entry.metadata().map_or(0, |metadata| metadata.len());
+            sandbox.validate_write_size(size)?;
+            total += size;
+        }
+        Ok(())
+    })?;
+    Ok(total)
+}
+
 /// Move/rename files and directories
 pub fn mv(sandbox: &Sandbox, source: &Path, dest: &Path) -> AgentResult<()> {
     sandbox.validate_read(source)?;
@@ -142,3 +173,32 @@ pub fn touch(sandbox: &Sandbox, path: &Path) -> AgentResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SandboxConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cp_checks_write_quota_before_running() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = SandboxConfig::builder()
+            .root(temp_dir.path())
+            .max_total_write_bytes(4)
+            .build()
+            .unwrap();
+        let sandbox = Sandbox::new(config);
+
+        let source = temp_dir.path().join("source.txt");
+        let dest = temp_dir.path().join("dest.txt");
+        fs::write(&source, "0123456789").unwrap();
+
+        // Rejected by the quota without needing the winutils executable
+        let err = cp(&sandbox, &source, &dest, false).unwrap_err();
+        assert!(matches!(err, AgentError::QuotaExceeded(_)));
+        assert!(!dest.exists());
+        assert_eq!(sandbox.bytes_written(), 0);
+    }
+}
@@ -30,6 +30,8 @@ pub enum AgentError {
     Unsupported(String),
     /// Encoding error
     EncodingError(String),
+    /// Cumulative write quota exhausted
+    QuotaExceeded(String),
 }
 
 impl std::fmt::Display for AgentError {
@@ -43,6 +45,7 @@ impl std::fmt::Display for AgentError {
             Self::NotFound(msg) => write!(f, "Not found: {}", msg),
             Self::Unsupported(msg) => write!(f, "Unsupported: {}", msg),
             Self::EncodingError(msg) => write!(f, "Encoding error: {}", msg),
+            Self::QuotaExceeded(msg) => write!(f, "Quota exceeded: {}", msg),
         }
     }
 }
@@ -93,6 +96,22 @@ pub struct SandboxConfig {
     pub max_batch_size: usize,
     /// Security policy (optional, for enhanced security controls)
     pub security_policy: Option<SecurityPolicy>,
+    /// Base directory for temporary files; relative paths resolve against `root`
+    pub temp_dir: Option<PathBuf>,
+    /// Additional roots that operations may also use
+    pub extra_roots: Vec<PathBuf>,
+    /// Reject all write operations
+    pub read_only: bool,
+    /// Glob patterns (relative to the containing root) that are never accessible
+    pub deny_globs: Vec<String>,
+    /// Maximum number of bytes a single write operation may produce
+    pub max_write_bytes: Option<u64>,
+    /// Maximum number of bytes all write operations may produce together
+    pub max_total_write_bytes: Option<u64>,
+    /// Whether paths may traverse symlinks inside the sandbox
+    pub follow_symlinks: bool,
+    /// Validate write operations and report their effects without applying them
+    pub dry_run: bool,
 }
 
 impl Default for SandboxConfig {
@@ -103,12 +122,23 @@ impl Default for SandboxConfig {
             max_read_size: 100 * 1024 * 1024, // 100MB
             max_batch_size: 1000,
             security_policy: None, // Legacy mode by default
+            temp_dir: None,
+            extra_roots: Vec::new(),
+            read_only: false,
+            deny_globs: Vec::new(),
+            max_write_bytes: None,
+            max_total_write_bytes: None,
+            follow_symlinks: true,
+            dry_run: false,
         }
     }
 }
 
 impl SandboxConfig {
     /// Creates a new sandbox config with the given root
+    ///
+    /// Shortcut for `SandboxConfig::builder().root(root).build()` with no
+    /// further options.
     pub fn new(root: PathBuf) -> Self {
         Self {
             root,
@@ -116,6 +146,16 @@ impl SandboxConfig {
         }
     }
 
+    /// Creates a builder starting from the default configuration
+    pub fn builder() -> SandboxConfigBuilder {
+        SandboxConfigBuilder::default()
+    }
+
+    /// All roots, primary first
+    pub fn roots(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.root).chain(self.extra_roots.iter())
+    }
+
     /// Creates a new sandbox config with a security policy
     pub fn with_security_policy(root: PathBuf, policy: SecurityPolicy) -> Self {
         Self {
@@ -124,6 +164,7 @@ impl SandboxConfig {
             max_read_size: 100 * 1024 * 1024,
             max_batch_size: 1000,
             security_policy: Some(policy),
+            ..Default::default()
         }
     }
 
@@ -148,6 +189,12 @@ impl SandboxConfig {
         self
     }
 
+    /// Sets the base directory for temporary files (e.g. `tmp/session-1`)
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
     /// Sets the security policy
     pub fn with_policy(mut self, policy: SecurityPolicy) -> Self {
         self.security_policy = Some(policy);
@@ -187,6 +234,93 @@ impl SandboxConfig {
     }
 }
 
+/// Builder for [`SandboxConfig`] with fluent options
+#[derive(Debug, Clone, Default)]
+pub struct SandboxConfigBuilder {
+    config: SandboxConfig,
+}
+
+impl SandboxConfigBuilder {
+    /// Sets the primary root directory
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.config.root = root.into();
+        self
+    }
+
+    /// Adds an additional root directory
+    pub fn add_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.config.extra_roots.push(root.into());
+        self
+    }
+
+    /// Sets whether write operations are rejected
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.config.read_only = read_only;
+        self
+    }
+
+    /// Adds a glob pattern for paths that are never accessible
+    pub fn deny_glob(mut self, pattern: impl Into<String>) -> Self {
+        self.config.deny_globs.push(pattern.into());
+        self
+    }
+
+    /// Sets the maximum number of bytes a single write may produce
+    pub fn max_write_bytes(mut self, bytes: u64) -> Self {
+        self.config.max_write_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum number of bytes all writes may produce together
+    pub fn max_total_write_bytes(mut self, bytes: u64) -> Self {
+        self.config.max_total_write_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets whether paths may traverse symlinks inside the sandbox
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.config.follow_symlinks = follow;
+        self
+    }
+
+    /// Sets whether write operations only report what they would do
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.config.dry_run = dry_run;
+        self
+    }
+
+    /// Sets the base directory for temporary files
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.temp_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the maximum read size
+    pub fn max_read_size(mut self, size: usize) -> Self {
+        self.config.max_read_size = size;
+        self
+    }
+
+    /// Sets the security policy
+    pub fn security_policy(mut self, policy: SecurityPolicy) -> Self {
+        self.config.security_policy = Some(policy);
+        self
+    }
+
+    /// Builds the config
+    ///
+    /// # Errors
+    /// Returns error if a deny glob is not a valid pattern.
+    pub fn build(self) -> AgentResult<SandboxConfig> {
+        for pattern in &self.config.deny_globs {
+            globset::Glob::new(pattern).map_err(|e| {
+                AgentError::InvalidInput(format!("Invalid deny glob '{}': {}", pattern, e))
+            })?;
+        }
+        Ok(self.config)
+    }
+}
+
 /// Options for cat operation
 #[derive(Debug, Clone, Default)]
 pub struct CatOptions {
@@ -196,6 +330,8 @@ pub struct CatOptions {
     pub show_ends: bool,
     /// Squeeze multiple blank lines
     pub squeeze_blank: bool,
+    /// Fail instead of returning empty output when a glob matches nothing
+    pub error_on_no_match: bool,
 }
 
 /// Options for ls operation
@@ -213,6 +349,36 @@ pub struct LsOptions {
     pub sort_by_time: bool,
     /// Reverse sort order
     pub reverse: bool,
+    /// Append a type indicator to names in text output (`ls -F`)
+    pub classify: bool,
+    /// How modification times are rendered; `None` keeps `YYYY-MM-DD HH:MM`
+    pub time_format: Option<LsTimeFormat>,
+    /// Describe symlinks by their targets (`ls -L`), descending into linked
+    /// directories when recursive. Targets outside the sandbox are not followed.
+    pub dereference: bool,
+}
+
+/// Rendering of modification times in `ls` output (all UTC)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LsTimeFormat {
+    /// ISO-8601 / RFC 3339, e.g. `2024-03-15T12:00:00Z`
+    Iso,
+    /// Age relative to now, e.g. `3 hours ago`
+    Relative,
+    /// A strftime pattern, e.g. `%d/%m/%Y`
+    Custom(String),
+}
+
+impl LsTimeFormat {
+    /// Parse a tool argument: `iso`, `relative`, or any other text as a
+    /// custom strftime pattern
+    pub fn parse(spec: &str) -> Self {
+        match spec {
+            "iso" => Self::Iso,
+            "relative" => Self::Relative,
+            pattern => Self::Custom(pattern.to_string()),
+        }
+    }
 }
 
 /// Options for head operation
@@ -329,6 +495,8 @@ pub struct GrepMatch {
     pub before: Vec<String>,
     /// Lines after match (for context)
     pub after: Vec<String>,
+    /// Whether the file is binary; `line` and context are then empty
+    pub binary: bool,
 }
 
 /// Options for sort operation
@@ -367,6 +535,28 @@ pub struct UniqOptions {
     pub skip_chars: usize,
 }
 
+/// Options for cut operation
+#[derive(Debug, Clone, Default)]
+pub struct CutOptions {
+    /// Fields to select, as 1-based indices and ranges (e.g. "1,3-5")
+    pub fields: Option<String>,
+    /// Characters to select, as 1-based indices and ranges
+    pub characters: Option<String>,
+    /// Field delimiter (default: tab)
+    pub delimiter: Option<char>,
+    /// Suppress lines without the delimiter when selecting fields
+    pub only_delimited: bool,
+}
+
+/// Options for tr operation
+#[derive(Debug, Clone, Default)]
+pub struct TrOptions {
+    /// Delete characters in the first set instead of translating
+    pub delete: bool,
+    /// Squeeze runs of repeated characters from the last set into one
+    pub squeeze: bool,
+}
+
 /// Shell type for command execution
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(windows, derive(Default))]
@@ -451,6 +641,42 @@ pub struct CommandResult {
     pub duration_ms: u64,
 }
 
+/// File type classification used to colorize or mark `ls` entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileKind {
+    /// Directory
+    Directory,
+    /// Regular file with an executable bit set (Unix) or executable extension
+    Executable,
+    /// Symbolic link, whatever it points to
+    Symlink,
+    /// Any other file
+    #[default]
+    Regular,
+}
+
+impl FileKind {
+    /// Suffix appended by `ls -F`: `/`, `*`, `@` or nothing
+    pub fn indicator(&self) -> &'static str {
+        match self {
+            Self::Directory => "/",
+            Self::Executable => "*",
+            Self::Symlink => "@",
+            Self::Regular => "",
+        }
+    }
+
+    /// Lowercase name for structured output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Directory => "directory",
+            Self::Executable => "executable",
+            Self::Symlink => "symlink",
+            Self::Regular => "regular",
+        }
+    }
+}
+
 /// File entry information
 #[derive(Debug, Clone)]
 pub struct FileEntry {
@@ -466,6 +692,14 @@ pub struct FileEntry {
     pub modified: Option<u64>,
     /// Permissions (Unix-style, 0 if not available)
     pub permissions: u32,
+    /// Number of hard links (None if not available)
+    pub nlink: Option<u64>,
+    /// Allocated 512-byte blocks (None if not available)
+    pub blocks: Option<u64>,
+    /// Classification for colorized or `-F` output
+    pub kind: FileKind,
+    /// Target of a symlink listed without dereferencing, as stored in the link
+    pub link_target: Option<PathBuf>,
 }
 
 /// Result of ls operation
@@ -567,6 +801,30 @@ mod tests {
         assert_eq!(config.max_read_size, 50 * 1024 * 1024);
     }
 
+    #[test]
+    fn test_sandbox_config_builder() {
+        let config = SandboxConfig::builder()
+            .root("/work")
+            .add_root("/data")
+            .read_only(true)
+            .deny_glob("*.key")
+            .max_write_bytes(1024)
+            .follow_symlinks(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            config.roots().collect::<Vec<_>>(),
+            vec![&PathBuf::from("/work"), &PathBuf::from("/data")]
+        );
+        assert!(config.read_only);
+        assert_eq!(config.deny_globs, vec!["*.key".to_string()]);
+        assert_eq!(config.max_write_bytes, Some(1024));
+        assert!(!config.follow_symlinks);
+
+        assert!(SandboxConfig::builder().deny_glob("[").build().is_err());
+    }
+
     #[test]
     fn test_error_conversion() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -4,11 +4,12 @@
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{self, Event, KeyCode},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use mistralrs_tui::components::{render_editor, EditorMode, EditorState};
+use mistralrs_tui::input::from_crossterm;
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -81,56 +82,15 @@ fn run_app(
         })?;
 
         // Handle input
-        if let Event::Key(key) = event::read()? {
-            match editor.mode {
-                EditorMode::Normal => match key.code {
-                    KeyCode::Char('q') => break,
-                    KeyCode::Char('i') => editor.set_mode(EditorMode::Insert),
-                    KeyCode::Char('v') => editor.set_mode(EditorMode::Visual),
-                    KeyCode::Char('h') | KeyCode::Left => editor.navigate_left(),
-                    KeyCode::Char('j') | KeyCode::Down => editor.navigate_down(),
-                    KeyCode::Char('k') | KeyCode::Up => editor.navigate_up(),
-                    KeyCode::Char('l') | KeyCode::Right => editor.navigate_right(),
-                    KeyCode::Char('0') | KeyCode::Home => editor.navigate_line_start(),
-                    KeyCode::Char('$') | KeyCode::End => editor.navigate_line_end(),
-                    KeyCode::Char('g') => editor.navigate_top(),
-                    KeyCode::Char('G') => editor.navigate_bottom(),
-                    _ => {}
-                },
-                EditorMode::Insert => {
-                    match key.code {
-                        KeyCode::Esc => editor.set_mode(EditorMode::Normal),
-                        KeyCode::Char(c) => {
-                            if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                if c == 's' {
-                                    // Save shortcut (no-op in demo)
-                                }
-                            } else {
-                                editor.insert_char(c);
-                            }
-                        }
-                        KeyCode::Enter => editor.insert_char('\n'),
-                        KeyCode::Backspace => editor.delete_char(),
-                        KeyCode::Delete => editor.delete_char_forward(),
-                        KeyCode::Left => editor.navigate_left(),
-                        KeyCode::Right => editor.navigate_right(),
-                        KeyCode::Up => editor.navigate_up(),
-                        KeyCode::Down => editor.navigate_down(),
-                        KeyCode::Home => editor.navigate_line_start(),
-                        KeyCode::End => editor.navigate_line_end(),
-                        _ => {}
-                    }
-                }
-                EditorMode::Visual => match key.code {
-                    KeyCode::Esc => editor.set_mode(EditorMode::Normal),
-                    KeyCode::Char('h') | KeyCode::Left => editor.navigate_left(),
-                    KeyCode::Char('j') | KeyCode::Down => editor.navigate_down(),
-                    KeyCode::Char('k') | KeyCode::Up => editor.navigate_up(),
-                    KeyCode::Char('l') | KeyCode::Right => editor.navigate_right(),
-                    _ => {}
-                },
+        let event = event::read()?;
+        if let Event::Key(key) = &event {
+            if editor.mode == EditorMode::Normal && key.code == KeyCode::Char('q') {
+                break;
             }
         }
+        if let Some(input) = from_crossterm(event) {
+            editor.handle_input(&input);
+        }
     }
 
     Ok(())
@@ -139,7 +99,7 @@ fn run_app(
 fn render_help_bar(f: &mut ratatui::Frame, area: Rect, editor: &EditorState) {
     let help_text = match editor.mode {
         EditorMode::Normal => {
-            "NORMAL | q: quit | i: insert | v: visual | hjkl/arrows: move | 0/$: line start/end | g/G: top/bottom"
+            "NORMAL | q: quit | i/a/o: insert | v: visual | hjkl/w/b/e: move | 0/$: line start/end | g/G: top/bottom | x: delete | u: undo"
         }
         EditorMode::Insert => {
             "INSERT | ESC: normal mode | Type to insert | Enter: new line | Backspace/Delete: delete"
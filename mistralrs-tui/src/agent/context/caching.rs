@@ -0,0 +1,214 @@
+//! Caching decorator for context gatherers
+//!
+//! Gatherers that hit the filesystem or network repeat the same work whenever
+//! the agent asks about the same query. `CachingGatherer` wraps any
+//! [`ContextGatherer`] and memoizes its output so existing gatherers gain
+//! caching without modification.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::gatherer::{ContextChunk, ContextGatherer, ContextPriority};
+
+/// Cached output of a single `gather` call.
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    chunks: Vec<ContextChunk>,
+    token_count: usize,
+    stored_at: Instant,
+}
+
+/// Context gatherer that caches the output of an inner gatherer.
+///
+/// Results are keyed by query (and optionally by iteration) and reused while
+/// they are younger than the TTL and fit within the requested token budget.
+/// Failed gathers are never cached.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mistralrs_tui::agent::context::{CachingGatherer, CompositeContextGatherer};
+/// use std::time::Duration;
+///
+/// let mut composite = CompositeContextGatherer::new(2048);
+/// // let cached = CachingGatherer::new(Box::new(docs_gatherer))
+/// //     .with_ttl(Duration::from_secs(60));
+/// // composite.add_gatherer(Box::new(cached));
+/// ```
+pub struct CachingGatherer {
+    inner: Box<dyn ContextGatherer>,
+    ttl: Option<Duration>,
+    per_iteration: bool,
+    cache: Mutex<HashMap<(String, Option<usize>), CacheEntry>>,
+}
+
+impl CachingGatherer {
+    /// Wrap a gatherer, caching its output by query with no expiry.
+    pub fn new(inner: Box<dyn ContextGatherer>) -> Self {
+        Self {
+            inner,
+            ttl: None,
+            per_iteration: false,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Expire cached results after `ttl`.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Key the cache by `(query, iteration)` instead of the query alone.
+    pub fn per_iteration(mut self, per_iteration: bool) -> Self {
+        self.per_iteration = per_iteration;
+        self
+    }
+
+    /// Drop all cached results.
+    pub fn clear(&self) {
+        self.cache.lock().clear();
+    }
+
+    /// Number of cached results.
+    pub fn cached_count(&self) -> usize {
+        self.cache.lock().len()
+    }
+
+    fn key(&self, query: &str, iteration: usize) -> (String, Option<usize>) {
+        (query.to_string(), self.per_iteration.then_some(iteration))
+    }
+
+    fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        self.ttl.is_none_or(|ttl| entry.stored_at.elapsed() < ttl)
+    }
+}
+
+#[async_trait]
+impl ContextGatherer for CachingGatherer {
+    async fn gather(
+        &self,
+        query: &str,
+        iteration: usize,
+        token_budget: usize,
+    ) -> Result<Vec<ContextChunk>> {
+        let key = self.key(query, iteration);
+
+        {
+            let mut cache = self.cache.lock();
+            match cache.get(&key) {
+                Some(entry) if self.is_fresh(entry) && entry.token_count <= token_budget => {
+                    tracing::debug!("Context cache hit for gatherer '{}'", self.inner.name());
+                    return Ok(entry.chunks.clone());
+                }
+                Some(entry) if !self.is_fresh(entry) => {
+                    cache.remove(&key);
+                }
+                _ => {}
+            }
+        }
+
+        let chunks = self.inner.gather(query, iteration, token_budget).await?;
+        let token_count = chunks.iter().map(|c| c.token_count).sum();
+        self.cache.lock().insert(
+            key,
+            CacheEntry {
+                chunks: chunks.clone(),
+                token_count,
+                stored_at: Instant::now(),
+            },
+        );
+
+        Ok(chunks)
+    }
+
+    fn priority(&self) -> ContextPriority {
+        self.inner.priority()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountingGatherer {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ContextGatherer for CountingGatherer {
+        async fn gather(
+            &self,
+            query: &str,
+            _iteration: usize,
+            _token_budget: usize,
+        ) -> Result<Vec<ContextChunk>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![ContextChunk::new(
+                format!("docs for {}", query),
+                "docs".to_string(),
+                ContextPriority::Medium,
+            )])
+        }
+
+        fn priority(&self) -> ContextPriority {
+            ContextPriority::Medium
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+    }
+
+    fn counting() -> (Arc<AtomicUsize>, CachingGatherer) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let gatherer = CachingGatherer::new(Box::new(CountingGatherer {
+            calls: Arc::clone(&calls),
+        }));
+        (calls, gatherer)
+    }
+
+    #[tokio::test]
+    async fn test_caching_gatherer_runs_inner_once() {
+        let (calls, gatherer) = counting();
+
+        for iteration in 0..3 {
+            let chunks = gatherer.gather("rust", iteration, 1000).await.unwrap();
+            assert_eq!(chunks[0].content, "docs for rust");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        gatherer.gather("python", 0, 1000).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(gatherer.cached_count(), 2);
+
+        assert_eq!(gatherer.name(), "counting");
+        assert_eq!(gatherer.priority(), ContextPriority::Medium);
+    }
+
+    #[tokio::test]
+    async fn test_caching_gatherer_ttl_and_iteration_keys() {
+        let (calls, gatherer) = counting();
+        let gatherer = gatherer.per_iteration(true).with_ttl(Duration::ZERO);
+
+        gatherer.gather("rust", 0, 1000).await.unwrap();
+        gatherer.gather("rust", 0, 1000).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let (calls, gatherer) = counting();
+        let gatherer = gatherer.per_iteration(true);
+        gatherer.gather("rust", 0, 1000).await.unwrap();
+        gatherer.gather("rust", 1, 1000).await.unwrap();
+        gatherer.gather("rust", 1, 1000).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -8,6 +8,12 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Metadata key holding a chunk's timestamp, used to prefer recent content.
+///
+/// Values may be Unix seconds (`"1700000000"`) or RFC 3339 (`"2024-01-04T12:00:00Z"`).
+pub const TIMESTAMP_METADATA_KEY: &str = "timestamp";
 
 /// Trait for context providers that gather relevant information for agent reasoning.
 ///
@@ -111,30 +117,90 @@ impl ContextChunk {
     pub fn add_metadata(&mut self, key: String, value: String) {
         self.metadata.insert(key, value);
     }
+
+    /// Parse the `timestamp` metadata entry as Unix seconds, if present and valid.
+    pub fn timestamp(&self) -> Option<i64> {
+        let value = self.metadata.get(TIMESTAMP_METADATA_KEY)?;
+        value.parse::<i64>().ok().or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|t| t.timestamp())
+        })
+    }
 }
 
 /// Priority levels for context chunks.
 ///
 /// Higher numeric values indicate higher priority. When token budget is limited,
-/// higher priority chunks are included first.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// higher priority chunks are included first. Besides the named levels,
+/// `Custom` carries an arbitrary value for finer-grained ordering; priorities
+/// compare, hash and test equal by [`value`](Self::value), so `Custom(75)`
+/// is equivalent to `High`.
+#[derive(Debug, Clone, Copy)]
 pub enum ContextPriority {
     /// Must be included (system prompts, critical instructions)
-    Critical = 100,
+    Critical,
     /// Important context (recent conversation, tool results)
-    High = 75,
+    High,
     /// Useful but not essential (general documentation)
-    Medium = 50,
+    Medium,
     /// Nice to have (examples, suggestions)
-    Low = 25,
+    Low,
     /// Only include if budget allows (tangential information)
-    Optional = 0,
+    Optional,
+    /// Arbitrary priority value (e.g. 60 to sit between `Medium` and `High`)
+    Custom(i32),
 }
 
 impl ContextPriority {
     /// Get numeric value for priority.
     pub fn value(&self) -> i32 {
-        *self as i32
+        match self {
+            Self::Critical => 100,
+            Self::High => 75,
+            Self::Medium => 50,
+            Self::Low => 25,
+            Self::Optional => 0,
+            Self::Custom(value) => *value,
+        }
+    }
+
+    /// Create a priority from a numeric value, using the named level if one matches.
+    pub fn from_value(value: i32) -> Self {
+        match value {
+            100 => Self::Critical,
+            75 => Self::High,
+            50 => Self::Medium,
+            25 => Self::Low,
+            0 => Self::Optional,
+            other => Self::Custom(other),
+        }
+    }
+}
+
+impl PartialEq for ContextPriority {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Eq for ContextPriority {}
+
+impl PartialOrd for ContextPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ContextPriority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value().cmp(&other.value())
+    }
+}
+
+impl std::hash::Hash for ContextPriority {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value().hash(state);
     }
 }
 
@@ -160,9 +226,15 @@ impl GatheredContext {
     }
 
     /// Create from a vector of chunks.
+    ///
+    /// Chunks are sorted by priority (highest first); ties are broken by
+    /// `timestamp` metadata, newest first, with untimestamped chunks last.
     pub fn from_chunks(mut chunks: Vec<ContextChunk>) -> Self {
-        // Sort by priority (highest first)
-        chunks.sort_by(|a, b| b.priority.cmp(&a.priority));
+        chunks.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| b.timestamp().cmp(&a.timestamp()))
+        });
 
         let total_tokens = chunks.iter().map(|c| c.token_count).sum();
         let sources = chunks
@@ -179,6 +251,27 @@ impl GatheredContext {
         }
     }
 
+    /// Create from a vector of chunks, keeping only what fits in `token_budget`.
+    ///
+    /// Chunks are ordered as in [`from_chunks`](Self::from_chunks) and kept in
+    /// that order; a chunk that does not fit in the remaining budget is dropped.
+    pub fn from_chunks_with_budget(chunks: Vec<ContextChunk>, token_budget: usize) -> Self {
+        let sorted = Self::from_chunks(chunks);
+        let mut remaining = token_budget;
+        let kept = sorted
+            .chunks
+            .into_iter()
+            .filter(|chunk| {
+                let fits = chunk.token_count <= remaining;
+                if fits {
+                    remaining -= chunk.token_count;
+                }
+                fits
+            })
+            .collect();
+        Self::from_chunks(kept)
+    }
+
     /// Combine all chunks into a single formatted string.
     pub fn format(&self) -> String {
         self.chunks
@@ -197,7 +290,9 @@ impl GatheredContext {
 /// Combines multiple context gatherers with priority-based token budget allocation.
 ///
 /// This composite gatherer invokes multiple gatherers in priority order,
-/// allocating the token budget fairly while respecting priorities.
+/// allocating the token budget fairly while respecting priorities. Optional
+/// per-gatherer caps limit how much of the budget a single gatherer may use,
+/// so a high-priority source cannot starve the others.
 ///
 /// # Examples
 ///
@@ -211,6 +306,8 @@ impl GatheredContext {
 pub struct CompositeContextGatherer {
     gatherers: Vec<Box<dyn ContextGatherer>>,
     token_budget: usize,
+    /// Maximum fraction of `token_budget` each named gatherer may use
+    budget_caps: HashMap<String, f64>,
 }
 
 impl CompositeContextGatherer {
@@ -223,6 +320,42 @@ impl CompositeContextGatherer {
         Self {
             gatherers: Vec::new(),
             token_budget,
+            budget_caps: HashMap::new(),
+        }
+    }
+
+    /// Cap the gatherer named `name` to a fraction of the total token budget.
+    ///
+    /// `fraction` is clamped to `0.0..=1.0`; e.g. `0.4` lets the gatherer use
+    /// at most 40% of the budget. The cap never raises a gatherer above the
+    /// budget still remaining when it is invoked.
+    pub fn set_budget_cap(&mut self, name: impl Into<String>, fraction: f64) {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        self.budget_caps.insert(name.into(), fraction);
+    }
+
+    /// Remove the budget cap for the gatherer named `name`.
+    pub fn clear_budget_cap(&mut self, name: &str) {
+        self.budget_caps.remove(name);
+    }
+
+    /// Get the budget cap fraction for the gatherer named `name`, if any.
+    pub fn budget_cap(&self, name: &str) -> Option<f64> {
+        self.budget_caps.get(name).copied()
+    }
+
+    /// Budget to offer `gatherer`: the remaining budget, limited by its cap.
+    fn gatherer_budget(&self, gatherer: &dyn ContextGatherer, remaining_budget: usize) -> usize {
+        match self.budget_caps.get(gatherer.name()) {
+            Some(fraction) => {
+                let cap = (self.token_budget as f64 * fraction).floor() as usize;
+                remaining_budget.min(cap)
+            }
+            None => remaining_budget,
         }
     }
 
@@ -242,8 +375,8 @@ impl CompositeContextGatherer {
     ///
     /// 1. Sort gatherers by priority (highest first)
     /// 2. For each gatherer:
-    ///    a. Calculate remaining budget
-    ///    b. Call gather() with remaining budget
+    ///    a. Calculate remaining budget, limited by the gatherer's cap
+    ///    b. Call gather() with that budget
     ///    c. Add chunks to result
     ///    d. Subtract tokens from remaining budget
     ///    e. Stop if budget exhausted
@@ -258,11 +391,60 @@ impl CompositeContextGatherer {
     ///
     /// Aggregated context from all gatherers, respecting the token budget.
     pub async fn gather_all(&self, query: &str, iteration: usize) -> Result<GatheredContext> {
-        if self.gatherers.is_empty() {
-            return Ok(GatheredContext::empty());
-        }
+        let mut all_chunks = Vec::new();
+        self.gather_into(query, iteration, &mut all_chunks).await;
 
+        Ok(GatheredContext::from_chunks_with_budget(
+            all_chunks,
+            self.token_budget,
+        ))
+    }
+
+    /// Gather context from all registered gatherers within a global deadline.
+    ///
+    /// Behaves like [`gather_all`](Self::gather_all), but stops invoking
+    /// gatherers once `deadline` has elapsed. Chunks collected before the
+    /// deadline are still merged and budget-checked, so hitting the deadline
+    /// yields partial context rather than an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The user's query
+    /// * `iteration` - Current iteration number
+    /// * `deadline` - Maximum total time to spend gathering
+    pub async fn gather_all_within(
+        &self,
+        query: &str,
+        iteration: usize,
+        deadline: Duration,
+    ) -> Result<GatheredContext> {
         let mut all_chunks = Vec::new();
+
+        if tokio::time::timeout(
+            deadline,
+            self.gather_into(query, iteration, &mut all_chunks),
+        )
+        .await
+        .is_err()
+        {
+            tracing::warn!(
+                "Context gathering hit {:?} deadline; using {} chunks gathered so far",
+                deadline,
+                all_chunks.len()
+            );
+        }
+
+        Ok(GatheredContext::from_chunks_with_budget(
+            all_chunks,
+            self.token_budget,
+        ))
+    }
+
+    /// Invoke gatherers in priority order, appending their chunks to `all_chunks`.
+    ///
+    /// Chunks are appended as each gatherer completes, so a caller that stops
+    /// awaiting early keeps everything gathered up to that point.
+    async fn gather_into(&self, query: &str, iteration: usize, all_chunks: &mut Vec<ContextChunk>) {
         let mut remaining_budget = self.token_budget;
 
         for gatherer in &self.gatherers {
@@ -274,13 +456,22 @@ impl CompositeContextGatherer {
                 break;
             }
 
+            let budget = self.gatherer_budget(gatherer.as_ref(), remaining_budget);
+            if budget == 0 {
+                tracing::debug!(
+                    "Gatherer '{}' capped at 0 tokens, skipping",
+                    gatherer.name()
+                );
+                continue;
+            }
+
             tracing::debug!(
                 "Invoking gatherer '{}' with budget {} tokens",
                 gatherer.name(),
-                remaining_budget
+                budget
             );
 
-            match gatherer.gather(query, iteration, remaining_budget).await {
+            match gatherer.gather(query, iteration, budget).await {
                 Ok(chunks) => {
                     let tokens_used: usize = chunks.iter().map(|c| c.token_count).sum();
 
@@ -302,8 +493,6 @@ impl CompositeContextGatherer {
                 }
             }
         }
-
-        Ok(GatheredContext::from_chunks(all_chunks))
     }
 
     /// Get the total token budget.
@@ -455,6 +644,42 @@ mod tests {
         assert!(ContextPriority::Low > ContextPriority::Optional);
     }
 
+    #[test]
+    fn test_custom_priority_ordering() {
+        assert_eq!(ContextPriority::Custom(60).value(), 60);
+        assert_eq!(ContextPriority::Custom(75), ContextPriority::High);
+        assert_eq!(ContextPriority::from_value(50), ContextPriority::Medium);
+        assert!(matches!(
+            ContextPriority::from_value(60),
+            ContextPriority::Custom(60)
+        ));
+
+        let chunk = |content: &str, priority| {
+            ContextChunk::new(content.to_string(), "src".to_string(), priority)
+        };
+        let ctx = GatheredContext::from_chunks(vec![
+            chunk("medium", ContextPriority::Medium),
+            chunk("sixty", ContextPriority::Custom(60)),
+            chunk("critical", ContextPriority::Critical),
+            chunk("above-critical", ContextPriority::Custom(150)),
+            chunk("negative", ContextPriority::Custom(-10)),
+            chunk("high", ContextPriority::High),
+        ]);
+
+        let contents: Vec<_> = ctx.chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec![
+                "above-critical",
+                "critical",
+                "high",
+                "sixty",
+                "medium",
+                "negative"
+            ]
+        );
+    }
+
     #[test]
     fn test_gathered_context_empty() {
         let ctx = GatheredContext::empty();
@@ -497,6 +722,42 @@ mod tests {
         assert!(ctx.sources.contains(&"source2".to_string()));
     }
 
+    #[test]
+    fn test_gathered_context_prefers_recent_chunks() {
+        let timestamped = |content: &str, timestamp: &str| {
+            let mut chunk = ContextChunk::new(
+                content.to_string(),
+                "history".to_string(),
+                ContextPriority::High,
+            );
+            chunk.add_metadata(TIMESTAMP_METADATA_KEY.to_string(), timestamp.to_string());
+            chunk
+        };
+
+        // Each chunk is 4 characters = 1 token
+        let chunks = vec![
+            timestamped("old1", "1700000000"),
+            ContextChunk::new(
+                "none".to_string(),
+                "history".to_string(),
+                ContextPriority::High,
+            ),
+            timestamped("new1", "2024-01-04T12:00:00Z"),
+            timestamped("mid1", "1700000500"),
+            ContextChunk::new(
+                "crit".to_string(),
+                "system".to_string(),
+                ContextPriority::Critical,
+            ),
+        ];
+
+        let ctx = GatheredContext::from_chunks_with_budget(chunks, 3);
+
+        let contents: Vec<_> = ctx.chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["crit", "new1", "mid1"]);
+        assert_eq!(ctx.total_tokens, 3);
+    }
+
     #[test]
     fn test_gathered_context_format() {
         let chunks = vec![
@@ -636,6 +897,123 @@ mod tests {
         assert_eq!(ctx.total_tokens, 50); // Exactly at budget
     }
 
+    #[tokio::test]
+    async fn test_composite_gatherer_budget_cap() {
+        let mut gatherer = CompositeContextGatherer::new(100);
+
+        // 10 tokens per chunk; "history" alone could fill the whole budget
+        let chunks = |name: &str, letter: &str, priority: ContextPriority| {
+            (0..10)
+                .map(|_| ContextChunk::new(letter.repeat(40), name.to_string(), priority))
+                .collect::<Vec<_>>()
+        };
+        gatherer.add_gatherer(Box::new(
+            MockGatherer::new("history", ContextPriority::Critical).with_chunks(chunks(
+                "history",
+                "h",
+                ContextPriority::Critical,
+            )),
+        ));
+        gatherer.add_gatherer(Box::new(
+            MockGatherer::new("docs", ContextPriority::Low).with_chunks(chunks(
+                "docs",
+                "d",
+                ContextPriority::Low,
+            )),
+        ));
+
+        // Uncapped, the first gatherer starves the second
+        let ctx = gatherer.gather_all("test", 0).await.unwrap();
+        assert_eq!(ctx.from_source("history").len(), 10);
+        assert!(ctx.from_source("docs").is_empty());
+
+        gatherer.set_budget_cap("history", 0.4);
+        assert_eq!(gatherer.budget_cap("history"), Some(0.4));
+
+        let ctx = gatherer.gather_all("test", 0).await.unwrap();
+        assert_eq!(ctx.from_source("history").len(), 4);
+        assert_eq!(ctx.from_source("docs").len(), 6);
+        assert_eq!(ctx.total_tokens, 100);
+
+        // A cap never exceeds what remains of the overall budget
+        gatherer.clear_budget_cap("history");
+        gatherer.set_budget_cap("docs", 1.0);
+        let ctx = gatherer.gather_all("test", 0).await.unwrap();
+        assert!(ctx.from_source("docs").is_empty());
+        assert_eq!(ctx.total_tokens, 100);
+    }
+
+    struct SlowGatherer {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl ContextGatherer for SlowGatherer {
+        async fn gather(
+            &self,
+            _query: &str,
+            _iteration: usize,
+            _token_budget: usize,
+        ) -> Result<Vec<ContextChunk>> {
+            tokio::time::sleep(self.delay).await;
+            Ok(vec![ContextChunk::new(
+                "slow".to_string(),
+                "slow".to_string(),
+                ContextPriority::Low,
+            )])
+        }
+
+        fn priority(&self) -> ContextPriority {
+            ContextPriority::Low
+        }
+
+        fn name(&self) -> &str {
+            "slow"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_composite_gatherer_within_deadline() {
+        let mut gatherer = CompositeContextGatherer::new(100);
+
+        for (name, priority) in [
+            ("fast1", ContextPriority::High),
+            ("fast2", ContextPriority::Medium),
+        ] {
+            gatherer.add_gatherer(Box::new(MockGatherer::new(name, priority).with_chunks(
+                vec![ContextChunk::new(
+                    name.to_string(),
+                    name.to_string(),
+                    priority,
+                )],
+            )));
+        }
+        gatherer.add_gatherer(Box::new(SlowGatherer {
+            delay: Duration::from_secs(30),
+        }));
+
+        let ctx = gatherer
+            .gather_all_within("test", 0, Duration::from_millis(50))
+            .await
+            .unwrap();
+
+        assert_eq!(ctx.chunks.len(), 2);
+        assert_eq!(ctx.chunks[0].content, "fast1");
+        assert_eq!(ctx.chunks[1].content, "fast2");
+        assert!(ctx.from_source("slow").is_empty());
+
+        // With enough time every gatherer contributes
+        let mut quick = CompositeContextGatherer::new(100);
+        quick.add_gatherer(Box::new(SlowGatherer {
+            delay: Duration::from_millis(1),
+        }));
+        let ctx = quick
+            .gather_all_within("test", 0, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(ctx.chunks.len(), 1);
+    }
+
     #[test]
     fn test_composite_gatherer_methods() {
         let mut gatherer = CompositeContextGatherer::new(100);
@@ -10,6 +10,9 @@
 //!
 //! - `ContextGatherer` trait: Interface for context providers
 //! - `CompositeContextGatherer`: Combines multiple gatherers with budget management
+//! - `CachingGatherer`: Decorator that memoizes any gatherer's output
+//! - `ToolResultGatherer`: Feeds recent tool observations back as context
+//! - `SummarizingGatherer`: Decorator that condenses over-budget output
 //! - `ContextChunk`: Individual piece of context with metadata
 //! - `GatheredContext`: Aggregated result from multiple gatherers
 //!
@@ -33,9 +36,15 @@
 //! # }
 //! ```
 
+pub mod caching;
 pub mod gatherer;
+pub mod summarizing;
+pub mod tool_results;
 
+pub use caching::CachingGatherer;
 pub use gatherer::{
     estimate_tokens, CompositeContextGatherer, ContextChunk, ContextGatherer, ContextPriority,
-    GatheredContext,
+    GatheredContext, TIMESTAMP_METADATA_KEY,
 };
+pub use summarizing::{HeadTailSummary, SummarizingGatherer, SummaryStrategy};
+pub use tool_results::{ToolResultGatherer, TOOL_NAME_METADATA_KEY};
@@ -0,0 +1,288 @@
+//! Summarizing decorator for context gatherers
+//!
+//! When a gatherer produces more content than the remaining budget allows,
+//! dropping or hard-truncating chunks loses information and can cut words in
+//! half. `SummarizingGatherer` wraps any [`ContextGatherer`] and condenses
+//! over-budget chunks with a pluggable [`SummaryStrategy`] so they fit.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::gatherer::{estimate_tokens, ContextChunk, ContextGatherer, ContextPriority};
+
+/// Marker inserted where a summary omits content.
+const OMISSION: &str = " ... ";
+
+/// Strategy for condensing text to fit a token budget.
+pub trait SummaryStrategy: Send + Sync {
+    /// Condense `content` to at most `max_tokens` tokens (as counted by
+    /// [`estimate_tokens`]).
+    fn summarize(&self, content: &str, max_tokens: usize) -> String;
+}
+
+/// Extractive summary keeping the head and tail of the content.
+///
+/// Whole sentences are taken alternately from the start and end until the
+/// budget is reached, with `" ... "` marking the omitted middle. If not even
+/// one sentence from each end fits, whole words are used instead, so output
+/// is never cut mid-word.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeadTailSummary;
+
+impl HeadTailSummary {
+    /// Keep units alternately from the head and tail within `max_len` bytes.
+    ///
+    /// Returns `None` if at least one unit from each end does not fit.
+    fn condense(units: &[&str], max_len: usize) -> Option<String> {
+        let mut head: Vec<&str> = Vec::new();
+        let mut tail: Vec<&str> = Vec::new();
+        let (mut i, mut j) = (0, units.len());
+        let mut used = OMISSION.len();
+
+        while i < j {
+            let from_head = head.len() <= tail.len();
+            let unit = if from_head { units[i] } else { units[j - 1] };
+            // Units are joined by a single space
+            let cost = unit.len() + 1;
+            if used + cost > max_len {
+                break;
+            }
+            used += cost;
+            if from_head {
+                head.push(unit);
+                i += 1;
+            } else {
+                tail.push(unit);
+                j -= 1;
+            }
+        }
+
+        if i >= j {
+            return Some(units.join(" "));
+        }
+        if head.is_empty() || tail.is_empty() {
+            return None;
+        }
+
+        tail.reverse();
+        Some(format!("{}{}{}", head.join(" "), OMISSION, tail.join(" ")))
+    }
+}
+
+impl SummaryStrategy for HeadTailSummary {
+    fn summarize(&self, content: &str, max_tokens: usize) -> String {
+        if estimate_tokens(content) <= max_tokens {
+            return content.to_string();
+        }
+        let max_len = max_tokens * 4;
+
+        let sentences: Vec<&str> = content
+            .split_inclusive(['.', '!', '?', '\n'])
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        if let Some(summary) = Self::condense(&sentences, max_len) {
+            return summary;
+        }
+
+        let words: Vec<&str> = content.split_whitespace().collect();
+        if let Some(summary) = Self::condense(&words, max_len) {
+            return summary;
+        }
+
+        // Too small for both ends: keep as many leading words as fit
+        let mut summary = String::new();
+        for word in words {
+            let sep = usize::from(!summary.is_empty());
+            if summary.len() + sep + word.len() > max_len {
+                break;
+            }
+            if sep == 1 {
+                summary.push(' ');
+            }
+            summary.push_str(word);
+        }
+        summary
+    }
+}
+
+/// Context gatherer that condenses an inner gatherer's output to fit the budget.
+///
+/// The inner gatherer is asked for up to `overfetch` times the budget. If its
+/// chunks exceed the budget, each chunk is summarized to a share of the budget
+/// proportional to its size, and marked with `summarized` metadata.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mistralrs_tui::agent::context::{HeadTailSummary, SummarizingGatherer};
+///
+/// // let gatherer = SummarizingGatherer::new(Box::new(docs_gatherer))
+/// //     .with_strategy(Box::new(HeadTailSummary));
+/// ```
+pub struct SummarizingGatherer {
+    inner: Box<dyn ContextGatherer>,
+    strategy: Box<dyn SummaryStrategy>,
+    overfetch: usize,
+}
+
+impl SummarizingGatherer {
+    /// Wrap a gatherer using [`HeadTailSummary`] and a 4x overfetch.
+    pub fn new(inner: Box<dyn ContextGatherer>) -> Self {
+        Self {
+            inner,
+            strategy: Box::new(HeadTailSummary),
+            overfetch: 4,
+        }
+    }
+
+    /// Use a different summarization strategy.
+    pub fn with_strategy(mut self, strategy: Box<dyn SummaryStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set how many times the budget the inner gatherer may return (minimum 1).
+    pub fn with_overfetch(mut self, factor: usize) -> Self {
+        self.overfetch = factor.max(1);
+        self
+    }
+}
+
+#[async_trait]
+impl ContextGatherer for SummarizingGatherer {
+    async fn gather(
+        &self,
+        query: &str,
+        iteration: usize,
+        token_budget: usize,
+    ) -> Result<Vec<ContextChunk>> {
+        let chunks = self
+            .inner
+            .gather(
+                query,
+                iteration,
+                token_budget.saturating_mul(self.overfetch),
+            )
+            .await?;
+
+        let total: usize = chunks.iter().map(|c| c.token_count).sum();
+        if total <= token_budget {
+            return Ok(chunks);
+        }
+
+        tracing::debug!(
+            "Summarizing {} tokens from '{}' into {} tokens",
+            total,
+            self.inner.name(),
+            token_budget
+        );
+
+        let mut remaining = token_budget;
+        let mut condensed = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            let share = chunk.token_count * token_budget / total;
+            let summary = self
+                .strategy
+                .summarize(&chunk.content, share.min(remaining));
+            let token_count = estimate_tokens(&summary);
+            if summary.is_empty() || token_count > remaining {
+                continue;
+            }
+            remaining -= token_count;
+
+            let mut metadata = chunk.metadata;
+            metadata.insert("summarized".to_string(), "true".to_string());
+            condensed.push(ContextChunk::with_metadata(
+                summary,
+                chunk.source,
+                chunk.priority,
+                metadata,
+            ));
+        }
+
+        Ok(condensed)
+    }
+
+    fn priority(&self) -> ContextPriority {
+        self.inner.priority()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct VerboseGatherer {
+        content: String,
+    }
+
+    #[async_trait]
+    impl ContextGatherer for VerboseGatherer {
+        async fn gather(
+            &self,
+            _query: &str,
+            _iteration: usize,
+            _token_budget: usize,
+        ) -> Result<Vec<ContextChunk>> {
+            Ok(vec![ContextChunk::new(
+                self.content.clone(),
+                "docs".to_string(),
+                ContextPriority::Medium,
+            )])
+        }
+
+        fn priority(&self) -> ContextPriority {
+            ContextPriority::Medium
+        }
+
+        fn name(&self) -> &str {
+            "verbose"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summarizing_gatherer_keeps_head_and_tail() {
+        let content = format!(
+            "The opening line matters. {}The closing line matters.",
+            "Filler sentence in the middle. ".repeat(50)
+        );
+        let gatherer = SummarizingGatherer::new(Box::new(VerboseGatherer { content }));
+
+        let chunks = gatherer.gather("query", 0, 30).await.unwrap();
+
+        assert_eq!(chunks.len(), 1);
+        let summary = &chunks[0].content;
+        assert!(chunks[0].token_count <= 30);
+        assert!(summary.starts_with("The opening line matters."));
+        assert!(summary.ends_with("The closing line matters."));
+        assert!(summary.contains(OMISSION));
+        assert_eq!(chunks[0].metadata.get("summarized").unwrap(), "true");
+    }
+
+    #[tokio::test]
+    async fn test_summarizing_gatherer_passes_through_small_content() {
+        let content = "Short enough.".to_string();
+        let gatherer = SummarizingGatherer::new(Box::new(VerboseGatherer {
+            content: content.clone(),
+        }));
+
+        let chunks = gatherer.gather("query", 0, 100).await.unwrap();
+        assert_eq!(chunks[0].content, content);
+        assert!(!chunks[0].metadata.contains_key("summarized"));
+    }
+
+    #[test]
+    fn test_head_tail_summary_falls_back_to_words() {
+        let content = "alpha beta gamma delta epsilon zeta eta theta iota kappa";
+        let summary = HeadTailSummary.summarize(content, 6);
+
+        assert!(estimate_tokens(&summary) <= 6);
+        assert!(summary.starts_with("alpha"));
+        assert!(summary.ends_with("kappa"));
+    }
+}
@@ -0,0 +1,168 @@
+//! Context gatherer for prior tool results
+//!
+//! Turns the ReAct engine's observation history into prioritized context
+//! chunks, so the next think phase sees recent tool output alongside other
+//! gathered context.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::gatherer::{ContextChunk, ContextGatherer, ContextPriority};
+use crate::agent::react::{Observation, ObservationKind};
+
+/// Metadata key holding the name of the tool that produced a chunk.
+pub const TOOL_NAME_METADATA_KEY: &str = "tool_name";
+
+/// Gatherer that emits the most recent tool observations as `High`-priority chunks.
+///
+/// Observations are emitted newest first; any that do not fit in the remaining
+/// token budget are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mistralrs_tui::agent::context::ToolResultGatherer;
+///
+/// // let gatherer = engine.tool_result_gatherer(5);
+/// let gatherer = ToolResultGatherer::new(Vec::new(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ToolResultGatherer {
+    /// Observations in chronological order (oldest first)
+    observations: Vec<Observation>,
+    /// Maximum number of observations to emit
+    max_observations: usize,
+}
+
+impl ToolResultGatherer {
+    /// Create a gatherer over `observations` (oldest first) emitting at most
+    /// `max_observations` of the newest.
+    pub fn new(observations: Vec<Observation>, max_observations: usize) -> Self {
+        Self {
+            observations,
+            max_observations,
+        }
+    }
+
+    /// Append a new observation to the history.
+    pub fn push(&mut self, observation: Observation) {
+        self.observations.push(observation);
+    }
+
+    fn to_chunk(observation: &Observation) -> ContextChunk {
+        let status = match observation.kind {
+            ObservationKind::Success => "success",
+            ObservationKind::Error { .. } => "error",
+            ObservationKind::Timeout => "timeout",
+            ObservationKind::PartialResult { .. } => "partial",
+            ObservationKind::Skipped => "skipped",
+        };
+
+        let mut chunk = ContextChunk::new(
+            observation.content.clone(),
+            "tool_results".to_string(),
+            ContextPriority::High,
+        );
+        chunk.add_metadata(
+            TOOL_NAME_METADATA_KEY.to_string(),
+            observation.tool_name.clone(),
+        );
+        chunk.add_metadata("status".to_string(), status.to_string());
+        chunk
+    }
+}
+
+#[async_trait]
+impl ContextGatherer for ToolResultGatherer {
+    async fn gather(
+        &self,
+        _query: &str,
+        _iteration: usize,
+        token_budget: usize,
+    ) -> Result<Vec<ContextChunk>> {
+        let mut remaining = token_budget;
+        let chunks = self
+            .observations
+            .iter()
+            .rev()
+            .take(self.max_observations)
+            .map(Self::to_chunk)
+            .filter(|chunk| {
+                let fits = chunk.token_count <= remaining;
+                if fits {
+                    remaining -= chunk.token_count;
+                }
+                fits
+            })
+            .collect();
+
+        Ok(chunks)
+    }
+
+    fn priority(&self) -> ContextPriority {
+        ContextPriority::High
+    }
+
+    fn name(&self) -> &str {
+        "tool_results"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::react::ObservationMetadata;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn observation(tool_name: &str, content: &str) -> Observation {
+        Observation {
+            tool_name: tool_name.to_string(),
+            tool_call_id: Uuid::new_v4(),
+            kind: ObservationKind::Success,
+            content: content.to_string(),
+            structured_data: None,
+            duration: Duration::from_millis(10),
+            metadata: ObservationMetadata::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_result_gatherer_newest_within_budget() {
+        // Each content is 8 characters = 2 tokens
+        let gatherer = ToolResultGatherer::new(
+            vec![
+                observation("ls", "oldest.."),
+                observation("cat", "older..."),
+                observation("grep", "newer..."),
+                observation("head", "newest.."),
+            ],
+            3,
+        );
+
+        let chunks = gatherer.gather("query", 0, 5).await.unwrap();
+
+        let contents: Vec<_> = chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["newest..", "newer..."]);
+        assert!(chunks
+            .iter()
+            .all(|c| c.priority == ContextPriority::High && c.source == "tool_results"));
+        assert_eq!(
+            chunks[0].metadata.get(TOOL_NAME_METADATA_KEY).unwrap(),
+            "head"
+        );
+        assert_eq!(chunks[1].metadata.get("status").unwrap(), "success");
+    }
+
+    #[tokio::test]
+    async fn test_tool_result_gatherer_max_observations() {
+        let mut gatherer = ToolResultGatherer::new(Vec::new(), 2);
+        for i in 0..5 {
+            gatherer.push(observation("ls", &format!("result {}", i)));
+        }
+
+        let chunks = gatherer.gather("query", 0, 1000).await.unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].content, "result 4");
+    }
+}
@@ -8,9 +8,10 @@
 
 use anyhow::{anyhow, Context, Result};
 use chrono::Utc;
+use mistralrs_agent_tools::tools::file::{format_long, format_short};
 use mistralrs_agent_tools::{
-    AgentToolkit, CatOptions, CommandOptions, GrepOptions, HeadOptions, LsOptions, ShellType,
-    SortOptions, TailOptions, UniqOptions, WcOptions,
+    AgentToolkit, CatOptions, CommandOptions, GrepOptions, HeadOptions, LsOptions, LsTimeFormat,
+    ShellType, SortOptions, TailOptions, UniqOptions, WcOptions,
 };
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
@@ -35,6 +36,9 @@ pub struct ToolExecutor {
     default_timeout: u64,
     /// Event bus for broadcasting execution events
     event_bus: Option<EventBus>,
+    /// Tools replaced in tests by a stub that succeeds once its gate is notified
+    #[cfg(test)]
+    stub_gates: std::collections::HashMap<String, std::sync::Arc<tokio::sync::Notify>>,
 }
 
 impl ToolExecutor {
@@ -44,6 +48,8 @@ impl ToolExecutor {
             toolkit,
             default_timeout: 30, // 30 seconds default
             event_bus: None,
+            #[cfg(test)]
+            stub_gates: Default::default(),
         }
     }
 
@@ -53,6 +59,8 @@ impl ToolExecutor {
             toolkit,
             default_timeout: 30,
             event_bus: Some(event_bus),
+            #[cfg(test)]
+            stub_gates: Default::default(),
         }
     }
 
@@ -62,6 +70,17 @@ impl ToolExecutor {
         self
     }
 
+    /// Replace `tool_name` with a stub that waits for `gate` to be notified
+    #[cfg(test)]
+    pub(crate) fn with_stub(
+        mut self,
+        tool_name: &str,
+        gate: std::sync::Arc<tokio::sync::Notify>,
+    ) -> Self {
+        self.stub_gates.insert(tool_name.to_string(), gate);
+        self
+    }
+
     /// Execute a tool by name with the given arguments
     ///
     /// This is the main entry point for tool execution. It:
@@ -148,6 +167,12 @@ impl ToolExecutor {
 
     /// Execute a specific tool (internal implementation)
     async fn execute_tool(&self, tool_name: &str, arguments: serde_json::Value) -> Result<String> {
+        #[cfg(test)]
+        if let Some(gate) = self.stub_gates.get(tool_name) {
+            gate.notified().await;
+            return Ok(format!("{} finished", tool_name));
+        }
+
         // Spawn blocking task since agent tools are synchronous
         let toolkit = self.toolkit.clone();
         let tool_name = tool_name.to_string();
@@ -219,14 +244,11 @@ fn execute_ls(toolkit: &AgentToolkit, args: &serde_json::Value) -> Result<String
         .ls(path, &options)
         .map_err(|e| anyhow::anyhow!("ls failed: {}", e))?;
 
-    // Format output
-    let mut output = String::new();
-    for entry in &result.entries {
-        output.push_str(&entry.name);
-        output.push('\n');
+    if options.long {
+        Ok(format_long(&result, &options))
+    } else {
+        Ok(format_short(&result, &options))
     }
-
-    Ok(output)
 }
 
 fn execute_cat(toolkit: &AgentToolkit, args: &serde_json::Value) -> Result<String> {
@@ -444,6 +466,18 @@ fn parse_ls_options(args: &serde_json::Value) -> Result<LsOptions> {
             .get("reverse")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        classify: args
+            .get("classify")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        time_format: args
+            .get("time_format")
+            .and_then(|v| v.as_str())
+            .map(LsTimeFormat::parse),
+        dereference: args
+            .get("dereference")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
     })
 }
 
@@ -461,6 +495,7 @@ fn parse_cat_options(args: &serde_json::Value) -> Result<CatOptions> {
             .get("squeeze_blank")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        ..Default::default()
     })
 }
 
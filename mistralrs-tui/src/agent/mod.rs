@@ -45,5 +45,6 @@ pub use llm_integration::{LLMIntegration, LLMToolCall};
 
 #[cfg(feature = "tui-agent")]
 pub use context::{
-    CompositeContextGatherer, ContextChunk, ContextGatherer, ContextPriority, GatheredContext,
+    CachingGatherer, CompositeContextGatherer, ContextChunk, ContextGatherer, ContextPriority,
+    GatheredContext, ToolResultGatherer,
 };
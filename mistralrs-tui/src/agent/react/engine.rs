@@ -68,18 +68,24 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use super::observation::{Observation, ObservationProcessor, ObservationSummary};
+use super::observation::{
+    render_args_compact, Observation, ObservationProcessor, ObservationSeverity,
+    ObservationSummary, ARGS_RENDER_LEN,
+};
+use super::registry::ToolRegistry;
 use super::state::{
     Action, IterationOutcome, ReActIteration, ReActPhase, ReActState, TerminationReason,
 };
 use super::thought::{PlannedAction, Thought, ThoughtParser};
 
 use crate::agent::context::gatherer::{CompositeContextGatherer, GatheredContext};
+use crate::agent::context::ToolResultGatherer;
 use crate::agent::events::{EventBus, ExecutionEvent};
 use crate::agent::execution::ToolExecutor;
 use crate::agent::llm_integration::LLMToolCall;
@@ -100,6 +106,10 @@ pub struct ReActConfig {
     /// Timeout for individual tool execution (seconds)
     pub tool_timeout_secs: u64,
 
+    /// Per-tool timeouts (seconds) overriding `tool_timeout_secs` by tool name
+    #[serde(default)]
+    pub tool_timeouts: HashMap<String, u64>,
+
     /// Maximum tokens to use for context window
     pub context_window_tokens: usize,
 
@@ -111,19 +121,116 @@ pub struct ReActConfig {
 
     /// Maximum observation tokens before truncation
     pub max_observation_tokens: usize,
+
+    /// Token budget shared by all observations of one iteration; errors get
+    /// a larger share than successful output. `None` disables it.
+    #[serde(default)]
+    pub observation_budget_tokens: Option<usize>,
+
+    /// Whether to list registered tools in the prompt (requires a tool registry)
+    #[serde(default = "default_list_tools_in_prompt")]
+    pub list_tools_in_prompt: bool,
+
+    /// Thoughts below this confidence are replanned instead of acted on.
+    /// Thoughts without a confidence score bypass the gate.
+    #[serde(default)]
+    pub min_confidence: Option<f32>,
+
+    /// Consecutive low-confidence retries before asking the user for input
+    #[serde(default = "default_confidence_retries")]
+    pub max_confidence_retries: usize,
+
+    /// Push each observation to the session context as soon as its action
+    /// completes; the observe phase then replaces them with the summary
+    #[serde(default)]
+    pub incremental_context: bool,
+
+    /// Skip the remaining actions of an iteration once one fails
+    /// non-recoverably. Forces sequential execution.
+    #[serde(default)]
+    pub stop_on_failure: bool,
+
+    /// Final answers given before this iteration (1-based) are deferred and
+    /// the model is asked to keep gathering evidence. 0 disables the check.
+    #[serde(default)]
+    pub min_iterations_before_final: usize,
+}
+
+fn default_confidence_retries() -> usize {
+    2
+}
+
+fn default_list_tools_in_prompt() -> bool {
+    true
+}
+
+impl ReActConfig {
+    /// Preset for quick, interactive tasks
+    ///
+    /// Few iterations and short timeouts, tools run in parallel, and the
+    /// iteration history is left out of the context to keep prompts small.
+    pub fn fast() -> Self {
+        Self {
+            max_iterations: 4,
+            session_timeout_secs: 60,
+            iteration_timeout_secs: 20,
+            tool_timeout_secs: 10,
+            parallel_tool_execution: true,
+            include_history_in_context: false,
+            max_observation_tokens: 250,
+            max_confidence_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Preset for long, multi-step investigations
+    ///
+    /// More iterations and longer timeouts, the iteration history is kept in
+    /// a larger context, and low-confidence thoughts are retried before the
+    /// agent acts on them.
+    pub fn thorough() -> Self {
+        Self {
+            max_iterations: 25,
+            session_timeout_secs: 900,
+            iteration_timeout_secs: 120,
+            tool_timeout_secs: 60,
+            context_window_tokens: 8192,
+            include_history_in_context: true,
+            max_observation_tokens: 1000,
+            min_confidence: Some(0.5),
+            max_confidence_retries: 3,
+            ..Default::default()
+        }
+    }
+
+    /// Timeout for a tool, using its override when one is configured
+    pub fn tool_timeout(&self, tool_name: &str) -> u64 {
+        self.tool_timeouts
+            .get(tool_name)
+            .copied()
+            .unwrap_or(self.tool_timeout_secs)
+    }
 }
 
 impl Default for ReActConfig {
     fn default() -> Self {
         Self {
             max_iterations: 10,
-            session_timeout_secs: 300,   // 5 minutes
-            iteration_timeout_secs: 60,  // 1 minute per iteration
-            tool_timeout_secs: 30,       // 30 seconds per tool
+            session_timeout_secs: 300,  // 5 minutes
+            iteration_timeout_secs: 60, // 1 minute per iteration
+            tool_timeout_secs: 30,      // 30 seconds per tool
+            tool_timeouts: HashMap::new(),
             context_window_tokens: 4096, // Standard context window
             parallel_tool_execution: true,
             include_history_in_context: true,
             max_observation_tokens: 500, // ~2000 chars per observation
+            observation_budget_tokens: None,
+            list_tools_in_prompt: default_list_tools_in_prompt(),
+            min_confidence: None,
+            max_confidence_retries: default_confidence_retries(),
+            incremental_context: false,
+            stop_on_failure: false,
+            min_iterations_before_final: 0,
         }
     }
 }
@@ -151,6 +258,61 @@ pub struct ReActResponse {
     pub session_id: Uuid,
 }
 
+impl ReActResponse {
+    /// Render the whole run as a readable transcript
+    ///
+    /// Each iteration lists its thought, actions (with compact arguments) and
+    /// observations, followed by the final answer and termination reason.
+    pub fn to_transcript(&self) -> String {
+        let mut transcript = String::new();
+
+        for iteration in &self.iterations {
+            transcript.push_str(&format!(
+                "Iteration {} — Thought: {}\n",
+                iteration.number, iteration.thought_text
+            ));
+
+            if !iteration.actions_taken.is_empty() {
+                transcript.push_str("Actions:\n");
+                for (idx, name) in iteration.actions_taken.iter().enumerate() {
+                    match iteration.action_arguments.get(idx) {
+                        Some(serde_json::Value::Null) | None => {
+                            transcript.push_str(&format!("- {}\n", name))
+                        }
+                        Some(args) => transcript.push_str(&format!(
+                            "- {} {}\n",
+                            name,
+                            render_args_compact(args, ARGS_RENDER_LEN)
+                        )),
+                    }
+                }
+            }
+
+            if !iteration.observations.is_empty() {
+                transcript.push_str("Observations:\n");
+                for observation in &iteration.observations {
+                    transcript.push_str(&format!("- {}\n", observation.replace('\n', "\n  ")));
+                }
+            }
+            transcript.push('\n');
+        }
+
+        match &self.final_answer {
+            Some(answer) => transcript.push_str(&format!("Final Answer: {}\n", answer)),
+            None => transcript.push_str("Final Answer: (none)\n"),
+        }
+        transcript.push_str(&format!(
+            "Terminated: {} after {} iteration(s), {} tool call(s) in {:.2}s\n",
+            self.terminated_reason.label(),
+            self.iterations.len(),
+            self.total_tools_executed,
+            self.total_duration.as_secs_f32()
+        ));
+
+        transcript
+    }
+}
+
 /// Main ReAct engine orchestrator
 ///
 /// Coordinates the Think-Act-Observe loop with all supporting components:
@@ -159,6 +321,7 @@ pub struct ReActResponse {
 /// - Tool execution via `ToolExecutor`
 /// - Observation processing via `ObservationProcessor`
 /// - Context gathering via `CompositeContextGatherer`
+/// - Tool validation via `ToolRegistry`
 /// - Event emission via `EventBus`
 pub struct TuiReActEngine {
     /// Tool executor for running actions
@@ -179,6 +342,23 @@ pub struct TuiReActEngine {
     /// Context gatherer (optional)
     context_gatherer: Option<CompositeContextGatherer>,
 
+    /// Registry of available tools (optional)
+    tool_registry: Option<ToolRegistry>,
+
+    /// Observations from the current session, oldest first
+    observation_history: Vec<Observation>,
+
+    /// Consecutive thoughts rejected by the confidence gate
+    confidence_retries: usize,
+
+    /// Length of the session context before this iteration's incremental
+    /// observations were pushed
+    incremental_context_start: Option<usize>,
+
+    /// Responses returned by the mock LLM before its built-in script
+    #[cfg(test)]
+    scripted_responses: Mutex<std::collections::VecDeque<String>>,
+
     /// Current session state
     state: Arc<Mutex<ReActState>>,
 
@@ -225,7 +405,8 @@ impl TuiReActEngine {
             Duration::from_secs(config.session_timeout_secs),
         );
 
-        let observation_processor = ObservationProcessor::new(config.max_observation_tokens);
+        let observation_processor = ObservationProcessor::new(config.max_observation_tokens)
+            .with_combined_budget(config.observation_budget_tokens);
 
         Self {
             tool_executor,
@@ -234,6 +415,12 @@ impl TuiReActEngine {
             thought_parser: ThoughtParser::new(),
             observation_processor,
             context_gatherer: None,
+            tool_registry: None,
+            observation_history: Vec::new(),
+            confidence_retries: 0,
+            incremental_context_start: None,
+            #[cfg(test)]
+            scripted_responses: Mutex::new(std::collections::VecDeque::new()),
             state: Arc::new(Mutex::new(state)),
             user_query: String::new(),
             cancelled: Arc::new(Mutex::new(false)),
@@ -253,6 +440,29 @@ impl TuiReActEngine {
         self
     }
 
+    /// Replace the thought parser used to read LLM responses (builder pattern)
+    ///
+    /// # Arguments
+    ///
+    /// * `parser` - Parser to use, e.g. with custom give-up phrases
+    pub fn with_thought_parser(mut self, parser: ThoughtParser) -> Self {
+        self.thought_parser = parser;
+        self
+    }
+
+    /// Add a tool registry to this engine (builder pattern)
+    ///
+    /// With a registry attached, available tools are listed in the prompt and
+    /// planned actions naming unregistered tools are rejected before execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry` - Registry of tools the engine may call
+    pub fn with_tool_registry(mut self, registry: ToolRegistry) -> Self {
+        self.tool_registry = Some(registry);
+        self
+    }
+
     /// Attach a real mistralrs [`Model`] so the engine uses live LLM inference
     /// instead of the built-in mock.  Requires the `tui-llm` Cargo feature.
     ///
@@ -325,6 +535,8 @@ impl TuiReActEngine {
             let mut state = self.state.lock().expect("Failed to lock state");
             state.reset();
         }
+        self.observation_history.clear();
+        self.confidence_retries = 0;
 
         // Reset cancellation flag
         *self.cancelled.lock().expect("Failed to lock cancelled") = false;
@@ -353,6 +565,9 @@ impl TuiReActEngine {
                     // Continue to next iteration
                     debug!("Iteration {} continuing", iteration);
                 }
+                Ok(IterationOutcome::Retry { reason }) => {
+                    debug!("Iteration {} replanning: {}", iteration, reason);
+                }
                 Ok(IterationOutcome::Complete {
                     final_answer: answer,
                 }) => {
@@ -362,6 +577,11 @@ impl TuiReActEngine {
                     termination_reason = TerminationReason::TaskComplete;
                     break;
                 }
+                Ok(IterationOutcome::Unsolved { reason }) => {
+                    info!("Agent gave up: {}", reason);
+                    termination_reason = TerminationReason::TaskUnsolved;
+                    break;
+                }
                 Ok(IterationOutcome::NeedsInput { prompt }) => {
                     // Need user input (not supported in current implementation)
                     warn!("Agent requested input: {}", prompt);
@@ -462,20 +682,68 @@ impl TuiReActEngine {
             }
         };
 
-        // Check if this is a final answer
-        if thought.is_final_answer {
+        // A give-up ends the session without counting as a solution
+        if thought.gave_up {
             let duration = iteration_start.elapsed();
-            self.complete_iteration(thought.reasoning.clone(), vec![], vec![], duration);
-            return Ok(IterationOutcome::Complete {
-                final_answer: thought.reasoning,
+            self.complete_iteration(thought.reasoning.clone(), vec![], vec![], vec![], duration);
+            return Ok(IterationOutcome::Unsolved {
+                reason: thought.reasoning,
             });
         }
 
+        // Replan low-confidence thoughts instead of acting on them
+        if let Some(outcome) = self.confidence_gate(&thought, iteration_start.elapsed()) {
+            return Ok(outcome);
+        }
+
+        // Check if this is a final answer
+        if thought.is_final_answer {
+            let iteration = self.current_iteration();
+            let min_iterations = self.config.min_iterations_before_final;
+            if iteration >= min_iterations {
+                let duration = iteration_start.elapsed();
+                self.complete_iteration(
+                    thought.reasoning.clone(),
+                    vec![],
+                    vec![],
+                    vec![],
+                    duration,
+                );
+                return Ok(IterationOutcome::Complete {
+                    final_answer: thought.reasoning,
+                });
+            }
+
+            // Too early: keep going, acting on any planned actions
+            debug!(
+                "Deferring final answer at iteration {} (minimum {})",
+                iteration, min_iterations
+            );
+            if thought.planned_actions.is_empty() {
+                let duration = iteration_start.elapsed();
+                self.complete_iteration(
+                    format!(
+                        "{} (final answer deferred: at least {} iterations required, \
+                         gather more evidence first)",
+                        thought.reasoning, min_iterations
+                    ),
+                    vec![],
+                    vec![],
+                    vec![],
+                    duration,
+                );
+                return Ok(IterationOutcome::Continue {
+                    thought_summary: thought.reasoning,
+                    action_count: 0,
+                });
+            }
+        }
+
         // Check if there are actions to execute
         if thought.planned_actions.is_empty() {
             // No actions, treat as continuation with just reasoning
             let duration = iteration_start.elapsed();
-            self.complete_iteration(thought.reasoning.clone(), vec![], vec![], duration);
+            self.complete_iteration(thought.reasoning.clone(), vec![], vec![], vec![], duration);
             return Ok(IterationOutcome::Continue {
                 thought_summary: thought.reasoning,
                 action_count: 0,
@@ -517,9 +785,16 @@ impl TuiReActEngine {
             .map(|o| o.content.clone())
             .collect();
 
+        let action_arguments: Vec<serde_json::Value> = thought
+            .planned_actions
+            .iter()
+            .map(|a| a.arguments.clone().unwrap_or(serde_json::Value::Null))
+            .collect();
+
         self.complete_iteration(
             thought.reasoning.clone(),
             action_names.clone(),
+            action_arguments,
             observation_texts,
             duration,
         );
@@ -573,12 +848,53 @@ impl TuiReActEngine {
         Ok(thought)
     }
 
+    /// Check a thought against `min_confidence`
+    ///
+    /// Returns `Retry` while retries remain and `NeedsInput` once they are
+    /// used up. Returns `None` (and resets the retry count) when the thought
+    /// passes or carries no confidence score.
+    fn confidence_gate(
+        &mut self,
+        thought: &Thought,
+        elapsed: Duration,
+    ) -> Option<IterationOutcome> {
+        let (Some(min), Some(confidence)) = (self.config.min_confidence, thought.confidence) else {
+            self.confidence_retries = 0;
+            return None;
+        };
+        if confidence >= min {
+            self.confidence_retries = 0;
+            return None;
+        }
+
+        let reason = format!("confidence {:.2} is below {:.2}", confidence, min);
+        // Keep the rejected thought in the history so the next prompt shows it
+        self.complete_iteration(
+            format!("{} (rejected: {}, replanning)", thought.reasoning, reason),
+            vec![],
+            vec![],
+            vec![],
+            elapsed,
+        );
+
+        if self.confidence_retries >= self.config.max_confidence_retries {
+            self.confidence_retries = 0;
+            return Some(IterationOutcome::NeedsInput {
+                prompt: format!("Unsure how to proceed ({}): {}", reason, thought.reasoning),
+            });
+        }
+        self.confidence_retries += 1;
+        Some(IterationOutcome::Retry { reason })
+    }
+
     /// ACT phase: Execute planned actions
     ///
     /// This phase:
-    /// 1. Converts planned actions to tool calls
-    /// 2. Executes tools (parallel or sequential based on config)
-    /// 3. Collects observations from results
+    /// 1. Turns actions naming tools missing from the registry (if any) into
+    ///    failed observations without running them
+    /// 2. Converts the remaining planned actions to tool calls
+    /// 3. Executes tools (parallel or sequential based on config)
+    /// 4. Collects observations from results, in planned order
     async fn act(&mut self, actions: Vec<PlannedAction>) -> Result<Vec<Observation>> {
         let iteration = self.current_iteration();
         self.emit_progress(
@@ -591,7 +907,13 @@ impl TuiReActEngine {
             (iteration as f64 / self.config.max_iterations as f64) * 100.0,
         );
 
-        // Convert planned actions to executable actions
+        if self.config.incremental_context {
+            let state = self.state.lock().expect("Failed to lock state");
+            self.incremental_context_start = Some(state.accumulated_context.len());
+        }
+
+        // Convert planned actions to executable actions, rejecting unknown tools
+        let mut rejected = Vec::with_capacity(actions.len());
         let mut executable_actions = Vec::new();
         for planned_action in &actions {
             let arguments = planned_action
@@ -600,29 +922,89 @@ impl TuiReActEngine {
                 .unwrap_or(serde_json::json!({}));
 
             let action = Action::new(&planned_action.tool_name, arguments);
-            executable_actions.push(action);
+            match self.unknown_tool_observation(&action) {
+                Some(observation) => {
+                    self.push_incremental_context(&observation);
+                    rejected.push(Some(observation));
+                }
+                None => {
+                    rejected.push(None);
+                    executable_actions.push(action);
+                }
+            }
         }
 
         // Execute tools
-        let observations = if self.config.parallel_tool_execution && executable_actions.len() > 1 {
+        let parallel = self.config.parallel_tool_execution && !self.config.stop_on_failure;
+        let executed = if executable_actions.is_empty() {
+            Ok(Vec::new())
+        } else if parallel && executable_actions.len() > 1 {
             // Parallel execution
-            self.execute_actions_parallel(executable_actions).await?
+            self.execute_actions_parallel(executable_actions).await
         } else {
             // Sequential execution
-            self.execute_actions_sequential(executable_actions).await?
+            self.execute_actions_sequential(executable_actions).await
+        };
+        let executed = match executed {
+            Ok(executed) => executed,
+            Err(e) => {
+                // Observe never runs for this step, so drop its partial context
+                self.discard_incremental_context();
+                return Err(e);
+            }
         };
 
+        // Put rejected actions back in their planned position
+        let mut executed = executed.into_iter();
+        let observations: Vec<Observation> = rejected
+            .into_iter()
+            .filter_map(|slot| slot.or_else(|| executed.next()))
+            .collect();
+
         debug!("Collected {} observations", observations.len());
 
         Ok(observations)
     }
 
+    /// Failed observation for an action naming a tool missing from the
+    /// registry, listing the tools that are available
+    ///
+    /// Returns `None` when no registry is configured or the tool is known.
+    fn unknown_tool_observation(&self, action: &Action) -> Option<Observation> {
+        let registry = self.tool_registry.as_ref()?;
+        if registry.contains(&action.tool_name) {
+            return None;
+        }
+
+        let available: Vec<&str> = registry.names().collect();
+        let result = crate::agent::toolkit::ToolCallResult {
+            success: false,
+            output: serde_json::Value::Null,
+            error: Some(format!(
+                "Unknown tool '{}'. Available tools: {}",
+                action.tool_name,
+                available.join(", ")
+            )),
+            duration: Duration::ZERO,
+        };
+        let tool_call = ToolCall {
+            id: Uuid::new_v4(),
+            tool_name: action.tool_name.clone(),
+            arguments: action.arguments.clone(),
+            result: Some(result.clone()),
+            timestamp: Utc::now(),
+            session_id: Some(self.session_id),
+        };
+        Some(self.observation_processor.process(&result, &tool_call))
+    }
+
     /// OBSERVE phase: Process tool results
     ///
     /// This phase:
     /// 1. Processes raw tool results into observations
     /// 2. Summarizes observations for context
-    /// 3. Adds observations to accumulated context
+    /// 3. Adds observations to accumulated context, replacing any pushed
+    ///    incrementally during the act phase
     async fn observe(&mut self, observations: Vec<Observation>) -> Result<ObservationSummary> {
         let iteration = self.current_iteration();
         self.emit_progress(
@@ -637,8 +1019,13 @@ impl TuiReActEngine {
         // Add summary to context
         {
             let mut state = self.state.lock().expect("Failed to lock state");
+            if let Some(start) = self.incremental_context_start.take() {
+                state.accumulated_context.truncate(start);
+            }
             state.add_context(summary.formatted_for_llm.clone());
         }
+        self.observation_history
+            .extend(summary.observations.iter().cloned());
 
         debug!(
             "Observation summary: {} observations, all successful: {}",
@@ -655,8 +1042,20 @@ impl TuiReActEngine {
         actions: Vec<Action>,
     ) -> Result<Vec<Observation>> {
         let mut observations = Vec::with_capacity(actions.len());
+        let mut failed_tool: Option<String> = None;
 
         for mut action in actions {
+            if let Some(failed) = &failed_tool {
+                action.mark_skipped();
+                let observation = self.observation_processor.skipped(
+                    &action.tool_name,
+                    &format!("Skipped because the earlier '{}' action failed", failed),
+                );
+                self.push_incremental_context(&observation);
+                observations.push(observation);
+                continue;
+            }
+
             action.mark_executing();
 
             let start = Instant::now();
@@ -665,7 +1064,7 @@ impl TuiReActEngine {
                 .execute(
                     &action.tool_name,
                     action.arguments.clone(),
-                    Some(self.config.tool_timeout_secs),
+                    Some(self.config.tool_timeout(&action.tool_name)),
                 )
                 .await?;
 
@@ -683,6 +1082,12 @@ impl TuiReActEngine {
 
             // Process into observation
             let observation = self.observation_processor.process(&result, &tool_call);
+            self.push_incremental_context(&observation);
+            if self.config.stop_on_failure
+                && observation.metadata.severity >= ObservationSeverity::Error
+            {
+                failed_tool = Some(action.tool_name.clone());
+            }
             observations.push(observation);
 
             // Update action status
@@ -701,16 +1106,17 @@ impl TuiReActEngine {
 
     /// Execute actions in parallel
     async fn execute_actions_parallel(&mut self, actions: Vec<Action>) -> Result<Vec<Observation>> {
-        let mut handles = Vec::new();
+        let mut tasks = tokio::task::JoinSet::new();
+        let count = actions.len();
 
         // Spawn parallel tasks
-        for action in actions {
+        for (index, action) in actions.into_iter().enumerate() {
             let executor = self.tool_executor.clone();
             let processor = self.observation_processor.clone();
-            let timeout = self.config.tool_timeout_secs;
+            let timeout = self.config.tool_timeout(&action.tool_name);
             let session_id = self.session_id;
 
-            let handle = tokio::spawn(async move {
+            tasks.spawn(async move {
                 let start = Instant::now();
                 let result = executor
                     .execute(&action.tool_name, action.arguments.clone(), Some(timeout))
@@ -734,20 +1140,40 @@ impl TuiReActEngine {
                     session_id: Some(session_id),
                 };
 
-                processor.process(&result, &tool_call)
+                (index, processor.process(&result, &tool_call))
             });
+        }
 
-            handles.push(handle);
+        // Collect results as they complete, keeping the planned order
+        let mut observations: Vec<Option<Observation>> = vec![None; count];
+        while let Some(joined) = tasks.join_next().await {
+            let (index, observation) = joined.context("Tool execution task failed")?;
+            self.push_incremental_context(&observation);
+            observations[index] = Some(observation);
         }
 
-        // Wait for all to complete
-        let mut observations = Vec::new();
-        for handle in handles {
-            let observation = handle.await.context("Tool execution task failed")?;
-            observations.push(observation);
+        Ok(observations.into_iter().flatten().collect())
+    }
+
+    /// Add a completed action's observation to the session context when
+    /// incremental context is enabled
+    fn push_incremental_context(&self, observation: &Observation) {
+        if !self.config.incremental_context {
+            return;
         }
+        let formatted = self
+            .observation_processor
+            .format_for_context(std::slice::from_ref(observation));
+        let mut state = self.state.lock().expect("Failed to lock state");
+        state.add_context(formatted);
+    }
 
-        Ok(observations)
+    /// Remove the entries pushed incrementally since the act phase started
+    fn discard_incremental_context(&mut self) {
+        if let Some(start) = self.incremental_context_start.take() {
+            let mut state = self.state.lock().expect("Failed to lock state");
+            state.accumulated_context.truncate(start);
+        }
     }
 
     /// Gather context for the current iteration
@@ -770,6 +1196,17 @@ impl TuiReActEngine {
         // System instructions
         prompt.push_str("You are a helpful AI assistant with access to tools.\n\n");
 
+        // Available tools
+        if self.config.list_tools_in_prompt {
+            if let Some(ref registry) = self.tool_registry {
+                if !registry.is_empty() {
+                    prompt.push_str("# Available Tools\n");
+                    prompt.push_str(&registry.format_for_prompt());
+                    prompt.push('\n');
+                }
+            }
+        }
+
         // Context
         if !context.chunks.is_empty() {
             prompt.push_str("# Context\n");
@@ -788,10 +1225,20 @@ impl TuiReActEngine {
                         iteration.number, iteration.thought_text
                     ));
                     if !iteration.actions_taken.is_empty() {
-                        prompt.push_str(&format!(
-                            "Actions: {}\n",
-                            iteration.actions_taken.join(", ")
-                        ));
+                        let actions: Vec<String> = iteration
+                            .actions_taken
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, name)| match iteration.action_arguments.get(idx) {
+                                Some(serde_json::Value::Null) | None => name.clone(),
+                                Some(args) => format!(
+                                    "{} {}",
+                                    name,
+                                    render_args_compact(args, ARGS_RENDER_LEN)
+                                ),
+                            })
+                            .collect();
+                        prompt.push_str(&format!("Actions: {}\n", actions.join(", ")));
                     }
                 }
                 prompt.push_str("\n");
@@ -855,6 +1302,16 @@ impl TuiReActEngine {
         // TODO: Replace with actual LLM API call
         // This is a placeholder for demonstration
 
+        #[cfg(test)]
+        if let Some(response) = self
+            .scripted_responses
+            .lock()
+            .expect("Failed to lock scripted responses")
+            .pop_front()
+        {
+            return Ok(response);
+        }
+
         let iteration = self.current_iteration();
         if iteration == 1 {
             Ok("Thought: I need to list the files to see what's available.\nAction: ls({\"path\": \".\", \"all\": false})".to_string())
@@ -880,6 +1337,17 @@ impl TuiReActEngine {
         state.phase
     }
 
+    /// Get the observations collected during the current session, oldest first
+    pub fn observation_history(&self) -> &[Observation] {
+        &self.observation_history
+    }
+
+    /// Create a gatherer that feeds the newest `max_observations` tool results
+    /// from this session back as context
+    pub fn tool_result_gatherer(&self, max_observations: usize) -> ToolResultGatherer {
+        ToolResultGatherer::new(self.observation_history.clone(), max_observations)
+    }
+
     /// Get the current iteration count
     pub fn current_iteration(&self) -> usize {
         let state = self.state.lock().expect("Failed to lock state");
@@ -905,11 +1373,18 @@ impl TuiReActEngine {
         &mut self,
         thought_text: String,
         actions_taken: Vec<String>,
+        action_arguments: Vec<serde_json::Value>,
         observations: Vec<String>,
         duration: Duration,
     ) {
         let mut state = self.state.lock().expect("Failed to lock state");
-        state.complete_iteration(thought_text, actions_taken, observations, duration);
+        state.complete_iteration(
+            thought_text,
+            actions_taken,
+            action_arguments,
+            observations,
+            duration,
+        );
     }
 
     /// Emit a progress event
@@ -949,6 +1424,7 @@ mod duration_serde {
 mod tests {
     use super::*;
     use crate::agent::execution::ToolExecutor;
+    use crate::agent::react::observation::ObservationKind;
     use mistralrs_agent_tools::AgentToolkit;
 
     #[tokio::test]
@@ -981,6 +1457,52 @@ mod tests {
         assert_eq!(state.max_iterations, 5);
     }
 
+    #[test]
+    fn test_config_presets() {
+        let default = ReActConfig::default();
+        assert_eq!(default.max_iterations, 10);
+        assert_eq!(default.tool_timeout_secs, 30);
+        assert!(default.include_history_in_context);
+        assert_eq!(default.min_confidence, None);
+
+        let fast = ReActConfig::fast();
+        let thorough = ReActConfig::thorough();
+
+        assert!(fast.max_iterations < default.max_iterations);
+        assert!(default.max_iterations < thorough.max_iterations);
+        assert!(fast.session_timeout_secs < thorough.session_timeout_secs);
+        assert!(fast.iteration_timeout_secs < thorough.iteration_timeout_secs);
+        assert!(fast.tool_timeout_secs < thorough.tool_timeout_secs);
+
+        assert!(fast.parallel_tool_execution);
+        assert!(!fast.include_history_in_context);
+        assert_eq!(fast.max_confidence_retries, 0);
+
+        assert!(thorough.include_history_in_context);
+        assert!(thorough.context_window_tokens > default.context_window_tokens);
+        assert_eq!(thorough.min_confidence, Some(0.5));
+        assert!(thorough.max_confidence_retries > default.max_confidence_retries);
+    }
+
+    #[test]
+    fn test_config_deserializes_without_newer_fields() {
+        let json = r#"{
+            "max_iterations": 7,
+            "session_timeout_secs": 300,
+            "iteration_timeout_secs": 60,
+            "tool_timeout_secs": 30,
+            "context_window_tokens": 4096,
+            "parallel_tool_execution": true,
+            "include_history_in_context": true,
+            "max_observation_tokens": 500
+        }"#;
+
+        let config: ReActConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.max_iterations, 7);
+        assert!(config.list_tools_in_prompt);
+        assert_eq!(config.max_confidence_retries, 2);
+    }
+
     #[tokio::test]
     async fn test_cancellation() {
         let toolkit = AgentToolkit::with_defaults();
@@ -996,6 +1518,381 @@ mod tests {
         assert!(engine.is_cancelled());
     }
 
+    #[tokio::test]
+    async fn test_confidence_gate_retries_low_confidence() {
+        let toolkit = AgentToolkit::with_defaults();
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+        let config = ReActConfig {
+            min_confidence: Some(0.5),
+            ..Default::default()
+        };
+        let mut engine = TuiReActEngine::with_config(executor, event_bus, config);
+        engine.scripted_responses.lock().unwrap().extend([
+            r#"{"thought": "Maybe list files?", "action": "ls", "arguments": {"path": "."}, "confidence": 0.2}"#.to_string(),
+            r#"{"thought": "List the files", "action": "ls", "arguments": {"path": "."}, "confidence": 0.9}"#.to_string(),
+        ]);
+
+        let outcome = engine.step().await.unwrap();
+        assert!(
+            matches!(&outcome, IterationOutcome::Retry { reason } if reason.contains("0.20 is below 0.50"))
+        );
+        assert_eq!(engine.confidence_retries, 1);
+
+        let outcome = engine.step().await.unwrap();
+        assert!(matches!(
+            outcome,
+            IterationOutcome::Continue {
+                action_count: 1,
+                ..
+            }
+        ));
+        assert_eq!(engine.confidence_retries, 0);
+
+        let state = engine.state.lock().unwrap();
+        assert!(state.iteration_history[0]
+            .thought_text
+            .contains("replanning"));
+        assert_eq!(state.iteration_history[1].actions_taken, vec!["ls"]);
+    }
+
+    #[tokio::test]
+    async fn test_confidence_gate_asks_for_input_after_retries() {
+        let toolkit = AgentToolkit::with_defaults();
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+        let config = ReActConfig {
+            min_confidence: Some(0.5),
+            max_confidence_retries: 1,
+            ..Default::default()
+        };
+        let mut engine = TuiReActEngine::with_config(executor, event_bus, config);
+        let unsure = r#"{"thought": "Not sure", "action": "ls", "confidence": 0.1}"#;
+        engine
+            .scripted_responses
+            .lock()
+            .unwrap()
+            .extend([unsure.to_string(), unsure.to_string()]);
+
+        assert!(matches!(
+            engine.step().await.unwrap(),
+            IterationOutcome::Retry { .. }
+        ));
+        assert!(matches!(
+            engine.step().await.unwrap(),
+            IterationOutcome::NeedsInput { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tool_keeps_session_running() {
+        let toolkit = AgentToolkit::with_defaults();
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+        let mut registry = ToolRegistry::new();
+        registry.register("ls", "List directory contents", serde_json::json!({}));
+
+        let mut engine = TuiReActEngine::new(executor, event_bus).with_tool_registry(registry);
+        engine.scripted_responses.lock().unwrap().extend([
+            r#"{"thought": "Delete it", "action": "rm", "arguments": {"path": "x"}}"#.to_string(),
+            "Final Answer: rm is not available".to_string(),
+        ]);
+
+        assert!(matches!(
+            engine.step().await.unwrap(),
+            IterationOutcome::Continue {
+                action_count: 1,
+                ..
+            }
+        ));
+        assert!(engine
+            .state
+            .lock()
+            .unwrap()
+            .accumulated_context
+            .iter()
+            .any(|entry| entry.contains("Unknown tool 'rm'. Available tools: ls")));
+        assert!(matches!(
+            engine.step().await.unwrap(),
+            IterationOutcome::Complete { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_min_iterations_before_final() {
+        let toolkit = AgentToolkit::with_defaults();
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+        let config = ReActConfig {
+            min_iterations_before_final: 3,
+            ..Default::default()
+        };
+        let mut engine = TuiReActEngine::with_config(executor, event_bus, config);
+        let done = "Final Answer: nothing to do";
+        engine.scripted_responses.lock().unwrap().extend([
+            done.to_string(),
+            done.to_string(),
+            done.to_string(),
+        ]);
+
+        for _ in 0..2 {
+            assert!(matches!(
+                engine.step().await.unwrap(),
+                IterationOutcome::Continue {
+                    action_count: 0,
+                    ..
+                }
+            ));
+        }
+        assert!(matches!(
+            engine.step().await.unwrap(),
+            IterationOutcome::Complete { .. }
+        ));
+
+        let state = engine.state.lock().unwrap();
+        assert_eq!(state.iteration_history.len(), 3);
+        assert!(state.iteration_history[0]
+            .thought_text
+            .contains("final answer deferred: at least 3 iterations required"));
+    }
+
+    #[tokio::test]
+    async fn test_incremental_context_during_act() {
+        for parallel in [false, true] {
+            let toolkit = AgentToolkit::with_defaults();
+            let event_bus = EventBus::new(100);
+            let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+            let config = ReActConfig {
+                incremental_context: true,
+                parallel_tool_execution: parallel,
+                ..Default::default()
+            };
+            let mut engine = TuiReActEngine::with_config(executor, event_bus, config);
+            engine
+                .state
+                .lock()
+                .unwrap()
+                .add_context("earlier".to_string());
+
+            let actions = vec![
+                PlannedAction::new("ls", "first").with_arguments(serde_json::json!({"path": "."})),
+                PlannedAction::new("ls", "second")
+                    .with_arguments(serde_json::json!({"path": ".", "all": true})),
+            ];
+            let observations = engine.act(actions).await.unwrap();
+
+            // One context entry per completed action, before observing
+            {
+                let state = engine.state.lock().unwrap();
+                assert_eq!(state.accumulated_context.len(), 3);
+                assert!(state.accumulated_context[1..]
+                    .iter()
+                    .all(|c| c.starts_with("Observation from ls")));
+            }
+
+            // The summary replaces the incremental entries
+            let summary = engine.observe(observations).await.unwrap();
+            let state = engine.state.lock().unwrap();
+            assert_eq!(
+                state.accumulated_context,
+                vec!["earlier".to_string(), summary.formatted_for_llm]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stop_on_failure_skips_remaining_actions() {
+        let toolkit = AgentToolkit::with_defaults();
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+        let config = ReActConfig {
+            stop_on_failure: true,
+            ..Default::default()
+        };
+        let mut engine = TuiReActEngine::with_config(executor, event_bus, config);
+
+        let actions = vec![
+            // Missing `paths` is not something a retry fixes
+            PlannedAction::new("cat", "read").with_arguments(serde_json::json!({})),
+            PlannedAction::new("ls", "list").with_arguments(serde_json::json!({"path": "."})),
+        ];
+        let observations = engine.act(actions).await.unwrap();
+
+        assert_eq!(observations.len(), 2);
+        assert!(matches!(
+            observations[0].kind,
+            ObservationKind::Error {
+                recoverable: false,
+                ..
+            }
+        ));
+        assert!(matches!(observations[1].kind, ObservationKind::Skipped));
+        assert_eq!(observations[1].tool_name, "ls");
+        assert!(observations[1].content.contains("'cat'"));
+    }
+
+    /// Run a gated `slow` stub tool and an ungated `fast` one with a one
+    /// second default timeout, releasing `slow` five (paused) seconds in
+    async fn act_with_slow_tool(
+        parallel: bool,
+        overrides: &[(&str, u64)],
+    ) -> Result<Vec<Observation>> {
+        let slow = Arc::new(tokio::sync::Notify::new());
+        let fast = Arc::new(tokio::sync::Notify::new());
+        fast.notify_one();
+
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(AgentToolkit::with_defaults(), event_bus.clone())
+            .with_stub("slow", slow.clone())
+            .with_stub("fast", fast);
+        let config = ReActConfig {
+            tool_timeout_secs: 1,
+            tool_timeouts: overrides
+                .iter()
+                .map(|(name, secs)| (name.to_string(), *secs))
+                .collect(),
+            parallel_tool_execution: parallel,
+            ..Default::default()
+        };
+        let mut engine = TuiReActEngine::with_config(executor, event_bus, config);
+
+        let actions = vec![
+            PlannedAction::new("slow", "slow build"),
+            PlannedAction::new("fast", "quick check"),
+        ];
+        let (observations, ()) = tokio::join!(engine.act(actions), async {
+            // The paused clock only jumps once every tool is waiting
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            slow.notify_one();
+        });
+        observations
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_per_tool_timeout_overrides_default() {
+        for parallel in [false, true] {
+            let observations = act_with_slow_tool(parallel, &[("slow", 10)]).await.unwrap();
+            assert!(observations
+                .iter()
+                .all(|o| matches!(o.kind, ObservationKind::Success)));
+        }
+
+        // Without the override the short default applies
+        let observations = act_with_slow_tool(true, &[("fast", 10)]).await.unwrap();
+        assert!(!matches!(observations[0].kind, ObservationKind::Success));
+        assert!(matches!(observations[1].kind, ObservationKind::Success));
+        assert!(act_with_slow_tool(false, &[]).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_act_discards_incremental_context() {
+        let slow = Arc::new(tokio::sync::Notify::new());
+        let fast = Arc::new(tokio::sync::Notify::new());
+        fast.notify_one();
+
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(AgentToolkit::with_defaults(), event_bus.clone())
+            .with_stub("slow", slow)
+            .with_stub("fast", fast);
+        let config = ReActConfig {
+            tool_timeout_secs: 1,
+            incremental_context: true,
+            parallel_tool_execution: false,
+            ..Default::default()
+        };
+        let mut engine = TuiReActEngine::with_config(executor, event_bus, config);
+        engine
+            .state
+            .lock()
+            .unwrap()
+            .add_context("earlier".to_string());
+
+        // `fast` pushes an entry before `slow` times out and fails the phase
+        let actions = vec![
+            PlannedAction::new("fast", "quick check"),
+            PlannedAction::new("slow", "slow build"),
+        ];
+        assert!(engine.act(actions).await.is_err());
+
+        assert!(engine.incremental_context_start.is_none());
+        assert_eq!(
+            engine.state.lock().unwrap().accumulated_context,
+            vec!["earlier".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_renders_action_arguments() {
+        let toolkit = AgentToolkit::with_defaults();
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+        let mut engine = TuiReActEngine::new(executor, event_bus);
+        engine.scripted_responses.lock().unwrap().push_back(
+            r#"{"thought": "List the files", "action": "ls", "arguments": {"path": ".", "all": true}}"#
+                .to_string(),
+        );
+        engine.step().await.unwrap();
+
+        let prompt = engine.build_prompt(&GatheredContext::empty()).unwrap();
+        assert!(prompt.contains("Actions: ls {all: true, path: \".\"}\n"));
+    }
+
+    #[test]
+    fn test_response_transcript() {
+        let iteration =
+            |number: usize, thought: &str, actions: Vec<&str>, observations: Vec<&str>| {
+                ReActIteration {
+                    number,
+                    thought_text: thought.to_string(),
+                    actions_taken: actions.into_iter().map(String::from).collect(),
+                    action_arguments: vec![serde_json::json!({"path": "src"})],
+                    observations: observations.into_iter().map(String::from).collect(),
+                    duration: Duration::from_millis(20),
+                }
+            };
+        let mut response = ReActResponse {
+            final_answer: Some("There are two files".to_string()),
+            iterations: vec![
+                iteration(
+                    1,
+                    "List the source directory",
+                    vec!["ls"],
+                    vec!["a.rs\nb.rs"],
+                ),
+                iteration(2, "I know the answer", vec![], vec![]),
+            ],
+            terminated_reason: TerminationReason::TaskComplete,
+            total_duration: Duration::from_millis(1500),
+            total_tools_executed: 1,
+            session_id: Uuid::new_v4(),
+        };
+
+        let transcript = response.to_transcript();
+        assert!(transcript.starts_with(
+            "Iteration 1 — Thought: List the source directory\n\
+             Actions:\n- ls {path: \"src\"}\n\
+             Observations:\n- a.rs\n  b.rs\n\n"
+        ));
+        assert!(transcript.contains("Iteration 2 — Thought: I know the answer\n\n"));
+        assert!(transcript.ends_with(
+            "Final Answer: There are two files\n\
+             Terminated: task complete after 2 iteration(s), 1 tool call(s) in 1.50s\n"
+        ));
+
+        response.final_answer = None;
+        response.terminated_reason = TerminationReason::MaxIterationsReached;
+        let transcript = response.to_transcript();
+        assert!(transcript.contains("Final Answer: (none)\n"));
+        assert!(transcript.contains("Terminated: maximum iterations reached"));
+    }
+
     #[tokio::test]
     async fn test_build_prompt() {
         let toolkit = AgentToolkit::with_defaults();
@@ -1010,5 +1907,39 @@ mod tests {
 
         assert!(prompt.contains("What is the weather?"));
         assert!(prompt.contains("User Query"));
+        assert!(!prompt.contains("Available Tools"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_registry_in_prompt_and_validation() {
+        let toolkit = AgentToolkit::with_defaults();
+        let event_bus = EventBus::new(100);
+        let executor = ToolExecutor::with_events(toolkit, event_bus.clone());
+
+        let mut registry = ToolRegistry::new();
+        registry.register("ls", "List directory contents", serde_json::json!({}));
+        registry.register("cat", "Print file contents", serde_json::json!({}));
+
+        let mut engine = TuiReActEngine::new(executor, event_bus).with_tool_registry(registry);
+        engine.user_query = "Show me the files".to_string();
+
+        let prompt = engine.build_prompt(&GatheredContext::empty()).unwrap();
+        assert!(prompt.contains("# Available Tools"));
+        assert!(prompt.contains("- ls: List directory contents"));
+        assert!(prompt.contains("- cat: Print file contents"));
+
+        let observations = engine
+            .act(vec![PlannedAction::new("rm", "delete everything")])
+            .await
+            .unwrap();
+        assert_eq!(observations.len(), 1);
+        assert_eq!(observations[0].tool_name, "rm");
+        assert!(matches!(
+            observations[0].kind,
+            ObservationKind::Error { .. }
+        ));
+        assert!(observations[0]
+            .content
+            .contains("Unknown tool 'rm'. Available tools: ls, cat"));
     }
 }
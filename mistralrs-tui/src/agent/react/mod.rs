@@ -6,15 +6,21 @@
 #[cfg(feature = "tui-agent")]
 pub mod engine;
 pub mod observation;
+#[cfg(feature = "tui-agent")]
+pub mod registry;
 pub mod state;
 pub mod thought;
 
 #[cfg(feature = "tui-agent")]
 pub use engine::{ReActConfig, ReActResponse, TuiReActEngine};
 
+#[cfg(feature = "tui-agent")]
+pub use registry::ToolRegistry;
+
 pub use observation::{
-    Observation, ObservationKind, ObservationMetadata, ObservationProcessor, ObservationSummary,
-    TruncationStrategy,
+    render_args_compact, Observation, ObservationKind, ObservationMetadata, ObservationProcessor,
+    ObservationSeverity, ObservationSummary, ObservationTemplate, SeverityCounts,
+    TruncationStrategy, TruncationUnit, ARGS_RENDER_LEN,
 };
 
 pub use state::{
@@ -23,6 +29,6 @@ pub use state::{
 };
 
 pub use thought::{
-    ExtractionPattern, FallbackStrategy, PlannedAction, Thought as ParsedThought,
-    ThoughtParseError, ThoughtParser,
+    ExtractionPattern, FallbackStrategy, PlannedAction, StreamingThoughtParser,
+    Thought as ParsedThought, ThoughtParseError, ThoughtParser,
 };
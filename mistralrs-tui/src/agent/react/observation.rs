@@ -7,6 +7,8 @@
 #![cfg(feature = "tui-agent")]
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -33,6 +35,19 @@ pub struct Observation {
     pub metadata: ObservationMetadata,
 }
 
+impl Observation {
+    /// Untruncated content when it was kept, otherwise the LLM-facing content
+    ///
+    /// Intended for hosts that offer to expand truncated output; the full
+    /// text is never used when formatting observations for the prompt.
+    pub fn full_content(&self) -> &str {
+        self.metadata
+            .full_content
+            .as_deref()
+            .unwrap_or(&self.content)
+    }
+}
+
 /// Classification of observation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -54,11 +69,93 @@ pub enum ObservationKind {
         /// Estimated completeness (0.0-1.0)
         completeness: f32,
     },
+    /// Tool was not run, e.g. because an earlier action failed
+    Skipped,
+}
+
+/// How much an observation should affect the agent's plan
+///
+/// Ordered from least to most severe.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ObservationSeverity {
+    /// Nothing went wrong
+    #[default]
+    Info,
+    /// Something went wrong but the agent can retry or work around it
+    Warning,
+    /// The call failed and retrying the same call will not help
+    Error,
+    /// The failure points to a broken environment; the agent should stop
+    Fatal,
+}
+
+impl ObservationSeverity {
+    /// Upper-case label used in formatted observations
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Info => "INFO",
+            Self::Warning => "WARNING",
+            Self::Error => "ERROR",
+            Self::Fatal => "FATAL",
+        }
+    }
+
+    /// Relative share of a combined observation budget
+    ///
+    /// Failures carry the information the agent needs to recover, so they
+    /// get more room than successful output.
+    pub fn budget_weight(&self) -> usize {
+        match self {
+            Self::Info => 1,
+            Self::Warning => 2,
+            Self::Error | Self::Fatal => 4,
+        }
+    }
+}
+
+/// Number of observations at each severity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    /// Observations at [`ObservationSeverity::Info`]
+    pub info: usize,
+    /// Observations at [`ObservationSeverity::Warning`]
+    pub warning: usize,
+    /// Observations at [`ObservationSeverity::Error`]
+    pub error: usize,
+    /// Observations at [`ObservationSeverity::Fatal`]
+    pub fatal: usize,
+}
+
+impl SeverityCounts {
+    /// Count for a single severity
+    pub fn get(&self, severity: ObservationSeverity) -> usize {
+        match severity {
+            ObservationSeverity::Info => self.info,
+            ObservationSeverity::Warning => self.warning,
+            ObservationSeverity::Error => self.error,
+            ObservationSeverity::Fatal => self.fatal,
+        }
+    }
+
+    fn add(&mut self, severity: ObservationSeverity) {
+        match severity {
+            ObservationSeverity::Info => self.info += 1,
+            ObservationSeverity::Warning => self.warning += 1,
+            ObservationSeverity::Error => self.error += 1,
+            ObservationSeverity::Fatal => self.fatal += 1,
+        }
+    }
 }
 
 /// Metadata about observation processing
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ObservationMetadata {
+    /// Severity derived from the observation kind and error message
+    #[serde(default)]
+    pub severity: ObservationSeverity,
     /// Estimated token count of observation content
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tokens_estimated: Option<usize>,
@@ -66,6 +163,15 @@ pub struct ObservationMetadata {
     pub truncated: bool,
     /// Original content length before truncation
     pub original_length: usize,
+    /// Content before truncation, kept only when the processor is asked to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_content: Option<String>,
+    /// Call arguments rendered with [`render_args_compact`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+    /// Estimated token count of the content before truncation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_tokens: Option<usize>,
 }
 
 /// Summary of multiple observations
@@ -77,6 +183,9 @@ pub struct ObservationSummary {
     pub all_successful: bool,
     /// Whether any errors are potentially recoverable
     pub has_recoverable_errors: bool,
+    /// Number of observations at each severity
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
     /// Total duration across all tool calls
     #[serde(with = "duration_serde")]
     pub total_duration: Duration,
@@ -96,6 +205,12 @@ pub enum TruncationStrategy {
     HeadOnly,
     /// Keep only the end of content
     TailOnly,
+    /// Keep the end of errors and timeouts, where the root cause usually
+    /// is, and the head and tail of everything else
+    ErrorAware {
+        /// Ratio of tokens to keep from the head of non-error content
+        head_ratio: f32,
+    },
 }
 
 impl Default for TruncationStrategy {
@@ -104,6 +219,184 @@ impl Default for TruncationStrategy {
     }
 }
 
+/// Unit in which truncation is reported to the LLM
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationUnit {
+    /// Content length in characters
+    #[default]
+    Characters,
+    /// Estimated tokens, matching the processor's token budget
+    Tokens,
+}
+
+impl TruncationUnit {
+    /// Plural name substituted for `{unit}`
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Characters => "characters",
+            Self::Tokens => "tokens",
+        }
+    }
+}
+
+/// Wording used by [`ObservationProcessor::format_for_context`]
+///
+/// Each field is a template in which `{name}` placeholders are substituted;
+/// unknown placeholders are left as-is. The default reproduces the plain
+/// `Observation from TOOL:` / `[SUCCESS in Xs]` layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObservationTemplate {
+    /// Layout of a single observation: `{tool}`, `{args}`, `{status}`,
+    /// `{severity}`, `{content}`, `{note}`
+    pub observation: String,
+    /// Inserted between consecutive observations
+    pub separator: String,
+    /// Status for successful calls: `{duration}`
+    pub success: String,
+    /// Status for failed calls: `{code}`, `{recoverable}`, `{duration}`
+    pub error: String,
+    /// Substituted for `{recoverable}` when the error is recoverable
+    pub recoverable_hint: String,
+    /// Status for timed out calls: `{duration}`
+    pub timeout: String,
+    /// Status for partial results: `{completeness}`, `{duration}`
+    pub partial: String,
+    /// Status for skipped calls (no placeholders)
+    #[serde(default = "default_skipped_status")]
+    pub skipped: String,
+    /// Substituted for `{severity}` above [`ObservationSeverity::Info`]: `{level}`
+    pub severity_tag: String,
+    /// Substituted for `{note}` when content was truncated: `{original}`,
+    /// `{shown}`, `{unit}`
+    pub truncation_note: String,
+    /// Unit of the counts in `truncation_note` and `middle_marker`
+    #[serde(default)]
+    pub truncation_unit: TruncationUnit,
+    /// Inserted where [`TruncationStrategy::HeadTail`] cut content:
+    /// `{omitted}`, `{unit}`
+    #[serde(default = "default_middle_marker")]
+    pub middle_marker: String,
+}
+
+impl Default for ObservationTemplate {
+    fn default() -> Self {
+        Self {
+            observation: "Observation from {tool}:\n{status}{severity}\n{content}{note}"
+                .to_string(),
+            separator: "\n\n".to_string(),
+            success: "[SUCCESS in {duration}s]".to_string(),
+            error: "[ERROR: {code}{recoverable}] (took {duration}s)".to_string(),
+            recoverable_hint: " (recoverable)".to_string(),
+            timeout: "[TIMEOUT after {duration}s]".to_string(),
+            partial: "[PARTIAL RESULT: {completeness}% complete in {duration}s]".to_string(),
+            skipped: default_skipped_status(),
+            severity_tag: " [{level}]".to_string(),
+            truncation_note:
+                "\n\n[Note: Output truncated from {original} to {shown} {unit} for brevity]"
+                    .to_string(),
+            truncation_unit: TruncationUnit::default(),
+            middle_marker: default_middle_marker(),
+        }
+    }
+}
+
+fn default_middle_marker() -> String {
+    "\n\n...[middle section truncated]...\n\n".to_string()
+}
+
+/// Longest string value shown when rendering call arguments
+pub const ARGS_RENDER_LEN: usize = 40;
+
+/// Array items shown by [`render_args_compact`] before eliding the rest
+const COMPACT_ARRAY_ITEMS: usize = 3;
+
+/// Render tool arguments on one line for prompts and observation headers
+///
+/// Object keys are sorted so the output is stable, strings longer than
+/// `max_len` characters are cut with `…`, and arrays show their first few
+/// items followed by a count of the rest.
+pub fn render_args_compact(value: &JsonValue, max_len: usize) -> String {
+    let mut output = String::new();
+    write_compact(value, max_len, &mut output);
+    output
+}
+
+fn write_compact(value: &JsonValue, max_len: usize, output: &mut String) {
+    match value {
+        JsonValue::String(s) => {
+            let mut shown: String = s.chars().take(max_len).collect();
+            if shown.len() < s.len() {
+                shown.push('…');
+            }
+            output.push_str(&JsonValue::String(shown).to_string());
+        }
+        JsonValue::Array(items) => {
+            output.push('[');
+            for (idx, item) in items.iter().take(COMPACT_ARRAY_ITEMS).enumerate() {
+                if idx > 0 {
+                    output.push_str(", ");
+                }
+                write_compact(item, max_len, output);
+            }
+            if items.len() > COMPACT_ARRAY_ITEMS {
+                output.push_str(&format!(", … +{} more", items.len() - COMPACT_ARRAY_ITEMS));
+            }
+            output.push(']');
+        }
+        JsonValue::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            output.push('{');
+            for (idx, key) in keys.into_iter().enumerate() {
+                if idx > 0 {
+                    output.push_str(", ");
+                }
+                output.push_str(key);
+                output.push_str(": ");
+                write_compact(&map[key], max_len, output);
+            }
+            output.push('}');
+        }
+        other => output.push_str(&other.to_string()),
+    }
+}
+
+fn default_skipped_status() -> String {
+    "[SKIPPED]".to_string()
+}
+
+/// Substitute `{name}` placeholders in a single pass
+///
+/// Substituted values are not rescanned, so braces in tool output are safe.
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                output.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
 /// Processor for converting tool results into structured observations
 #[derive(Debug, Clone)]
 pub struct ObservationProcessor {
@@ -111,6 +404,14 @@ pub struct ObservationProcessor {
     max_observation_tokens: usize,
     /// Strategy for truncating long content
     truncation_strategy: TruncationStrategy,
+    /// Wording of formatted observations
+    template: ObservationTemplate,
+    /// Expected JSON schema of successful output, keyed by tool name
+    output_schemas: HashMap<String, JsonValue>,
+    /// Whether truncated observations keep their full content in metadata
+    keep_full_output: bool,
+    /// Token budget shared by the observations of one summary
+    combined_budget_tokens: Option<usize>,
 }
 
 impl ObservationProcessor {
@@ -122,6 +423,10 @@ impl ObservationProcessor {
         Self {
             max_observation_tokens: max_tokens,
             truncation_strategy: TruncationStrategy::default(),
+            template: ObservationTemplate::default(),
+            output_schemas: HashMap::new(),
+            keep_full_output: false,
+            combined_budget_tokens: None,
         }
     }
 
@@ -134,27 +439,107 @@ impl ObservationProcessor {
         Self {
             max_observation_tokens: max_tokens,
             truncation_strategy: strategy,
+            template: ObservationTemplate::default(),
+            output_schemas: HashMap::new(),
+            keep_full_output: false,
+            combined_budget_tokens: None,
         }
     }
 
+    /// Use a custom template when formatting observations for the LLM
+    ///
+    /// # Arguments
+    /// * `template` - Wording for observations and their status lines
+    pub fn with_template(mut self, template: ObservationTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Validate successful output of `tool_name` against a JSON schema
+    ///
+    /// Output that violates the schema is reported as a recoverable error
+    /// listing the violations. Supported keywords: `type`, `enum`, `const`,
+    /// `properties`, `required`, `additionalProperties` (boolean), `items`,
+    /// `minItems`, `maxItems`, `minLength`, `maxLength`, `minimum` and
+    /// `maximum`.
+    ///
+    /// # Arguments
+    /// * `tool_name` - Tool whose output is validated
+    /// * `schema` - JSON schema the output must match
+    pub fn with_output_schema(mut self, tool_name: impl Into<String>, schema: JsonValue) -> Self {
+        self.output_schemas.insert(tool_name.into(), schema);
+        self
+    }
+
+    /// Keep the untruncated content of truncated observations
+    ///
+    /// The text is stored in [`ObservationMetadata::full_content`] for hosts
+    /// to display; the LLM still only sees the truncated content.
+    pub fn with_full_output(mut self, keep: bool) -> Self {
+        self.keep_full_output = keep;
+        self
+    }
+
+    /// Share a token budget across the observations of each summary
+    ///
+    /// When the observations passed to [`summarize`](Self::summarize) exceed
+    /// the budget together, each gets a share weighted by
+    /// [`ObservationSeverity::budget_weight`], so successful output is
+    /// truncated harder than errors. `None` disables the budget.
+    pub fn with_combined_budget(mut self, tokens: Option<usize>) -> Self {
+        self.combined_budget_tokens = tokens;
+        self
+    }
+
+    /// Get the template used for formatting observations
+    pub fn template(&self) -> &ObservationTemplate {
+        &self.template
+    }
+
     /// Process a tool call result into a structured observation
     ///
     /// # Arguments
     /// * `result` - The tool execution result
     /// * `call` - The original tool call metadata
     pub fn process(&self, result: &ToolCallResult, call: &ToolCall) -> Observation {
-        let kind = self.classify_result(result);
-        let raw_content = self.extract_content(result);
+        let mut kind = self.classify_result(result);
+        let mut raw_content = self.extract_content(result);
+
+        if let Some(schema) = self.output_schemas.get(&call.tool_name) {
+            let mut violations = Vec::new();
+            if result.success {
+                schema_violations(schema, &result.output, "$", &mut violations);
+            }
+            if !violations.is_empty() {
+                kind = ObservationKind::Error {
+                    code: Some("SCHEMA_VIOLATION".to_string()),
+                    recoverable: true,
+                };
+                raw_content = format!(
+                    "Error: Output does not match the expected schema:\n- {}",
+                    violations.join("\n- ")
+                );
+            }
+        }
+
         let original_length = raw_content.len();
+        let original_tokens = self.estimate_tokens(&raw_content);
 
-        let (content, truncated) =
-            if self.estimate_tokens(&raw_content) > self.max_observation_tokens {
-                (self.truncate_content(&raw_content), true)
-            } else {
-                (raw_content, false)
-            };
+        let (content, truncated, full_content) = if original_tokens > self.max_observation_tokens {
+            let content = self.truncate_content(&raw_content, &kind);
+            let full = self.keep_full_output.then_some(raw_content);
+            (content, true, full)
+        } else {
+            (raw_content, false, None)
+        };
 
         let tokens_estimated = Some(self.estimate_tokens(&content));
+        let severity = self.classify_severity(&kind, result.error.as_deref().unwrap_or(""));
+        let arguments = match &call.arguments {
+            JsonValue::Null => None,
+            JsonValue::Object(map) if map.is_empty() => None,
+            args => Some(render_args_compact(args, ARGS_RENDER_LEN)),
+        };
 
         Observation {
             tool_name: call.tool_name.clone(),
@@ -164,9 +549,38 @@ impl ObservationProcessor {
             structured_data: Some(result.output.clone()),
             duration: result.duration,
             metadata: ObservationMetadata {
+                severity,
                 tokens_estimated,
                 truncated,
                 original_length,
+                full_content,
+                arguments,
+                original_tokens: Some(original_tokens),
+            },
+        }
+    }
+
+    /// Observation for an action that was not executed
+    ///
+    /// `reason` becomes the content, e.g. which earlier action failed.
+    pub fn skipped(&self, tool_name: &str, reason: &str) -> Observation {
+        let kind = ObservationKind::Skipped;
+        let severity = self.classify_severity(&kind, "");
+        Observation {
+            tool_name: tool_name.to_string(),
+            tool_call_id: Uuid::new_v4(),
+            kind,
+            content: reason.to_string(),
+            structured_data: None,
+            duration: Duration::ZERO,
+            metadata: ObservationMetadata {
+                severity,
+                tokens_estimated: Some(self.estimate_tokens(reason)),
+                truncated: false,
+                original_length: reason.len(),
+                full_content: None,
+                arguments: None,
+                original_tokens: None,
             },
         }
     }
@@ -175,7 +589,11 @@ impl ObservationProcessor {
     ///
     /// # Arguments
     /// * `observations` - List of observations to summarize
-    pub fn summarize(&self, observations: Vec<Observation>) -> ObservationSummary {
+    pub fn summarize(&self, mut observations: Vec<Observation>) -> ObservationSummary {
+        if let Some(budget) = self.combined_budget_tokens {
+            self.apply_budget(&mut observations, budget);
+        }
+
         let all_successful = observations
             .iter()
             .all(|o| matches!(o.kind, ObservationKind::Success));
@@ -190,6 +608,11 @@ impl ObservationProcessor {
             )
         });
 
+        let mut severity_counts = SeverityCounts::default();
+        for obs in &observations {
+            severity_counts.add(obs.metadata.severity);
+        }
+
         let total_duration = observations
             .iter()
             .fold(Duration::ZERO, |acc, o| acc + o.duration);
@@ -200,66 +623,165 @@ impl ObservationProcessor {
             observations,
             all_successful,
             has_recoverable_errors,
+            severity_counts,
             total_duration,
             formatted_for_llm,
         }
     }
 
+    /// Truncate observations so their contents fit a combined token budget
+    ///
+    /// Observations smaller than their weighted share are kept whole and the
+    /// rest of the budget is shared again among the larger ones.
+    fn apply_budget(&self, observations: &mut [Observation], budget: usize) {
+        let tokens: Vec<usize> = observations
+            .iter()
+            .map(|o| {
+                o.metadata
+                    .tokens_estimated
+                    .unwrap_or_else(|| self.estimate_tokens(&o.content))
+            })
+            .collect();
+        if tokens.iter().sum::<usize>() <= budget {
+            return;
+        }
+
+        let mut remaining = budget;
+        let mut pending: Vec<usize> = (0..observations.len()).collect();
+        loop {
+            let total_weight: usize = pending
+                .iter()
+                .map(|&idx| observations[idx].metadata.severity.budget_weight())
+                .sum();
+            let shares: Vec<(usize, usize)> = pending
+                .iter()
+                .map(|&idx| {
+                    let weight = observations[idx].metadata.severity.budget_weight();
+                    (idx, remaining * weight / total_weight)
+                })
+                .collect();
+
+            let (fits, over): (Vec<_>, Vec<_>) = shares
+                .into_iter()
+                .partition(|&(idx, share)| tokens[idx] <= share);
+            if fits.is_empty() {
+                for (idx, share) in over {
+                    self.truncate_observation(&mut observations[idx], share);
+                }
+                return;
+            }
+
+            remaining -= fits.iter().map(|&(idx, _)| tokens[idx]).sum::<usize>();
+            pending = over.into_iter().map(|(idx, _)| idx).collect();
+        }
+    }
+
+    /// Re-truncate an observation's content to `max_tokens`
+    fn truncate_observation(&self, obs: &mut Observation, max_tokens: usize) {
+        let source = obs.metadata.full_content.as_deref().unwrap_or(&obs.content);
+        let content = self.truncate_to(source, max_tokens, &obs.kind);
+        if self.keep_full_output && obs.metadata.full_content.is_none() {
+            obs.metadata.full_content = Some(std::mem::take(&mut obs.content));
+        }
+        obs.metadata.tokens_estimated = Some(self.estimate_tokens(&content));
+        obs.metadata.truncated = true;
+        obs.content = content;
+    }
+
     /// Format observations for LLM context window
     ///
     /// # Arguments
     /// * `observations` - List of observations to format
     pub fn format_for_context(&self, observations: &[Observation]) -> String {
+        let template = &self.template;
         let mut formatted = String::new();
 
         for (idx, obs) in observations.iter().enumerate() {
             if idx > 0 {
-                formatted.push_str("\n\n");
+                formatted.push_str(&template.separator);
             }
 
-            formatted.push_str(&format!("Observation from {}:\n", obs.tool_name));
+            let duration = format!("{:.2}", obs.duration.as_secs_f32());
 
             // Status line
             let status_line = match &obs.kind {
                 ObservationKind::Success => {
-                    format!("[SUCCESS in {:.2}s]", obs.duration.as_secs_f32())
+                    fill_template(&template.success, &[("duration", &duration)])
                 }
                 ObservationKind::Error { code, recoverable } => {
                     let code_str = code.as_deref().unwrap_or("UNKNOWN");
-                    let recover_hint = if *recoverable { " (recoverable)" } else { "" };
-                    format!(
-                        "[ERROR: {}{}] (took {:.2}s)",
-                        code_str,
-                        recover_hint,
-                        obs.duration.as_secs_f32()
+                    let recover_hint = if *recoverable {
+                        template.recoverable_hint.as_str()
+                    } else {
+                        ""
+                    };
+                    fill_template(
+                        &template.error,
+                        &[
+                            ("code", code_str),
+                            ("recoverable", recover_hint),
+                            ("duration", &duration),
+                        ],
                     )
                 }
                 ObservationKind::Timeout => {
-                    format!("[TIMEOUT after {:.2}s]", obs.duration.as_secs_f32())
-                }
-                ObservationKind::PartialResult { completeness } => {
-                    format!(
-                        "[PARTIAL RESULT: {:.0}% complete in {:.2}s]",
-                        completeness * 100.0,
-                        obs.duration.as_secs_f32()
-                    )
+                    fill_template(&template.timeout, &[("duration", &duration)])
                 }
+                ObservationKind::PartialResult { completeness } => fill_template(
+                    &template.partial,
+                    &[
+                        ("completeness", &format!("{:.0}", completeness * 100.0)),
+                        ("duration", &duration),
+                    ],
+                ),
+                ObservationKind::Skipped => template.skipped.clone(),
             };
 
-            formatted.push_str(&status_line);
-            formatted.push('\n');
+            // Truncation notice
+            let note = if obs.metadata.truncated {
+                let (original, shown) = match template.truncation_unit {
+                    TruncationUnit::Characters => (obs.metadata.original_length, obs.content.len()),
+                    TruncationUnit::Tokens => (
+                        obs.metadata
+                            .original_tokens
+                            .unwrap_or_else(|| obs.metadata.original_length.div_ceil(4)),
+                        obs.metadata
+                            .tokens_estimated
+                            .unwrap_or_else(|| self.estimate_tokens(&obs.content)),
+                    ),
+                };
+                fill_template(
+                    &template.truncation_note,
+                    &[
+                        ("original", &original.to_string()),
+                        ("shown", &shown.to_string()),
+                        ("unit", template.truncation_unit.label()),
+                    ],
+                )
+            } else {
+                String::new()
+            };
 
-            // Content
-            formatted.push_str(&obs.content);
+            let severity = if obs.metadata.severity > ObservationSeverity::Info {
+                fill_template(
+                    &template.severity_tag,
+                    &[("level", obs.metadata.severity.label())],
+                )
+            } else {
+                String::new()
+            };
 
-            // Truncation notice
-            if obs.metadata.truncated {
-                formatted.push_str(&format!(
-                    "\n\n[Note: Output truncated from {} to {} characters for brevity]",
-                    obs.metadata.original_length,
-                    obs.content.len()
-                ));
-            }
+            formatted.push_str(&fill_template(
+                &template.observation,
+                &[
+                    ("tool", &obs.tool_name),
+                    ("args", obs.metadata.arguments.as_deref().unwrap_or("")),
+                    ("status", &status_line),
+                    ("severity", &severity),
+                    ("content", &obs.content),
+                    ("note", &note),
+                ],
+            ));
         }
 
         formatted
@@ -269,14 +791,29 @@ impl ObservationProcessor {
     ///
     /// # Arguments
     /// * `content` - Content to truncate
-    fn truncate_content(&self, content: &str) -> String {
-        let max_chars = self.max_observation_tokens * 4; // Approximate 4 chars per token
+    /// * `kind` - Classification of the content, for error-aware truncation
+    fn truncate_content(&self, content: &str, kind: &ObservationKind) -> String {
+        self.truncate_to(content, self.max_observation_tokens, kind)
+    }
+
+    /// Truncate content to roughly `max_tokens` with the configured strategy
+    fn truncate_to(&self, content: &str, max_tokens: usize, kind: &ObservationKind) -> String {
+        let max_chars = max_tokens * 4; // Approximate 4 chars per token
 
         if content.len() <= max_chars {
             return content.to_string();
         }
 
-        match self.truncation_strategy {
+        // Error-aware truncation keeps the tail of failures, the head and tail otherwise
+        let strategy = match (self.truncation_strategy, kind) {
+            (
+                TruncationStrategy::ErrorAware { .. },
+                ObservationKind::Error { .. } | ObservationKind::Timeout,
+            ) => TruncationStrategy::TailOnly,
+            (strategy, _) => strategy,
+        };
+
+        match strategy {
             TruncationStrategy::HeadOnly => {
                 let mut truncated = content.chars().take(max_chars).collect::<String>();
                 truncated.push_str("\n...[truncated]");
@@ -288,7 +825,8 @@ impl ObservationProcessor {
                 truncated.push_str(&content.chars().skip(skip_count).collect::<String>());
                 truncated
             }
-            TruncationStrategy::HeadTail { head_ratio } => {
+            TruncationStrategy::HeadTail { head_ratio }
+            | TruncationStrategy::ErrorAware { head_ratio } => {
                 let head_ratio = head_ratio.clamp(0.0, 1.0);
                 let head_chars = (max_chars as f32 * head_ratio) as usize;
                 let tail_chars = max_chars.saturating_sub(head_chars);
@@ -296,8 +834,20 @@ impl ObservationProcessor {
                 let total_chars = content.chars().count();
                 let skip_count = total_chars.saturating_sub(tail_chars);
 
+                let omitted = skip_count.saturating_sub(head_chars);
+                let omitted = match self.template.truncation_unit {
+                    TruncationUnit::Characters => omitted,
+                    TruncationUnit::Tokens => omitted.div_ceil(4),
+                };
+
                 let mut truncated = content.chars().take(head_chars).collect::<String>();
-                truncated.push_str("\n\n...[middle section truncated]...\n\n");
+                truncated.push_str(&fill_template(
+                    &self.template.middle_marker,
+                    &[
+                        ("omitted", &omitted.to_string()),
+                        ("unit", self.template.truncation_unit.label()),
+                    ],
+                ));
                 truncated.push_str(&content.chars().skip(skip_count).collect::<String>());
                 truncated
             }
@@ -333,6 +883,32 @@ impl ObservationProcessor {
         }
     }
 
+    /// Derive the severity of an observation
+    ///
+    /// Recoverable errors and partial results are warnings, timeouts and other
+    /// errors are errors, and errors pointing at a broken environment are fatal.
+    fn classify_severity(&self, kind: &ObservationKind, error_msg: &str) -> ObservationSeverity {
+        match kind {
+            ObservationKind::Success => ObservationSeverity::Info,
+            ObservationKind::PartialResult { .. } | ObservationKind::Skipped => {
+                ObservationSeverity::Warning
+            }
+            ObservationKind::Timeout => ObservationSeverity::Error,
+            ObservationKind::Error {
+                recoverable: true, ..
+            } => ObservationSeverity::Warning,
+            ObservationKind::Error { .. } => {
+                let error_lower = error_msg.to_lowercase();
+                let fatal_patterns = ["corrupted", "incompatible", "out of memory", "panicked"];
+                if fatal_patterns.iter().any(|p| error_lower.contains(p)) {
+                    ObservationSeverity::Fatal
+                } else {
+                    ObservationSeverity::Error
+                }
+            }
+        }
+    }
+
     /// Extract content from tool result for observation
     fn extract_content(&self, result: &ToolCallResult) -> String {
         if result.success {
@@ -427,6 +1003,163 @@ impl ObservationProcessor {
     }
 }
 
+/// Collect the ways `value` violates `schema`, each prefixed with its JSON path
+fn schema_violations(schema: &JsonValue, value: &JsonValue, path: &str, out: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        // `true` accepts everything, `false` nothing
+        if schema == &JsonValue::Bool(false) {
+            out.push(format!("{path}: no value is allowed"));
+        }
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            JsonValue::String(t) => vec![t.as_str()],
+            JsonValue::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| json_type_matches(t, value)) {
+            out.push(format!(
+                "{path}: expected {}, found {}",
+                allowed.join(" or "),
+                json_type_name(value)
+            ));
+            // Remaining keywords assume the right type
+            return;
+        }
+    }
+
+    if let Some(JsonValue::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            out.push(format!(
+                "{path}: {value} is not one of {}",
+                JsonValue::Array(options.clone())
+            ));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            out.push(format!("{path}: expected {constant}, found {value}"));
+        }
+    }
+
+    match value {
+        JsonValue::Object(map) => {
+            if let Some(JsonValue::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(|k| k.as_str()) {
+                    if !map.contains_key(key) {
+                        out.push(format!("{path}: missing required property '{key}'"));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            for (key, child) in map {
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => {
+                        schema_violations(child_schema, child, &format!("{path}.{key}"), out)
+                    }
+                    None if schema.get("additionalProperties") == Some(&JsonValue::Bool(false)) => {
+                        out.push(format!("{path}: unexpected property '{key}'"));
+                    }
+                    None => {}
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            check_bounds(
+                schema,
+                "minItems",
+                "maxItems",
+                items.len(),
+                "items",
+                path,
+                out,
+            );
+            if let Some(item_schema) = schema.get("items") {
+                for (idx, item) in items.iter().enumerate() {
+                    schema_violations(item_schema, item, &format!("{path}[{idx}]"), out);
+                }
+            }
+        }
+        JsonValue::String(text) => {
+            let len = text.chars().count();
+            check_bounds(
+                schema,
+                "minLength",
+                "maxLength",
+                len,
+                "characters",
+                path,
+                out,
+            );
+        }
+        JsonValue::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+                if number < min {
+                    out.push(format!("{path}: {number} is less than the minimum {min}"));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+                if number > max {
+                    out.push(format!(
+                        "{path}: {number} is greater than the maximum {max}"
+                    ));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check a length against a schema's minimum/maximum length keywords
+fn check_bounds(
+    schema: &serde_json::Map<String, JsonValue>,
+    min_key: &str,
+    max_key: &str,
+    len: usize,
+    unit: &str,
+    path: &str,
+    out: &mut Vec<String>,
+) {
+    if let Some(min) = schema.get(min_key).and_then(|m| m.as_u64()) {
+        if (len as u64) < min {
+            out.push(format!(
+                "{path}: expected at least {min} {unit}, found {len}"
+            ));
+        }
+    }
+    if let Some(max) = schema.get(max_key).and_then(|m| m.as_u64()) {
+        if len as u64 > max {
+            out.push(format!(
+                "{path}: expected at most {max} {unit}, found {len}"
+            ));
+        }
+    }
+}
+
+/// Whether `value` is an instance of the JSON schema type `name`
+fn json_type_matches(name: &str, value: &JsonValue) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => json_type_name(value) == other,
+    }
+}
+
+/// JSON schema type name of a value
+fn json_type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
 impl Default for ObservationProcessor {
     fn default() -> Self {
         Self::new(500) // 500 tokens default (~2000 chars)
@@ -529,12 +1262,124 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_output_schema_validation() {
+        let schema = json!({
+            "type": "object",
+            "required": ["path", "size"],
+            "properties": {
+                "path": {"type": "string", "minLength": 1},
+                "size": {"type": "integer", "minimum": 0},
+                "tags": {"type": "array", "items": {"enum": ["dir", "file"]}}
+            },
+            "additionalProperties": false
+        });
+        let processor = ObservationProcessor::new(1000).with_output_schema("test_tool", schema);
+        let call = create_test_call();
+
+        let conforming =
+            create_success_result(json!({"path": "a.txt", "size": 3, "tags": ["file"]}));
+        let obs = processor.process(&conforming, &call);
+        assert!(matches!(obs.kind, ObservationKind::Success));
+
+        let output = json!({"path": "", "size": -1, "tags": ["link"], "mode": 644});
+        let obs = processor.process(&create_success_result(output.clone()), &call);
+        match &obs.kind {
+            ObservationKind::Error { code, recoverable } => {
+                assert_eq!(code.as_deref(), Some("SCHEMA_VIOLATION"));
+                assert!(recoverable);
+            }
+            other => panic!("Expected Error kind, got {other:?}"),
+        }
+        assert!(obs
+            .content
+            .contains("$.path: expected at least 1 characters, found 0"));
+        assert!(obs
+            .content
+            .contains("$.size: -1 is less than the minimum 0"));
+        assert!(obs
+            .content
+            .contains("$.tags[0]: \"link\" is not one of [\"dir\",\"file\"]"));
+        assert!(obs.content.contains("$: unexpected property 'mode'"));
+        assert_eq!(obs.structured_data, Some(output));
+
+        let obs = processor.process(&create_success_result(json!("plain text")), &call);
+        assert!(obs.content.contains("$: expected object, found string"));
+
+        // Failed calls and other tools are unaffected
+        let obs = processor.process(&create_error_result("File not found"), &call);
+        assert!(
+            matches!(&obs.kind, ObservationKind::Error { code, .. } if code.as_deref() != Some("SCHEMA_VIOLATION"))
+        );
+        let other = ToolCall {
+            tool_name: "other_tool".to_string(),
+            ..create_test_call()
+        };
+        let obs = processor.process(&create_success_result(json!("plain text")), &other);
+        assert!(matches!(obs.kind, ObservationKind::Success));
+    }
+
+    #[test]
+    fn test_severity_classification() {
+        let processor = ObservationProcessor::new(1000);
+        let call = create_test_call();
+        let severity = |result: ToolCallResult| processor.process(&result, &call).metadata.severity;
+
+        assert_eq!(
+            severity(create_success_result(json!("ok"))),
+            ObservationSeverity::Info
+        );
+        assert_eq!(
+            severity(create_error_result("File not found")),
+            ObservationSeverity::Warning
+        );
+        assert_eq!(
+            severity(create_error_result("parse error at line 3")),
+            ObservationSeverity::Error
+        );
+        assert_eq!(
+            severity(create_error_result("index corrupted")),
+            ObservationSeverity::Fatal
+        );
+        assert_eq!(
+            processor.classify_severity(&ObservationKind::Timeout, ""),
+            ObservationSeverity::Error
+        );
+        assert!(ObservationSeverity::Warning < ObservationSeverity::Fatal);
+    }
+
+    #[test]
+    fn test_summarize_counts_severities() {
+        let processor = ObservationProcessor::new(1000);
+        let call = create_test_call();
+
+        let summary = processor.summarize(vec![
+            processor.process(&create_success_result(json!("Output")), &call),
+            processor.process(&create_error_result("File not found"), &call),
+            processor.process(&create_error_result("No such file"), &call),
+            processor.process(&create_error_result("Invalid syntax"), &call),
+        ]);
+
+        assert_eq!(
+            summary.severity_counts,
+            SeverityCounts {
+                info: 1,
+                warning: 2,
+                error: 1,
+                fatal: 0,
+            }
+        );
+        assert_eq!(summary.severity_counts.get(ObservationSeverity::Warning), 2);
+        assert!(summary.formatted_for_llm.contains("(took 0.03s) [ERROR]"));
+        assert!(!summary.formatted_for_llm.contains("[INFO]"));
+    }
+
     #[test]
     fn test_truncation_head_only() {
         let processor = ObservationProcessor::with_truncation(10, TruncationStrategy::HeadOnly);
         let long_text = "A".repeat(200);
 
-        let truncated = processor.truncate_content(&long_text);
+        let truncated = processor.truncate_content(&long_text, &ObservationKind::Success);
 
         assert!(truncated.len() < long_text.len());
         assert!(truncated.starts_with("AAAA"));
@@ -546,7 +1391,7 @@ mod tests {
         let processor = ObservationProcessor::with_truncation(10, TruncationStrategy::TailOnly);
         let long_text = "A".repeat(200);
 
-        let truncated = processor.truncate_content(&long_text);
+        let truncated = processor.truncate_content(&long_text, &ObservationKind::Success);
 
         assert!(truncated.len() < long_text.len());
         assert!(truncated.ends_with("AAAA"));
@@ -561,7 +1406,7 @@ mod tests {
         );
         let long_text = "A".repeat(200);
 
-        let truncated = processor.truncate_content(&long_text);
+        let truncated = processor.truncate_content(&long_text, &ObservationKind::Success);
 
         assert!(truncated.len() < long_text.len());
         assert!(truncated.starts_with("AAAA"));
@@ -569,6 +1414,124 @@ mod tests {
         assert!(truncated.contains("[middle section truncated]"));
     }
 
+    #[test]
+    fn test_truncation_error_aware() {
+        let processor = ObservationProcessor::with_truncation(
+            20,
+            TruncationStrategy::ErrorAware { head_ratio: 0.5 },
+        );
+        let call = create_test_call();
+
+        let frames: Vec<String> = (0..40)
+            .map(|i| format!("  at frame_{i} (src/lib.rs:{i})"))
+            .collect();
+        let trace = format!(
+            "Traceback:\n{}\nRootCause: config file missing",
+            frames.join("\n")
+        );
+        let obs = processor.process(&create_error_result(&trace), &call);
+        assert!(obs.metadata.truncated);
+        assert!(obs.content.starts_with("...[truncated]"));
+        assert!(obs
+            .content
+            .ends_with("frame_39 (src/lib.rs:39)\nRootCause: config file missing"));
+        assert!(!obs.content.contains("Traceback"));
+
+        // Successful output keeps both ends
+        let output = format!("HEAD{}TAIL", "A".repeat(200));
+        let obs = processor.process(&create_success_result(json!(output)), &call);
+        assert!(obs.content.starts_with("HEAD"));
+        assert!(obs.content.ends_with("TAIL"));
+        assert!(obs.content.contains("[middle section truncated]"));
+    }
+
+    #[test]
+    fn test_truncation_reported_in_tokens() {
+        let template = ObservationTemplate {
+            truncation_unit: TruncationUnit::Tokens,
+            middle_marker: "\n<{omitted} {unit} omitted>\n".to_string(),
+            ..ObservationTemplate::default()
+        };
+        let processor = ObservationProcessor::with_truncation(
+            10,
+            TruncationStrategy::HeadTail { head_ratio: 0.5 },
+        )
+        .with_template(template);
+        let call = create_test_call();
+
+        let obs = processor.process(&create_success_result(json!("A".repeat(200))), &call);
+        // 40 of 200 characters kept around the marker
+        assert!(obs.content.contains("\n<40 tokens omitted>\n"));
+        assert_eq!(obs.metadata.original_tokens, Some(50));
+
+        let shown = obs.metadata.tokens_estimated.unwrap();
+        let formatted = processor.format_for_context(&[obs]);
+        assert!(formatted.ends_with(&format!(
+            "[Note: Output truncated from 50 to {} tokens for brevity]",
+            shown
+        )));
+
+        // Characters stay the default unit
+        let processor = ObservationProcessor::new(10);
+        let obs = processor.process(&create_success_result(json!("A".repeat(200))), &call);
+        assert!(obs.content.contains("...[middle section truncated]..."));
+        assert!(processor.format_for_context(&[obs]).contains("from 200 to"));
+    }
+
+    #[test]
+    fn test_full_output_kept_for_truncated_content() {
+        let long_text = format!("{}{}", "A".repeat(200), "END");
+        let call = create_test_call();
+        let result = create_success_result(json!(long_text));
+
+        let processor = ObservationProcessor::with_truncation(10, TruncationStrategy::HeadOnly)
+            .with_full_output(true);
+        let obs = processor.process(&result, &call);
+
+        assert!(obs.metadata.truncated);
+        assert!(!obs.content.contains("END"));
+        assert_eq!(obs.full_content(), long_text);
+        assert_eq!(
+            obs.metadata.full_content.as_deref(),
+            Some(long_text.as_str())
+        );
+
+        // The prompt only ever sees the truncated content
+        let formatted = processor.format_for_context(std::slice::from_ref(&obs));
+        assert!(!formatted.contains("END"));
+
+        // Off by default
+        let processor = ObservationProcessor::with_truncation(10, TruncationStrategy::HeadOnly);
+        let obs = processor.process(&result, &call);
+        assert!(obs.metadata.full_content.is_none());
+        assert_eq!(obs.full_content(), obs.content);
+    }
+
+    #[test]
+    fn test_combined_budget_favors_errors() {
+        let processor = ObservationProcessor::new(1000).with_combined_budget(Some(400));
+        let call = create_test_call();
+        let long = "x".repeat(1600);
+
+        let summary = processor.summarize(vec![
+            processor.process(&create_success_result(json!(long)), &call),
+            processor.process(&create_error_result(&format!("Invalid {long}")), &call),
+            processor.process(&create_success_result(json!(long)), &call),
+            processor.process(&create_success_result(json!("short")), &call),
+        ]);
+        let obs = &summary.observations;
+
+        // Each observation fit the per-observation cap on its own
+        assert!(obs.iter().all(|o| o.metadata.original_length <= 4000));
+        assert!(obs[0].metadata.truncated && obs[1].metadata.truncated);
+        assert!(!obs[3].metadata.truncated);
+        assert!(obs[1].content.len() > 3 * obs[0].content.len());
+
+        // The budget covers content; truncation markers add a few tokens each
+        let total: usize = obs.iter().filter_map(|o| o.metadata.tokens_estimated).sum();
+        assert!(total <= 450, "combined tokens {total}");
+    }
+
     #[test]
     fn test_format_for_context() {
         let processor = ObservationProcessor::new(1000);
@@ -586,6 +1549,105 @@ mod tests {
         assert!(formatted.contains("Not found"));
     }
 
+    #[test]
+    fn test_default_template_layout() {
+        let processor = ObservationProcessor::new(1000);
+        let call = create_test_call();
+
+        let obs1 = processor.process(&create_success_result(json!("First output")), &call);
+        let obs2 = processor.process(&create_error_result("ENOENT: File not found"), &call);
+
+        assert_eq!(
+            processor.format_for_context(&[obs1, obs2]),
+            "Observation from test_tool:\n[SUCCESS in 0.05s]\nFirst output\n\n\
+             Observation from test_tool:\n[ERROR: ENOENT (recoverable)] (took 0.03s) [WARNING]\n\
+             Error: ENOENT: File not found"
+        );
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let template = ObservationTemplate {
+            observation:
+                "<observation tool=\"{tool}\" status=\"{status}\">\n{content}\n</observation>"
+                    .to_string(),
+            separator: "\n".to_string(),
+            success: "ok".to_string(),
+            error: "error:{code}".to_string(),
+            ..ObservationTemplate::default()
+        };
+        let processor = ObservationProcessor::new(1000).with_template(template.clone());
+        assert_eq!(processor.template(), &template);
+        let call = create_test_call();
+
+        let obs1 = processor.process(&create_success_result(json!("{content} stays")), &call);
+        let obs2 = processor.process(&create_error_result("ENOENT: File not found"), &call);
+        let summary = processor.summarize(vec![obs1, obs2]);
+
+        assert_eq!(
+            summary.formatted_for_llm,
+            "<observation tool=\"test_tool\" status=\"ok\">\n{content} stays\n</observation>\n\
+             <observation tool=\"test_tool\" status=\"error:ENOENT\">\n\
+             Error: ENOENT: File not found\n</observation>"
+        );
+        // Structured fields are independent of the template
+        assert_eq!(summary.observations[0].content, "{content} stays");
+        assert!(matches!(
+            summary.observations[0].kind,
+            ObservationKind::Success
+        ));
+        assert!(!summary.all_successful);
+        assert!(summary.has_recoverable_errors);
+    }
+
+    #[test]
+    fn test_render_args_compact() {
+        let args = json!({
+            "path": "src/main.rs",
+            "options": {"recursive": true, "depth": 2},
+            "files": ["a", "b", "c", "d", "e"],
+            "limit": null
+        });
+        assert_eq!(
+            render_args_compact(&args, 40),
+            "{files: [\"a\", \"b\", \"c\", … +2 more], limit: null, \
+             options: {depth: 2, recursive: true}, path: \"src/main.rs\"}"
+        );
+
+        let long = json!({"content": "x".repeat(100)});
+        assert_eq!(render_args_compact(&long, 5), "{content: \"xxxxx…\"}");
+        assert_eq!(render_args_compact(&json!("short"), 5), "\"short\"");
+    }
+
+    #[test]
+    fn test_args_placeholder() {
+        let template = ObservationTemplate {
+            observation: "{tool} {args}: {content}".to_string(),
+            ..ObservationTemplate::default()
+        };
+        let processor = ObservationProcessor::new(1000).with_template(template);
+        let mut call = create_test_call();
+        call.arguments = json!({"path": "notes.txt", "lines": 5});
+
+        let obs = processor.process(&create_success_result(json!("done")), &call);
+        assert_eq!(
+            obs.metadata.arguments.as_deref(),
+            Some("{lines: 5, path: \"notes.txt\"}")
+        );
+        assert_eq!(
+            processor.format_for_context(&[obs]),
+            "test_tool {lines: 5, path: \"notes.txt\"}: done"
+        );
+    }
+
+    #[test]
+    fn test_fill_template() {
+        assert_eq!(
+            fill_template("{a}-{b}-{missing}-{", &[("a", "{b}"), ("b", "2")]),
+            "{b}-2-{missing}-{"
+        );
+    }
+
     #[test]
     fn test_summarize() {
         let processor = ObservationProcessor::new(1000);
@@ -652,9 +1714,13 @@ mod tests {
             structured_data: Some(json!({"key": "value"})),
             duration: Duration::from_millis(100),
             metadata: ObservationMetadata {
+                severity: ObservationSeverity::Info,
                 tokens_estimated: Some(50),
                 truncated: false,
                 original_length: 12,
+                full_content: None,
+                arguments: None,
+                original_tokens: None,
             },
         };
 
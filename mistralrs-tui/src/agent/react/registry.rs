@@ -0,0 +1,123 @@
+//! Tool registry for the ReAct engine
+//!
+//! This module provides an introspectable registry of the tools the engine can
+//! dispatch to. The registry is used to advertise available tools in the prompt
+//! and to answer planned actions that name unknown tools with a failed
+//! observation instead of running them.
+
+#![cfg(feature = "tui-agent")]
+
+use indexmap::IndexMap;
+use serde_json::Value as JsonValue;
+
+use crate::agent::discovery::{ToolCatalog, ToolDefinition};
+
+/// Registry of tools available to the ReAct engine, in registration order
+#[derive(Debug, Clone, Default)]
+pub struct ToolRegistry {
+    tools: IndexMap<String, ToolDefinition>,
+}
+
+impl ToolRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry containing every tool in the catalog
+    pub fn from_catalog(catalog: &ToolCatalog) -> Self {
+        let mut registry = Self::new();
+        for tool in catalog.tools() {
+            registry.register_definition(tool.clone());
+        }
+        registry
+    }
+
+    /// Register a tool by name, description and JSON-schema parameters
+    ///
+    /// Registering a name twice replaces the earlier definition.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: JsonValue,
+    ) {
+        self.register_definition(ToolDefinition {
+            name: name.into(),
+            description: description.into(),
+            parameters,
+            examples: None,
+        });
+    }
+
+    /// Register a full tool definition
+    pub fn register_definition(&mut self, definition: ToolDefinition) {
+        self.tools.insert(definition.name.clone(), definition);
+    }
+
+    /// Check whether a tool is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
+    }
+
+    /// Get a tool definition by name
+    pub fn get(&self, name: &str) -> Option<&ToolDefinition> {
+        self.tools.get(name)
+    }
+
+    /// Iterate over registered tool names
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tools.keys().map(String::as_str)
+    }
+
+    /// Number of registered tools
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Format the registered tools as a prompt section body
+    ///
+    /// Each line has the form `- name: description`.
+    pub fn format_for_prompt(&self) -> String {
+        self.tools
+            .values()
+            .map(|tool| format!("- {}: {}\n", tool.name, tool.description))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_register_and_lookup() {
+        let mut registry = ToolRegistry::new();
+        registry.register("ls", "List files", json!({"type": "object"}));
+        registry.register("cat", "Show files", json!({"type": "object"}));
+
+        assert_eq!(registry.len(), 2);
+        assert!(registry.contains("ls"));
+        assert!(!registry.contains("rm"));
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["ls", "cat"]);
+        assert_eq!(
+            registry.format_for_prompt(),
+            "- ls: List files\n- cat: Show files\n"
+        );
+    }
+
+    #[test]
+    fn test_from_catalog() {
+        let catalog = ToolCatalog::new();
+        let registry = ToolRegistry::from_catalog(&catalog);
+
+        assert_eq!(registry.len(), catalog.tools().len());
+        assert!(registry.contains("grep"));
+    }
+}
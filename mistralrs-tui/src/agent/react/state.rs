@@ -108,6 +108,8 @@ pub enum ReActPhase {
 pub enum TerminationReason {
     /// LLM determined the task is complete
     TaskComplete,
+    /// LLM gave up without solving the task
+    TaskUnsolved,
     /// Hit the maximum iteration limit
     MaxIterationsReached,
     /// User cancelled the session
@@ -118,11 +120,28 @@ pub enum TerminationReason {
     Timeout,
 }
 
+#[cfg(feature = "tui-agent")]
+impl TerminationReason {
+    /// Human-readable description used in transcripts
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TaskComplete => "task complete",
+            Self::TaskUnsolved => "task unsolved",
+            Self::MaxIterationsReached => "maximum iterations reached",
+            Self::UserCancelled => "cancelled by user",
+            Self::Error => "error",
+            Self::Timeout => "timed out",
+        }
+    }
+}
+
 /// Outcome of a single ReAct iteration
 ///
 /// After each iteration, the system evaluates whether to:
 /// - Continue to the next iteration
+/// - Retry the iteration's reasoning without acting
 /// - Complete with a final answer
+/// - Stop because the LLM gave up
 /// - Request additional input from the user
 /// - Terminate due to an error
 #[cfg(feature = "tui-agent")]
@@ -136,11 +155,21 @@ pub enum IterationOutcome {
         /// Number of actions queued
         action_count: usize,
     },
+    /// Thought was rejected; think again without acting
+    Retry {
+        /// Why the thought was rejected
+        reason: String,
+    },
     /// Task is complete
     Complete {
         /// Final answer from the LLM
         final_answer: String,
     },
+    /// LLM gave up on the task
+    Unsolved {
+        /// The give-up response from the LLM
+        reason: String,
+    },
     /// Need additional input from user
     NeedsInput {
         /// Prompt for the user
@@ -165,6 +194,9 @@ pub struct ReActIteration {
     pub thought_text: String,
     /// Names of actions that were executed
     pub actions_taken: Vec<String>,
+    /// Arguments of each action in `actions_taken`, in the same order
+    #[serde(default)]
+    pub action_arguments: Vec<serde_json::Value>,
     /// Observations collected from action executions
     pub observations: Vec<String>,
     /// How long this iteration took
@@ -373,6 +405,9 @@ impl ReActState {
             (ReActPhase::Acting, ReActPhase::Observing) => true,
             (ReActPhase::Observing, ReActPhase::Thinking) => true,
 
+            // Thinking again after a thought with nothing to act on
+            (ReActPhase::Thinking, ReActPhase::Thinking) => true,
+
             // Can terminate from any state
             (_, ReActPhase::Terminated { .. }) => true,
 
@@ -469,6 +504,7 @@ impl ReActState {
         &mut self,
         thought_text: String,
         actions_taken: Vec<String>,
+        action_arguments: Vec<serde_json::Value>,
         observations: Vec<String>,
         duration: Duration,
     ) {
@@ -476,6 +512,7 @@ impl ReActState {
             number: self.iteration,
             thought_text,
             actions_taken,
+            action_arguments,
             observations,
             duration,
         };
@@ -795,6 +832,7 @@ mod tests {
         state.complete_iteration(
             "I should check the file".to_string(),
             vec!["cat".to_string()],
+            vec![serde_json::json!({"path": "README.md"})],
             vec!["file contents".to_string()],
             Duration::from_millis(250),
         );
@@ -802,6 +840,10 @@ mod tests {
         assert_eq!(state.iteration_history.len(), 1);
         assert_eq!(state.iteration_history[0].number, 0);
         assert_eq!(state.iteration_history[0].actions_taken.len(), 1);
+        assert_eq!(
+            state.iteration_history[0].action_arguments,
+            vec![serde_json::json!({"path": "README.md"})]
+        );
         assert_eq!(state.iteration_history[0].observations.len(), 1);
     }
 }
@@ -7,7 +7,9 @@
 //!
 //! - **ReAct Classic**: Traditional "Thought: ...\nAction: ..." format
 //! - **JSON Structured**: Structured JSON with thought/action fields
+//! - **YAML Structured**: The same fields as a fenced YAML document
 //! - **Tool Calls Inline**: Tool calls embedded directly in responses
+//! - **Code Blocks**: Fenced code blocks run through a code executor tool
 //! - **Free Form**: Unstructured text analysis
 //!
 //! # Example
@@ -54,6 +56,10 @@ pub struct Thought {
     /// Whether this is the final answer (terminates agent loop)
     pub is_final_answer: bool,
 
+    /// Whether the final answer gives up on the task (e.g. "I don't know")
+    #[serde(default)]
+    pub gave_up: bool,
+
     /// Raw LLM response content for debugging
     pub raw_content: String,
 }
@@ -67,6 +73,7 @@ impl Thought {
             confidence: None,
             planned_actions: Vec::new(),
             is_final_answer: false,
+            gave_up: false,
             raw_content: reasoning,
         }
     }
@@ -79,6 +86,7 @@ impl Thought {
             confidence: Some(1.0),
             planned_actions: Vec::new(),
             is_final_answer: true,
+            gave_up: false,
             raw_content: answer,
         }
     }
@@ -142,9 +150,17 @@ pub enum ExtractionPattern {
     /// Structured JSON with thought/action fields
     JsonStructured,
 
+    /// Structured YAML with thought/action fields, in a ```yaml fence or
+    /// a `---` document
+    YamlStructured,
+
     /// Tool calls embedded in response (OpenAI/Anthropic format)
     ToolCallsInline,
 
+    /// Fenced code blocks with a language tag, each run through the code
+    /// executor tool (not in the default pattern list)
+    CodeBlocks,
+
     /// Free-form text analysis
     FreeForm,
 }
@@ -181,6 +197,21 @@ pub enum ThoughtParseError {
     ClarificationNeeded,
 }
 
+/// Phrases that mark a response as giving up on the task, matched
+/// case-insensitively
+const DEFAULT_GIVE_UP_PHRASES: &[&str] = &[
+    "i don't know",
+    "i do not know",
+    "i cannot complete this",
+    "i can't complete this",
+    "i am unable to",
+    "i'm unable to",
+    "i give up",
+];
+
+/// Default tool that [`ExtractionPattern::CodeBlocks`] actions target
+pub const DEFAULT_CODE_EXECUTOR: &str = "run_code";
+
 /// Parser for LLM responses into structured thoughts
 ///
 /// The parser attempts multiple extraction patterns in sequence,
@@ -191,6 +222,12 @@ pub struct ThoughtParser {
 
     /// Strategy to use when all patterns fail
     fallback_strategy: FallbackStrategy,
+
+    /// Lowercased phrases that flag a thought as giving up
+    give_up_phrases: Vec<String>,
+
+    /// Tool that code block actions are sent to
+    code_executor: String,
 }
 
 impl Default for ThoughtParser {
@@ -205,17 +242,21 @@ impl ThoughtParser {
     /// Default pattern order:
     /// 1. ToolCallsInline (highest priority - direct tool calls)
     /// 2. JsonStructured (structured JSON responses)
-    /// 3. ReActClassic (traditional format)
-    /// 4. FreeForm (fallback for unstructured text)
+    /// 3. YamlStructured (structured YAML responses)
+    /// 4. ReActClassic (traditional format)
+    /// 5. FreeForm (fallback for unstructured text)
     pub fn new() -> Self {
         Self {
             patterns: vec![
                 ExtractionPattern::ToolCallsInline,
                 ExtractionPattern::JsonStructured,
+                ExtractionPattern::YamlStructured,
                 ExtractionPattern::ReActClassic,
                 ExtractionPattern::FreeForm,
             ],
             fallback_strategy: FallbackStrategy::TreatAsThought,
+            give_up_phrases: default_give_up_phrases(),
+            code_executor: DEFAULT_CODE_EXECUTOR.to_string(),
         }
     }
 
@@ -224,6 +265,8 @@ impl ThoughtParser {
         Self {
             patterns,
             fallback_strategy: FallbackStrategy::TreatAsThought,
+            give_up_phrases: default_give_up_phrases(),
+            code_executor: DEFAULT_CODE_EXECUTOR.to_string(),
         }
     }
 
@@ -233,6 +276,40 @@ impl ThoughtParser {
         self
     }
 
+    /// Replace the phrases that mark a response as giving up
+    ///
+    /// Matching is case-insensitive. An empty list disables detection.
+    pub fn with_give_up_phrases<I, S>(mut self, phrases: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.give_up_phrases = phrases
+            .into_iter()
+            .map(|phrase| phrase.into().to_lowercase())
+            .collect();
+        self
+    }
+
+    /// Set the tool that [`ExtractionPattern::CodeBlocks`] actions target
+    pub fn with_code_executor(mut self, tool: impl Into<String>) -> Self {
+        self.code_executor = tool.into();
+        self
+    }
+
+    /// Check whether a final answer opens with one of the give-up phrases
+    ///
+    /// Only the start of the answer is matched, so an answer that mentions
+    /// e.g. what a library "is unable to" do is not mistaken for giving up.
+    pub fn detect_give_up(&self, answer: &str) -> bool {
+        // Normalise curly apostrophes so "don’t" matches "don't"
+        let answer = answer.to_lowercase().replace('\u{2019}', "'");
+        let answer = answer.trim_start_matches(|c: char| !c.is_alphanumeric());
+        self.give_up_phrases
+            .iter()
+            .any(|phrase| answer.starts_with(phrase.as_str()))
+    }
+
     /// Parse LLM response into a structured thought
     ///
     /// # Arguments
@@ -254,15 +331,24 @@ impl ThoughtParser {
             return Err(ThoughtParseError::EmptyResponse);
         }
 
-        // Try each pattern in order
-        for pattern in &self.patterns {
-            if let Ok(thought) = self.try_pattern(trimmed, tool_calls, *pattern) {
-                return Ok(thought);
-            }
-        }
+        // Try each pattern in order, then the fallback if all of them fail
+        let mut thought = match self
+            .patterns
+            .iter()
+            .find_map(|pattern| self.try_pattern(trimmed, tool_calls, *pattern).ok())
+        {
+            Some(thought) => thought,
+            None => self.apply_fallback(trimmed)?,
+        };
 
-        // All patterns failed, use fallback
-        self.apply_fallback(trimmed)
+        // Only a final answer can give up; intermediate thoughts are still working
+        if thought.is_final_answer {
+            let answer = self
+                .extract_final_answer(&thought.reasoning)
+                .unwrap_or_else(|| thought.reasoning.clone());
+            thought.gave_up = self.detect_give_up(&answer);
+        }
+        Ok(thought)
     }
 
     /// Try a specific extraction pattern
@@ -275,6 +361,8 @@ impl ThoughtParser {
         match pattern {
             ExtractionPattern::ToolCallsInline => self.parse_tool_calls_inline(text, tool_calls),
             ExtractionPattern::JsonStructured => self.parse_json_structured(text),
+            ExtractionPattern::YamlStructured => self.parse_yaml_structured(text),
+            ExtractionPattern::CodeBlocks => self.parse_code_blocks(text),
             ExtractionPattern::ReActClassic => self.parse_react_classic(text),
             ExtractionPattern::FreeForm => self.parse_free_form(text),
         }
@@ -309,6 +397,7 @@ impl ThoughtParser {
             confidence: None,
             planned_actions,
             is_final_answer: is_final && tool_calls.is_empty(),
+            gave_up: false,
             raw_content: text.to_string(),
         })
     }
@@ -330,11 +419,66 @@ impl ThoughtParser {
 
         let value: JsonValue = serde_json::from_str(json_text)?;
 
+        self.thought_from_fields(&value, text)
+    }
+
+    /// Parse structured YAML response
+    ///
+    /// Accepts the same fields as [`parse_json_structured`](Self::parse_json_structured)
+    /// inside a ```yaml fence or a document starting with `---`:
+    /// ```yaml
+    /// thought: I need to check the weather
+    /// action: get_weather
+    /// arguments:
+    ///   city: London
+    /// ```
+    /// YAML plans are read more leniently than JSON ones: `tool`/`name` stand
+    /// in for `action`, a textual `final_answer` ends the loop and doubles as
+    /// the reasoning, and a list of actions may be given under `actions`.
+    fn parse_yaml_structured(&self, text: &str) -> Result<Thought, ThoughtParseError> {
+        let yaml_text = self
+            .extract_yaml(text)
+            .ok_or_else(|| ThoughtParseError::ParseError("No YAML document found".to_string()))?;
+
+        let yaml: serde_norway::Value = serde_norway::from_str(yaml_text)
+            .map_err(|e| ThoughtParseError::ParseError(format!("Invalid YAML: {e}")))?;
+        // Going through JSON keeps the field handling in one place
+        let mut value = serde_json::to_value(yaml)?;
+
+        let actions = match value.as_object_mut() {
+            Some(obj) => {
+                normalize_yaml_fields(obj);
+                obj.remove("actions")
+            }
+            None => None,
+        };
+
+        let mut thought = self.thought_from_fields(&value, text)?;
+        if let Some(JsonValue::Array(actions)) = actions {
+            thought
+                .planned_actions
+                .extend(actions.into_iter().filter_map(|action| match action {
+                    JsonValue::Object(mut action) => {
+                        normalize_yaml_fields(&mut action);
+                        self.planned_action_from_fields(&action)
+                    }
+                    _ => None,
+                }));
+        }
+        Ok(thought)
+    }
+
+    /// Build a thought from a parsed JSON object
+    fn thought_from_fields(
+        &self,
+        value: &JsonValue,
+        text: &str,
+    ) -> Result<Thought, ThoughtParseError> {
         let obj = match value.as_object() {
             Some(obj) => obj,
             None => {
                 return Err(ThoughtParseError::ParseError(
-                    "JSON is not an object".to_string(),
+                    "Structured response is not an object".to_string(),
                 ));
             }
         };
@@ -355,20 +499,7 @@ impl ThoughtParser {
         }
 
         // Extract action
-        let mut planned_actions = Vec::new();
-        if let Some(action) = obj.get("action").and_then(|v| v.as_str()) {
-            let arguments = obj.get("arguments").cloned();
-            let description = obj
-                .get("description")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Execute action");
-
-            let mut planned_action = PlannedAction::new(action, description);
-            if let Some(args) = arguments {
-                planned_action = planned_action.with_arguments(args);
-            }
-            planned_actions.push(planned_action);
-        }
+        let planned_actions = self.planned_action_from_fields(obj).into_iter().collect();
 
         // Extract final answer flag
         let is_final_answer = obj
@@ -388,6 +519,73 @@ impl ThoughtParser {
             confidence,
             planned_actions,
             is_final_answer,
+            gave_up: false,
+            raw_content: text.to_string(),
+        })
+    }
+
+    /// Build a planned action from an object with an `action` field
+    fn planned_action_from_fields(
+        &self,
+        obj: &serde_json::Map<String, JsonValue>,
+    ) -> Option<PlannedAction> {
+        let action = obj.get("action").and_then(|v| v.as_str())?;
+        let description = obj
+            .get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Execute action");
+
+        let mut planned_action = PlannedAction::new(action, description);
+        if let Some(args) = obj.get("arguments") {
+            planned_action = planned_action.with_arguments(args.clone());
+        }
+        Some(planned_action)
+    }
+
+    /// Parse fenced code blocks as code executor actions
+    ///
+    /// Each block with a language tag becomes one action whose arguments are
+    /// `{"language": ..., "code": ...}`. Untagged blocks are left alone, as
+    /// they usually hold output or prose. The text around the blocks is the
+    /// reasoning.
+    fn parse_code_blocks(&self, text: &str) -> Result<Thought, ThoughtParseError> {
+        static CODE_BLOCK: OnceLock<Regex> = OnceLock::new();
+        let re = CODE_BLOCK
+            .get_or_init(|| Regex::new(r"(?s)```([A-Za-z0-9_+#-]+)[ \t]*\n(.*?)```").unwrap());
+
+        let mut planned_actions = Vec::new();
+        let mut reasoning = String::new();
+        let mut last_end = 0;
+        for cap in re.captures_iter(text) {
+            let block = cap.get(0).expect("group 0 always matches");
+            let language = cap[1].to_lowercase();
+            let code = cap[2].trim_end();
+
+            reasoning.push_str(&text[last_end..block.start()]);
+            last_end = block.end();
+
+            planned_actions.push(
+                PlannedAction::new(&self.code_executor, format!("Run {} code", language))
+                    .with_arguments(serde_json::json!({
+                        "language": language,
+                        "code": code,
+                    })),
+            );
+        }
+
+        if planned_actions.is_empty() {
+            return Err(ThoughtParseError::ParseError(
+                "No code blocks found".to_string(),
+            ));
+        }
+        reasoning.push_str(&text[last_end..]);
+
+        Ok(Thought {
+            reasoning: reasoning.trim().to_string(),
+            confidence: None,
+            planned_actions,
+            is_final_answer: false,
+            gave_up: false,
             raw_content: text.to_string(),
         })
     }
@@ -427,6 +625,7 @@ impl ThoughtParser {
             confidence: None,
             planned_actions,
             is_final_answer: false,
+            gave_up: false,
             raw_content: text.to_string(),
         })
     }
@@ -440,6 +639,7 @@ impl ThoughtParser {
             confidence: None,
             planned_actions: Vec::new(),
             is_final_answer: is_final,
+            gave_up: false,
             raw_content: text.to_string(),
         })
     }
@@ -575,6 +775,24 @@ impl ThoughtParser {
         None
     }
 
+    /// Extract a YAML document from a ```yaml fence or a leading `---` marker
+    fn extract_yaml<'a>(&self, text: &'a str) -> Option<&'a str> {
+        static YAML_BLOCK: OnceLock<Regex> = OnceLock::new();
+        let re = YAML_BLOCK.get_or_init(|| Regex::new(r"(?s)```ya?ml[ \t]*\n(.*?)```").unwrap());
+
+        if let Some(cap) = re.captures(text) {
+            let mat = cap.get(1)?;
+            return Some(&text[mat.start()..mat.end()]);
+        }
+
+        let trimmed = text.trim_start();
+        if trimmed.starts_with("---") {
+            return Some(trimmed);
+        }
+
+        None
+    }
+
     /// Try to parse JSON, returning None if it fails
     fn try_parse_json(&self, text: &str) -> Option<JsonValue> {
         serde_json::from_str(text).ok()
@@ -590,6 +808,147 @@ impl ThoughtParser {
     }
 }
 
+/// Incremental parser for responses arriving in chunks from a streaming backend
+///
+/// Chunks are accumulated and the [`ThoughtParser`] patterns are re-run on
+/// the buffer, so the UI can show reasoning as it arrives. Actions and the
+/// final answer are only reported from complete lines (or a complete JSON
+/// object), since a partial line may still change. Call
+/// [`finish`](Self::finish) once the response is complete for the same
+/// result as a one-shot [`ThoughtParser::parse`].
+pub struct StreamingThoughtParser {
+    parser: ThoughtParser,
+    buffer: String,
+}
+
+impl Default for StreamingThoughtParser {
+    fn default() -> Self {
+        Self::new(ThoughtParser::new())
+    }
+}
+
+impl StreamingThoughtParser {
+    /// Create a streaming parser using `parser`'s patterns and settings
+    pub fn new(parser: ThoughtParser) -> Self {
+        Self {
+            parser,
+            buffer: String::new(),
+        }
+    }
+
+    /// Append a chunk of the response
+    pub fn push(&mut self, chunk: &str) {
+        self.buffer.push_str(chunk);
+    }
+
+    /// The response received so far
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Reasoning extracted from the response so far
+    ///
+    /// A partial line after the first is left out until it completes, since
+    /// it may turn out to be an `Action:` marker. Empty while a structured
+    /// JSON response is still incomplete.
+    pub fn reasoning(&self) -> String {
+        if let Some(thought) = self.structured_thought() {
+            return thought.reasoning;
+        }
+        if self.is_pending_json() {
+            return String::new();
+        }
+        let text = match self.buffer.rfind('\n') {
+            Some(idx) => &self.buffer[..idx],
+            None => &self.buffer,
+        };
+        self.parser.extract_reasoning(text)
+    }
+
+    /// Actions planned on complete lines (or in a complete JSON object)
+    pub fn planned_actions(&self) -> Vec<PlannedAction> {
+        match self.structured_thought() {
+            Some(thought) => thought.planned_actions,
+            None if self.is_pending_json() => Vec::new(),
+            None => self.parser.extract_planned_actions(self.complete_lines()),
+        }
+    }
+
+    /// The final answer, once its line (or the JSON object) is complete
+    pub fn final_answer(&self) -> Option<String> {
+        match self.structured_thought() {
+            Some(thought) => thought.is_final_answer.then_some(thought.reasoning),
+            None if self.is_pending_json() => None,
+            None => self.parser.extract_final_answer(self.complete_lines()),
+        }
+    }
+
+    /// Parse the complete response, consuming the stream
+    pub fn finish(self) -> Result<Thought, ThoughtParseError> {
+        self.parser.parse(&self.buffer, &[])
+    }
+
+    /// The buffer up to and including its last newline
+    fn complete_lines(&self) -> &str {
+        match self.buffer.rfind('\n') {
+            Some(idx) => &self.buffer[..=idx],
+            None => "",
+        }
+    }
+
+    /// Whether the buffer looks like a JSON response that hasn't closed yet
+    fn is_pending_json(&self) -> bool {
+        let trimmed = self.buffer.trim_start();
+        (trimmed.starts_with('{') || trimmed.starts_with("```json"))
+            && self.structured_thought().is_none()
+    }
+
+    /// The thought from a complete JSON response, if one has arrived
+    fn structured_thought(&self) -> Option<Thought> {
+        let json_text = self.parser.extract_json(&self.buffer)?;
+        self.parser.try_parse_json(json_text)?;
+        self.parser.parse_json_structured(&self.buffer).ok()
+    }
+}
+
+/// Rewrite the YAML-only spellings of a plan into the fields the JSON
+/// parser reads: `tool`/`name` become `action`, and a textual
+/// `final_answer` becomes the reasoning plus a `true` flag
+fn normalize_yaml_fields(obj: &mut serde_json::Map<String, JsonValue>) {
+    if !obj.contains_key("action") {
+        if let Some(tool) = obj
+            .get("tool")
+            .or_else(|| obj.get("name"))
+            .filter(|v| v.is_string())
+            .cloned()
+        {
+            obj.insert("action".to_string(), tool);
+        }
+    }
+
+    let answer = obj
+        .get("final_answer")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .map(str::to_string);
+    if let Some(answer) = answer {
+        if !["thought", "reasoning", "analysis"]
+            .iter()
+            .any(|key| obj.get(*key).is_some_and(|v| v.is_string()))
+        {
+            obj.insert("thought".to_string(), JsonValue::String(answer));
+        }
+        obj.insert("final_answer".to_string(), JsonValue::Bool(true));
+    }
+}
+
+fn default_give_up_phrases() -> Vec<String> {
+    DEFAULT_GIVE_UP_PHRASES
+        .iter()
+        .map(|phrase| phrase.to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -659,6 +1018,146 @@ mod tests {
         assert_eq!(thought.planned_actions.len(), 1);
     }
 
+    #[test]
+    fn test_parse_yaml_structured() {
+        let parser = ThoughtParser::new();
+        let response = r#"Here is my plan:
+```yaml
+thought: I need the weather before answering
+action: get_weather
+arguments:
+  city: London
+  units: metric
+confidence: 0.9
+```
+"#;
+
+        let thought = parser.parse(response, &[]).unwrap();
+
+        assert!(!thought.is_final_answer);
+        assert_eq!(thought.reasoning, "I need the weather before answering");
+        assert_eq!(thought.confidence, Some(0.9));
+        assert_eq!(thought.planned_actions.len(), 1);
+        assert_eq!(thought.planned_actions[0].tool_name, "get_weather");
+        assert_eq!(
+            thought.planned_actions[0].arguments,
+            Some(serde_json::json!({"city": "London", "units": "metric"}))
+        );
+    }
+
+    #[test]
+    fn test_parse_yaml_actions_and_final_answer() {
+        let parser = ThoughtParser::with_patterns(vec![ExtractionPattern::YamlStructured]);
+
+        let response = "---\nthought: Read both files\nactions:\n  - tool: cat\n    arguments: {path: a.txt}\n  - action: cat\n    arguments: {path: b.txt}\n";
+        let thought = parser.parse(response, &[]).unwrap();
+        assert_eq!(thought.planned_actions.len(), 2);
+        assert_eq!(
+            thought.planned_actions[1].arguments,
+            Some(serde_json::json!({"path": "b.txt"}))
+        );
+
+        let response = "```yml\nfinal_answer: It is sunny\n```";
+        let thought = parser.parse(response, &[]).unwrap();
+        assert!(thought.is_final_answer);
+        assert_eq!(thought.reasoning, "It is sunny");
+    }
+
+    #[test]
+    fn test_json_ignores_yaml_only_fields() {
+        let parser = ThoughtParser::with_patterns(vec![ExtractionPattern::JsonStructured])
+            .with_fallback(FallbackStrategy::RequestClarification);
+
+        let response = r#"{"thought": "Read it", "tool": "cat", "actions": [{"action": "ls"}], "final_answer": "done"}"#;
+        let thought = parser.parse(response, &[]).unwrap();
+        assert!(thought.planned_actions.is_empty());
+        assert!(!thought.is_final_answer);
+
+        assert!(matches!(
+            parser.parse(r#"{"final_answer": "It is sunny"}"#, &[]),
+            Err(ThoughtParseError::ClarificationNeeded)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_yaml_falls_through() {
+        let parser = ThoughtParser::new();
+        let response = "```yaml\nthought: [unclosed\n```\nAction: search";
+
+        let thought = parser.parse(response, &[]).unwrap();
+        assert_eq!(thought.planned_actions.len(), 1);
+        assert_eq!(thought.planned_actions[0].tool_name, "search");
+
+        let yaml_only = ThoughtParser::with_patterns(vec![ExtractionPattern::YamlStructured])
+            .with_fallback(FallbackStrategy::RequestClarification);
+        assert!(matches!(
+            yaml_only.parse("thought: bare yaml is not picked up", &[]),
+            Err(ThoughtParseError::ClarificationNeeded)
+        ));
+    }
+
+    #[test]
+    fn test_streaming_parser_matches_one_shot() {
+        let response = "Thought: I need to check the weather in London\nAction: get_weather\nAction Input: {\"city\": \"London\"}";
+        let chunks = [
+            "Thought: I need",
+            " to check the weather",
+            " in London\nAct",
+            "ion: get_wea",
+            "ther\n",
+            "Action Input: {\"city\": ",
+            "\"London\"}",
+        ];
+
+        let mut stream = StreamingThoughtParser::default();
+        let mut reasoning_lengths = Vec::new();
+        let mut action_counts = Vec::new();
+        for chunk in chunks {
+            stream.push(chunk);
+            reasoning_lengths.push(stream.reasoning().len());
+            action_counts.push(stream.planned_actions().len());
+        }
+
+        // Reasoning grows while the thought streams in, then settles
+        assert!(reasoning_lengths[0] < reasoning_lengths[1]);
+        assert!(reasoning_lengths[1] < reasoning_lengths[2]);
+        assert!(reasoning_lengths.windows(2).all(|w| w[0] <= w[1]));
+        // The action is only reported once its line is complete
+        assert_eq!(action_counts, vec![0, 0, 0, 0, 1, 1, 1]);
+        assert_eq!(stream.final_answer(), None);
+        assert_eq!(stream.buffer(), response);
+
+        let streamed = stream.finish().unwrap();
+        let one_shot = ThoughtParser::new().parse(response, &[]).unwrap();
+        assert_eq!(streamed.reasoning, one_shot.reasoning);
+        assert_eq!(streamed.is_final_answer, one_shot.is_final_answer);
+        assert_eq!(streamed.planned_actions.len(), 1);
+        assert_eq!(streamed.planned_actions[0].tool_name, "get_weather");
+        assert_eq!(
+            streamed.planned_actions[0].arguments,
+            one_shot.planned_actions[0].arguments
+        );
+    }
+
+    #[test]
+    fn test_streaming_parser_final_answer_and_json() {
+        let mut stream = StreamingThoughtParser::default();
+        stream.push("Thought: Done\nFinal Answer: It is sun");
+        assert_eq!(stream.final_answer(), None);
+        stream.push("ny\n");
+        assert_eq!(stream.final_answer().as_deref(), Some("It is sunny"));
+        assert!(stream.finish().unwrap().is_final_answer);
+
+        let mut stream = StreamingThoughtParser::default();
+        stream.push(r#"{"thought": "Need the weather", "action": "get_weather""#);
+        assert!(stream.reasoning().is_empty());
+        assert!(stream.planned_actions().is_empty());
+        stream.push(r#", "final_answer": false}"#);
+        assert_eq!(stream.reasoning(), "Need the weather");
+        assert_eq!(stream.planned_actions().len(), 1);
+        assert_eq!(stream.final_answer(), None);
+    }
+
     #[test]
     fn test_parse_tool_calls_inline() {
         let parser = ThoughtParser::new();
@@ -762,6 +1261,111 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_single_code_block() {
+        let parser = ThoughtParser::with_patterns(vec![
+            ExtractionPattern::CodeBlocks,
+            ExtractionPattern::FreeForm,
+        ]);
+        let response = "Let me compute it.\n```python\nprint(2 + 2)\n```";
+
+        let thought = parser.parse(response, &[]).unwrap();
+        assert_eq!(thought.reasoning, "Let me compute it.");
+        assert_eq!(thought.planned_actions.len(), 1);
+        assert_eq!(thought.planned_actions[0].tool_name, DEFAULT_CODE_EXECUTOR);
+        assert_eq!(
+            thought.planned_actions[0].arguments,
+            Some(serde_json::json!({"language": "python", "code": "print(2 + 2)"}))
+        );
+
+        // Non-code responses fall through to the next pattern
+        let thought = parser.parse("Final Answer: 4", &[]).unwrap();
+        assert!(thought.planned_actions.is_empty());
+        assert!(thought.is_final_answer);
+    }
+
+    #[test]
+    fn test_parse_multiple_code_blocks() {
+        let parser = ThoughtParser::with_patterns(vec![ExtractionPattern::CodeBlocks])
+            .with_code_executor("sandbox");
+        let response = "First set up:\n```Python\nx = 1\n```\nThen check:\n```bash\nls -la\n```\n```\nplain output\n```";
+
+        let thought = parser.parse(response, &[]).unwrap();
+        assert_eq!(thought.planned_actions.len(), 2);
+        assert!(thought
+            .planned_actions
+            .iter()
+            .all(|action| action.tool_name == "sandbox"));
+        let args: Vec<_> = thought
+            .planned_actions
+            .iter()
+            .map(|action| action.arguments.clone().unwrap())
+            .collect();
+        assert_eq!(args[0]["language"], "python");
+        assert_eq!(args[0]["code"], "x = 1");
+        assert_eq!(args[1]["language"], "bash");
+        assert_eq!(args[1]["code"], "ls -la");
+        assert!(thought.reasoning.starts_with("First set up:"));
+        assert!(thought.reasoning.contains("Then check:"));
+    }
+
+    #[test]
+    fn test_detect_give_up_phrases() {
+        let parser = ThoughtParser::new();
+
+        let thought = parser
+            .parse("Final Answer: I don't know which file you mean.", &[])
+            .unwrap();
+        assert!(thought.gave_up);
+
+        let thought = parser
+            .parse(
+                "Final Answer: I CANNOT COMPLETE THIS without network access.",
+                &[],
+            )
+            .unwrap();
+        assert!(thought.gave_up);
+
+        // An intermediate thought is still working on the task
+        let thought = parser
+            .parse("Thought: I don't know which file yet, let me think.", &[])
+            .unwrap();
+        assert!(!thought.is_final_answer);
+        assert!(!thought.gave_up);
+
+        // Still planning actions, so not a give-up
+        let thought = parser
+            .parse("Thought: I don't know yet\nAction: ls", &[])
+            .unwrap();
+        assert!(!thought.gave_up);
+
+        let thought = parser.parse("Final Answer: 42", &[]).unwrap();
+        assert!(!thought.gave_up);
+
+        // A phrase later in a real answer is not a give-up
+        let thought = parser
+            .parse(
+                "Thought: I don't know the API yet, so I checked the docs.\n\
+                 Final Answer: The library is unable to stream, so use Y. \
+                 I'm unable to find a workaround in X itself.",
+                &[],
+            )
+            .unwrap();
+        assert!(thought.is_final_answer);
+        assert!(!thought.gave_up);
+    }
+
+    #[test]
+    fn test_custom_give_up_phrases() {
+        let parser = ThoughtParser::new().with_give_up_phrases(["No Idea"]);
+        let parse = |parser: &ThoughtParser, text: &str| parser.parse(text, &[]).unwrap();
+        assert!(parse(&parser, "Final Answer: no idea, sorry").gave_up);
+        assert!(!parse(&parser, "Final Answer: I don't know").gave_up);
+
+        let parser = ThoughtParser::new().with_give_up_phrases(Vec::<String>::new());
+        assert!(!parse(&parser, "Final Answer: I give up").gave_up);
+    }
+
     #[test]
     fn test_action_with_inline_args() {
         let parser = ThoughtParser::new();
@@ -463,6 +463,11 @@ impl App {
             InputEvent::Resize(_, _) => {
                 // Nothing to do yet, layout is responsive.
             }
+            InputEvent::Mouse(_) => {
+                // Mouse capture is not enabled for the app's panes, so there is
+                // nothing to route; mouse-aware components are driven through
+                // `ComponentManager::handle_mouse` by whoever hosts them.
+            }
             InputEvent::Key(key) => self.handle_key(key, runtime)?,
         }
         Ok(())
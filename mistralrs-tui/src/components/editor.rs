@@ -6,22 +6,37 @@
 //! - Line numbers
 //! - Cursor navigation
 //! - File loading/saving
+//! - Lazy, read-only viewing of large files
+//! - Indentation-based folding
+//! - Auto-save after a period of inactivity
 //! - Language detection
 
 use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    collections::BTreeMap,
     fs,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    ops::Range,
     path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::file_explorer::FileExplorerComponent;
+use super::traits::{rect_contains, Component, ComponentContext, ComponentManager, EventResult};
+use crate::input::{InputEvent, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
 /// Editor mode (vim-like)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -70,6 +85,38 @@ impl SyntaxPattern {
     }
 }
 
+/// Color theme mapping token categories to styles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyntaxTheme {
+    /// Language keywords
+    pub keyword: Style,
+    /// String literals
+    pub string: Style,
+    /// Comments
+    pub comment: Style,
+    /// Numeric literals
+    pub number: Style,
+    /// Macro invocations (and Python decorators)
+    pub macro_call: Style,
+    /// Attributes
+    pub attribute: Style,
+}
+
+impl Default for SyntaxTheme {
+    fn default() -> Self {
+        Self {
+            keyword: Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+            string: Style::default().fg(Color::Green),
+            comment: Style::default().fg(Color::DarkGray),
+            number: Style::default().fg(Color::Yellow),
+            macro_call: Style::default().fg(Color::Cyan),
+            attribute: Style::default().fg(Color::Blue),
+        }
+    }
+}
+
 /// Language-specific syntax patterns
 #[derive(Debug, Clone)]
 struct LanguageSyntax {
@@ -77,80 +124,70 @@ struct LanguageSyntax {
 }
 
 impl LanguageSyntax {
+    /// Create syntax patterns for a detected language
+    fn for_language(language: &str, theme: &SyntaxTheme) -> Result<Self> {
+        match language {
+            "rust" => Self::rust(theme),
+            "python" => Self::python(theme),
+            _ => Self::default(theme),
+        }
+    }
+
     /// Create syntax patterns for Rust
-    fn rust() -> Result<Self> {
+    fn rust(theme: &SyntaxTheme) -> Result<Self> {
         Ok(Self {
             patterns: vec![
-                // Keywords (bold magenta)
+                // Keywords
                 SyntaxPattern::new(
                     r"\b(fn|let|mut|const|static|struct|enum|impl|trait|pub|use|mod|crate|self|super|as|where|for|while|loop|if|else|match|return|break|continue|async|await|dyn|Box|Vec|String|Result|Option|Some|None|Ok|Err)\b",
-                    Style::default()
-                        .fg(Color::Magenta)
-                        .add_modifier(Modifier::BOLD),
-                )?,
-                // Strings (green)
-                SyntaxPattern::new(
-                    r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#,
-                    Style::default().fg(Color::Green),
-                )?,
-                // Comments (dark gray)
-                SyntaxPattern::new(r"//.*$", Style::default().fg(Color::DarkGray))?,
-                // Numbers (yellow)
-                SyntaxPattern::new(
-                    r"\b\d+(?:\.\d+)?(?:[eE][+-]?\d+)?\b",
-                    Style::default().fg(Color::Yellow),
+                    theme.keyword,
                 )?,
-                // Macros (cyan)
-                SyntaxPattern::new(r"\b\w+!", Style::default().fg(Color::Cyan))?,
-                // Attributes (blue)
-                SyntaxPattern::new(r"#\[.*?\]", Style::default().fg(Color::Blue))?,
+                // Strings
+                SyntaxPattern::new(r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#, theme.string)?,
+                // Comments
+                SyntaxPattern::new(r"//.*$", theme.comment)?,
+                // Numbers
+                SyntaxPattern::new(r"\b\d+(?:\.\d+)?(?:[eE][+-]?\d+)?\b", theme.number)?,
+                // Macros
+                SyntaxPattern::new(r"\b\w+!", theme.macro_call)?,
+                // Attributes
+                SyntaxPattern::new(r"#\[.*?\]", theme.attribute)?,
             ],
         })
     }
 
     /// Create syntax patterns for Python
-    fn python() -> Result<Self> {
+    fn python(theme: &SyntaxTheme) -> Result<Self> {
         Ok(Self {
             patterns: vec![
-                // Keywords (bold magenta)
+                // Keywords
                 SyntaxPattern::new(
                     r"\b(def|class|import|from|as|if|elif|else|for|while|return|yield|break|continue|pass|try|except|finally|with|async|await|lambda|and|or|not|is|in|True|False|None)\b",
-                    Style::default()
-                        .fg(Color::Magenta)
-                        .add_modifier(Modifier::BOLD),
+                    theme.keyword,
                 )?,
-                // Strings (green) - including triple quotes
+                // Strings - including triple quotes
                 SyntaxPattern::new(
                     r#"(?:"""(?:[^"\\]|\\.)*"""|'''(?:[^'\\]|\\.)*'''|"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*')"#,
-                    Style::default().fg(Color::Green),
+                    theme.string,
                 )?,
-                // Comments (dark gray)
-                SyntaxPattern::new(r"#.*$", Style::default().fg(Color::DarkGray))?,
-                // Numbers (yellow)
-                SyntaxPattern::new(
-                    r"\b\d+(?:\.\d+)?(?:[eE][+-]?\d+)?\b",
-                    Style::default().fg(Color::Yellow),
-                )?,
-                // Decorators (cyan)
-                SyntaxPattern::new(r"@\w+", Style::default().fg(Color::Cyan))?,
+                // Comments
+                SyntaxPattern::new(r"#.*$", theme.comment)?,
+                // Numbers
+                SyntaxPattern::new(r"\b\d+(?:\.\d+)?(?:[eE][+-]?\d+)?\b", theme.number)?,
+                // Decorators
+                SyntaxPattern::new(r"@\w+", theme.macro_call)?,
             ],
         })
     }
 
     /// Create default syntax (minimal highlighting)
-    fn default() -> Result<Self> {
+    fn default(theme: &SyntaxTheme) -> Result<Self> {
         Ok(Self {
             patterns: vec![
-                // Strings (green)
-                SyntaxPattern::new(
-                    r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#,
-                    Style::default().fg(Color::Green),
-                )?,
-                // Numbers (yellow)
-                SyntaxPattern::new(
-                    r"\b\d+(?:\.\d+)?(?:[eE][+-]?\d+)?\b",
-                    Style::default().fg(Color::Yellow),
-                )?,
+                // Strings
+                SyntaxPattern::new(r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#, theme.string)?,
+                // Numbers
+                SyntaxPattern::new(r"\b\d+(?:\.\d+)?(?:[eE][+-]?\d+)?\b", theme.number)?,
             ],
         })
     }
@@ -200,6 +237,202 @@ impl LanguageSyntax {
     }
 }
 
+/// Number of lines between byte-offset checkpoints in a lazy line index
+const LAZY_CHECKPOINT_INTERVAL: usize = 256;
+
+/// Configuration for opening large files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LargeFileConfig {
+    /// Files at or above this size (in bytes) are opened in lazy, read-only mode
+    pub threshold_bytes: u64,
+    /// Maximum number of lines kept resident in memory while in lazy mode
+    pub max_resident_lines: usize,
+}
+
+impl Default for LargeFileConfig {
+    fn default() -> Self {
+        Self {
+            threshold_bytes: 16 * 1024 * 1024,
+            max_resident_lines: 10_000,
+        }
+    }
+}
+
+/// Sparse line index for a file that is too large to hold in memory
+///
+/// Only a window of lines is loaded into `EditorState::content`; the rest is
+/// read from disk on demand, seeking to the nearest checkpoint.
+#[derive(Debug, Clone)]
+struct LazyBuffer {
+    path: PathBuf,
+    /// Byte offset of every `LAZY_CHECKPOINT_INTERVAL`-th line
+    checkpoints: Vec<u64>,
+    /// Total number of lines in the file
+    total_lines: usize,
+    /// Total number of characters, counting one per line break
+    total_chars: usize,
+    /// Absolute line number of `content[0]`
+    window_start: usize,
+    /// Maximum number of resident lines
+    max_resident_lines: usize,
+}
+
+impl LazyBuffer {
+    /// Scan a file once to build its checkpoint index
+    fn index(path: &Path, max_resident_lines: usize) -> Result<Self> {
+        let file =
+            fs::File::open(path).with_context(|| format!("opening file: {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let mut checkpoints = vec![0];
+        let mut offset = 0u64;
+        let mut total_lines = 0;
+        let mut total_chars = 0;
+        let mut buf = Vec::new();
+
+        loop {
+            buf.clear();
+            let read = reader
+                .read_until(b'\n', &mut buf)
+                .with_context(|| format!("indexing file: {}", path.display()))?;
+            if read == 0 {
+                break;
+            }
+            offset += read as u64;
+            total_lines += 1;
+
+            // Count UTF-8 scalar values without decoding (skip continuation bytes)
+            let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            total_chars += line.iter().filter(|&&b| b & 0xC0 != 0x80).count();
+            if total_lines % LAZY_CHECKPOINT_INTERVAL == 0 {
+                checkpoints.push(offset);
+            }
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            checkpoints,
+            total_lines: total_lines.max(1),
+            total_chars: total_chars + total_lines.saturating_sub(1),
+            window_start: 0,
+            max_resident_lines: max_resident_lines.max(1),
+        })
+    }
+
+    /// Read up to `max_resident_lines` lines starting at line `start`
+    fn read_window(&self, start: usize) -> Result<Vec<String>> {
+        let checkpoint = (start / LAZY_CHECKPOINT_INTERVAL).min(self.checkpoints.len() - 1);
+        let mut file = fs::File::open(&self.path)
+            .with_context(|| format!("opening file: {}", self.path.display()))?;
+        file.seek(SeekFrom::Start(self.checkpoints[checkpoint]))?;
+
+        let mut reader = BufReader::new(file);
+        let mut line_no = checkpoint * LAZY_CHECKPOINT_INTERVAL;
+        let mut lines = Vec::with_capacity(self.max_resident_lines.min(self.total_lines));
+        let mut buf = Vec::new();
+
+        while lines.len() < self.max_resident_lines {
+            buf.clear();
+            if reader.read_until(b'\n', &mut buf)? == 0 {
+                break;
+            }
+            if line_no >= start {
+                lines.push(decode_line(&buf));
+            }
+            line_no += 1;
+        }
+
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        Ok(lines)
+    }
+}
+
+/// Decode a raw line, stripping the trailing `\n` or `\r\n`
+fn decode_line(buf: &[u8]) -> String {
+    let buf = buf.strip_suffix(b"\n").unwrap_or(buf);
+    let buf = buf.strip_suffix(b"\r").unwrap_or(buf);
+    String::from_utf8_lossy(buf).into_owned()
+}
+
+/// Display width of a line's leading whitespace
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Character class used by word motions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punct,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_whitespace() {
+            Self::Space
+        } else if c.is_alphanumeric() || c == '_' {
+            Self::Word
+        } else {
+            Self::Punct
+        }
+    }
+}
+
+/// Lines moved per mouse wheel step
+const SCROLL_LINES: usize = 3;
+
+/// Display width of a tab when marking trailing whitespace or measuring indentation
+const TAB_WIDTH: usize = 4;
+
+/// Maximum number of line edits kept for undo
+const UNDO_LIMIT: usize = 100;
+
+/// Reversible line-level edit
+///
+/// `inserted` lines starting at `row` replaced the `removed` lines.
+#[derive(Debug, Clone)]
+struct LineEdit {
+    row: usize,
+    removed: Vec<String>,
+    inserted: usize,
+    cursor: (usize, usize),
+}
+
+/// Persistable view state of an [`EditorState`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorViewState {
+    pub file_path: Option<PathBuf>,
+    pub cursor_row: usize,
+    pub cursor_col: usize,
+    pub scroll_offset: usize,
+}
+
+/// Cursor position and buffer summary for a host status bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorStatus {
+    /// Cursor row (1-based)
+    pub row: usize,
+    /// Cursor column (1-based, in characters)
+    pub col: usize,
+    /// Total number of lines
+    pub total_lines: usize,
+    /// Total number of characters, counting one per line break
+    pub total_chars: usize,
+    /// Whether content has been modified
+    pub modified: bool,
+    /// Current editor mode
+    pub mode: EditorMode,
+    /// Detected language
+    pub language: String,
+}
+
 /// Editor state
 #[derive(Debug, Clone)]
 pub struct EditorState {
@@ -219,8 +452,22 @@ pub struct EditorState {
     pub modified: bool,
     /// Current editor mode
     pub mode: EditorMode,
+    /// Whether to mark trailing whitespace when rendering
+    pub show_trailing_whitespace: bool,
+    /// Whether edits, insert mode and saving are disabled
+    pub read_only: bool,
     /// Syntax highlighting patterns
     syntax: LanguageSyntax,
+    /// Colors used for syntax highlighting
+    theme: SyntaxTheme,
+    /// Line index when viewing a large file lazily
+    lazy: Option<LazyBuffer>,
+    /// Line edits that can be undone, most recent last
+    undo_stack: Vec<LineEdit>,
+    /// Folded rows, mapping each fold's header row to its last hidden row
+    folds: BTreeMap<usize, usize>,
+    /// When the content was last edited, for idle auto-save
+    last_edit: Option<Instant>,
 }
 
 impl EditorState {
@@ -235,12 +482,31 @@ impl EditorState {
             language: "text".to_string(),
             modified: false,
             mode: EditorMode::Normal,
-            syntax: LanguageSyntax::default()?,
+            show_trailing_whitespace: false,
+            read_only: false,
+            syntax: LanguageSyntax::default(&SyntaxTheme::default())?,
+            theme: SyntaxTheme::default(),
+            lazy: None,
+            undo_stack: Vec::new(),
+            folds: BTreeMap::new(),
+            last_edit: None,
         })
     }
 
     /// Open a file in the editor
     pub fn open_file(path: &Path) -> Result<Self> {
+        Self::open_file_with_config(path, &LargeFileConfig::default())
+    }
+
+    /// Open a file, switching to lazy read-only mode if it exceeds the size threshold
+    pub fn open_file_with_config(path: &Path, config: &LargeFileConfig) -> Result<Self> {
+        let size = fs::metadata(path)
+            .with_context(|| format!("reading metadata: {}", path.display()))?
+            .len();
+        if size >= config.threshold_bytes {
+            return Self::open_lazy(path, config.max_resident_lines);
+        }
+
         let content_str = fs::read_to_string(path)
             .with_context(|| format!("reading file: {}", path.display()))?;
 
@@ -251,11 +517,37 @@ impl EditorState {
         };
 
         let language = detect_language(path);
-        let syntax = match language.as_str() {
-            "rust" => LanguageSyntax::rust()?,
-            "python" => LanguageSyntax::python()?,
-            _ => LanguageSyntax::default()?,
-        };
+        let theme = SyntaxTheme::default();
+        let syntax = LanguageSyntax::for_language(&language, &theme)?;
+
+        Ok(Self {
+            content,
+            cursor_row: 0,
+            cursor_col: 0,
+            scroll_offset: 0,
+            file_path: Some(path.to_path_buf()),
+            language,
+            modified: false,
+            mode: EditorMode::Normal,
+            show_trailing_whitespace: false,
+            read_only: false,
+            syntax,
+            theme,
+            lazy: None,
+            undo_stack: Vec::new(),
+            folds: BTreeMap::new(),
+            last_edit: None,
+        })
+    }
+
+    /// Open a file lazily, keeping at most `max_resident_lines` lines in memory
+    fn open_lazy(path: &Path, max_resident_lines: usize) -> Result<Self> {
+        let lazy = LazyBuffer::index(path, max_resident_lines)?;
+        let content = lazy.read_window(0)?;
+
+        let language = detect_language(path);
+        let theme = SyntaxTheme::default();
+        let syntax = LanguageSyntax::for_language(&language, &theme)?;
 
         Ok(Self {
             content,
@@ -266,12 +558,111 @@ impl EditorState {
             language,
             modified: false,
             mode: EditorMode::Normal,
+            show_trailing_whitespace: false,
+            read_only: false,
             syntax,
+            theme,
+            lazy: Some(lazy),
+            undo_stack: Vec::new(),
+            folds: BTreeMap::new(),
+            last_edit: None,
         })
     }
 
+    /// Whether the file is being viewed lazily (read-only)
+    pub fn is_lazy(&self) -> bool {
+        self.lazy.is_some()
+    }
+
+    /// Whether the buffer can't be edited, either by request or because it
+    /// is viewed lazily
+    pub fn is_read_only(&self) -> bool {
+        self.read_only || self.is_lazy()
+    }
+
+    /// Number of lines currently held in memory
+    pub fn resident_line_count(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Get a line by absolute row, if it is resident
+    pub fn line(&self, row: usize) -> Option<&str> {
+        let start = self.lazy.as_ref().map_or(0, |lazy| lazy.window_start);
+        row.checked_sub(start)
+            .and_then(|idx| self.content.get(idx))
+            .map(String::as_str)
+    }
+
+    /// Length of a line by absolute row (0 if not resident)
+    fn line_len(&self, row: usize) -> usize {
+        self.line(row).map_or(0, str::len)
+    }
+
+    /// Make sure rows `first..first + count` are resident, reloading the window if needed
+    fn ensure_resident(&mut self, first: usize, count: usize) {
+        let Some(lazy) = self.lazy.as_mut() else {
+            return;
+        };
+
+        let last = (first + count).min(lazy.total_lines);
+        if first >= lazy.window_start && last <= lazy.window_start + self.content.len() {
+            return;
+        }
+
+        // Center the requested range in the new window
+        let slack = lazy.max_resident_lines.saturating_sub(count) / 2;
+        let start = first
+            .saturating_sub(slack)
+            .min(lazy.total_lines.saturating_sub(lazy.max_resident_lines));
+
+        match lazy.read_window(start) {
+            Ok(lines) => {
+                lazy.window_start = start;
+                self.content = lines;
+            }
+            Err(e) => tracing::warn!("Failed to load lines from {}: {e:#}", lazy.path.display()),
+        }
+    }
+
+    /// Capture the view state for persistence
+    pub fn view_state(&self) -> EditorViewState {
+        EditorViewState {
+            file_path: self.file_path.clone(),
+            cursor_row: self.cursor_row,
+            cursor_col: self.cursor_col,
+            scroll_offset: self.scroll_offset,
+        }
+    }
+
+    /// Restore a view state captured by [`view_state`](Self::view_state)
+    ///
+    /// Reopens the saved file if a different one is loaded, keeping the
+    /// current theme, then clamps the cursor to the content.
+    pub fn restore_view_state(&mut self, view: EditorViewState) -> Result<()> {
+        if let Some(path) = &view.file_path {
+            if self.file_path.as_ref() != Some(path) {
+                let theme = self.theme;
+                *self = Self::open_file(path)?;
+                self.set_theme(theme)?;
+            }
+        }
+
+        self.cursor_row = view.cursor_row.min(self.line_count().saturating_sub(1));
+        self.scroll_offset = view.scroll_offset.min(self.cursor_row);
+        self.scroll_to_cursor();
+        self.cursor_col = view.cursor_col;
+        self.clamp_cursor_col();
+        Ok(())
+    }
+
     /// Save the current content to file
     pub fn save(&mut self) -> Result<()> {
+        if self.is_lazy() {
+            anyhow::bail!("Large file is open read-only");
+        }
+        if self.read_only {
+            anyhow::bail!("File is open read-only");
+        }
         if let Some(path) = &self.file_path {
             let content_str = self.content.join("\n");
             fs::write(path, content_str)
@@ -285,6 +676,12 @@ impl EditorState {
 
     /// Save to a specific path
     pub fn save_as(&mut self, path: &Path) -> Result<()> {
+        if self.is_lazy() {
+            anyhow::bail!("Large file is open read-only");
+        }
+        if self.read_only {
+            anyhow::bail!("File is open read-only");
+        }
         let content_str = self.content.join("\n");
         fs::write(path, content_str)
             .with_context(|| format!("writing file: {}", path.display()))?;
@@ -294,24 +691,70 @@ impl EditorState {
 
         // Update language detection
         self.language = detect_language(path);
-        self.syntax = match self.language.as_str() {
-            "rust" => LanguageSyntax::rust()?,
-            "python" => LanguageSyntax::python()?,
-            _ => LanguageSyntax::default()?,
-        };
+        self.syntax = LanguageSyntax::for_language(&self.language, &self.theme)?;
 
         Ok(())
     }
 
+    /// Record an edit, for the modified flag and idle auto-save
+    fn mark_modified(&mut self) {
+        self.modified = true;
+        self.last_edit = Some(Instant::now());
+    }
+
+    /// Whether unsaved edits have been idle for at least `idle`
+    ///
+    /// Never true for read-only buffers or buffers without a file path, since
+    /// [`save`](Self::save) would fail for them.
+    pub fn should_autosave(&self, idle: Duration) -> bool {
+        self.should_autosave_at(idle, Instant::now())
+    }
+
+    /// [`should_autosave`](Self::should_autosave) as of `now`
+    fn should_autosave_at(&self, idle: Duration, now: Instant) -> bool {
+        if !self.modified || self.is_read_only() || self.file_path.is_none() {
+            return false;
+        }
+        self.last_edit
+            .is_some_and(|edited| now.saturating_duration_since(edited) >= idle)
+    }
+
+    /// Save if edits have been idle for at least `idle`, returning whether it saved
+    pub fn autosave_if_idle(&mut self, idle: Duration) -> Result<bool> {
+        if !self.should_autosave(idle) {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Set the syntax highlighting theme
+    pub fn set_theme(&mut self, theme: SyntaxTheme) -> Result<()> {
+        self.syntax = LanguageSyntax::for_language(&self.language, &theme)?;
+        self.theme = theme;
+        Ok(())
+    }
+
+    /// Get the syntax highlighting theme
+    pub fn theme(&self) -> &SyntaxTheme {
+        &self.theme
+    }
+
     /// Insert a character at the cursor position
     pub fn insert_char(&mut self, c: char) {
         if c == '\n' {
             self.insert_newline();
             return;
         }
+        if self.is_read_only() {
+            return;
+        }
 
         if self.cursor_row >= self.content.len() {
+            self.record_change(self.content.len(), 0, 1);
             self.content.push(String::new());
+        } else {
+            self.record_change(self.cursor_row, 1, 1);
         }
 
         let line = &mut self.content[self.cursor_row];
@@ -320,194 +763,861 @@ impl EditorState {
             self.cursor_col = line.len();
         } else {
             line.insert(self.cursor_col, c);
-            self.cursor_col += 1;
+            self.cursor_col += c.len_utf8();
         }
 
-        self.modified = true;
+        self.mark_modified();
     }
 
     /// Insert a newline at the cursor position
     fn insert_newline(&mut self) {
+        if self.is_read_only() {
+            return;
+        }
         if self.cursor_row >= self.content.len() {
+            self.record_change(self.content.len(), 0, 1);
             self.content.push(String::new());
             self.cursor_row = self.content.len() - 1;
             self.cursor_col = 0;
             return;
         }
 
+        self.record_change(self.cursor_row, 1, 2);
+
         // Clone the line to avoid borrowing issues
         let line = self.content[self.cursor_row].clone();
         let (before, after) = line.split_at(self.cursor_col);
 
         self.content[self.cursor_row] = before.to_string();
         self.content.insert(self.cursor_row + 1, after.to_string());
+        self.folds.clear();
 
         self.cursor_row += 1;
         self.cursor_col = 0;
-        self.modified = true;
+        self.mark_modified();
     }
 
     /// Delete the character before the cursor (backspace)
     pub fn delete_char(&mut self) {
+        if self.is_read_only() {
+            return;
+        }
         if self.cursor_col == 0 {
             // At start of line - join with previous line
             if self.cursor_row > 0 {
+                self.record_change(self.cursor_row - 1, 2, 1);
                 let current_line = self.content.remove(self.cursor_row);
+                self.folds.clear();
                 self.cursor_row -= 1;
                 self.cursor_col = self.content[self.cursor_row].len();
                 self.content[self.cursor_row].push_str(&current_line);
-                self.modified = true;
+                self.mark_modified();
             }
         } else {
             // Delete character before cursor
-            let line = &mut self.content[self.cursor_row];
-            if self.cursor_col <= line.len() {
-                line.remove(self.cursor_col - 1);
-                self.cursor_col -= 1;
-                self.modified = true;
+            if self.cursor_col <= self.content[self.cursor_row].len() {
+                self.record_change(self.cursor_row, 1, 1);
+                let prev = self.prev_char_boundary();
+                self.content[self.cursor_row].remove(prev);
+                self.cursor_col = prev;
+                self.mark_modified();
             }
         }
     }
 
     /// Delete the character at the cursor (delete key)
     pub fn delete_char_forward(&mut self) {
-        if self.cursor_row >= self.content.len() {
+        if self.is_read_only() || self.cursor_row >= self.content.len() {
             return;
         }
 
-        let line = &mut self.content[self.cursor_row];
-        if self.cursor_col < line.len() {
-            line.remove(self.cursor_col);
-            self.modified = true;
+        if self.cursor_col < self.content[self.cursor_row].len() {
+            self.record_change(self.cursor_row, 1, 1);
+            self.content[self.cursor_row].remove(self.cursor_col);
+            self.mark_modified();
         } else if self.cursor_row < self.content.len() - 1 {
             // At end of line - join with next line
+            self.record_change(self.cursor_row, 2, 1);
             let next_line = self.content.remove(self.cursor_row + 1);
+            self.folds.clear();
             self.content[self.cursor_row].push_str(&next_line);
-            self.modified = true;
+            self.mark_modified();
         }
     }
 
-    /// Move cursor up
-    pub fn navigate_up(&mut self) {
-        if self.cursor_row > 0 {
-            self.cursor_row -= 1;
-            self.clamp_cursor_col();
-            self.scroll_to_cursor();
+    /// Delete the current line (vim `dd`)
+    ///
+    /// Deleting the only line leaves a single empty line.
+    pub fn delete_line(&mut self) {
+        if self.is_read_only() || self.cursor_row >= self.content.len() {
+            return;
         }
-    }
 
-    /// Move cursor down
-    pub fn navigate_down(&mut self) {
-        if self.cursor_row < self.content.len().saturating_sub(1) {
-            self.cursor_row += 1;
-            self.clamp_cursor_col();
-            self.scroll_to_cursor();
-        }
+        let row = self.cursor_row;
+        let line = self.content.remove(row);
+        self.folds.clear();
+        let inserted = if self.content.is_empty() {
+            self.content.push(String::new());
+            1
+        } else {
+            0
+        };
+        self.record_edit(LineEdit {
+            row,
+            removed: vec![line],
+            inserted,
+            cursor: (row, self.cursor_col),
+        });
+
+        self.cursor_row = row.min(self.content.len() - 1);
+        self.clamp_cursor_col();
+        self.mark_modified();
     }
 
-    /// Move cursor left
-    pub fn navigate_left(&mut self) {
-        if self.cursor_col > 0 {
-            self.cursor_col -= 1;
-        } else if self.cursor_row > 0 {
-            self.cursor_row -= 1;
-            self.cursor_col = self.content[self.cursor_row].len();
-            self.scroll_to_cursor();
+    /// Insert a copy of the current line below it and move onto the copy
+    pub fn duplicate_line(&mut self) {
+        if self.is_read_only() || self.cursor_row >= self.content.len() {
+            return;
         }
+
+        let row = self.cursor_row;
+        let line = self.content[row].clone();
+        self.content.insert(row + 1, line);
+        self.folds.clear();
+        self.record_edit(LineEdit {
+            row: row + 1,
+            removed: Vec::new(),
+            inserted: 1,
+            cursor: (row, self.cursor_col),
+        });
+
+        self.cursor_row = row + 1;
+        self.scroll_to_cursor();
+        self.mark_modified();
     }
 
-    /// Move cursor right
-    pub fn navigate_right(&mut self) {
-        if self.cursor_row >= self.content.len() {
-            return;
+    /// Strip trailing whitespace from every line, returning how many lines changed
+    ///
+    /// The change is a single undo step. The cursor is clamped to the trimmed line.
+    pub fn trim_trailing_whitespace(&mut self) -> usize {
+        if self.is_read_only() {
+            return 0;
         }
 
-        let line_len = self.content[self.cursor_row].len();
-        if self.cursor_col < line_len {
-            self.cursor_col += 1;
-        } else if self.cursor_row < self.content.len() - 1 {
-            self.cursor_row += 1;
-            self.cursor_col = 0;
-            self.scroll_to_cursor();
+        let changed: Vec<usize> = self
+            .content
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.trim_end().len() != line.len())
+            .map(|(row, _)| row)
+            .collect();
+        let (Some(&first), Some(&last)) = (changed.first(), changed.last()) else {
+            return 0;
+        };
+
+        self.record_edit(LineEdit {
+            row: first,
+            removed: self.content[first..=last].to_vec(),
+            inserted: last - first + 1,
+            cursor: (self.cursor_row, self.cursor_col),
+        });
+        for &row in &changed {
+            let len = self.content[row].trim_end().len();
+            self.content[row].truncate(len);
         }
-    }
 
-    /// Move cursor to start of line
-    pub fn navigate_line_start(&mut self) {
-        self.cursor_col = 0;
+        self.clamp_cursor_col();
+        self.mark_modified();
+        changed.len()
     }
 
-    /// Move cursor to end of line
-    pub fn navigate_line_end(&mut self) {
-        if self.cursor_row < self.content.len() {
-            self.cursor_col = self.content[self.cursor_row].len();
+    /// Undo the most recent line edit, returning whether anything was undone
+    pub fn undo(&mut self) -> bool {
+        if self.is_read_only() {
+            return false;
         }
-    }
+        let Some(edit) = self.undo_stack.pop() else {
+            return false;
+        };
 
-    /// Move cursor to top of file
-    pub fn navigate_top(&mut self) {
-        self.cursor_row = 0;
-        self.cursor_col = 0;
-        self.scroll_offset = 0;
-    }
+        let end = (edit.row + edit.inserted).min(self.content.len());
+        self.content.splice(edit.row..end, edit.removed);
+        self.folds.clear();
+        if self.content.is_empty() {
+            self.content.push(String::new());
+        }
 
-    /// Move cursor to bottom of file
-    pub fn navigate_bottom(&mut self) {
-        self.cursor_row = self.content.len().saturating_sub(1);
+        self.cursor_row = edit.cursor.0.min(self.content.len() - 1);
+        self.cursor_col = edit.cursor.1;
         self.clamp_cursor_col();
         self.scroll_to_cursor();
+        self.mark_modified();
+        true
     }
 
-    /// Clamp cursor column to valid range for current line
-    fn clamp_cursor_col(&mut self) {
-        if self.cursor_row < self.content.len() {
-            let line_len = self.content[self.cursor_row].len();
-            if self.cursor_col > line_len {
-                self.cursor_col = line_len;
-            }
-        }
-    }
-
-    /// Scroll view to ensure cursor is visible
-    fn scroll_to_cursor(&mut self) {
-        // This will be used during rendering to adjust scroll_offset
-        // The actual adjustment happens in render_editor based on visible height
+    /// Whether there are edits to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
     }
 
-    /// Adjust scroll offset based on visible height
-    pub fn adjust_scroll(&mut self, visible_height: usize) {
-        if visible_height == 0 {
-            return;
+    /// Fold the block indented deeper than `row` below it, returning whether a fold was made
+    ///
+    /// The block runs until the next non-blank line indented no deeper than
+    /// `row`. Blank lines inside it are hidden too; trailing ones stay visible.
+    /// Folds are dropped by edits that add or remove lines, and are not
+    /// available while viewing a large file lazily.
+    pub fn fold_at(&mut self, row: usize) -> bool {
+        if self.is_lazy() || self.is_hidden(row) {
+            return false;
         }
+        let Some(header) = self.content.get(row).filter(|line| !line.trim().is_empty()) else {
+            return false;
+        };
 
-        // Scroll down if cursor is below visible area
-        if self.cursor_row >= self.scroll_offset + visible_height {
-            self.scroll_offset = self.cursor_row - visible_height + 1;
+        let indent = indent_width(header);
+        let mut end = row;
+        for (idx, line) in self.content.iter().enumerate().skip(row + 1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if indent_width(line) <= indent {
+                break;
+            }
+            end = idx;
+        }
+        if end == row {
+            return false;
         }
 
-        // Scroll up if cursor is above visible area
-        if self.cursor_row < self.scroll_offset {
-            self.scroll_offset = self.cursor_row;
+        self.folds.insert(row, end);
+        if (row + 1..=end).contains(&self.cursor_row) {
+            self.cursor_row = row;
+            self.clamp_cursor_col();
         }
+        true
     }
 
-    /// Set editor mode
-    pub fn set_mode(&mut self, mode: EditorMode) {
-        self.mode = mode;
+    /// Remove the fold headed by `row`, returning whether there was one
+    pub fn unfold_at(&mut self, row: usize) -> bool {
+        self.folds.remove(&row).is_some()
     }
 
-    /// Get current line content
-    pub fn current_line(&self) -> &str {
-        if self.cursor_row < self.content.len() {
-            &self.content[self.cursor_row]
-        } else {
-            ""
-        }
+    /// Fold or unfold the block under the cursor row (vim `z`)
+    pub fn toggle_fold(&mut self) -> bool {
+        self.unfold_at(self.cursor_row) || self.fold_at(self.cursor_row)
     }
 
-    /// Get total line count
+    /// Whether `row` heads a fold
+    pub fn is_folded(&self, row: usize) -> bool {
+        self.folds.contains_key(&row)
+    }
+
+    /// Number of lines shown, counting each fold as its header line
+    pub fn visible_line_count(&self) -> usize {
+        self.line_count() - self.hidden_rows(0..self.line_count())
+    }
+
+    /// Header row of the outermost fold hiding `row`, if any
+    fn fold_hiding(&self, row: usize) -> Option<usize> {
+        self.folds
+            .range(..row)
+            .find(|(_, &end)| row <= end)
+            .map(|(&start, _)| start)
+    }
+
+    /// Whether `row` is hidden inside a fold
+    fn is_hidden(&self, row: usize) -> bool {
+        self.fold_hiding(row).is_some()
+    }
+
+    /// Nearest visible row at or above `row`
+    fn visible_row(&self, row: usize) -> usize {
+        self.fold_hiding(row).unwrap_or(row)
+    }
+
+    /// Number of hidden rows within `range`
+    fn hidden_rows(&self, range: Range<usize>) -> usize {
+        let mut hidden = 0;
+        let mut covered = None;
+        for (&start, &end) in &self.folds {
+            // Folds nested in one already counted are hidden with it
+            if covered.is_some_and(|covered| start <= covered) {
+                continue;
+            }
+            covered = Some(end);
+            let first = (start + 1).max(range.start);
+            let last = (end + 1).min(range.end);
+            hidden += last.saturating_sub(first);
+        }
+        hidden
+    }
+
+    /// Next visible row below the visible `row`
+    fn next_visible_row(&self, row: usize) -> Option<usize> {
+        let next = self.folds.get(&row).map_or(row + 1, |&end| end + 1);
+        (next < self.line_count()).then_some(next)
+    }
+
+    /// Previous visible row above `row`
+    fn prev_visible_row(&self, row: usize) -> Option<usize> {
+        row.checked_sub(1).map(|prev| self.visible_row(prev))
+    }
+
+    /// Visible rows starting from the visible `first`
+    fn visible_rows_from(&self, first: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(
+            Some(first).filter(|&row| row < self.line_count()),
+            move |&row| self.next_visible_row(row),
+        )
+    }
+
+    /// Record that `lines` lines from `row` are about to become `inserted` lines
+    fn record_change(&mut self, row: usize, lines: usize, inserted: usize) {
+        let end = (row + lines).min(self.content.len());
+        let removed = self.content[row.min(end)..end].to_vec();
+        self.record_edit(LineEdit {
+            row,
+            removed,
+            inserted,
+            cursor: (self.cursor_row, self.cursor_col),
+        });
+    }
+
+    /// Push an edit onto the undo stack, dropping the oldest past the limit
+    fn record_edit(&mut self, edit: LineEdit) {
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(edit);
+    }
+
+    /// Move cursor up
+    pub fn navigate_up(&mut self) {
+        if let Some(row) = self.prev_visible_row(self.cursor_row) {
+            self.cursor_row = row;
+            self.scroll_to_cursor();
+            self.clamp_cursor_col();
+        }
+    }
+
+    /// Move cursor down
+    pub fn navigate_down(&mut self) {
+        if let Some(row) = self.next_visible_row(self.cursor_row) {
+            self.cursor_row = row;
+            self.scroll_to_cursor();
+            self.clamp_cursor_col();
+        }
+    }
+
+    /// Move cursor left
+    pub fn navigate_left(&mut self) {
+        if self.cursor_col > 0 {
+            self.cursor_col = self.prev_char_boundary();
+        } else if let Some(row) = self.prev_visible_row(self.cursor_row) {
+            self.cursor_row = row;
+            self.scroll_to_cursor();
+            self.cursor_col = self.line_len(self.cursor_row);
+        }
+    }
+
+    /// Move cursor right
+    pub fn navigate_right(&mut self) {
+        if self.cursor_row >= self.line_count() {
+            return;
+        }
+
+        let line_len = self.line_len(self.cursor_row);
+        if self.cursor_col < line_len {
+            self.cursor_col = self.next_char_boundary();
+        } else if let Some(row) = self.next_visible_row(self.cursor_row) {
+            self.cursor_row = row;
+            self.cursor_col = 0;
+            self.scroll_to_cursor();
+        }
+    }
+
+    /// Move cursor to start of line
+    pub fn navigate_line_start(&mut self) {
+        self.cursor_col = 0;
+    }
+
+    /// Move cursor to end of line
+    pub fn navigate_line_end(&mut self) {
+        if self.cursor_row < self.line_count() {
+            self.cursor_col = self.line_len(self.cursor_row);
+        }
+    }
+
+    /// Move cursor to top of file
+    pub fn navigate_top(&mut self) {
+        self.cursor_row = 0;
+        self.cursor_col = 0;
+        self.scroll_offset = 0;
+        self.scroll_to_cursor();
+    }
+
+    /// Move cursor to bottom of file
+    pub fn navigate_bottom(&mut self) {
+        self.cursor_row = self.visible_row(self.line_count().saturating_sub(1));
+        self.scroll_to_cursor();
+        self.clamp_cursor_col();
+    }
+
+    /// Move cursor to the start of the next word (vim `w`)
+    ///
+    /// Words are runs of alphanumeric/underscore characters or runs of
+    /// punctuation; whitespace and line breaks separate them.
+    pub fn navigate_word_forward(&mut self) {
+        let mut pos = (self.cursor_row, self.cursor_col);
+
+        // Skip the rest of the current word
+        if let Some(class) = self.char_at(pos).map(CharClass::of) {
+            if class != CharClass::Space {
+                pos = self.advance_while(pos, class);
+            }
+        }
+        pos = self.advance_while(pos, CharClass::Space);
+
+        self.set_cursor(pos);
+    }
+
+    /// Move cursor to the end of the current or next word (vim `e`)
+    pub fn navigate_word_end(&mut self) {
+        let Some(mut pos) = self.next_position((self.cursor_row, self.cursor_col)) else {
+            return;
+        };
+
+        pos = self.advance_while(pos, CharClass::Space);
+        if let Some(class) = self.char_at(pos).map(CharClass::of) {
+            while let Some(next) = self.next_position(pos) {
+                if self.char_at(next).map(CharClass::of) != Some(class) {
+                    break;
+                }
+                pos = next;
+            }
+        }
+
+        self.set_cursor(pos);
+    }
+
+    /// Move cursor to the start of the current or previous word (vim `b`)
+    pub fn navigate_word_backward(&mut self) {
+        let Some(mut pos) = self.prev_position((self.cursor_row, self.cursor_col)) else {
+            return;
+        };
+
+        while self.char_at(pos).map(CharClass::of) == Some(CharClass::Space) {
+            match self.prev_position(pos) {
+                Some(prev) => pos = prev,
+                None => break,
+            }
+        }
+        if let Some(class) = self.char_at(pos).map(CharClass::of) {
+            while let Some(prev) = self.prev_position(pos) {
+                if self.char_at(prev).map(CharClass::of) != Some(class) {
+                    break;
+                }
+                pos = prev;
+            }
+        }
+
+        self.set_cursor(pos);
+    }
+
+    /// Find the bracket matching the one under the cursor
+    ///
+    /// Returns the (row, byte column) of the balanced partner for `()`, `[]`
+    /// or `{}`, scanning forward from an opening bracket and backward from a
+    /// closing one. Brackets inside string literals and line comments are
+    /// ignored; only resident lines are scanned.
+    pub fn matching_bracket(&self) -> Option<(usize, usize)> {
+        let comment = self.line_comment();
+        let row = self.cursor_row;
+        let brackets = code_brackets(self.line(row)?, comment);
+        let idx = brackets
+            .iter()
+            .position(|&(col, _)| col == self.cursor_col)?;
+        let (open, close) = bracket_pair(brackets[idx].1)?;
+        let forward = brackets[idx].1 == open;
+
+        let rows: Box<dyn Iterator<Item = usize>> = if forward {
+            Box::new(row..self.line_count())
+        } else {
+            Box::new((0..=row).rev())
+        };
+
+        let mut depth = 0usize;
+        for r in rows {
+            // Stop at the edge of the resident window
+            let Some(line) = self.line(r) else {
+                break;
+            };
+            let line_brackets = if r == row {
+                if forward {
+                    brackets[idx..].to_vec()
+                } else {
+                    brackets[..=idx].to_vec()
+                }
+            } else {
+                code_brackets(line, comment)
+            };
+
+            let ordered: Box<dyn Iterator<Item = &(usize, char)>> = if forward {
+                Box::new(line_brackets.iter())
+            } else {
+                Box::new(line_brackets.iter().rev())
+            };
+            for &(col, c) in ordered {
+                if c == brackets[idx].1 {
+                    depth += 1;
+                } else if c == open || c == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some((r, col));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Line comment prefix for the current language
+    fn line_comment(&self) -> &'static str {
+        match self.language.as_str() {
+            "python" | "toml" | "yaml" => "#",
+            _ => "//",
+        }
+    }
+
+    /// Character at a (row, byte column) position; line ends read as `'\n'`
+    ///
+    /// Loads the row if it is outside the resident window, so word motions
+    /// in lazy mode see real text rather than empty lines.
+    fn char_at(&mut self, (row, col): (usize, usize)) -> Option<char> {
+        self.ensure_resident(row, 1);
+        let line = self.line(row).unwrap_or("");
+        match line.get(col..).and_then(|rest| rest.chars().next()) {
+            Some(c) => Some(c),
+            None if row + 1 < self.line_count() => Some('\n'),
+            None => None,
+        }
+    }
+
+    /// Position of the next character boundary, crossing into the next line
+    fn next_position(&mut self, (row, col): (usize, usize)) -> Option<(usize, usize)> {
+        self.ensure_resident(row, 1);
+        let line = self.line(row).unwrap_or("");
+        match line.get(col..).and_then(|rest| rest.chars().next()) {
+            Some(c) => Some((row, col + c.len_utf8())),
+            None if row + 1 < self.line_count() => Some((row + 1, 0)),
+            None => None,
+        }
+    }
+
+    /// Position of the previous character boundary, crossing into the previous line end
+    fn prev_position(&mut self, (row, col): (usize, usize)) -> Option<(usize, usize)> {
+        if col > 0 {
+            self.ensure_resident(row, 1);
+            let line = self.line(row).unwrap_or("");
+            let c = line.get(..col)?.chars().next_back()?;
+            Some((row, col - c.len_utf8()))
+        } else if row > 0 {
+            self.ensure_resident(row - 1, 1);
+            Some((row - 1, self.line_len(row - 1)))
+        } else {
+            None
+        }
+    }
+
+    /// Advance from `pos` while the character there belongs to `class`
+    fn advance_while(&mut self, mut pos: (usize, usize), class: CharClass) -> (usize, usize) {
+        while self.char_at(pos).map(CharClass::of) == Some(class) {
+            match self.next_position(pos) {
+                Some(next) => pos = next,
+                None => break,
+            }
+        }
+        pos
+    }
+
+    /// Move the cursor to a (row, byte column) position
+    ///
+    /// A position inside a fold moves the cursor onto the fold's header.
+    fn set_cursor(&mut self, (row, col): (usize, usize)) {
+        let visible = self.visible_row(row);
+        let row_changed = visible != self.cursor_row;
+        self.cursor_row = visible;
+        self.cursor_col = col;
+        if visible != row {
+            self.clamp_cursor_col();
+        }
+        if row_changed {
+            self.scroll_to_cursor();
+        }
+    }
+
+    /// Handle a mouse event for an editor rendered into `area`
+    ///
+    /// A left click moves the cursor to the character under the pointer and
+    /// the wheel moves the cursor by a few lines. Returns whether the event
+    /// changed the state.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, area: Rect) -> bool {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let inner = area.inner(Margin::new(1, 1));
+                if !rect_contains(inner, event.column, event.row) {
+                    return false;
+                }
+                let Some(row) = self
+                    .visible_rows_from(self.scroll_offset)
+                    .nth((event.row - inner.y) as usize)
+                else {
+                    return false;
+                };
+
+                // Skip the line number gutter and its trailing space
+                let column = ((event.column - inner.x) as usize)
+                    .saturating_sub(self.line_number_width() + 1);
+                self.cursor_row = row;
+                self.scroll_to_cursor();
+                let line = self.line(row).unwrap_or("");
+                self.cursor_col = line
+                    .char_indices()
+                    .nth(column)
+                    .map_or(line.len(), |(idx, _)| idx);
+                true
+            }
+            MouseEventKind::ScrollUp => {
+                for _ in 0..SCROLL_LINES {
+                    self.navigate_up();
+                }
+                true
+            }
+            MouseEventKind::ScrollDown => {
+                for _ in 0..SCROLL_LINES {
+                    self.navigate_down();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Width of the line number gutter, excluding its trailing space
+    fn line_number_width(&self) -> usize {
+        self.line_count().to_string().len().max(3)
+    }
+
+    /// Clamp cursor column to valid range for current line
+    ///
+    /// A column inside a multibyte character moves to that character's start.
+    fn clamp_cursor_col(&mut self) {
+        if self.cursor_row < self.line_count() {
+            let line = self.current_line();
+            let mut col = self.cursor_col.min(line.len());
+            while !line.is_char_boundary(col) {
+                col -= 1;
+            }
+            self.cursor_col = col;
+        }
+    }
+
+    /// Byte column of the character before the cursor (0 at line start)
+    fn prev_char_boundary(&self) -> usize {
+        self.current_line()
+            .get(..self.cursor_col)
+            .and_then(|before| before.chars().next_back())
+            .map_or(0, |c| self.cursor_col - c.len_utf8())
+    }
+
+    /// Byte column after the character at the cursor (line end at the end)
+    fn next_char_boundary(&self) -> usize {
+        let line = self.current_line();
+        line.get(self.cursor_col..)
+            .and_then(|after| after.chars().next())
+            .map_or(line.len(), |c| self.cursor_col + c.len_utf8())
+    }
+
+    /// Scroll view to ensure cursor is visible
+    fn scroll_to_cursor(&mut self) {
+        // The scroll_offset adjustment happens in render_editor based on visible height;
+        // here we only make sure the cursor line is loaded when viewing lazily
+        self.ensure_resident(self.cursor_row, 1);
+    }
+
+    /// Adjust scroll offset based on visible height
+    pub fn adjust_scroll(&mut self, visible_height: usize) {
+        if visible_height == 0 {
+            return;
+        }
+
+        // Never start the view inside a fold
+        self.scroll_offset = self.visible_row(self.scroll_offset);
+
+        // Scroll down if cursor is below visible area
+        if self.cursor_row >= self.scroll_offset
+            && self.screen_offset(self.scroll_offset, self.cursor_row) >= visible_height
+        {
+            let mut top = self.cursor_row;
+            for _ in 1..visible_height {
+                top = self.prev_visible_row(top).unwrap_or(top);
+            }
+            self.scroll_offset = top;
+        }
+
+        // Scroll up if cursor is above visible area
+        if self.cursor_row < self.scroll_offset {
+            self.scroll_offset = self.cursor_row;
+        }
+    }
+
+    /// Number of screen lines between the visible rows `top` and `row`
+    fn screen_offset(&self, top: usize, row: usize) -> usize {
+        (row - top) - self.hidden_rows(top..row)
+    }
+
+    /// Set editor mode
+    ///
+    /// Insert mode is refused while the buffer is read-only.
+    pub fn set_mode(&mut self, mode: EditorMode) {
+        if mode == EditorMode::Insert && self.is_read_only() {
+            return;
+        }
+        self.mode = mode;
+    }
+
+    /// Apply vim-like key handling to an input event
+    ///
+    /// Normal mode handles motions (`hjkl`, `w`/`b`/`e`, `0`/`$`, `g`/`G`),
+    /// edits (`x`, `u`) and mode switches (`i`, `a`, `o`, `v`); insert mode
+    /// inserts text until `Esc`; visual mode handles motions until `Esc`.
+    /// Keys with Control or Alt held are left for the host (e.g. `Ctrl+S`).
+    ///
+    /// Returns whether the event was consumed.
+    pub fn handle_input(&mut self, event: &InputEvent) -> bool {
+        let InputEvent::Key(key) = event else {
+            return false;
+        };
+        if key.modifiers.control || key.modifiers.alt {
+            return false;
+        }
+
+        if self.handle_motion(key) {
+            return true;
+        }
+
+        match self.mode {
+            EditorMode::Normal => match key.code {
+                KeyCode::Char('i') | KeyCode::Insert => self.set_mode(EditorMode::Insert),
+                KeyCode::Char('a') => {
+                    if self.cursor_col < self.line_len(self.cursor_row) {
+                        self.cursor_col = self.next_char_boundary();
+                    }
+                    self.set_mode(EditorMode::Insert);
+                }
+                KeyCode::Char('o') => {
+                    self.navigate_line_end();
+                    self.insert_char('\n');
+                    self.set_mode(EditorMode::Insert);
+                }
+                KeyCode::Char('v') => self.set_mode(EditorMode::Visual),
+                KeyCode::Char('x') | KeyCode::Delete => self.delete_char_forward(),
+                KeyCode::Char('u') => {
+                    self.undo();
+                }
+                KeyCode::Char('z') => {
+                    self.toggle_fold();
+                }
+                _ => return false,
+            },
+            EditorMode::Insert => match key.code {
+                KeyCode::Esc => self.set_mode(EditorMode::Normal),
+                KeyCode::Char(c) => self.insert_char(c),
+                KeyCode::Enter => self.insert_char('\n'),
+                KeyCode::Tab => self.insert_char('\t'),
+                KeyCode::Backspace => self.delete_char(),
+                KeyCode::Delete => self.delete_char_forward(),
+                _ => return false,
+            },
+            EditorMode::Visual => match key.code {
+                KeyCode::Esc => self.set_mode(EditorMode::Normal),
+                _ => return false,
+            },
+        }
+        true
+    }
+
+    /// Apply a cursor motion key for the current mode
+    ///
+    /// Arrow, Home and End keys move in every mode; letter motions only
+    /// outside insert mode.
+    fn handle_motion(&mut self, key: &KeyEvent) -> bool {
+        let letters = self.mode != EditorMode::Insert;
+        match key.code {
+            KeyCode::Left => self.navigate_left(),
+            KeyCode::Right => self.navigate_right(),
+            KeyCode::Up => self.navigate_up(),
+            KeyCode::Down => self.navigate_down(),
+            KeyCode::Home => self.navigate_line_start(),
+            KeyCode::End => self.navigate_line_end(),
+            KeyCode::Char(c) if letters => match c {
+                'h' => self.navigate_left(),
+                'j' => self.navigate_down(),
+                'k' => self.navigate_up(),
+                'l' => self.navigate_right(),
+                'w' => self.navigate_word_forward(),
+                'e' => self.navigate_word_end(),
+                'b' => self.navigate_word_backward(),
+                '0' => self.navigate_line_start(),
+                '$' => self.navigate_line_end(),
+                'g' => self.navigate_top(),
+                'G' => self.navigate_bottom(),
+                _ => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Get current line content
+    pub fn current_line(&self) -> &str {
+        self.line(self.cursor_row).unwrap_or("")
+    }
+
+    /// Get total line count
     pub fn line_count(&self) -> usize {
-        self.content.len()
+        self.lazy
+            .as_ref()
+            .map_or(self.content.len(), |lazy| lazy.total_lines)
+    }
+
+    /// Get total character count, counting one per line break
+    pub fn char_count(&self) -> usize {
+        match &self.lazy {
+            Some(lazy) => lazy.total_chars,
+            None => {
+                let chars: usize = self.content.iter().map(|line| line.chars().count()).sum();
+                chars + self.content.len().saturating_sub(1)
+            }
+        }
+    }
+
+    /// Get cursor position and buffer summary for a status bar
+    pub fn status_info(&self) -> EditorStatus {
+        EditorStatus {
+            row: self.cursor_row + 1,
+            col: self
+                .line(self.cursor_row)
+                .and_then(|line| line.get(..self.cursor_col))
+                .map_or(self.cursor_col, |before| before.chars().count())
+                + 1,
+            total_lines: self.line_count(),
+            total_chars: self.char_count(),
+            modified: self.modified,
+            mode: self.mode,
+            language: self.language.clone(),
+        }
     }
 
     /// Get file name for display
@@ -532,9 +1642,16 @@ impl Default for EditorState {
             language: "text".to_string(),
             modified: false,
             mode: EditorMode::Normal,
+            show_trailing_whitespace: false,
+            read_only: false,
             syntax: LanguageSyntax {
                 patterns: Vec::new(),
             },
+            theme: SyntaxTheme::default(),
+            lazy: None,
+            undo_stack: Vec::new(),
+            folds: BTreeMap::new(),
+            last_edit: None,
         })
     }
 }
@@ -550,13 +1667,20 @@ impl Editor {
 
         // Adjust scroll offset to keep cursor visible
         state.adjust_scroll(visible_height);
+        state.ensure_resident(state.scroll_offset, visible_height);
 
         // Build title with file info
         let title = format!(
             "{} [{}] {}",
             state.file_name(),
             state.language,
-            if state.modified { "[+]" } else { "" }
+            if state.is_read_only() {
+                "[RO]"
+            } else if state.modified {
+                "[+]"
+            } else {
+                ""
+            }
         );
 
         let mut block = Block::default().title(title).borders(Borders::ALL);
@@ -565,59 +1689,299 @@ impl Editor {
             block = block.border_style(Style::default().fg(state.mode.color()));
         }
 
-        // Calculate line number width (for display)
-        let line_num_width = format!("{}", state.content.len()).len().max(3);
+        // Calculate line number width (for display)
+        let line_num_width = state.line_number_width();
+
+        // Build visible lines with syntax highlighting
+        let visible_lines: Vec<Line> = state
+            .visible_rows_from(state.scroll_offset)
+            .take(visible_height)
+            .filter_map(|idx| state.line(idx).map(|line| (idx, line)))
+            .map(|(idx, line)| {
+                let line_num = format!("{:>width$} ", idx + 1, width = line_num_width);
+                let mut spans = vec![Span::styled(line_num, Style::default().fg(Color::DarkGray))];
+
+                // Apply syntax highlighting, marking trailing whitespace if requested
+                let code = if state.show_trailing_whitespace {
+                    line.trim_end()
+                } else {
+                    line
+                };
+                let mut highlighted = state.syntax.highlight_line(code);
+                spans.append(&mut highlighted);
+                if code.len() < line.len() {
+                    let width: usize = line[code.len()..]
+                        .chars()
+                        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+                        .sum();
+                    spans.push(Span::styled(
+                        " ".repeat(width),
+                        Style::default().bg(Color::Red),
+                    ));
+                }
+                if let Some(&end) = state.folds.get(&idx) {
+                    spans.push(Span::styled(
+                        format!(" ⋯ {} lines folded", end - idx),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+
+                // Highlight current line
+                if idx == state.cursor_row {
+                    Line::from(spans)
+                        .style(Style::default().bg(Color::Rgb(40, 40, 40)).fg(Color::White))
+                } else {
+                    Line::from(spans)
+                }
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(visible_lines).block(block);
+        frame.render_widget(paragraph, area);
+
+        // Calculate cursor position for display (accounting for line numbers and borders)
+        let cursor_x = area.x + 1 + line_num_width as u16 + 1 + state.cursor_col as u16;
+        let cursor_y =
+            area.y + 1 + state.screen_offset(state.scroll_offset, state.cursor_row) as u16;
+
+        // Set cursor position if in Insert mode and focused
+        if state.mode == EditorMode::Insert && focused {
+            if cursor_x < area.x + area.width && cursor_y < area.y + area.height {
+                frame.set_cursor_position((cursor_x, cursor_y));
+            }
+        }
+    }
+}
+
+/// Render the editor component
+///
+/// This is a convenience function that matches the pattern used in other components
+pub fn render_editor(frame: &mut Frame, area: Rect, state: &mut EditorState, focused: bool) {
+    Editor::render(frame, area, state, focused);
+}
+
+/// Editor managed as a [`Component`]
+///
+/// Keys are handled by [`EditorState::handle_input`] while the component is
+/// active. It becomes active when focused; losing focus returns it to normal
+/// mode.
+pub struct EditorComponent {
+    // Rendering adjusts the scroll offset, but `Component::render` takes `&self`
+    state: RefCell<EditorState>,
+    active: bool,
+    /// Idle time after which unsaved edits are saved on tick
+    autosave_idle: Option<Duration>,
+}
+
+impl EditorComponent {
+    /// Component ID used with [`ComponentManager`](super::ComponentManager)
+    pub const ID: &'static str = "editor";
+
+    /// Shared context key holding the cursor position while the editor is focused
+    pub const CURSOR_KEY: &'static str = "editor.cursor";
+
+    /// Wrap an editor state
+    pub fn new(state: EditorState) -> Self {
+        Self {
+            state: RefCell::new(state),
+            active: false,
+            autosave_idle: None,
+        }
+    }
+
+    /// Save unsaved edits on tick once they have been idle for `idle`
+    ///
+    /// `None` disables auto-save.
+    pub fn set_autosave(&mut self, idle: Option<Duration>) {
+        self.autosave_idle = idle;
+    }
+
+    /// Borrow the editor state
+    pub fn state(&self) -> Ref<'_, EditorState> {
+        self.state.borrow()
+    }
+
+    /// Mutably borrow the editor state
+    pub fn state_mut(&mut self) -> &mut EditorState {
+        self.state.get_mut()
+    }
+
+    /// Whether the editor currently has focus
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Replace the buffer with the contents of `path`
+    ///
+    /// The current buffer is kept if the file cannot be read.
+    pub fn open_file(&mut self, path: &Path) -> Result<()> {
+        *self.state.get_mut() = EditorState::open_file(path)?;
+        Ok(())
+    }
+}
+
+/// Open the file last chosen in the file explorer in the editor component
+///
+/// Consumes the path published under
+/// [`FileExplorerComponent::SELECTED_FILE_KEY`]. Failures, including unsaved
+/// changes in the editor, are reported through the status message. Returns
+/// whether a file was opened.
+pub fn open_selected_in_editor(manager: &mut ComponentManager) -> bool {
+    let Some(path) = manager
+        .context_mut()
+        .remove_shared(FileExplorerComponent::SELECTED_FILE_KEY)
+    else {
+        return false;
+    };
+    let path = PathBuf::from(path);
+
+    let result = match manager
+        .get_component_mut(EditorComponent::ID)
+        .and_then(|c| c.as_any_mut().downcast_mut::<EditorComponent>())
+    {
+        None => return false,
+        Some(editor) if editor.state().modified => {
+            Err(anyhow!("unsaved changes in {}", editor.state().file_name()))
+        }
+        Some(editor) => editor.open_file(&path),
+    };
+
+    let ctx = manager.context_mut();
+    match result {
+        Ok(()) => {
+            ctx.set_status(format!("Opened {}", path.display()));
+            true
+        }
+        Err(e) => {
+            ctx.set_status(format!("Cannot open {}: {e:#}", path.display()));
+            false
+        }
+    }
+}
+
+impl Component for EditorComponent {
+    fn handle_event(&mut self, event: &InputEvent, _ctx: &mut ComponentContext) -> EventResult {
+        if self.active && self.state.get_mut().handle_input(event) {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    fn handle_mouse(
+        &mut self,
+        event: &MouseEvent,
+        area: Rect,
+        _ctx: &mut ComponentContext,
+    ) -> EventResult {
+        if self.state.get_mut().handle_mouse(event, area) {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    fn render(&self, area: Rect, frame: &mut Frame<'_>, focused: bool) {
+        render_editor(frame, area, &mut self.state.borrow_mut(), focused);
+    }
+
+    fn id(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn tick(&mut self, ctx: &mut ComponentContext) {
+        if let Some(idle) = self.autosave_idle {
+            let state = self.state.get_mut();
+            match state.autosave_if_idle(idle) {
+                Ok(true) => ctx.set_status(format!("Auto-saved {}", state.file_name())),
+                Ok(false) => {}
+                Err(e) => {
+                    // Retry only after the next edit instead of on every tick
+                    state.last_edit = None;
+                    ctx.set_status(format!("Auto-save failed: {e:#}"));
+                }
+            }
+        }
+
+        if self.active {
+            let status = self.state.get_mut().status_info();
+            ctx.set_shared(
+                Self::CURSOR_KEY,
+                format!("Ln {}, Col {}", status.row, status.col),
+            );
+        } else {
+            ctx.remove_shared(Self::CURSOR_KEY);
+        }
+    }
+
+    fn on_focus_change(&mut self, focused: bool) {
+        self.active = focused;
+        if !focused {
+            self.state.get_mut().set_mode(EditorMode::Normal);
+        }
+    }
 
-        // Build visible lines with syntax highlighting
-        let visible_lines: Vec<Line> = state
-            .content
-            .iter()
-            .enumerate()
-            .skip(state.scroll_offset)
-            .take(visible_height)
-            .map(|(idx, line)| {
-                let line_num = format!("{:>width$} ", idx + 1, width = line_num_width);
-                let mut spans = vec![Span::styled(line_num, Style::default().fg(Color::DarkGray))];
+    fn save_state(&self) -> JsonValue {
+        serde_json::to_value(self.state.borrow().view_state()).unwrap_or_default()
+    }
 
-                // Apply syntax highlighting
-                let mut highlighted = state.syntax.highlight_line(line);
-                spans.append(&mut highlighted);
+    fn load_state(&mut self, state: JsonValue) -> Result<()> {
+        self.state
+            .get_mut()
+            .restore_view_state(serde_json::from_value(state)?)
+    }
 
-                // Highlight current line
-                if idx == state.cursor_row {
-                    Line::from(spans)
-                        .style(Style::default().bg(Color::Rgb(40, 40, 40)).fg(Color::White))
-                } else {
-                    Line::from(spans)
-                }
-            })
-            .collect();
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 
-        let paragraph = Paragraph::new(visible_lines).block(block);
-        frame.render_widget(paragraph, area);
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
 
-        // Calculate cursor position for display (accounting for line numbers and borders)
-        let cursor_x = area.x + 1 + line_num_width as u16 + 1 + state.cursor_col as u16;
-        let cursor_y = area.y + 1 + (state.cursor_row - state.scroll_offset) as u16;
+/// Opening and closing characters for a bracket, if `c` is one
+fn bracket_pair(c: char) -> Option<(char, char)> {
+    match c {
+        '(' | ')' => Some(('(', ')')),
+        '[' | ']' => Some(('[', ']')),
+        '{' | '}' => Some(('{', '}')),
+        _ => None,
+    }
+}
 
-        // Set cursor position if in Insert mode and focused
-        if state.mode == EditorMode::Insert && focused {
-            if cursor_x < area.x + area.width && cursor_y < area.y + area.height {
-                frame.set_cursor_position((cursor_x, cursor_y));
+/// Brackets in a line that are outside string literals and line comments
+///
+/// Strings are recognized by double quotes (with backslash escapes) and are
+/// assumed not to span lines.
+fn code_brackets(line: &str, comment: &str) -> Vec<(usize, char)> {
+    let mut brackets = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (col, c) in line.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
             }
+        } else if c == '"' {
+            in_string = true;
+        } else if line[col..].starts_with(comment) {
+            break;
+        } else if bracket_pair(c).is_some() {
+            brackets.push((col, c));
         }
     }
-}
 
-/// Render the editor component
-///
-/// This is a convenience function that matches the pattern used in other components
-pub fn render_editor(frame: &mut Frame, area: Rect, state: &mut EditorState, focused: bool) {
-    Editor::render(frame, area, state, focused);
+    brackets
 }
 
 /// Detect programming language from file extension
-fn detect_language(path: &Path) -> String {
+pub(crate) fn detect_language(path: &Path) -> String {
     path.extension()
         .and_then(|ext| ext.to_str())
         .map(|ext| match ext.to_lowercase().as_str() {
@@ -712,6 +2076,283 @@ mod tests {
         assert_eq!(editor.cursor_col, 0);
     }
 
+    #[test]
+    fn test_word_navigation() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec![
+            "foo.bar(baz)  qux".to_string(),
+            "  next_word end".to_string(),
+        ];
+
+        let mut forward = Vec::new();
+        for _ in 0..8 {
+            editor.navigate_word_forward();
+            forward.push((editor.cursor_row, editor.cursor_col));
+        }
+        assert_eq!(
+            forward,
+            vec![
+                (0, 3),
+                (0, 4),
+                (0, 7),
+                (0, 8),
+                (0, 11),
+                (0, 14),
+                (1, 2),
+                (1, 12)
+            ]
+        );
+
+        editor.navigate_top();
+        let mut ends = Vec::new();
+        for _ in 0..9 {
+            editor.navigate_word_end();
+            ends.push((editor.cursor_row, editor.cursor_col));
+        }
+        assert_eq!(
+            ends,
+            vec![
+                (0, 2),
+                (0, 3),
+                (0, 6),
+                (0, 7),
+                (0, 10),
+                (0, 11),
+                (0, 16),
+                (1, 10),
+                (1, 14)
+            ]
+        );
+
+        editor.cursor_row = 1;
+        editor.cursor_col = 12;
+        let mut backward = Vec::new();
+        for _ in 0..4 {
+            editor.navigate_word_backward();
+            backward.push((editor.cursor_row, editor.cursor_col));
+        }
+        assert_eq!(backward, vec![(1, 2), (0, 14), (0, 11), (0, 8)]);
+    }
+
+    #[test]
+    fn test_word_navigation_multibyte() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["héllo wörld!".to_string()];
+
+        editor.navigate_word_forward();
+        assert_eq!(editor.cursor_col, "héllo ".len());
+        editor.navigate_word_end();
+        assert_eq!(editor.cursor_col, "héllo wörl".len());
+        editor.navigate_word_backward();
+        assert_eq!(editor.cursor_col, "héllo ".len());
+        assert!(editor.current_line().is_char_boundary(editor.cursor_col));
+    }
+
+    #[test]
+    fn test_delete_line() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["one".to_string(), "two".to_string(), "3".to_string()];
+        editor.cursor_row = 1;
+        editor.cursor_col = 2;
+
+        editor.delete_line();
+        assert_eq!(editor.content, vec!["one", "3"]);
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, 1));
+        assert!(editor.modified);
+
+        assert!(editor.undo());
+        assert_eq!(editor.content, vec!["one", "two", "3"]);
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, 2));
+        assert!(!editor.undo());
+    }
+
+    #[test]
+    fn test_delete_only_line_leaves_empty_line() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["only".to_string()];
+
+        editor.delete_line();
+        assert_eq!(editor.content, vec![""]);
+        assert_eq!((editor.cursor_row, editor.cursor_col), (0, 0));
+
+        editor.undo();
+        assert_eq!(editor.content, vec!["only"]);
+    }
+
+    #[test]
+    fn test_duplicate_line() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["first".to_string(), "second".to_string()];
+        editor.cursor_col = 3;
+
+        editor.duplicate_line();
+        assert_eq!(editor.content, vec!["first", "first", "second"]);
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, 3));
+        assert!(editor.modified);
+
+        assert!(editor.can_undo());
+        editor.undo();
+        assert_eq!(editor.content, vec!["first", "second"]);
+        assert_eq!(editor.cursor_row, 0);
+    }
+
+    #[test]
+    fn test_undo_after_mixed_edits() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        editor.cursor_row = 1;
+
+        editor.delete_line();
+        editor.navigate_top();
+        editor.insert_char('\n');
+        assert_eq!(editor.content, vec!["", "a", "c"]);
+
+        // Each edit is undone in turn, newest first
+        assert!(editor.undo());
+        assert_eq!(editor.content, vec!["a", "c"]);
+        assert_eq!((editor.cursor_row, editor.cursor_col), (0, 0));
+        assert!(editor.undo());
+        assert_eq!(editor.content, vec!["a", "b", "c"]);
+
+        // Vim `x` undoes on its own, before the older line deletion
+        editor.navigate_top();
+        editor.navigate_down();
+        editor.delete_line();
+        type_keys(&mut editor, "x");
+        assert_eq!(editor.content, vec!["a", ""]);
+        type_keys(&mut editor, "u");
+        assert_eq!(editor.content, vec!["a", "c"]);
+        type_keys(&mut editor, "u");
+        assert_eq!(editor.content, vec!["a", "b", "c"]);
+
+        // Typing and joining lines with backspace
+        editor.navigate_top();
+        editor.navigate_line_end();
+        editor.insert_char('!');
+        editor.navigate_down();
+        editor.navigate_line_start();
+        editor.delete_char();
+        assert_eq!(editor.content, vec!["a!b", "c"]);
+        assert!(editor.undo());
+        assert_eq!(editor.content, vec!["a!", "b", "c"]);
+        assert!(editor.undo());
+        assert_eq!(editor.content, vec!["a", "b", "c"]);
+        assert!(!editor.undo());
+    }
+
+    #[test]
+    fn test_matching_bracket_same_line() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec![r#"call(a[0], ")", (b)) // )"#.to_string()];
+
+        editor.cursor_col = 4;
+        assert_eq!(editor.matching_bracket(), Some((0, 19)));
+        editor.cursor_col = 19;
+        assert_eq!(editor.matching_bracket(), Some((0, 4)));
+        editor.cursor_col = 6;
+        assert_eq!(editor.matching_bracket(), Some((0, 8)));
+
+        // Not on a bracket
+        editor.cursor_col = 0;
+        assert_eq!(editor.matching_bracket(), None);
+    }
+
+    #[test]
+    fn test_matching_bracket_across_lines() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec![
+            "fn main() {".to_string(),
+            "    if x { y(); }".to_string(),
+            "}".to_string(),
+        ];
+
+        editor.cursor_col = 10;
+        assert_eq!(editor.matching_bracket(), Some((2, 0)));
+
+        editor.cursor_row = 2;
+        editor.cursor_col = 0;
+        assert_eq!(editor.matching_bracket(), Some((0, 10)));
+    }
+
+    #[test]
+    fn test_matching_bracket_unmatched() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["let v = [1, 2;".to_string(), "x)".to_string()];
+
+        editor.cursor_col = 8;
+        assert_eq!(editor.matching_bracket(), None);
+
+        editor.cursor_row = 1;
+        editor.cursor_col = 1;
+        assert_eq!(editor.matching_bracket(), None);
+    }
+
+    #[test]
+    fn test_mouse_click_moves_cursor() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["first".to_string(), "héllo world".to_string()];
+        let area = Rect::new(10, 5, 40, 10);
+
+        // Border (1) + gutter (3) + space (1) puts text at column 15
+        let click = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 17, 7);
+        assert!(editor.handle_mouse(&click, area));
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, "hé".len()));
+
+        // Past the end of the line clamps to the line end
+        let click = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 45, 6);
+        assert!(editor.handle_mouse(&click, area));
+        assert_eq!((editor.cursor_row, editor.cursor_col), (0, 5));
+
+        // Below the last line or outside the area
+        let click = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 17, 9);
+        assert!(!editor.handle_mouse(&click, area));
+        let click = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 0, 0);
+        assert!(!editor.handle_mouse(&click, area));
+        assert_eq!((editor.cursor_row, editor.cursor_col), (0, 5));
+    }
+
+    #[test]
+    fn test_view_state_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("notes.txt");
+        fs::write(&path, "one\ntwo\nthree").unwrap();
+
+        let mut editor = EditorState::open_file(&path).unwrap();
+        editor.navigate_down();
+        editor.navigate_line_end();
+        let view = editor.view_state();
+
+        let mut restored = EditorState::new().unwrap();
+        restored.restore_view_state(view).unwrap();
+        assert_eq!(restored.file_path.as_deref(), Some(path.as_path()));
+        assert_eq!((restored.cursor_row, restored.cursor_col), (1, 3));
+    }
+
+    #[test]
+    fn test_trim_trailing_whitespace() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec![
+            "clean".to_string(),
+            "spaces   ".to_string(),
+            "tabs\t \t".to_string(),
+            "    ".to_string(),
+        ];
+        editor.cursor_row = 1;
+        editor.cursor_col = 8;
+
+        assert_eq!(editor.trim_trailing_whitespace(), 3);
+        assert_eq!(editor.content, vec!["clean", "spaces", "tabs", ""]);
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, 6));
+        assert!(editor.modified);
+
+        assert_eq!(editor.trim_trailing_whitespace(), 0);
+
+        editor.undo();
+        assert_eq!(editor.content[1], "spaces   ");
+        assert_eq!(editor.content[2], "tabs\t \t");
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, 8));
+    }
+
     #[test]
     fn test_newline_insertion() {
         let mut editor = EditorState::new().unwrap();
@@ -730,6 +2371,195 @@ mod tests {
         assert_eq!(editor.cursor_col, 2);
     }
 
+    fn key(code: KeyCode) -> InputEvent {
+        InputEvent::Key(KeyEvent::new(code, crate::input::Modifiers::NONE))
+    }
+
+    fn type_keys(editor: &mut EditorState, keys: &str) {
+        for c in keys.chars() {
+            assert!(editor.handle_input(&key(KeyCode::Char(c))), "{c:?}");
+        }
+    }
+
+    #[test]
+    fn test_handle_input_edits_buffer() {
+        let mut editor = EditorState::new().unwrap();
+
+        type_keys(&mut editor, "ihello");
+        assert_eq!(editor.mode, EditorMode::Insert);
+        assert!(editor.handle_input(&key(KeyCode::Enter)));
+        type_keys(&mut editor, "world");
+        assert!(editor.handle_input(&key(KeyCode::Backspace)));
+        assert!(editor.handle_input(&key(KeyCode::Esc)));
+        assert_eq!(editor.mode, EditorMode::Normal);
+        assert_eq!(editor.content, vec!["hello", "worl"]);
+
+        // Jump to the top, delete the first character, then append a line
+        type_keys(&mut editor, "gx");
+        assert_eq!(editor.content[0], "ello");
+        type_keys(&mut editor, "o!");
+        assert!(editor.handle_input(&key(KeyCode::Esc)));
+        assert_eq!(editor.content, vec!["ello", "!", "worl"]);
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, 1));
+    }
+
+    #[test]
+    fn test_handle_input_multibyte() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["cafe".to_string()];
+
+        // Type in the middle of the line, then keep typing after the é
+        type_keys(&mut editor, "lliéü");
+        assert_eq!(editor.content[0], "caéüfe");
+        assert_eq!(editor.cursor_col, "caéü".len());
+
+        assert!(editor.handle_input(&key(KeyCode::Left)));
+        assert_eq!(editor.cursor_col, "caé".len());
+        assert!(editor.handle_input(&key(KeyCode::Backspace)));
+        assert_eq!(editor.content[0], "caüfe");
+        assert!(editor.handle_input(&key(KeyCode::Right)));
+        assert_eq!(editor.cursor_col, "caü".len());
+        type_keys(&mut editor, "ñ");
+        assert!(editor.handle_input(&key(KeyCode::Esc)));
+        assert_eq!(editor.content[0], "caüñfe");
+
+        // Append after a multibyte character from normal mode
+        type_keys(&mut editor, "0llla!");
+        assert_eq!(editor.content[0], "caüñ!fe");
+        assert!(editor.current_line().is_char_boundary(editor.cursor_col));
+    }
+
+    #[test]
+    fn test_handle_input_motions_and_modes() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec!["one two".to_string(), "three".to_string()];
+
+        type_keys(&mut editor, "w");
+        assert_eq!(editor.cursor_col, 4);
+        type_keys(&mut editor, "j0");
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1, 0));
+
+        type_keys(&mut editor, "v");
+        assert_eq!(editor.mode, EditorMode::Visual);
+        type_keys(&mut editor, "$");
+        assert_eq!(editor.cursor_col, 5);
+        // Insert-only keys are not consumed in visual mode
+        assert!(!editor.handle_input(&key(KeyCode::Char('z'))));
+        assert!(editor.handle_input(&key(KeyCode::Esc)));
+        assert_eq!(editor.mode, EditorMode::Normal);
+
+        // Letters are text in insert mode, and host shortcuts pass through
+        type_keys(&mut editor, "ij");
+        assert_eq!(editor.content[1], "threej");
+        let save = InputEvent::Key(KeyEvent::new(
+            KeyCode::Char('s'),
+            crate::input::Modifiers {
+                control: true,
+                ..Default::default()
+            },
+        ));
+        assert!(!editor.handle_input(&save));
+        assert!(!editor.handle_input(&InputEvent::Tick));
+    }
+
+    struct Placeholder;
+
+    impl Component for Placeholder {
+        fn handle_event(
+            &mut self,
+            _event: &InputEvent,
+            _ctx: &mut ComponentContext,
+        ) -> EventResult {
+            EventResult::Ignored
+        }
+
+        fn render(&self, _area: Rect, _frame: &mut Frame<'_>, _focused: bool) {}
+
+        fn id(&self) -> &'static str {
+            "placeholder"
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    fn managed_editor(manager: &ComponentManager) -> &EditorComponent {
+        manager
+            .get_component(EditorComponent::ID)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<EditorComponent>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_editor_component_routes_keys() {
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(EditorComponent::new(EditorState::new().unwrap())));
+        manager.register(Box::new(Placeholder));
+        assert_eq!(manager.focused_id(), Some(EditorComponent::ID));
+        assert!(managed_editor(&manager).is_active());
+
+        for c in ['i', 'h', 'i'] {
+            assert_eq!(
+                manager.handle_event(&key(KeyCode::Char(c))),
+                EventResult::Consumed
+            );
+        }
+        let editor = managed_editor(&manager);
+        assert_eq!(editor.state().content[0], "hi");
+        assert_eq!(editor.state().mode, EditorMode::Insert);
+
+        // Losing focus leaves insert mode
+        manager.focus_next();
+        let editor = managed_editor(&manager);
+        assert!(!editor.is_active());
+        assert_eq!(editor.state().mode, EditorMode::Normal);
+
+        assert!(manager.focus_by_id(EditorComponent::ID));
+        assert!(managed_editor(&manager).is_active());
+    }
+
+    #[test]
+    fn test_open_selected_in_editor() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("a.txt"), "first\nsecond\n").unwrap();
+        std::fs::create_dir(root.join("locked")).unwrap();
+
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(FileExplorerComponent::new(
+            crate::components::FileExplorerState::new(&root).unwrap(),
+        )));
+        manager.register(Box::new(EditorComponent::new(EditorState::new().unwrap())));
+        assert!(!open_selected_in_editor(&mut manager));
+
+        // The directory sorts first; move to the file and open it
+        manager.handle_event(&key(KeyCode::Char('j')));
+        manager.handle_event(&key(KeyCode::Enter));
+        assert!(open_selected_in_editor(&mut manager));
+        let editor = managed_editor(&manager);
+        assert_eq!(editor.state().content, vec!["first", "second"]);
+        assert_eq!(editor.state().file_path, Some(root.join("a.txt")));
+
+        // The selection is consumed
+        assert!(!open_selected_in_editor(&mut manager));
+
+        // Unreadable paths leave the buffer alone and report a status
+        manager.context_mut().set_shared(
+            FileExplorerComponent::SELECTED_FILE_KEY,
+            root.join("missing.txt").to_string_lossy(),
+        );
+        assert!(!open_selected_in_editor(&mut manager));
+        assert!(manager.status().unwrap().starts_with("Cannot open"));
+        assert_eq!(managed_editor(&manager).state().content[0], "first");
+    }
+
     #[test]
     fn test_mode_switching() {
         let mut editor = EditorState::new().unwrap();
@@ -744,7 +2574,7 @@ mod tests {
 
     #[test]
     fn test_syntax_rust_keywords() {
-        let syntax = LanguageSyntax::rust().unwrap();
+        let syntax = LanguageSyntax::rust(&SyntaxTheme::default()).unwrap();
         let line = "fn main() { let x = 42; }";
         let spans = syntax.highlight_line(line);
 
@@ -754,11 +2584,287 @@ mod tests {
 
     #[test]
     fn test_syntax_python_keywords() {
-        let syntax = LanguageSyntax::python().unwrap();
+        let syntax = LanguageSyntax::python(&SyntaxTheme::default()).unwrap();
         let line = "def hello(): print('world')";
         let spans = syntax.highlight_line(line);
 
         // Should have highlighted spans for keywords and strings
         assert!(!spans.is_empty());
     }
+
+    #[test]
+    fn test_syntax_custom_theme() {
+        let theme = SyntaxTheme {
+            keyword: Style::default().fg(Color::Red),
+            ..SyntaxTheme::default()
+        };
+        let syntax = LanguageSyntax::rust(&theme).unwrap();
+        let spans = syntax.highlight_line("fn main() {}");
+
+        assert_eq!(spans[0].content, "fn");
+        assert_eq!(spans[0].style, theme.keyword);
+    }
+
+    #[test]
+    fn test_status_info() {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = vec![
+            "fn main() {".to_string(),
+            "    let x = 1;".to_string(),
+            "}".to_string(),
+        ];
+
+        editor.navigate_down();
+        editor.navigate_right();
+        editor.navigate_right();
+
+        let status = editor.status_info();
+        assert_eq!(status.row, 2);
+        assert_eq!(status.col, 3);
+        assert_eq!(status.total_lines, 3);
+        assert_eq!(status.total_chars, 11 + 14 + 1 + 2);
+        assert!(!status.modified);
+        assert_eq!(status.mode, EditorMode::Normal);
+        assert_eq!(status.language, "text");
+
+        // Columns count characters, not bytes
+        editor.content[1] = "héllo wörld".to_string();
+        editor.cursor_col = 0;
+        for _ in 0..8 {
+            editor.navigate_right();
+        }
+        assert_eq!(editor.status_info().col, 9);
+    }
+
+    #[test]
+    fn test_should_autosave_after_idle_edits() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("notes.txt");
+        fs::write(&path, "draft").unwrap();
+        let idle = Duration::from_secs(5);
+
+        let mut editor = EditorState::open_file(&path).unwrap();
+        let later = Instant::now() + idle;
+        assert!(!editor.should_autosave_at(idle, later));
+
+        editor.navigate_line_end();
+        editor.insert_char('!');
+        assert!(!editor.should_autosave_at(idle, Instant::now()));
+        assert!(editor.should_autosave_at(idle, Instant::now() + idle));
+
+        // Saving clears the pending edit
+        assert!(editor.autosave_if_idle(Duration::ZERO).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "draft!");
+        assert!(!editor.should_autosave_at(idle, Instant::now() + idle));
+        assert!(!editor.autosave_if_idle(Duration::ZERO).unwrap());
+
+        // Read-only buffers and buffers without a path are never auto-saved
+        editor.insert_char('?');
+        editor.read_only = true;
+        assert!(!editor.should_autosave_at(idle, Instant::now() + idle));
+        editor.read_only = false;
+        editor.file_path = None;
+        assert!(!editor.should_autosave_at(idle, Instant::now() + idle));
+    }
+
+    #[test]
+    fn test_read_only_ignores_edits() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("secrets.env");
+        fs::write(&path, "TOKEN=abc\nDEBUG=1").unwrap();
+
+        let mut editor = EditorState::open_file(&path).unwrap();
+        editor.read_only = true;
+        assert!(editor.is_read_only());
+
+        editor.insert_char('x');
+        editor.insert_char('\n');
+        editor.delete_char_forward();
+        editor.delete_line();
+        editor.duplicate_line();
+        editor.navigate_line_end();
+        editor.delete_char();
+        assert_eq!(editor.content, vec!["TOKEN=abc", "DEBUG=1"]);
+        assert!(!editor.modified);
+
+        // Vim keys can't edit or enter insert mode; motions still work
+        type_keys(&mut editor, "ixoj");
+        assert_eq!(editor.mode, EditorMode::Normal);
+        assert_eq!(editor.cursor_row, 1);
+        assert_eq!(editor.content, vec!["TOKEN=abc", "DEBUG=1"]);
+
+        assert!(editor.save().is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "TOKEN=abc\nDEBUG=1");
+
+        editor.read_only = false;
+        editor.set_mode(EditorMode::Insert);
+        assert_eq!(editor.mode, EditorMode::Insert);
+    }
+
+    #[test]
+    fn test_large_file_lazy_loading() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.log");
+        let content: String = (0..50_000).map(|i| format!("line {i}\n")).collect();
+        fs::write(&path, content).unwrap();
+
+        let config = LargeFileConfig {
+            threshold_bytes: 1024,
+            max_resident_lines: 1000,
+        };
+        let mut editor = EditorState::open_file_with_config(&path, &config).unwrap();
+
+        assert!(editor.is_lazy());
+        assert_eq!(editor.line_count(), 50_000);
+        assert_eq!(
+            editor.char_count(),
+            fs::read_to_string(&path).unwrap().len() - 1
+        );
+        assert!(editor.resident_line_count() <= 1000);
+        assert_eq!(editor.current_line(), "line 0");
+
+        // Scrolling past the resident window loads later content
+        for _ in 0..1500 {
+            editor.navigate_down();
+        }
+        assert_eq!(editor.current_line(), "line 1500");
+        assert!(editor.resident_line_count() <= 1000);
+
+        editor.navigate_bottom();
+        assert_eq!(editor.current_line(), "line 49999");
+        assert!(editor.resident_line_count() <= 1000);
+
+        editor.navigate_top();
+        assert_eq!(editor.current_line(), "line 0");
+
+        // Editing and saving are disabled
+        editor.insert_char('x');
+        assert_eq!(editor.current_line(), "line 0");
+        assert!(!editor.modified);
+        assert!(editor.save().is_err());
+    }
+
+    #[test]
+    fn test_lazy_word_motion_crosses_resident_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sparse.log");
+        let content = format!("start\n{}  target word\nend\n", "\n".repeat(1500));
+        fs::write(&path, content).unwrap();
+
+        let config = LargeFileConfig {
+            threshold_bytes: 1024,
+            max_resident_lines: 100,
+        };
+        let mut editor = EditorState::open_file_with_config(&path, &config).unwrap();
+        assert!(editor.is_lazy());
+
+        // The blank run is far longer than the window; `w` must not treat
+        // unloaded rows as empty and overshoot
+        editor.navigate_word_forward();
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1501, 2));
+        assert_eq!(editor.current_line(), "  target word");
+
+        editor.navigate_word_end();
+        assert_eq!((editor.cursor_row, editor.cursor_col), (1501, 7));
+
+        editor.navigate_word_backward();
+        editor.navigate_word_backward();
+        assert_eq!((editor.cursor_row, editor.cursor_col), (0, 0));
+        assert_eq!(editor.current_line(), "start");
+        assert!(editor.resident_line_count() <= 100);
+    }
+
+    #[test]
+    fn test_small_file_loads_eagerly() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small.rs");
+        fs::write(&path, "fn main() {}\n").unwrap();
+
+        let editor = EditorState::open_file(&path).unwrap();
+        assert!(!editor.is_lazy());
+        assert_eq!(editor.content, vec!["fn main() {}".to_string()]);
+    }
+
+    fn folding_editor() -> EditorState {
+        let mut editor = EditorState::new().unwrap();
+        editor.content = [
+            "fn main() {",
+            "    if a {",
+            "        b();",
+            "",
+            "        c();",
+            "    }",
+            "}",
+            "",
+            "fn other() {}",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        editor
+    }
+
+    #[test]
+    fn test_fold_and_unfold_change_visible_lines() {
+        let mut editor = folding_editor();
+        let content = editor.content.clone();
+        assert_eq!(editor.visible_line_count(), 9);
+
+        // The blank line inside the block is hidden, the closing brace is not
+        assert!(editor.fold_at(1));
+        assert!(editor.is_folded(1));
+        assert_eq!(editor.visible_line_count(), 6);
+
+        // Nested folds are hidden along with the outer one
+        assert!(editor.fold_at(0));
+        assert_eq!(editor.visible_line_count(), 4);
+
+        assert!(editor.unfold_at(0));
+        assert_eq!(editor.visible_line_count(), 6);
+        assert!(editor.unfold_at(1));
+        assert!(!editor.unfold_at(1));
+        assert_eq!(editor.visible_line_count(), 9);
+        assert_eq!(editor.content, content);
+
+        // Nothing deeper to fold under a blank or top-level last line
+        assert!(!editor.fold_at(3));
+        assert!(!editor.fold_at(8));
+    }
+
+    #[test]
+    fn test_navigation_skips_folded_lines() {
+        let mut editor = folding_editor();
+        editor.cursor_row = 4;
+        assert!(editor.fold_at(1));
+        assert_eq!(editor.cursor_row, 1);
+
+        editor.navigate_down();
+        assert_eq!(editor.cursor_row, 5);
+        editor.navigate_up();
+        assert_eq!(editor.cursor_row, 1);
+
+        // The view scrolls by visible lines: 0, 1, 5, 6, 7, 8
+        editor.navigate_bottom();
+        editor.adjust_scroll(3);
+        assert_eq!(editor.scroll_offset, 6);
+        editor.navigate_top();
+        editor.adjust_scroll(3);
+        assert_eq!(editor.scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_folds_toggle_and_reset_on_line_edits() {
+        let mut editor = folding_editor();
+        type_keys(&mut editor, "z");
+        assert!(editor.is_folded(0));
+        assert_eq!(editor.visible_line_count(), 4);
+        type_keys(&mut editor, "z");
+        assert!(!editor.is_folded(0));
+
+        editor.fold_at(0);
+        editor.navigate_bottom();
+        editor.delete_line();
+        assert!(!editor.is_folded(0));
+        assert_eq!(editor.visible_line_count(), editor.line_count());
+    }
 }
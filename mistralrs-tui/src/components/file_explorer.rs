@@ -2,26 +2,39 @@
 //!
 //! Provides a tree-view file browser with the following features:
 //! - Directory expansion/collapse
-//! - File filtering by glob patterns
-//! - Keyboard navigation
-//! - File metadata display (size, modified time)
+//! - File filtering by glob patterns and `.gitignore` rules
+//! - Keyboard navigation and breadcrumb jumps to ancestor directories
+//! - Multi-selection for batch operations
+//! - File metadata display (size, modified time, permissions)
+//! - Bounded previews of the selected file for a side pane
+//! - Named bookmarks for jumping back to frequently used directories
 //! - Visual indicators for directories and files
 
 use std::{
-    collections::HashSet,
-    fs,
+    any::Any,
+    collections::{BTreeMap, HashSet},
+    fs::{self, File},
+    io::Read,
     path::{Path, PathBuf},
     time::SystemTime,
 };
 
 use anyhow::{Context, Result};
+use ignore::WalkBuilder;
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState},
     Frame,
 };
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::editor::detect_language;
+use super::format::format_relative_time;
+use super::traits::{rect_contains, Component, ComponentContext, EventResult};
+use crate::input::{InputEvent, KeyCode, MouseButton, MouseEvent, MouseEventKind};
 
 /// Represents a single file or directory entry in the explorer
 #[derive(Debug, Clone)]
@@ -36,10 +49,14 @@ pub struct FileEntry {
     pub size: Option<u64>,
     /// Last modified time
     pub modified: Option<SystemTime>,
+    /// Permission string (`-rw-r--r--` on Unix, read-only flag elsewhere)
+    pub permissions: String,
     /// Depth in the tree (for indentation)
     pub depth: usize,
     /// Whether this directory is expanded (only relevant for directories)
     pub is_expanded: bool,
+    /// Whether this entry is part of the multi-selection
+    pub is_selected: bool,
 }
 
 impl FileEntry {
@@ -59,8 +76,10 @@ impl FileEntry {
             is_dir,
             size: if is_dir { None } else { Some(metadata.len()) },
             modified: metadata.modified().ok(),
+            permissions: format_permissions(&metadata),
             depth,
             is_expanded: false,
+            is_selected: false,
         })
     }
 
@@ -77,16 +96,69 @@ impl FileEntry {
         };
 
         let indent = "  ".repeat(self.depth);
-        let size_str = self.size.map(format_file_size).unwrap_or_default();
+        let marker = if self.is_selected { "✓ " } else { "" };
+        let details: Vec<String> = self
+            .size
+            .map(format_file_size)
+            .into_iter()
+            .chain(self.modified.map(format_relative_time))
+            .collect();
 
-        if size_str.is_empty() {
-            format!("{}{} {}", indent, icon, self.name)
+        if details.is_empty() {
+            format!("{}{}{} {}", indent, marker, icon, self.name)
         } else {
-            format!("{}{} {} ({})", indent, icon, self.name, size_str)
+            format!(
+                "{}{}{} {} ({})",
+                indent,
+                marker,
+                icon,
+                self.name,
+                details.join(", ")
+            )
+        }
+    }
+}
+
+/// Preview of a file's leading contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreviewContent {
+    /// Leading text of the file, cut at the last complete line if `truncated`
+    Text {
+        content: String,
+        language: String,
+        truncated: bool,
+    },
+    /// The file contains NUL bytes or invalid UTF-8
+    Binary { language: String, size: u64 },
+    /// Not even one line of the file fits in the preview budget
+    TooLarge { language: String, size: u64 },
+}
+
+impl PreviewContent {
+    /// Language detected from the file extension
+    pub fn language(&self) -> &str {
+        match self {
+            Self::Text { language, .. }
+            | Self::Binary { language, .. }
+            | Self::TooLarge { language, .. } => language,
         }
     }
 }
 
+/// Persistable view state of a [`FileExplorerState`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplorerViewState {
+    pub current_dir: PathBuf,
+    pub cursor: usize,
+    pub expanded_dirs: Vec<PathBuf>,
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub filter_full_path: bool,
+    pub show_permissions: bool,
+    pub respect_gitignore: bool,
+    pub bookmarks: BTreeMap<String, PathBuf>,
+}
+
 /// State for the file explorer component
 #[derive(Debug, Clone)]
 pub struct FileExplorerState {
@@ -98,8 +170,21 @@ pub struct FileExplorerState {
     pub cursor: usize,
     /// Set of expanded directory paths
     pub expanded_dirs: HashSet<PathBuf>,
+    /// Set of selected entry paths
+    pub selected: HashSet<PathBuf>,
     /// Optional filter pattern (glob)
     pub filter: Option<String>,
+    /// Whether the filter matches paths relative to `current_dir` instead of
+    /// file names; patterns with `**` or `/` then search subdirectories
+    pub filter_full_path: bool,
+    /// Whether to render the permission column
+    pub show_permissions: bool,
+    /// Whether to hide entries ignored by the repository's `.gitignore` rules
+    pub respect_gitignore: bool,
+    /// Optional root directory the explorer cannot navigate above
+    pub root: Option<PathBuf>,
+    /// Named bookmarked directories
+    bookmarks: BTreeMap<String, PathBuf>,
 }
 
 impl FileExplorerState {
@@ -111,7 +196,13 @@ impl FileExplorerState {
             entries: Vec::new(),
             cursor: 0,
             expanded_dirs: HashSet::new(),
+            selected: HashSet::new(),
             filter: None,
+            filter_full_path: false,
+            show_permissions: false,
+            respect_gitignore: false,
+            root: None,
+            bookmarks: BTreeMap::new(),
         };
         state.refresh()?;
         Ok(state)
@@ -119,6 +210,9 @@ impl FileExplorerState {
 
     /// Refresh the directory contents
     pub fn refresh(&mut self) -> Result<()> {
+        // Keep selections whose paths still exist
+        self.selected.retain(|path| path.exists());
+
         self.entries.clear();
         self.load_entries(&self.current_dir.clone(), 0)?;
 
@@ -149,12 +243,43 @@ impl FileExplorerState {
             }
         });
 
+        let filter = self.filter.clone();
+        let recursive = self.filter_full_path
+            && filter
+                .as_deref()
+                .is_some_and(|f| f.contains("**") || f.contains('/'));
+
+        // Paths that survive .gitignore rules (None when the option is off)
+        let not_ignored = if self.respect_gitignore {
+            Some(non_ignored_children(dir))
+        } else {
+            None
+        };
+
         for entry in entries {
             let path = entry.path();
 
+            if let Some(not_ignored) = &not_ignored {
+                if !not_ignored.contains(&path) {
+                    continue;
+                }
+            }
+
             // Apply filter if present
-            if let Some(filter) = &self.filter {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            if let Some(filter) = &filter {
+                if self.filter_full_path {
+                    let matched = relative_path(&path, &self.current_dir)
+                        .is_some_and(|rel| path_glob_match(&rel, filter));
+                    if !matched {
+                        // Symlinked directories are not descended into, so
+                        // link cycles cannot recurse forever
+                        let is_real_dir = fs::symlink_metadata(&path).is_ok_and(|m| m.is_dir());
+                        if recursive && is_real_dir {
+                            self.load_matching_subtree(&path, depth)?;
+                        }
+                        continue;
+                    }
+                } else if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
                     if !glob_match(name, filter) {
                         continue;
                     }
@@ -164,6 +289,7 @@ impl FileExplorerState {
             if let Ok(mut file_entry) = FileEntry::from_path(&path, depth) {
                 let is_expanded = self.expanded_dirs.contains(&path);
                 file_entry.is_expanded = is_expanded;
+                file_entry.is_selected = self.selected.contains(&path);
                 self.entries.push(file_entry);
 
                 // If this is an expanded directory, recursively load its contents
@@ -176,6 +302,23 @@ impl FileExplorerState {
         Ok(())
     }
 
+    /// Show `dir` expanded, but only if something below it matches the filter
+    fn load_matching_subtree(&mut self, dir: &Path, depth: usize) -> Result<()> {
+        let Ok(mut dir_entry) = FileEntry::from_path(dir, depth) else {
+            return Ok(());
+        };
+        dir_entry.is_expanded = true;
+        dir_entry.is_selected = self.selected.contains(dir);
+
+        let start = self.entries.len();
+        self.entries.push(dir_entry);
+        self.load_entries(dir, depth + 1)?;
+        if self.entries.len() == start + 1 {
+            self.entries.truncate(start);
+        }
+        Ok(())
+    }
+
     /// Move cursor up
     pub fn navigate_up(&mut self) {
         if self.cursor > 0 {
@@ -212,9 +355,7 @@ impl FileExplorerState {
         let path = entry.path.clone();
 
         if entry.is_dir {
-            self.current_dir = path;
-            self.cursor = 0;
-            self.refresh()?;
+            self.change_dir(path)?;
             Ok(None)
         } else {
             Ok(Some(path))
@@ -223,14 +364,156 @@ impl FileExplorerState {
 
     /// Go to parent directory
     pub fn go_parent(&mut self) -> Result<()> {
+        if self.root.as_deref() == Some(self.current_dir.as_path()) {
+            return Ok(());
+        }
         if let Some(parent) = self.current_dir.parent() {
-            self.current_dir = parent.to_path_buf();
-            self.cursor = 0;
-            self.refresh()?;
+            self.change_dir(parent.to_path_buf())?;
+        }
+        Ok(())
+    }
+
+    /// Restrict navigation to `root` and its descendants
+    ///
+    /// If the current directory lies outside the new root, the explorer moves to the root.
+    pub fn set_root(&mut self, root: Option<PathBuf>) -> Result<()> {
+        let root = root
+            .map(|r| r.canonicalize().context("resolving root path"))
+            .transpose()?;
+
+        if let Some(root) = &root {
+            if !self.current_dir.starts_with(root) {
+                self.root = Some(root.clone());
+                return self.change_dir(root.clone());
+            }
+        }
+
+        self.root = root;
+        Ok(())
+    }
+
+    /// Get the breadcrumb path, from the outermost reachable ancestor to the current directory
+    pub fn ancestors(&self) -> Vec<PathBuf> {
+        let mut ancestors: Vec<PathBuf> = self
+            .current_dir
+            .ancestors()
+            .take_while(|p| self.root.as_ref().is_none_or(|root| p.starts_with(root)))
+            .map(Path::to_path_buf)
+            .collect();
+        ancestors.reverse();
+        ancestors
+    }
+
+    /// Jump to the ancestor at `index` in [`ancestors`](Self::ancestors)
+    pub fn navigate_to_ancestor(&mut self, index: usize) -> Result<()> {
+        let target = self
+            .ancestors()
+            .into_iter()
+            .nth(index)
+            .with_context(|| format!("ancestor index {index} out of range"))?;
+
+        self.change_dir(target)
+    }
+
+    /// Bookmark the current directory under `name`, replacing any existing bookmark
+    pub fn add_bookmark(&mut self, name: impl Into<String>) {
+        self.bookmarks.insert(name.into(), self.current_dir.clone());
+    }
+
+    /// Remove a bookmark, returning its directory if it existed
+    pub fn remove_bookmark(&mut self, name: &str) -> Option<PathBuf> {
+        self.bookmarks.remove(name)
+    }
+
+    /// List bookmarks as `(name, directory)` pairs, sorted by name
+    pub fn bookmarks(&self) -> Vec<(&str, &Path)> {
+        self.bookmarks
+            .iter()
+            .map(|(name, dir)| (name.as_str(), dir.as_path()))
+            .collect()
+    }
+
+    /// Navigate to a bookmarked directory
+    ///
+    /// Fails if the bookmark is unknown, the directory no longer exists, or it
+    /// lies outside [`root`](Self::root).
+    pub fn goto_bookmark(&mut self, name: &str) -> Result<()> {
+        let dir = self
+            .bookmarks
+            .get(name)
+            .with_context(|| format!("unknown bookmark: {name}"))?
+            .clone();
+
+        if !dir.is_dir() {
+            anyhow::bail!("bookmarked directory no longer exists: {}", dir.display());
+        }
+        if let Some(root) = &self.root {
+            if !dir.starts_with(root) {
+                anyhow::bail!("bookmark is outside the explorer root: {}", dir.display());
+            }
+        }
+
+        self.change_dir(dir)
+    }
+
+    /// Capture the view state for persistence
+    pub fn view_state(&self) -> ExplorerViewState {
+        let mut expanded_dirs: Vec<PathBuf> = self.expanded_dirs.iter().cloned().collect();
+        expanded_dirs.sort();
+
+        ExplorerViewState {
+            current_dir: self.current_dir.clone(),
+            cursor: self.cursor,
+            expanded_dirs,
+            filter: self.filter.clone(),
+            filter_full_path: self.filter_full_path,
+            show_permissions: self.show_permissions,
+            respect_gitignore: self.respect_gitignore,
+            bookmarks: self.bookmarks.clone(),
         }
+    }
+
+    /// Restore a view state captured by [`view_state`](Self::view_state)
+    ///
+    /// The saved directory must still exist and lie within [`root`](Self::root);
+    /// expanded directories that no longer exist are dropped.
+    pub fn restore_view_state(&mut self, view: ExplorerViewState) -> Result<()> {
+        let dir = view.current_dir.canonicalize().with_context(|| {
+            format!("resolving saved directory: {}", view.current_dir.display())
+        })?;
+        if let Some(root) = &self.root {
+            if !dir.starts_with(root) {
+                anyhow::bail!(
+                    "saved directory is outside the explorer root: {}",
+                    dir.display()
+                );
+            }
+        }
+
+        self.expanded_dirs = view
+            .expanded_dirs
+            .into_iter()
+            .filter(|p| p.is_dir())
+            .collect();
+        self.filter = view.filter;
+        self.filter_full_path = view.filter_full_path;
+        self.show_permissions = view.show_permissions;
+        self.respect_gitignore = view.respect_gitignore;
+        self.bookmarks = view.bookmarks;
+
+        self.change_dir(dir)?;
+        self.cursor = view.cursor.min(self.entries.len().saturating_sub(1));
         Ok(())
     }
 
+    /// Switch to another directory, resetting the cursor and selection
+    fn change_dir(&mut self, dir: PathBuf) -> Result<()> {
+        self.current_dir = dir;
+        self.cursor = 0;
+        self.selected.clear();
+        self.refresh()
+    }
+
     /// Toggle expansion of the selected directory
     pub fn toggle_expand(&mut self) -> Result<()> {
         if self.entries.is_empty() {
@@ -264,6 +547,121 @@ impl FileExplorerState {
         self.set_filter(None)
     }
 
+    /// Toggle selection of the entry under the cursor
+    pub fn toggle_selection(&mut self) {
+        let Some(entry) = self.entries.get_mut(self.cursor) else {
+            return;
+        };
+
+        entry.is_selected = !entry.is_selected;
+        if entry.is_selected {
+            self.selected.insert(entry.path.clone());
+        } else {
+            self.selected.remove(&entry.path);
+        }
+    }
+
+    /// Get the selected paths, sorted
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.selected.iter().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// Clear the selection
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+        for entry in &mut self.entries {
+            entry.is_selected = false;
+        }
+    }
+
+    /// Match the filter against relative paths instead of file names
+    pub fn set_filter_full_path(&mut self, enabled: bool) -> Result<()> {
+        self.filter_full_path = enabled;
+        self.cursor = 0;
+        self.refresh()
+    }
+
+    /// Enable or disable hiding of `.gitignore`d entries
+    pub fn set_respect_gitignore(&mut self, enabled: bool) -> Result<()> {
+        self.respect_gitignore = enabled;
+        self.cursor = 0;
+        self.refresh()
+    }
+
+    /// Show or hide the permission column
+    pub fn set_show_permissions(&mut self, show: bool) {
+        self.show_permissions = show;
+    }
+
+    /// Toggle the permission column
+    pub fn toggle_permissions(&mut self) {
+        self.show_permissions = !self.show_permissions;
+    }
+
+    /// Preview the file under the cursor, reading at most `max_bytes`
+    ///
+    /// The file must lie within [`root`](Self::root) after resolving symlinks.
+    pub fn preview_selected(&self, max_bytes: usize) -> Result<PreviewContent> {
+        let entry = self.current_entry().context("no entry selected")?;
+        if entry.is_dir {
+            anyhow::bail!("cannot preview directory: {}", entry.path.display());
+        }
+
+        let path = entry
+            .path
+            .canonicalize()
+            .with_context(|| format!("resolving path: {}", entry.path.display()))?;
+        if let Some(root) = &self.root {
+            if !path.starts_with(root) {
+                anyhow::bail!("path is outside the explorer root: {}", path.display());
+            }
+        }
+
+        let file =
+            File::open(&path).with_context(|| format!("opening file: {}", path.display()))?;
+        let size = file.metadata().context("reading file metadata")?.len();
+        let mut buf = Vec::with_capacity(max_bytes.min(size as usize));
+        file.take(max_bytes as u64)
+            .read_to_end(&mut buf)
+            .with_context(|| format!("reading file: {}", path.display()))?;
+
+        Ok(preview_from_bytes(&buf, size, detect_language(&path)))
+    }
+
+    /// Handle a mouse event for an explorer rendered into `area`
+    ///
+    /// A left click selects the entry under the pointer and scrolling moves the
+    /// cursor. Returns whether the event changed the state.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, area: Rect) -> bool {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let inner = area.inner(Margin::new(1, 1));
+                if !rect_contains(inner, event.column, event.row) {
+                    return false;
+                }
+                // The list scrolls just far enough to keep the cursor visible
+                let offset = self.cursor.saturating_sub(inner.height as usize - 1);
+                let index = offset + (event.row - inner.y) as usize;
+                if index >= self.entries.len() {
+                    return false;
+                }
+                self.cursor = index;
+                true
+            }
+            MouseEventKind::ScrollUp => {
+                self.navigate_up();
+                true
+            }
+            MouseEventKind::ScrollDown => {
+                self.navigate_down();
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Get the currently selected entry
     pub fn current_entry(&self) -> Option<&FileEntry> {
         self.entries.get(self.cursor)
@@ -286,7 +684,11 @@ impl FileExplorer {
             .iter()
             .enumerate()
             .map(|(idx, entry)| {
-                let display_text = entry.display_line();
+                let display_text = if state.show_permissions {
+                    format!("{} {}", entry.permissions, entry.display_line())
+                } else {
+                    entry.display_line()
+                };
 
                 // Highlight current item
                 let style = if idx == state.cursor {
@@ -341,6 +743,117 @@ impl FileExplorer {
     }
 }
 
+/// File explorer managed as a [`Component`]
+///
+/// Opening a file publishes its path to the shared context under
+/// [`SELECTED_FILE_KEY`](Self::SELECTED_FILE_KEY) so other components can
+/// pick it up.
+pub struct FileExplorerComponent {
+    state: FileExplorerState,
+}
+
+impl FileExplorerComponent {
+    /// Component ID used with [`ComponentManager`](super::ComponentManager)
+    pub const ID: &'static str = "explorer";
+
+    /// Shared context key holding the path of the last opened file
+    pub const SELECTED_FILE_KEY: &'static str = "explorer.selected_file";
+
+    /// Wrap an explorer state
+    pub fn new(state: FileExplorerState) -> Self {
+        Self { state }
+    }
+
+    /// Borrow the explorer state
+    pub fn state(&self) -> &FileExplorerState {
+        &self.state
+    }
+
+    /// Mutably borrow the explorer state
+    pub fn state_mut(&mut self) -> &mut FileExplorerState {
+        &mut self.state
+    }
+
+    /// Apply a key press, returning whether it was handled
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ComponentContext) -> Result<bool> {
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => self.state.navigate_up(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.navigate_down(),
+            KeyCode::Home | KeyCode::Char('g') => self.state.navigate_top(),
+            KeyCode::End | KeyCode::Char('G') => self.state.navigate_bottom(),
+            KeyCode::Enter => {
+                if let Some(path) = self.state.enter_selection()? {
+                    ctx.set_shared(Self::SELECTED_FILE_KEY, path.to_string_lossy());
+                }
+            }
+            KeyCode::Backspace | KeyCode::Char('h') => self.state.go_parent()?,
+            KeyCode::Char(' ') | KeyCode::Char('l') => self.state.toggle_expand()?,
+            KeyCode::Char('v') => self.state.toggle_selection(),
+            KeyCode::Char('r') => self.state.refresh()?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+impl Component for FileExplorerComponent {
+    fn handle_event(&mut self, event: &InputEvent, ctx: &mut ComponentContext) -> EventResult {
+        let InputEvent::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        if key.modifiers.control || key.modifiers.alt {
+            return EventResult::Ignored;
+        }
+
+        match self.handle_key(key.code, ctx) {
+            Ok(true) => EventResult::Consumed,
+            Ok(false) => EventResult::Ignored,
+            Err(e) => {
+                ctx.set_status(format!("Error: {e:#}"));
+                EventResult::Consumed
+            }
+        }
+    }
+
+    fn handle_mouse(
+        &mut self,
+        event: &MouseEvent,
+        area: Rect,
+        _ctx: &mut ComponentContext,
+    ) -> EventResult {
+        if self.state.handle_mouse(event, area) {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    fn render(&self, area: Rect, frame: &mut Frame<'_>, focused: bool) {
+        FileExplorer::render(frame, area, &self.state, focused);
+    }
+
+    fn id(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn save_state(&self) -> JsonValue {
+        serde_json::to_value(self.state.view_state()).unwrap_or_default()
+    }
+
+    fn load_state(&mut self, state: JsonValue) -> Result<()> {
+        self.state
+            .restore_view_state(serde_json::from_value(state)?)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 /// Render the file explorer component
 ///
 /// This is a convenience function that matches the pattern used in ui.rs
@@ -371,6 +884,117 @@ fn format_file_size(size: u64) -> String {
     }
 }
 
+/// Collect the immediate children of `dir` that are not excluded by `.gitignore` rules
+///
+/// Rules from parent directories, `.git/info/exclude` and the global excludes file are
+/// honored; hidden files are left alone so this composes with the other filters.
+fn non_ignored_children(dir: &Path) -> HashSet<PathBuf> {
+    WalkBuilder::new(dir)
+        .max_depth(Some(1))
+        .hidden(false)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.depth() == 1)
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// Format permissions from metadata
+///
+/// On Unix this is the familiar `ls -l` mode string (e.g. `-rw-r--r--`);
+/// elsewhere only the read-only flag is available, shown as `-r--` or `-rw-`.
+fn format_permissions(metadata: &fs::Metadata) -> String {
+    let kind = if metadata.is_dir() { 'd' } else { '-' };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = metadata.permissions().mode();
+        let mut perms = String::with_capacity(10);
+        perms.push(kind);
+        for shift in [6, 3, 0] {
+            let bits = (mode >> shift) & 0o7;
+            perms.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+            perms.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+            perms.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+        }
+        perms
+    }
+
+    #[cfg(not(unix))]
+    {
+        let write = if metadata.permissions().readonly() {
+            '-'
+        } else {
+            'w'
+        };
+        format!("{kind}r{write}-")
+    }
+}
+
+/// Classify the leading bytes of a file of `size` bytes as a preview
+fn preview_from_bytes(buf: &[u8], size: u64, language: String) -> PreviewContent {
+    if buf.contains(&0) {
+        return PreviewContent::Binary { language, size };
+    }
+
+    let truncated = (buf.len() as u64) < size;
+    let text = match std::str::from_utf8(buf) {
+        Ok(text) => text,
+        // A multi-byte character cut off by the byte limit is not binary
+        Err(e) if truncated && e.error_len().is_none() => {
+            std::str::from_utf8(&buf[..e.valid_up_to()]).unwrap_or_default()
+        }
+        Err(_) => return PreviewContent::Binary { language, size },
+    };
+
+    if !truncated {
+        return PreviewContent::Text {
+            content: text.to_string(),
+            language,
+            truncated,
+        };
+    }
+
+    match text.rfind('\n') {
+        Some(end) => PreviewContent::Text {
+            content: text[..=end].to_string(),
+            language,
+            truncated,
+        },
+        None => PreviewContent::TooLarge { language, size },
+    }
+}
+
+/// `path` relative to `base` with `/` separators
+fn relative_path(path: &Path, base: &Path) -> Option<String> {
+    let rel = path.strip_prefix(base).ok()?;
+    let parts: Vec<&str> = rel
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    Some(parts.join("/"))
+}
+
+/// Match a `/`-separated path against a glob where `**` spans any number
+/// of directories and other segments use [`glob_match`]
+fn path_glob_match(path: &str, pattern: &str) -> bool {
+    fn match_segments(path: &[&str], pattern: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(&path[skip..], rest)),
+            Some((segment, rest)) => path.split_first().is_some_and(|(first, tail)| {
+                glob_match(first, segment) && match_segments(tail, rest)
+            }),
+        }
+    }
+
+    let path: Vec<&str> = path.split('/').collect();
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    match_segments(&path, &pattern)
+}
+
 /// Simple glob pattern matching (supports * and ? wildcards)
 fn glob_match(text: &str, pattern: &str) -> bool {
     // Simple implementation - could be replaced with glob crate if needed
@@ -414,6 +1038,11 @@ fn glob_match(text: &str, pattern: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::{KeyEvent, Modifiers};
+
+    fn key(code: KeyCode) -> InputEvent {
+        InputEvent::Key(KeyEvent::new(code, Modifiers::NONE))
+    }
 
     #[test]
     fn test_format_file_size() {
@@ -445,8 +1074,10 @@ mod tests {
             is_dir: false,
             size: Some(1024),
             modified: None,
+            permissions: String::new(),
             depth: 0,
             is_expanded: false,
+            is_selected: false,
         };
 
         let display = entry.display_line();
@@ -455,6 +1086,24 @@ mod tests {
         assert!(display.contains("1.0 KB"));
     }
 
+    #[test]
+    fn test_file_entry_display_modified() {
+        let temp_dir = std::env::temp_dir();
+        let entry = FileEntry {
+            path: temp_dir.join("test.txt"),
+            name: "test.txt".to_string(),
+            is_dir: false,
+            size: Some(1024),
+            modified: Some(SystemTime::now()),
+            permissions: String::new(),
+            depth: 0,
+            is_expanded: false,
+            is_selected: false,
+        };
+
+        assert!(entry.display_line().ends_with("(1.0 KB, just now)"));
+    }
+
     #[test]
     fn test_directory_entry_display() {
         let temp_dir = std::env::temp_dir();
@@ -464,8 +1113,10 @@ mod tests {
             is_dir: true,
             size: None,
             modified: None,
+            permissions: String::new(),
             depth: 0,
             is_expanded: false,
+            is_selected: false,
         };
 
         let display = entry.display_line();
@@ -476,4 +1127,325 @@ mod tests {
         let display_expanded = entry.display_line();
         assert!(display_expanded.contains("📂"));
     }
+
+    #[test]
+    fn test_multi_select() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir(root.join("sub")).unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(root.join(name), name).unwrap();
+        }
+
+        // Entries: sub/, a.txt, b.txt, c.txt
+        let mut state = FileExplorerState::new(&root).unwrap();
+        state.navigate_down();
+        state.toggle_selection();
+        state.navigate_down();
+        state.navigate_down();
+        state.toggle_selection();
+
+        assert_eq!(
+            state.selected_paths(),
+            vec![root.join("a.txt"), root.join("c.txt")]
+        );
+        assert!(state.entries[1].display_line().contains('✓'));
+        assert!(!state.entries[2].display_line().contains('✓'));
+
+        // Toggling again deselects
+        state.toggle_selection();
+        assert_eq!(state.selected_paths(), vec![root.join("a.txt")]);
+
+        // Survives a refresh, dropping paths that disappeared
+        state.navigate_down();
+        state.toggle_selection();
+        state.refresh().unwrap();
+        assert!(state.entries[1].is_selected);
+        fs::remove_file(root.join("a.txt")).unwrap();
+        state.refresh().unwrap();
+        assert_eq!(state.selected_paths(), vec![root.join("c.txt")]);
+
+        // Cleared on directory change
+        state.navigate_top();
+        state.enter_selection().unwrap();
+        assert!(state.selected_paths().is_empty());
+
+        state.go_parent().unwrap();
+        state.navigate_down();
+        state.toggle_selection();
+        state.clear_selection();
+        assert!(state.selected_paths().is_empty());
+        assert!(state.entries.iter().all(|e| !e.is_selected));
+    }
+
+    #[test]
+    fn test_ancestors_and_navigation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let nested = root.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+
+        let mut state = FileExplorerState::new(&nested).unwrap();
+        state.set_root(Some(root.clone())).unwrap();
+
+        assert_eq!(
+            state.ancestors(),
+            vec![root.clone(), root.join("a"), nested.clone()]
+        );
+
+        state.navigate_to_ancestor(1).unwrap();
+        assert_eq!(state.current_dir, root.join("a"));
+        assert_eq!(state.cursor, 0);
+
+        state.navigate_to_ancestor(0).unwrap();
+        assert_eq!(state.current_dir, root);
+
+        // Cannot leave the root
+        state.go_parent().unwrap();
+        assert_eq!(state.current_dir, root);
+        assert!(state.navigate_to_ancestor(5).is_err());
+    }
+
+    #[test]
+    fn test_bookmarks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let project = root.join("project");
+        fs::create_dir_all(project.join("src")).unwrap();
+
+        let mut state = FileExplorerState::new(&project).unwrap();
+        state.add_bookmark("proj");
+        assert_eq!(state.bookmarks(), vec![("proj", project.as_path())]);
+
+        state.enter_selection().unwrap();
+        assert_eq!(state.current_dir, project.join("src"));
+        state.go_parent().unwrap();
+        state.go_parent().unwrap();
+        assert_eq!(state.current_dir, root);
+
+        state.goto_bookmark("proj").unwrap();
+        assert_eq!(state.current_dir, project);
+        assert!(state.goto_bookmark("missing").is_err());
+
+        // Bookmarks outside the root are rejected
+        state.set_root(Some(project.join("src"))).unwrap();
+        assert!(state.goto_bookmark("proj").is_err());
+        assert_eq!(state.current_dir, project.join("src"));
+    }
+
+    #[test]
+    fn test_path_glob_match() {
+        assert!(path_glob_match("main.rs", "**/*.rs"));
+        assert!(path_glob_match("src/a/b/test_x.rs", "**/test_*.rs"));
+        assert!(path_glob_match("src/lib.rs", "src/*.rs"));
+        assert!(!path_glob_match("src/a/lib.rs", "src/*.rs"));
+        assert!(path_glob_match("src/a/lib.rs", "src/**/lib.rs"));
+        assert!(!path_glob_match("docs/readme.md", "**/*.rs"));
+    }
+
+    #[test]
+    fn test_filter_full_path_recursive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/nested/deep")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("top.rs"), "").unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("src/notes.txt"), "").unwrap();
+        fs::write(root.join("src/nested/deep/test_a.rs"), "").unwrap();
+        fs::write(root.join("docs/readme.md"), "").unwrap();
+
+        let mut state = FileExplorerState::new(root).unwrap();
+        state.set_filter_full_path(true).unwrap();
+        state.set_filter(Some("**/*.rs".to_string())).unwrap();
+
+        let listing: Vec<(usize, String)> = state
+            .entries
+            .iter()
+            .map(|e| (e.depth, e.name.clone()))
+            .collect();
+        assert_eq!(
+            listing,
+            vec![
+                (0, "src".to_string()),
+                (1, "nested".to_string()),
+                (2, "deep".to_string()),
+                (3, "test_a.rs".to_string()),
+                (1, "main.rs".to_string()),
+                (0, "top.rs".to_string()),
+            ]
+        );
+        assert!(state.entries[0].is_expanded);
+
+        // Only the deep match survives a narrower pattern
+        state.set_filter(Some("**/test_*.rs".to_string())).unwrap();
+        let names: Vec<&str> = state.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["src", "nested", "deep", "test_a.rs"]);
+
+        // Name matching is unchanged when the mode is off
+        state.set_filter_full_path(false).unwrap();
+        state.set_filter(Some("*.rs".to_string())).unwrap();
+        let names: Vec<&str> = state.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["top.rs"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_filter_full_path_skips_symlinked_dirs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        std::os::unix::fs::symlink(root, root.join("src/loop")).unwrap();
+
+        let mut state = FileExplorerState::new(root).unwrap();
+        state.set_filter_full_path(true).unwrap();
+        state.set_filter(Some("**/*.rs".to_string())).unwrap();
+
+        let names: Vec<&str> = state.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["src", "main.rs"]);
+    }
+
+    #[test]
+    fn test_respect_gitignore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "ignored.log\n").unwrap();
+        fs::write(temp_dir.path().join("ignored.log"), "noise").unwrap();
+        fs::write(temp_dir.path().join("kept.txt"), "signal").unwrap();
+
+        let mut state = FileExplorerState::new(temp_dir.path()).unwrap();
+        let names = |state: &FileExplorerState| -> Vec<String> {
+            state.entries.iter().map(|e| e.name.clone()).collect()
+        };
+        assert!(names(&state).contains(&"ignored.log".to_string()));
+
+        state.set_respect_gitignore(true).unwrap();
+        assert!(!names(&state).contains(&"ignored.log".to_string()));
+        assert!(names(&state).contains(&"kept.txt".to_string()));
+
+        // Composes with the glob filter
+        state.set_filter(Some("*.log".to_string())).unwrap();
+        assert!(state.entries.is_empty());
+
+        state.clear_filter().unwrap();
+        state.set_respect_gitignore(false).unwrap();
+        assert!(names(&state).contains(&"ignored.log".to_string()));
+    }
+
+    #[test]
+    fn test_preview_text_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::write(root.join("main.rs"), "fn main() {}\n// second line\n").unwrap();
+
+        let mut state = FileExplorerState::new(&root).unwrap();
+        state.set_root(Some(root.clone())).unwrap();
+
+        assert_eq!(
+            state.preview_selected(1024).unwrap(),
+            PreviewContent::Text {
+                content: "fn main() {}\n// second line\n".to_string(),
+                language: "rust".to_string(),
+                truncated: false,
+            }
+        );
+        assert_eq!(
+            state.preview_selected(16).unwrap(),
+            PreviewContent::Text {
+                content: "fn main() {}\n".to_string(),
+                language: "rust".to_string(),
+                truncated: true,
+            }
+        );
+        assert!(matches!(
+            state.preview_selected(4).unwrap(),
+            PreviewContent::TooLarge { size: 28, .. }
+        ));
+    }
+
+    #[test]
+    fn test_preview_binary_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::write(root.join("image.png"), [0x89, b'P', b'N', b'G', 0, 0, 1]).unwrap();
+
+        let state = FileExplorerState::new(&root).unwrap();
+        let preview = state.preview_selected(1024).unwrap();
+
+        assert_eq!(
+            preview,
+            PreviewContent::Binary {
+                language: "text".to_string(),
+                size: 7,
+            }
+        );
+        assert_eq!(preview.language(), "text");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_string() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("script.sh");
+        fs::write(&path, "echo hi").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let entry = FileEntry::from_path(&path, 0).unwrap();
+        assert_eq!(entry.permissions, "-rw-r--r--");
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let entry = FileEntry::from_path(&path, 0).unwrap();
+        assert_eq!(entry.permissions, "-rwxr-xr-x");
+
+        let dir_entry = FileEntry::from_path(temp_dir.path(), 0).unwrap();
+        assert!(dir_entry.permissions.starts_with('d'));
+    }
+
+    #[test]
+    fn test_component_enter_publishes_selected_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+        std::fs::write(root.join("README.md"), "# readme").unwrap();
+
+        let mut component = FileExplorerComponent::new(FileExplorerState::new(&root).unwrap());
+        let mut ctx = ComponentContext::new();
+
+        // Entering a directory publishes nothing
+        assert_eq!(
+            component.handle_event(&key(KeyCode::Enter), &mut ctx),
+            EventResult::Consumed
+        );
+        assert_eq!(component.state().current_dir(), root.join("src"));
+        assert!(ctx
+            .get_shared(FileExplorerComponent::SELECTED_FILE_KEY)
+            .is_none());
+
+        component.handle_event(&key(KeyCode::Char('h')), &mut ctx);
+        component.handle_event(&key(KeyCode::Char('j')), &mut ctx);
+        assert_eq!(component.state().current_entry().unwrap().name, "README.md");
+        component.handle_event(&key(KeyCode::Enter), &mut ctx);
+
+        let selected = root.join("README.md");
+        assert_eq!(
+            ctx.get_shared(FileExplorerComponent::SELECTED_FILE_KEY),
+            Some(selected.to_str().unwrap())
+        );
+
+        let ctrl_j = InputEvent::Key(KeyEvent::new(
+            KeyCode::Char('j'),
+            Modifiers {
+                control: true,
+                ..Modifiers::NONE
+            },
+        ));
+        assert_eq!(
+            component.handle_event(&ctrl_j, &mut ctx),
+            EventResult::Ignored
+        );
+    }
 }
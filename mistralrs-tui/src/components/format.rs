@@ -0,0 +1,83 @@
+//! Shared formatting helpers for component rendering
+
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Datelike, Utc};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Format a timestamp relative to the current time
+///
+/// See [`format_relative_time_from`] for the output format.
+pub fn format_relative_time(t: SystemTime) -> String {
+    format_relative_time_from(t, SystemTime::now())
+}
+
+/// Format a timestamp relative to `now` as a compact string
+///
+/// - under a minute (or in the future): `just now`
+/// - under an hour: `5m`
+/// - under a day: `3h`
+/// - under a week: `2d`
+/// - otherwise the UTC date: `Jan 4`, with the year appended when it differs
+///   from `now`'s year (`Jan 4 2023`)
+pub fn format_relative_time_from(t: SystemTime, now: SystemTime) -> String {
+    let elapsed = now.duration_since(t).unwrap_or(Duration::ZERO).as_secs();
+
+    if elapsed < MINUTE {
+        "just now".to_string()
+    } else if elapsed < HOUR {
+        format!("{}m", elapsed / MINUTE)
+    } else if elapsed < DAY {
+        format!("{}h", elapsed / HOUR)
+    } else if elapsed < WEEK {
+        format!("{}d", elapsed / DAY)
+    } else {
+        let date = DateTime::<Utc>::from(t);
+        if date.year() == DateTime::<Utc>::from(now).year() {
+            date.format("%b %-d").to_string()
+        } else {
+            date.format("%b %-d %Y").to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-03-15 12:00:00 UTC
+    fn fixed_now() -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_710_504_000)
+    }
+
+    fn ago(secs: u64) -> String {
+        let now = fixed_now();
+        format_relative_time_from(now - Duration::from_secs(secs), now)
+    }
+
+    #[test]
+    fn test_relative_time_buckets() {
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(60), "1m");
+        assert_eq!(ago(59 * MINUTE + 59), "59m");
+        assert_eq!(ago(HOUR), "1h");
+        assert_eq!(ago(23 * HOUR), "23h");
+        assert_eq!(ago(DAY), "1d");
+        assert_eq!(ago(6 * DAY), "6d");
+        assert_eq!(ago(WEEK), "Mar 8");
+        assert_eq!(ago(70 * DAY), "Jan 5");
+        assert_eq!(ago(100 * DAY), "Dec 6 2023");
+    }
+
+    #[test]
+    fn test_relative_time_future_is_just_now() {
+        let now = fixed_now();
+        let future = now + Duration::from_secs(HOUR);
+        assert_eq!(format_relative_time_from(future, now), "just now");
+    }
+}
@@ -7,11 +7,37 @@ use ratatui::{
     style::{Color, Style},
     text::Span,
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::format::format_relative_time_from;
+
+/// Kind of working tree the status describes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepoKind {
+    /// The repository's main working tree
+    #[default]
+    Main,
+    /// A worktree created with `git worktree add`
+    LinkedWorktree,
+    /// A submodule checked out inside a superproject
+    Submodule,
+}
+
+impl RepoKind {
+    /// Short label shown next to the branch, if any
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            RepoKind::Main => None,
+            RepoKind::LinkedWorktree => Some("[wt]"),
+            RepoKind::Submodule => Some("[sub]"),
+        }
+    }
+}
 
 /// Git repository status information
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct GitStatus {
     /// Current branch name
     pub branch: Option<String>,
@@ -19,12 +45,20 @@ pub struct GitStatus {
     pub ahead: usize,
     /// Number of commits behind remote
     pub behind: usize,
-    /// Number of modified files
+    /// Commit time of `HEAD`, if the branch has any commits
+    pub last_commit: Option<SystemTime>,
+    /// Number of modified files with no staged changes
     pub modified: usize,
-    /// Number of staged files
+    /// Number of staged files with no further worktree changes
     pub staged: usize,
+    /// Number of files with both staged and unstaged changes (`MM`)
+    pub staged_and_modified: usize,
     /// Number of untracked files
     pub untracked: usize,
+    /// Number of submodules with uncommitted or untracked changes
+    pub dirty_submodules: usize,
+    /// Whether this is the main worktree, a linked worktree or a submodule
+    pub repo_kind: RepoKind,
     /// Whether the current path is in a git repository
     pub is_repo: bool,
 }
@@ -32,19 +66,35 @@ pub struct GitStatus {
 impl GitStatus {
     /// Check if the repository is clean (no changes)
     pub fn is_clean(&self) -> bool {
-        self.modified == 0 && self.staged == 0 && self.untracked == 0
+        self.modified == 0
+            && self.staged == 0
+            && self.staged_and_modified == 0
+            && self.untracked == 0
+            && self.dirty_submodules == 0
     }
 
     /// Check if the branch is synchronized with remote
     pub fn is_synced(&self) -> bool {
         self.ahead == 0 && self.behind == 0
     }
+
+    /// Age of the `HEAD` commit relative to `now`, e.g. `3h`
+    pub fn last_commit_age(&self, now: SystemTime) -> Option<String> {
+        self.last_commit
+            .map(|time| format_relative_time_from(time, now))
+    }
+
+    /// Check whether anything shown in the status bar differs from `previous`
+    pub fn changed_since(&self, previous: &GitStatus) -> bool {
+        self != previous
+    }
 }
 
 /// Git status provider that queries git via shell commands
 pub struct GitStatusProvider {
     repo_path: std::path::PathBuf,
     cached_status: GitStatus,
+    last_refreshed: Instant,
 }
 
 impl GitStatusProvider {
@@ -53,11 +103,23 @@ impl GitStatusProvider {
         let mut provider = Self {
             repo_path: repo_path.to_path_buf(),
             cached_status: GitStatus::default(),
+            last_refreshed: Instant::now(),
         };
         provider.refresh();
         provider
     }
 
+    /// Create a provider seeded with a known status instead of querying git
+    ///
+    /// The status is replaced on the next [`refresh`](Self::refresh).
+    pub fn with_status(repo_path: &Path, status: GitStatus) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            cached_status: status,
+            last_refreshed: Instant::now(),
+        }
+    }
+
     /// Check if the given path is in a git repository
     pub fn is_in_repo(&self) -> bool {
         self.cached_status.is_repo
@@ -68,8 +130,29 @@ impl GitStatusProvider {
         &self.cached_status
     }
 
+    /// When the status was last refreshed (or seeded)
+    pub fn last_refreshed(&self) -> Instant {
+        self.last_refreshed
+    }
+
+    /// Refresh if at least `interval` has passed since the last refresh
+    ///
+    /// Returns `true` only when a refresh ran and the status changed, so
+    /// callers can skip redrawing otherwise.
+    pub fn refresh_if_stale(&mut self, interval: Duration) -> bool {
+        if self.last_refreshed.elapsed() < interval {
+            return false;
+        }
+
+        let previous = self.cached_status.clone();
+        self.refresh();
+        self.cached_status.changed_since(&previous)
+    }
+
     /// Refresh git status by querying git commands
     pub fn refresh(&mut self) {
+        self.last_refreshed = Instant::now();
+
         // Check if we're in a git repo
         if !self.check_is_repo() {
             self.cached_status = GitStatus::default();
@@ -81,18 +164,40 @@ impl GitStatusProvider {
             ..Default::default()
         };
 
-        // Get branch name
+        // Get branch name. Git resolves the innermost repository, so inside a
+        // submodule this is the submodule's branch, not the superproject's.
         status.branch = self.get_branch_name();
 
+        // Get repository layout
+        let toplevel = match self.get_repo_layout() {
+            Some((kind, toplevel)) => {
+                status.repo_kind = kind;
+                Some(toplevel)
+            }
+            None => None,
+        };
+
         // Get ahead/behind counts
         let (ahead, behind) = self.get_ahead_behind();
         status.ahead = ahead;
         status.behind = behind;
 
-        // Get file status counts
-        let (modified, staged, untracked) = self.get_file_status();
+        status.last_commit = self.get_last_commit_time();
+
+        // Only repositories with submodules pay for the extra status call
+        let dirty_submodules = if toplevel.is_some_and(|dir| dir.join(".gitmodules").is_file()) {
+            self.get_dirty_submodules()
+        } else {
+            Vec::new()
+        };
+        status.dirty_submodules = dirty_submodules.len();
+
+        // Get file status counts; dirty submodules are only counted above
+        let (modified, staged, staged_and_modified, untracked) =
+            self.get_file_status(&dirty_submodules);
         status.modified = modified;
         status.staged = staged;
+        status.staged_and_modified = staged_and_modified;
         status.untracked = untracked;
 
         self.cached_status = status;
@@ -104,13 +209,21 @@ impl GitStatusProvider {
             return String::new();
         }
 
-        let branch = self.cached_status.branch.as_deref().unwrap_or("(detached)");
+        let mut branch = self
+            .cached_status
+            .branch
+            .as_deref()
+            .unwrap_or("(detached)")
+            .to_string();
+        if let Some(label) = self.cached_status.repo_kind.label() {
+            branch = format!("{} {}", branch, label);
+        }
 
         if self.cached_status.is_clean() && self.cached_status.is_synced() {
             return format!("{} ✓", branch);
         }
 
-        let mut parts = vec![branch.to_string()];
+        let mut parts = vec![branch];
 
         // Add ahead/behind indicators
         if self.cached_status.ahead > 0 {
@@ -127,9 +240,15 @@ impl GitStatusProvider {
         if self.cached_status.staged > 0 {
             parts.push(format!("+{}", self.cached_status.staged));
         }
+        if self.cached_status.staged_and_modified > 0 {
+            parts.push(format!("±{}", self.cached_status.staged_and_modified));
+        }
         if self.cached_status.untracked > 0 {
             parts.push(format!("?{}", self.cached_status.untracked));
         }
+        if self.cached_status.dirty_submodules > 0 {
+            parts.push(format!("§{}", self.cached_status.dirty_submodules));
+        }
 
         parts.join(" ")
     }
@@ -161,6 +280,49 @@ impl GitStatusProvider {
         }
     }
 
+    /// Determine the repository kind and the working tree's top-level directory
+    fn get_repo_layout(&self) -> Option<(RepoKind, PathBuf)> {
+        // `--path-format=absolute` needs git 2.31, so relative directories are
+        // resolved here instead
+        let output = Command::new("git")
+            .args([
+                "rev-parse",
+                "--show-toplevel",
+                "--git-dir",
+                "--git-common-dir",
+                "--show-superproject-working-tree",
+            ])
+            .current_dir(&self.repo_path)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let text = String::from_utf8(output.stdout).ok()?;
+        let mut lines = text.lines();
+        let toplevel = PathBuf::from(lines.next()?);
+        let git_dir = self.resolve_git_path(lines.next()?);
+        let common_dir = self.resolve_git_path(lines.next()?);
+        // Only printed when the repository is a submodule
+        let superproject = lines.next().filter(|line| !line.is_empty());
+
+        let kind = if superproject.is_some() {
+            RepoKind::Submodule
+        } else if git_dir != common_dir {
+            RepoKind::LinkedWorktree
+        } else {
+            RepoKind::Main
+        };
+        Some((kind, toplevel))
+    }
+
+    /// Make a directory printed by `git rev-parse` absolute and canonical
+    fn resolve_git_path(&self, path: &str) -> PathBuf {
+        let path = self.repo_path.join(path);
+        path.canonicalize().unwrap_or(path)
+    }
+
     fn get_ahead_behind(&self) -> (usize, usize) {
         // Try to get ahead/behind counts relative to upstream
         let output = Command::new("git")
@@ -185,7 +347,24 @@ impl GitStatusProvider {
         (0, 0)
     }
 
-    fn get_file_status(&self) -> (usize, usize, usize) {
+    /// Commit time of `HEAD`; `None` on an unborn branch
+    fn get_last_commit_time(&self) -> Option<SystemTime> {
+        let output = Command::new("git")
+            .args(["log", "-1", "--format=%ct"])
+            .current_dir(&self.repo_path)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let secs: u64 = String::from_utf8(output.stdout).ok()?.trim().parse().ok()?;
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    /// Count file changes, leaving out the given dirty submodules, which
+    /// `git status` also lists as modified
+    fn get_file_status(&self, dirty_submodules: &[String]) -> (usize, usize, usize, usize) {
         let output = Command::new("git")
             .args(["status", "--porcelain"])
             .current_dir(&self.repo_path)
@@ -194,17 +373,71 @@ impl GitStatusProvider {
         if let Ok(output) = output {
             if output.status.success() {
                 if let Ok(text) = String::from_utf8(output.stdout) {
-                    return self.parse_porcelain_status(&text);
+                    let text: Vec<&str> = text
+                        .lines()
+                        .filter(|line| {
+                            !line
+                                .get(3..)
+                                .is_some_and(|path| dirty_submodules.iter().any(|sub| sub == path))
+                        })
+                        .collect();
+                    return self.parse_porcelain_status(&text.join("\n"));
                 }
             }
         }
 
-        (0, 0, 0)
+        (0, 0, 0, 0)
+    }
+
+    fn get_dirty_submodules(&self) -> Vec<String> {
+        let output = Command::new("git")
+            .args(["status", "--porcelain=v2", "--ignore-submodules=none"])
+            .current_dir(&self.repo_path)
+            .output();
+
+        match output {
+            Ok(output) if output.status.success() => String::from_utf8(output.stdout)
+                .map(|text| self.parse_dirty_submodules(&text))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Paths of submodules whose porcelain v2 `<sub>` field (`S<c><m><u>`)
+    /// reports tracked (`m`) or untracked (`u`) changes
+    ///
+    /// A submodule whose only change is a new commit (`c`) is not dirty and
+    /// stays in the file counts.
+    fn parse_dirty_submodules(&self, porcelain: &str) -> Vec<String> {
+        porcelain
+            .lines()
+            .filter_map(|line| {
+                // Ordinary entries have 8 fields before the path, renames 9
+                let fields: Vec<&str> = match line.get(..2)? {
+                    "1 " => line.splitn(9, ' ').collect(),
+                    "2 " => line.splitn(10, ' ').collect(),
+                    _ => return None,
+                };
+                let flags: Vec<char> = fields.get(2)?.chars().collect();
+                let dirty =
+                    flags.len() == 4 && flags[0] == 'S' && (flags[2] == 'M' || flags[3] == 'U');
+                if !dirty {
+                    return None;
+                }
+                let path = fields.last()?.split('\t').next()?;
+                Some(path.to_string())
+            })
+            .collect()
     }
 
-    fn parse_porcelain_status(&self, porcelain: &str) -> (usize, usize, usize) {
+    /// Count `(modified, staged, staged_and_modified, untracked)` files
+    ///
+    /// Each file lands in exactly one bucket, so a partially staged file
+    /// counts as `staged_and_modified` rather than as both.
+    fn parse_porcelain_status(&self, porcelain: &str) -> (usize, usize, usize, usize) {
         let mut modified = 0;
         let mut staged = 0;
+        let mut staged_and_modified = 0;
         let mut untracked = 0;
 
         for line in porcelain.lines() {
@@ -215,23 +448,23 @@ impl GitStatusProvider {
             let index_status = line.chars().nth(0).unwrap_or(' ');
             let worktree_status = line.chars().nth(1).unwrap_or(' ');
 
-            // Check for staged changes (index status)
-            if index_status != ' ' && index_status != '?' {
-                staged += 1;
-            }
-
-            // Check for worktree changes
-            if worktree_status != ' ' && worktree_status != '?' {
-                modified += 1;
-            }
-
             // Check for untracked files
             if index_status == '?' && worktree_status == '?' {
                 untracked += 1;
+                continue;
+            }
+
+            let has_staged = index_status != ' ' && index_status != '?';
+            let has_worktree = worktree_status != ' ' && worktree_status != '?';
+            match (has_staged, has_worktree) {
+                (true, true) => staged_and_modified += 1,
+                (true, false) => staged += 1,
+                (false, true) => modified += 1,
+                (false, false) => {}
             }
         }
 
-        (modified, staged, untracked)
+        (modified, staged, staged_and_modified, untracked)
     }
 }
 
@@ -241,17 +474,14 @@ pub fn render_git_status(status: &GitStatus) -> Span<'static> {
         return Span::raw("");
     }
 
-    let provider = GitStatusProvider {
-        repo_path: std::path::PathBuf::new(),
-        cached_status: status.clone(),
-    };
+    let provider = GitStatusProvider::with_status(Path::new(""), status.clone());
 
     let text = provider.format_status_line();
 
     // Determine color based on status
     let style = if status.is_clean() && status.is_synced() {
         Style::default().fg(Color::Green)
-    } else if status.modified > 0 || status.untracked > 0 {
+    } else if status.modified > 0 || status.staged_and_modified > 0 || status.untracked > 0 {
         Style::default().fg(Color::Yellow)
     } else if status.ahead > 0 || status.behind > 0 {
         Style::default().fg(Color::Cyan)
@@ -308,34 +538,83 @@ mod tests {
         let provider = GitStatusProvider {
             repo_path: std::path::PathBuf::new(),
             cached_status: GitStatus::default(),
+            last_refreshed: Instant::now(),
         };
 
         // Test modified file
-        let (modified, staged, untracked) = provider.parse_porcelain_status(" M file.txt");
+        let (modified, staged, _, untracked) = provider.parse_porcelain_status(" M file.txt");
         assert_eq!(modified, 1);
         assert_eq!(staged, 0);
         assert_eq!(untracked, 0);
 
         // Test staged file
-        let (modified, staged, untracked) = provider.parse_porcelain_status("M  file.txt");
+        let (modified, staged, _, untracked) = provider.parse_porcelain_status("M  file.txt");
         assert_eq!(modified, 0);
         assert_eq!(staged, 1);
         assert_eq!(untracked, 0);
 
         // Test untracked file
-        let (modified, staged, untracked) = provider.parse_porcelain_status("?? file.txt");
+        let (modified, staged, _, untracked) = provider.parse_porcelain_status("?? file.txt");
         assert_eq!(modified, 0);
         assert_eq!(staged, 0);
         assert_eq!(untracked, 1);
 
         // Test multiple files
         let porcelain = " M file1.txt\nM  file2.txt\n?? file3.txt";
-        let (modified, staged, untracked) = provider.parse_porcelain_status(porcelain);
+        let (modified, staged, _, untracked) = provider.parse_porcelain_status(porcelain);
+        assert_eq!(modified, 1);
+        assert_eq!(staged, 1);
+        assert_eq!(untracked, 1);
+    }
+
+    #[test]
+    fn test_parse_porcelain_staged_and_modified() {
+        let provider = GitStatusProvider {
+            repo_path: std::path::PathBuf::new(),
+            cached_status: GitStatus::default(),
+            last_refreshed: Instant::now(),
+        };
+
+        let porcelain = "MM file1.txt\nAM file2.txt\n M file3.txt\nM  file4.txt\n?? file5.txt";
+        let (modified, staged, staged_and_modified, untracked) =
+            provider.parse_porcelain_status(porcelain);
         assert_eq!(modified, 1);
         assert_eq!(staged, 1);
+        assert_eq!(staged_and_modified, 2);
         assert_eq!(untracked, 1);
     }
 
+    #[test]
+    fn test_format_status_line_staged_and_modified() {
+        let mut provider = GitStatusProvider {
+            repo_path: std::path::PathBuf::new(),
+            cached_status: GitStatus {
+                is_repo: true,
+                branch: Some("main".to_string()),
+                ..Default::default()
+            },
+            last_refreshed: Instant::now(),
+        };
+        let (modified, staged, staged_and_modified, untracked) =
+            provider.parse_porcelain_status("MM a.rs\nMM b.rs\n M c.rs\nA  d.rs\n?? e.rs");
+        provider.cached_status.modified = modified;
+        provider.cached_status.staged = staged;
+        provider.cached_status.staged_and_modified = staged_and_modified;
+        provider.cached_status.untracked = untracked;
+
+        assert_eq!(provider.format_status_line(), "main *1 +1 ±2 ?1");
+
+        // A lone partially staged file is not clean
+        provider.cached_status = GitStatus {
+            is_repo: true,
+            branch: Some("main".to_string()),
+            staged_and_modified: 1,
+            ..Default::default()
+        };
+        assert!(!provider.cached_status.is_clean());
+        assert_eq!(provider.format_status_line(), "main ±1");
+    }
+
     #[test]
     fn test_format_status_line_clean() {
         let provider = GitStatusProvider {
@@ -345,6 +624,7 @@ mod tests {
                 branch: Some("main".to_string()),
                 ..Default::default()
             },
+            last_refreshed: Instant::now(),
         };
 
         assert_eq!(provider.format_status_line(), "main ✓");
@@ -361,18 +641,113 @@ mod tests {
                 behind: 1,
                 modified: 3,
                 staged: 2,
+                staged_and_modified: 0,
                 untracked: 1,
+                ..Default::default()
             },
+            last_refreshed: Instant::now(),
         };
 
         assert_eq!(provider.format_status_line(), "main ↑2 ↓1 *3 +2 ?1");
     }
 
+    #[test]
+    fn test_parse_dirty_submodules() {
+        let provider = GitStatusProvider::with_status(Path::new(""), GitStatus::default());
+        let porcelain = "\
+1 .M S.M. 160000 160000 160000 abc abc libs/a
+1 .M SC.. 160000 160000 160000 abc def libs/b
+1 .M S..U 160000 160000 160000 abc abc libs/c
+1 .M N... 100644 100644 100644 abc abc src/main.rs
+2 R. S..U 160000 160000 160000 abc abc R100 libs/d	libs/old d
+? notes.txt";
+        assert_eq!(
+            provider.parse_dirty_submodules(porcelain),
+            vec!["libs/a", "libs/c", "libs/d"]
+        );
+    }
+
+    #[test]
+    fn test_format_status_line_repo_kind_and_submodules() {
+        let mut status = GitStatus {
+            is_repo: true,
+            branch: Some("feature".to_string()),
+            repo_kind: RepoKind::LinkedWorktree,
+            ..Default::default()
+        };
+        let provider = GitStatusProvider::with_status(Path::new(""), status.clone());
+        assert_eq!(provider.format_status_line(), "feature [wt] ✓");
+
+        status.repo_kind = RepoKind::Submodule;
+        status.modified = 1;
+        status.dirty_submodules = 1;
+        let provider = GitStatusProvider::with_status(Path::new(""), status);
+        assert_eq!(provider.format_status_line(), "feature [sub] *1 §1");
+    }
+
+    #[test]
+    fn test_last_commit_age() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut status = GitStatus {
+            is_repo: true,
+            ..Default::default()
+        };
+        assert_eq!(status.last_commit_age(now), None);
+
+        status.last_commit = Some(now - Duration::from_secs(3 * 3600));
+        assert_eq!(status.last_commit_age(now).as_deref(), Some("3h"));
+    }
+
+    #[test]
+    fn test_changed_since() {
+        let previous = GitStatus {
+            is_repo: true,
+            branch: Some("main".to_string()),
+            modified: 1,
+            ..Default::default()
+        };
+        assert!(!previous.clone().changed_since(&previous));
+
+        let current = GitStatus {
+            modified: 2,
+            ..previous.clone()
+        };
+        assert!(current.changed_since(&previous));
+    }
+
+    #[test]
+    fn test_refresh_if_stale() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let seeded = GitStatus {
+            is_repo: true,
+            branch: Some("main".to_string()),
+            modified: 3,
+            ..Default::default()
+        };
+        let mut provider = GitStatusProvider::with_status(temp_dir.path(), seeded.clone());
+        let seeded_at = provider.last_refreshed();
+
+        // Within the interval nothing is queried
+        assert!(!provider.refresh_if_stale(Duration::from_secs(3600)));
+        assert_eq!(provider.status(), &seeded);
+        assert_eq!(provider.last_refreshed(), seeded_at);
+
+        // Once stale, the refresh runs and reports the change
+        assert!(provider.refresh_if_stale(Duration::ZERO));
+        assert!(provider.last_refreshed() > seeded_at);
+        if !provider.is_in_repo() {
+            assert_eq!(provider.status(), &GitStatus::default());
+            // A second refresh finds nothing new
+            assert!(!provider.refresh_if_stale(Duration::ZERO));
+        }
+    }
+
     #[test]
     fn test_format_status_line_no_repo() {
         let provider = GitStatusProvider {
             repo_path: std::path::PathBuf::new(),
             cached_status: GitStatus::default(),
+            last_refreshed: Instant::now(),
         };
 
         assert_eq!(provider.format_status_line(), "");
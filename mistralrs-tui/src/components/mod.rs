@@ -1,5 +1,7 @@
 //! Reusable UI components for the TUI application
 
+pub mod format;
+
 #[cfg(feature = "tui-agent")]
 pub mod traits;
 
@@ -13,13 +15,30 @@ pub mod git_status;
 pub mod editor;
 
 #[cfg(feature = "tui-agent")]
-pub use traits::{Component, ComponentContext, ComponentManager, EventResult, FocusTarget};
+pub mod status_bar;
+
+pub use format::{format_relative_time, format_relative_time_from};
+
+#[cfg(feature = "tui-agent")]
+pub use traits::{
+    AsyncTickFuture, Component, ComponentContext, ComponentManager, EventResult, FocusTarget,
+    IntervalTask,
+};
+
+#[cfg(feature = "tui-agent")]
+pub use file_explorer::{
+    ExplorerViewState, FileEntry, FileExplorer, FileExplorerComponent, FileExplorerState,
+    PreviewContent,
+};
 
 #[cfg(feature = "tui-agent")]
-pub use file_explorer::{FileEntry, FileExplorer, FileExplorerState};
+pub use git_status::{render_git_status, GitStatus, GitStatusProvider, RepoKind};
 
 #[cfg(feature = "tui-agent")]
-pub use git_status::{render_git_status, GitStatus, GitStatusProvider};
+pub use editor::{
+    open_selected_in_editor, render_editor, Editor, EditorComponent, EditorMode, EditorState,
+    EditorStatus, EditorViewState, LargeFileConfig, SyntaxTheme,
+};
 
 #[cfg(feature = "tui-agent")]
-pub use editor::{render_editor, Editor, EditorMode, EditorState};
+pub use status_bar::StatusBarComponent;
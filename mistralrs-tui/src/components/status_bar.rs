@@ -0,0 +1,231 @@
+//! Status bar component combining git status, status messages and cursor info
+//!
+//! The bar is not focusable. It collects what it displays on each tick: the
+//! context status message, the focused editor's cursor position (published by
+//! [`EditorComponent`]) and, at a fixed interval, the git status. The git
+//! segment is only rebuilt when the refreshed status actually changed; the age
+//! of the last commit next to it is recomputed on every draw.
+
+use std::{
+    any::Any,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::editor::EditorComponent;
+use super::git_status::{render_git_status, GitStatusProvider};
+use super::traits::{Component, ComponentContext, EventResult};
+use crate::input::InputEvent;
+
+/// Default interval between git status refreshes
+const DEFAULT_GIT_REFRESH: Duration = Duration::from_secs(5);
+
+/// Bottom status bar
+pub struct StatusBarComponent {
+    git: GitStatusProvider,
+    git_span: Span<'static>,
+    refresh_interval: Duration,
+    show_cursor: bool,
+    message: Option<String>,
+    cursor: Option<String>,
+}
+
+impl StatusBarComponent {
+    /// Component ID used with [`ComponentManager`](super::ComponentManager)
+    pub const ID: &'static str = "status_bar";
+
+    /// Create a status bar reporting on the repository at `repo_path`
+    pub fn new(repo_path: &Path) -> Self {
+        Self::with_provider(GitStatusProvider::new(repo_path))
+    }
+
+    /// Create a status bar from an existing git status provider
+    pub fn with_provider(git: GitStatusProvider) -> Self {
+        Self {
+            git_span: render_git_status(git.status()),
+            git,
+            refresh_interval: DEFAULT_GIT_REFRESH,
+            show_cursor: true,
+            message: None,
+            cursor: None,
+        }
+    }
+
+    /// Set how often the git status is refreshed
+    pub fn refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Set whether the focused editor's cursor position is shown
+    pub fn show_cursor(mut self, show: bool) -> Self {
+        self.show_cursor = show;
+        self
+    }
+
+    /// Build the status line as spans
+    ///
+    /// Layout: git status and last commit age, then the status message,
+    /// then `Ln x, Col y`.
+    pub fn line(&self) -> Line<'static> {
+        self.line_at(SystemTime::now())
+    }
+
+    /// Build the status line with commit ages measured from `now`
+    fn line_at(&self, now: SystemTime) -> Line<'static> {
+        let mut spans = Vec::new();
+        if !self.git_span.content.is_empty() {
+            spans.push(self.git_span.clone());
+            if let Some(age) = self.git.status().last_commit_age(now) {
+                spans.push(Span::styled(
+                    format!(" ({})", age),
+                    Style::default().fg(Color::Gray),
+                ));
+            }
+        }
+
+        if let Some(message) = &self.message {
+            if !spans.is_empty() {
+                spans.push(Span::raw(" │ "));
+            }
+            spans.push(Span::raw(message.clone()));
+        }
+
+        if let Some(cursor) = self.cursor.as_ref().filter(|_| self.show_cursor) {
+            if !spans.is_empty() {
+                spans.push(Span::raw(" │ "));
+            }
+            spans.push(Span::styled(
+                cursor.clone(),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Component for StatusBarComponent {
+    fn handle_event(&mut self, _event: &InputEvent, _ctx: &mut ComponentContext) -> EventResult {
+        EventResult::Ignored
+    }
+
+    fn render(&self, area: Rect, frame: &mut Frame<'_>, _focused: bool) {
+        let paragraph = Paragraph::new(self.line()).style(Style::default().bg(Color::DarkGray));
+        frame.render_widget(paragraph, area);
+    }
+
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    fn id(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn tick(&mut self, ctx: &mut ComponentContext) {
+        self.message = ctx.status().map(str::to_string);
+        self.cursor = ctx
+            .get_shared(EditorComponent::CURSOR_KEY)
+            .map(str::to_string);
+
+        if self.git.refresh_if_stale(self.refresh_interval) {
+            self.git_span = render_git_status(self.git.status());
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ComponentManager, EditorState, GitStatus};
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_status_bar_combines_git_and_context() {
+        let status = GitStatus {
+            is_repo: true,
+            branch: Some("main".to_string()),
+            modified: 2,
+            ..Default::default()
+        };
+        let bar = StatusBarComponent::with_provider(GitStatusProvider::with_status(
+            Path::new("."),
+            status,
+        ));
+
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(EditorComponent::new(EditorState::new().unwrap())));
+        manager.register(Box::new(bar));
+        manager.context_mut().set_status("Saved");
+        manager.tick_all();
+
+        let bar = manager
+            .get_component(StatusBarComponent::ID)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StatusBarComponent>()
+            .unwrap();
+        let line = bar.line();
+        assert_eq!(line_text(&line), "main *2 │ Saved │ Ln 1, Col 1");
+        assert_eq!(line.spans[0].style.fg, Some(Color::Yellow));
+
+        // The bar never takes focus
+        manager.focus_next();
+        assert_eq!(manager.focused_id(), Some(EditorComponent::ID));
+    }
+
+    #[test]
+    fn test_status_bar_shows_last_commit_age() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let status = GitStatus {
+            is_repo: true,
+            branch: Some("main".to_string()),
+            last_commit: Some(now - Duration::from_secs(5 * 60)),
+            ..Default::default()
+        };
+        let bar = StatusBarComponent::with_provider(GitStatusProvider::with_status(
+            Path::new("."),
+            status,
+        ));
+
+        assert_eq!(line_text(&bar.line_at(now)), "main ✓ (5m)");
+    }
+
+    #[test]
+    fn test_status_bar_omits_empty_parts() {
+        let mut bar = StatusBarComponent::with_provider(GitStatusProvider::with_status(
+            Path::new("."),
+            GitStatus::default(),
+        ))
+        .show_cursor(false);
+        let mut ctx = ComponentContext::new();
+        ctx.set_shared(EditorComponent::CURSOR_KEY, "Ln 3, Col 4");
+
+        bar.tick(&mut ctx);
+        assert_eq!(line_text(&bar.line()), "");
+
+        ctx.set_status("Ready");
+        bar.tick(&mut ctx);
+        assert_eq!(line_text(&bar.line()), "Ready");
+    }
+}
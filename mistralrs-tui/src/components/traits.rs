@@ -1,13 +1,21 @@
 //! Component trait and infrastructure for IDE components
 
 #[cfg(feature = "tui-agent")]
-use crate::input::InputEvent;
+use crate::input::{InputEvent, MouseEvent};
+#[cfg(feature = "tui-agent")]
+use anyhow::{Context, Result};
 #[cfg(feature = "tui-agent")]
 use ratatui::{layout::Rect, Frame};
 #[cfg(feature = "tui-agent")]
+use serde_json::{Map, Value as JsonValue};
+#[cfg(feature = "tui-agent")]
 use std::any::Any;
 #[cfg(feature = "tui-agent")]
 use std::collections::HashMap;
+#[cfg(feature = "tui-agent")]
+use std::{future::Future, pin::Pin, time::Duration};
+#[cfg(feature = "tui-agent")]
+use tokio::{sync::mpsc, task::JoinHandle};
 
 #[cfg(feature = "tui-agent")]
 /// Result of handling an event
@@ -32,6 +40,41 @@ pub enum FocusTarget {
     Specific(&'static str),
 }
 
+#[cfg(feature = "tui-agent")]
+/// Future produced by one run of an [`IntervalTask`]
+///
+/// Resolves to an update for the owning component, or `None` if there is
+/// nothing to report.
+pub type AsyncTickFuture = Pin<Box<dyn Future<Output = Option<JsonValue>> + Send>>;
+
+#[cfg(feature = "tui-agent")]
+/// Periodic async work registered by a component
+///
+/// [`ComponentManager::start_async_ticks`] runs `task` on the tokio runtime
+/// every `interval`. Updates it produces are delivered back to the component
+/// through [`Component::handle_async_update`] on the next
+/// [`tick_all`](ComponentManager::tick_all).
+pub struct IntervalTask {
+    /// Time between runs; the first run happens one interval after start
+    pub interval: Duration,
+    task: Box<dyn FnMut() -> AsyncTickFuture + Send>,
+}
+
+#[cfg(feature = "tui-agent")]
+impl IntervalTask {
+    /// Create a task running `task` every `interval`
+    pub fn new<F, Fut>(interval: Duration, mut task: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Option<JsonValue>> + Send + 'static,
+    {
+        Self {
+            interval,
+            task: Box::new(move || Box::pin(task())),
+        }
+    }
+}
+
 #[cfg(feature = "tui-agent")]
 /// Context provided to components during event handling and updates
 pub struct ComponentContext {
@@ -95,6 +138,19 @@ pub trait Component: Send {
     /// Handle input event, return result
     fn handle_event(&mut self, event: &InputEvent, ctx: &mut ComponentContext) -> EventResult;
 
+    /// Handle a mouse event that landed inside the component's `area`
+    ///
+    /// Coordinates in `event` are absolute; `area` is the rectangle the
+    /// component was last rendered into.
+    fn handle_mouse(
+        &mut self,
+        _event: &MouseEvent,
+        _area: Rect,
+        _ctx: &mut ComponentContext,
+    ) -> EventResult {
+        EventResult::Ignored
+    }
+
     /// Render the component
     fn render(&self, area: Rect, frame: &mut Frame<'_>, focused: bool);
 
@@ -109,6 +165,33 @@ pub trait Component: Send {
     /// Optional tick for animations/updates
     fn tick(&mut self, _ctx: &mut ComponentContext) {}
 
+    /// Periodic async work to run on the runtime
+    ///
+    /// Called once when the manager starts async ticks. Components that only
+    /// need [`tick`](Self::tick) keep the default.
+    fn interval_task(&mut self) -> Option<IntervalTask> {
+        None
+    }
+
+    /// Receive an update produced by this component's [`IntervalTask`]
+    fn handle_async_update(&mut self, _update: JsonValue, _ctx: &mut ComponentContext) {}
+
+    /// Called by [`ComponentManager`] when the component gains or loses focus
+    fn on_focus_change(&mut self, _focused: bool) {}
+
+    /// Serialize the component's view state for persistence
+    ///
+    /// Runtime handles are not part of the view state. Returns `Null` for
+    /// components with nothing to persist.
+    fn save_state(&self) -> JsonValue {
+        JsonValue::Null
+    }
+
+    /// Restore view state previously produced by [`save_state`](Self::save_state)
+    fn load_state(&mut self, _state: JsonValue) -> Result<()> {
+        Ok(())
+    }
+
     /// For downcasting to concrete types
     fn as_any(&self) -> &dyn Any;
 
@@ -125,24 +208,94 @@ pub struct ComponentManager {
     focused_index: usize,
     /// Component context shared across all components
     context: ComponentContext,
+    /// Sender cloned into every interval task
+    async_tx: mpsc::UnboundedSender<(&'static str, JsonValue)>,
+    /// Updates from interval tasks, merged across components
+    async_rx: mpsc::UnboundedReceiver<(&'static str, JsonValue)>,
+    /// Running interval tasks; `Some` once async ticks have started
+    async_tasks: Option<Vec<JoinHandle<()>>>,
 }
 
 #[cfg(feature = "tui-agent")]
 impl ComponentManager {
     /// Create a new component manager
     pub fn new() -> Self {
+        let (async_tx, async_rx) = mpsc::unbounded_channel();
         Self {
             components: Vec::new(),
             focused_index: 0,
             context: ComponentContext::new(),
+            async_tx,
+            async_rx,
+            async_tasks: None,
         }
     }
 
     /// Register a new component
-    pub fn register(&mut self, component: Box<dyn Component>) {
+    ///
+    /// The first registered component starts out focused. If async ticks are
+    /// running, the component's interval task is started right away.
+    pub fn register(&mut self, mut component: Box<dyn Component>) {
+        if self.components.is_empty() && component.focusable() {
+            component.on_focus_change(true);
+        }
+        if self.async_tasks.is_some() {
+            self.spawn_interval_task(component.as_mut());
+        }
         self.components.push(component);
     }
 
+    /// Start every component's [`IntervalTask`] on the current tokio runtime
+    ///
+    /// Must be called from within a runtime. Calling it again while running
+    /// has no effect.
+    pub fn start_async_ticks(&mut self) {
+        if self.async_tasks.is_some() {
+            return;
+        }
+        self.async_tasks = Some(Vec::new());
+
+        let mut components = std::mem::take(&mut self.components);
+        for component in &mut components {
+            self.spawn_interval_task(component.as_mut());
+        }
+        self.components = components;
+    }
+
+    /// Abort all running interval tasks
+    ///
+    /// Updates already produced are still delivered by the next `tick_all`.
+    pub fn stop_async_ticks(&mut self) {
+        for handle in self.async_tasks.take().unwrap_or_default() {
+            handle.abort();
+        }
+    }
+
+    fn spawn_interval_task(&mut self, component: &mut dyn Component) {
+        let Some(IntervalTask { interval, mut task }) = component.interval_task() else {
+            return;
+        };
+        let id = component.id();
+        let tx = self.async_tx.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut timer =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                timer.tick().await;
+                if let Some(update) = task().await {
+                    if tx.send((id, update)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        if let Some(tasks) = &mut self.async_tasks {
+            tasks.push(handle);
+        }
+    }
+
     /// Get the number of registered components
     pub fn len(&self) -> usize {
         self.components.len()
@@ -181,6 +334,29 @@ impl ComponentManager {
         }
     }
 
+    /// Route a mouse event to the component whose area contains it
+    ///
+    /// `areas` must be the same layout passed to [`render_all`](Self::render_all).
+    /// The target component is focused if it is focusable. Events outside every
+    /// area are ignored.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, areas: &[Rect]) -> EventResult {
+        if areas.len() != self.components.len() {
+            return EventResult::Ignored;
+        }
+
+        let Some(index) = areas
+            .iter()
+            .position(|area| rect_contains(*area, event.column, event.row))
+        else {
+            return EventResult::Ignored;
+        };
+
+        if self.components[index].focusable() {
+            self.set_focus(index);
+        }
+        self.components[index].handle_mouse(event, areas[index], &mut self.context)
+    }
+
     /// Render all components in their respective areas
     pub fn render_all(&self, frame: &mut Frame<'_>, areas: &[Rect]) {
         if areas.len() != self.components.len() {
@@ -195,12 +371,53 @@ impl ComponentManager {
     }
 
     /// Tick all components for updates
+    ///
+    /// Pending updates from interval tasks are delivered first, in the order
+    /// they were produced, then every component's synchronous `tick` runs.
     pub fn tick_all(&mut self) {
+        while let Ok((id, update)) = self.async_rx.try_recv() {
+            if let Some(component) = self.components.iter_mut().find(|c| c.id() == id) {
+                component.handle_async_update(update, &mut self.context);
+            }
+        }
+
         for component in &mut self.components {
             component.tick(&mut self.context);
         }
     }
 
+    /// Collect the view state of every component, keyed by component ID
+    ///
+    /// Components returning `Null` from `save_state` are omitted.
+    pub fn snapshot(&self) -> JsonValue {
+        let states: Map<String, JsonValue> = self
+            .components
+            .iter()
+            .map(|c| (c.id().to_string(), c.save_state()))
+            .filter(|(_, state)| !state.is_null())
+            .collect();
+        JsonValue::Object(states)
+    }
+
+    /// Restore component view state from a [`snapshot`](Self::snapshot)
+    ///
+    /// Entries for unregistered components are ignored.
+    pub fn restore(&mut self, snapshot: &JsonValue) -> Result<()> {
+        let states = snapshot
+            .as_object()
+            .context("component snapshot must be a JSON object")?;
+
+        for component in &mut self.components {
+            if let Some(state) = states.get(component.id()) {
+                let id = component.id();
+                component
+                    .load_state(state.clone())
+                    .with_context(|| format!("restoring state of component '{id}'"))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Move focus to the next focusable component
     pub fn focus_next(&mut self) {
         if self.components.is_empty() {
@@ -208,12 +425,14 @@ impl ComponentManager {
         }
 
         let start = self.focused_index;
+        let mut index = start;
         loop {
-            self.focused_index = (self.focused_index + 1) % self.components.len();
-            if self.components[self.focused_index].focusable() || self.focused_index == start {
+            index = (index + 1) % self.components.len();
+            if self.components[index].focusable() || index == start {
                 break;
             }
         }
+        self.set_focus(index);
     }
 
     /// Move focus to the previous focusable component
@@ -223,28 +442,44 @@ impl ComponentManager {
         }
 
         let start = self.focused_index;
+        let mut index = start;
         loop {
-            self.focused_index = if self.focused_index == 0 {
+            index = if index == 0 {
                 self.components.len() - 1
             } else {
-                self.focused_index - 1
+                index - 1
             };
-            if self.components[self.focused_index].focusable() || self.focused_index == start {
+            if self.components[index].focusable() || index == start {
                 break;
             }
         }
+        self.set_focus(index);
     }
 
     /// Focus a component by its ID
     /// Returns true if the component was found and focused
     pub fn focus_by_id(&mut self, id: &str) -> bool {
-        for (idx, component) in self.components.iter().enumerate() {
-            if component.id() == id && component.focusable() {
-                self.focused_index = idx;
-                return true;
+        let found = self
+            .components
+            .iter()
+            .position(|c| c.id() == id && c.focusable());
+        match found {
+            Some(idx) => {
+                self.set_focus(idx);
+                true
             }
+            None => false,
+        }
+    }
+
+    /// Move focus to `index`, notifying the components involved
+    fn set_focus(&mut self, index: usize) {
+        if index == self.focused_index {
+            return;
         }
-        false
+        self.components[self.focused_index].on_focus_change(false);
+        self.focused_index = index;
+        self.components[index].on_focus_change(true);
     }
 
     /// Get the currently focused component's ID
@@ -290,9 +525,26 @@ impl Default for ComponentManager {
     }
 }
 
+#[cfg(feature = "tui-agent")]
+impl Drop for ComponentManager {
+    fn drop(&mut self) {
+        self.stop_async_ticks();
+    }
+}
+
+#[cfg(feature = "tui-agent")]
+/// Whether the cell at (`column`, `row`) lies within `area`
+pub(crate) fn rect_contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
+
 #[cfg(all(test, feature = "tui-agent"))]
 mod tests {
     use super::*;
+    use crate::components::{FileExplorerComponent, FileExplorerState};
+    use crate::input::{MouseButton, MouseEventKind};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     struct TestComponent {
         id: &'static str,
@@ -341,6 +593,105 @@ mod tests {
         }
     }
 
+    /// Component whose interval task counts its runs
+    struct CountingComponent {
+        runs: Arc<AtomicUsize>,
+        last_update: Option<JsonValue>,
+    }
+
+    impl Component for CountingComponent {
+        fn handle_event(
+            &mut self,
+            _event: &InputEvent,
+            _ctx: &mut ComponentContext,
+        ) -> EventResult {
+            EventResult::Ignored
+        }
+
+        fn render(&self, _area: Rect, _frame: &mut Frame<'_>, _focused: bool) {}
+
+        fn id(&self) -> &'static str {
+            "counter"
+        }
+
+        fn interval_task(&mut self) -> Option<IntervalTask> {
+            let runs = Arc::clone(&self.runs);
+            Some(IntervalTask::new(Duration::from_millis(20), move || {
+                let runs = Arc::clone(&runs);
+                async move {
+                    let count = runs.fetch_add(1, Ordering::SeqCst) + 1;
+                    Some(JsonValue::from(count))
+                }
+            }))
+        }
+
+        fn handle_async_update(&mut self, update: JsonValue, ctx: &mut ComponentContext) {
+            ctx.set_status(format!("runs: {update}"));
+            self.last_update = Some(update);
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    /// Advance the paused clock and let woken interval tasks run
+    async fn advance(millis: u64) {
+        tokio::time::advance(Duration::from_millis(millis)).await;
+        for _ in 0..3 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_async_tick_runs_on_interval() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(TestComponent::new("test1", true)));
+        manager.register(Box::new(CountingComponent {
+            runs: Arc::clone(&runs),
+            last_update: None,
+        }));
+
+        manager.start_async_ticks();
+        manager.tick_all();
+        // Nothing runs before the first interval elapses
+        advance(19).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        manager.tick_all();
+        assert!(manager.status().is_none());
+
+        advance(1).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        advance(20).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // Updates are only delivered by tick_all
+        let last_update = |manager: &ComponentManager| {
+            manager
+                .get_component("counter")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<CountingComponent>()
+                .unwrap()
+                .last_update
+                .clone()
+        };
+        assert_eq!(last_update(&manager), None);
+        manager.tick_all();
+        assert_eq!(last_update(&manager), Some(JsonValue::from(2)));
+        assert_eq!(manager.status(), Some("runs: 2"));
+
+        // Stopped tasks no longer advance the counter
+        manager.stop_async_ticks();
+        advance(100).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_component_manager_basic() {
         let mut manager = ComponentManager::new();
@@ -404,6 +755,74 @@ mod tests {
         assert_eq!(manager.focused_id(), Some("test1"));
     }
 
+    #[test]
+    fn test_mouse_click_routes_to_component_area() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
+        }
+
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(TestComponent::new("status", true)));
+        manager.register(Box::new(FileExplorerComponent::new(
+            FileExplorerState::new(temp_dir.path()).unwrap(),
+        )));
+        let areas = [Rect::new(0, 0, 20, 3), Rect::new(0, 3, 40, 10)];
+
+        // Row 3 is the top border, so row 6 is the third entry
+        let click = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 5, 6);
+        assert_eq!(manager.handle_mouse(&click, &areas), EventResult::Consumed);
+        assert_eq!(manager.focused_id(), Some("explorer"));
+
+        let explorer = manager.get_component("explorer").unwrap();
+        let state = explorer
+            .as_any()
+            .downcast_ref::<FileExplorerComponent>()
+            .unwrap()
+            .state();
+        assert_eq!(state.current_entry().unwrap().name, "c.txt");
+
+        // Outside every area
+        let outside = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 50, 20);
+        assert_eq!(manager.handle_mouse(&outside, &areas), EventResult::Ignored);
+        assert_eq!(manager.focused_id(), Some("explorer"));
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("src").join("nested")).unwrap();
+
+        let mut manager = ComponentManager::new();
+        manager.register(Box::new(TestComponent::new("status", true)));
+        let mut state = FileExplorerState::new(&root).unwrap();
+        state.enter_selection().unwrap();
+        manager.register(Box::new(FileExplorerComponent::new(state)));
+
+        let snapshot = manager.snapshot();
+        // Components without view state are left out
+        assert_eq!(snapshot.as_object().unwrap().len(), 1);
+
+        let mut restored = ComponentManager::new();
+        restored.register(Box::new(TestComponent::new("status", true)));
+        restored.register(Box::new(FileExplorerComponent::new(
+            FileExplorerState::new(&root).unwrap(),
+        )));
+        restored.restore(&snapshot).unwrap();
+
+        let explorer = restored.get_component("explorer").unwrap();
+        let state = explorer
+            .as_any()
+            .downcast_ref::<FileExplorerComponent>()
+            .unwrap()
+            .state();
+        assert_eq!(state.current_dir(), root.join("src"));
+        assert_eq!(state.current_entry().unwrap().name, "nested");
+
+        assert!(restored.restore(&JsonValue::Null).is_err());
+    }
+
     #[test]
     fn test_context_status() {
         let mut ctx = ComponentContext::new();
@@ -46,10 +46,45 @@ impl KeyEvent {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseEventKind {
+    Down(MouseButton),
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Mouse event at an absolute terminal cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    pub kind: MouseEventKind,
+    pub column: u16,
+    pub row: u16,
+    pub modifiers: Modifiers,
+}
+
+impl MouseEvent {
+    pub fn new(kind: MouseEventKind, column: u16, row: u16) -> Self {
+        Self {
+            kind,
+            column,
+            row,
+            modifiers: Modifiers::NONE,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputEvent {
     Tick,
     Key(KeyEvent),
+    Mouse(MouseEvent),
     Resize(u16, u16),
 }
 
@@ -63,11 +98,35 @@ pub fn from_crossterm(event: crossterm::event::Event) -> Option<InputEvent> {
             let code = convert_crossterm_code(key.code);
             Some(InputEvent::Key(KeyEvent::new(code, mods)))
         }
+        Event::Mouse(mouse) => convert_crossterm_mouse(mouse).map(InputEvent::Mouse),
         Event::Resize(width, height) => Some(InputEvent::Resize(width, height)),
         _ => None,
     }
 }
 
+#[cfg(feature = "terminal")]
+fn convert_crossterm_mouse(mouse: crossterm::event::MouseEvent) -> Option<MouseEvent> {
+    use crossterm::event::{MouseButton as B, MouseEventKind as K};
+
+    let kind = match mouse.kind {
+        K::Down(button) => MouseEventKind::Down(match button {
+            B::Left => MouseButton::Left,
+            B::Right => MouseButton::Right,
+            B::Middle => MouseButton::Middle,
+        }),
+        K::ScrollUp => MouseEventKind::ScrollUp,
+        K::ScrollDown => MouseEventKind::ScrollDown,
+        _ => return None,
+    };
+
+    Some(MouseEvent {
+        kind,
+        column: mouse.column,
+        row: mouse.row,
+        modifiers: convert_crossterm_modifiers(mouse.modifiers),
+    })
+}
+
 #[cfg(feature = "terminal")]
 fn convert_crossterm_modifiers(modifiers: crossterm::event::KeyModifiers) -> Modifiers {
     use crossterm::event::KeyModifiers as KM;
@@ -2,8 +2,43 @@
 
 #[cfg(feature = "tui-agent")]
 mod git_status_tests {
-    use mistralrs_tui::components::{GitStatus, GitStatusProvider};
+    use mistralrs_tui::components::{GitStatus, GitStatusProvider, RepoKind};
     use std::env;
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "-c",
+                "protocol.file.allow=always",
+                "-c",
+                "init.defaultBranch=main",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("Failed to run git");
+        assert!(
+            status.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&status.stderr)
+        );
+    }
+
+    fn init_repo(dir: &Path) {
+        fs::create_dir_all(dir).unwrap();
+        git(dir, &["init", "-q"]);
+        fs::write(dir.join("README.md"), "readme\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "initial"]);
+    }
 
     #[test]
     fn test_git_status_provider_in_repo() {
@@ -77,6 +112,7 @@ mod git_status_tests {
             modified: 0,
             staged: 0,
             untracked: 0,
+            ..Default::default()
         };
 
         // GitStatus should be displayable
@@ -95,6 +131,7 @@ mod git_status_tests {
             modified: 3,
             staged: 2,
             untracked: 1,
+            ..Default::default()
         };
 
         // Verify all fields are set correctly
@@ -138,4 +175,68 @@ mod git_status_tests {
         let span = render_git_status(&status);
         assert!(span.content.is_empty());
     }
+
+    #[test]
+    fn test_git_status_counts_dirty_submodules() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lib = temp_dir.path().join("lib");
+        let app = temp_dir.path().join("app");
+        init_repo(&lib);
+        init_repo(&app);
+        git(
+            &app,
+            &["submodule", "add", "-q", lib.to_str().unwrap(), "libs/lib"],
+        );
+        git(&app, &["commit", "-q", "-m", "add submodule"]);
+
+        let provider = GitStatusProvider::new(&app);
+        assert_eq!(provider.status().repo_kind, RepoKind::Main);
+        assert!(provider.status().last_commit.is_some());
+        assert_eq!(provider.status().dirty_submodules, 0);
+        assert_eq!(provider.format_status_line(), "main ✓");
+
+        // Uncommitted work inside the submodule
+        let submodule = app.join("libs/lib");
+        fs::write(submodule.join("README.md"), "changed\n").unwrap();
+        let provider = GitStatusProvider::new(&app);
+        assert_eq!(provider.status().dirty_submodules, 1);
+        // Counted once, not also as a modified file
+        assert_eq!(provider.status().modified, 0);
+        assert_eq!(provider.format_status_line(), "main §1");
+
+        // From inside, the status describes the submodule itself
+        git(&submodule, &["checkout", "-q", "-b", "sub-branch"]);
+        let provider = GitStatusProvider::new(&submodule);
+        assert_eq!(provider.status().repo_kind, RepoKind::Submodule);
+        assert_eq!(provider.status().branch.as_deref(), Some("sub-branch"));
+        assert_eq!(provider.status().modified, 1);
+        assert_eq!(provider.status().dirty_submodules, 0);
+    }
+
+    #[test]
+    fn test_git_status_recognizes_linked_worktree() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().join("repo");
+        let worktree = temp_dir.path().join("wt");
+        init_repo(&repo);
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "topic",
+                worktree.to_str().unwrap(),
+            ],
+        );
+
+        let provider = GitStatusProvider::new(&repo);
+        assert_eq!(provider.status().repo_kind, RepoKind::Main);
+
+        let provider = GitStatusProvider::new(&worktree);
+        assert_eq!(provider.status().repo_kind, RepoKind::LinkedWorktree);
+        assert_eq!(provider.status().branch.as_deref(), Some("topic"));
+        assert_eq!(provider.format_status_line(), "topic [wt] ✓");
+    }
 }